@@ -38,6 +38,7 @@ fn main() {
                 value: "192.0.2.1".to_string(),
                 ttl: None,
                 priority: None,
+                data: None,
             },
             DnsRecord {
                 name: "www".to_string(),
@@ -45,6 +46,7 @@ fn main() {
                 value: "192.0.2.2".to_string(),
                 ttl: Some(7200),
                 priority: None,
+                data: None,
             },
             DnsRecord {
                 name: "@".to_string(),
@@ -52,6 +54,7 @@ fn main() {
                 value: "mail.example.com.".to_string(),
                 ttl: None,
                 priority: Some(10),
+                data: None,
             },
         ],
         also_notify: None,
@@ -59,6 +62,8 @@ fn main() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let request = CreateZoneRequest {
@@ -66,6 +71,9 @@ fn main() {
         zone_type: ZONE_TYPE_PRIMARY.to_string(),
         zone_config: zone_config.clone(),
         update_key_name: None,
+        update_policy: None,
+        serial_strategy: None,
+        catalog: None,
     };
 
     // Example 2: Serialize to JSON (for HTTP API calls)
@@ -123,12 +131,15 @@ fn main() {
             value: "192.0.2.1".to_string(),
             ttl: None,
             priority: None,
+            data: None,
         }],
         also_notify: None,
         allow_transfer: None,
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     println!(
@@ -0,0 +1,149 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Zone transfer (AXFR, RFC 5936) client
+//!
+//! Reads the live contents of a zone straight from BIND9 over DNS/TCP. Unlike
+//! the on-disk zone file, an AXFR reflects dynamic updates that are still only
+//! in the journal, so it is the authoritative view of a zone's records.
+//!
+//! When a TSIG key is supplied the request is signed and every response
+//! message is verified (RFC 8945 §5.3.1); a transfer whose signatures do not
+//! verify is rejected as a whole.
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::debug;
+
+use crate::dns_wire::{self, TsigKey, TsigVerifier, CLASS_IN, TYPE_AXFR, TYPE_SOA};
+use crate::zone_file_parser::ResourceRecord;
+
+/// Default time limit for a whole transfer
+pub const DEFAULT_AXFR_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Transfer a zone from `server:port`.
+///
+/// Returns every record in the zone in transfer order, with the leading SOA
+/// first and the closing SOA omitted. All records carry an explicit TTL.
+///
+/// # Errors
+/// Returns an error if the connection fails, the server refuses the transfer
+/// (e.g. NOTAUTH / REFUSED), TSIG verification fails, the stream is malformed
+/// or ends before the closing SOA, or the timeout elapses.
+pub async fn transfer_zone(
+    server: &str,
+    port: u16,
+    zone: &str,
+    key: Option<&TsigKey>,
+    timeout: Duration,
+) -> Result<Vec<ResourceRecord>> {
+    tokio::time::timeout(timeout, transfer(server, port, zone, key))
+        .await
+        .map_err(|_| anyhow::anyhow!("zone transfer of {} timed out", zone))?
+}
+
+async fn transfer(
+    server: &str,
+    port: u16,
+    zone: &str,
+    key: Option<&TsigKey>,
+) -> Result<Vec<ResourceRecord>> {
+    let id = dns_wire::random_message_id();
+    let mut request = dns_wire::build_query(id, zone, TYPE_AXFR)?;
+    let mut verifier = match key {
+        Some(key) => {
+            let mac = key.sign(
+                &mut request,
+                dns_wire::unix_time(),
+                dns_wire::DEFAULT_TSIG_FUDGE,
+            )?;
+            Some(TsigVerifier::new(key, mac))
+        }
+        None => None,
+    };
+
+    let mut stream = TcpStream::connect((server, port))
+        .await
+        .with_context(|| format!("Failed to connect to {}:{}", server, port))?;
+
+    let mut framed = Vec::with_capacity(request.len() + 2);
+    framed.extend_from_slice(&(request.len() as u16).to_be_bytes());
+    framed.extend_from_slice(&request);
+    stream
+        .write_all(&framed)
+        .await
+        .context("Failed to send AXFR request")?;
+
+    let mut records = Vec::new();
+    let mut soa_seen = false;
+    let mut messages = 0usize;
+
+    loop {
+        let raw = read_message(&mut stream).await?;
+        let message = dns_wire::parse_message(&raw).context("Malformed AXFR response")?;
+        messages += 1;
+
+        if message.id != id {
+            return Err(anyhow::anyhow!("AXFR response ID mismatch"));
+        }
+        if message.rcode() != 0 {
+            return Err(anyhow::anyhow!(
+                "zone transfer refused: {}",
+                dns_wire::rcode_name(message.rcode())
+            ));
+        }
+        if let Some(verifier) = verifier.as_mut() {
+            verifier.verify(&raw, &message, dns_wire::unix_time())?;
+        }
+
+        for rr in message.answers {
+            if rr.rtype == TYPE_SOA {
+                if !soa_seen {
+                    soa_seen = true;
+                } else {
+                    if let Some(verifier) = verifier.as_ref() {
+                        if !verifier.is_complete() {
+                            return Err(anyhow::anyhow!("final AXFR message is not TSIG-signed"));
+                        }
+                    }
+                    debug!(
+                        "AXFR of {} complete: {} records in {} messages",
+                        zone,
+                        records.len(),
+                        messages
+                    );
+                    return Ok(records);
+                }
+            } else if !soa_seen {
+                return Err(anyhow::anyhow!("AXFR response does not start with SOA"));
+            }
+
+            if rr.class != CLASS_IN {
+                continue;
+            }
+            records.push(ResourceRecord {
+                name: rr.name,
+                ttl: Some(rr.ttl),
+                record_type: dns_wire::record_type_name(rr.rtype),
+                data: rr.data,
+            });
+        }
+    }
+}
+
+/// Read one length-prefixed DNS message from a TCP stream.
+async fn read_message(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let mut len = [0u8; 2];
+    stream
+        .read_exact(&mut len)
+        .await
+        .context("AXFR stream ended before the closing SOA")?;
+    let mut buf = vec![0u8; u16::from_be_bytes(len) as usize];
+    stream
+        .read_exact(&mut buf)
+        .await
+        .context("Truncated AXFR response message")?;
+    Ok(buf)
+}
@@ -0,0 +1,246 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for the AXFR client against an in-process TCP server
+
+#[cfg(test)]
+mod tests {
+    use crate::axfr::transfer_zone;
+    use crate::dns_wire::*;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    const SECRET: &str = "dGVzdC1zZWNyZXQtaGVyZQ==";
+
+    fn key() -> TsigKey {
+        TsigKey::new("xfer-key", "hmac-sha256", SECRET).unwrap()
+    }
+
+    fn soa_rdata() -> Vec<u8> {
+        let mut rdata = Vec::new();
+        encode_name("ns1.example.com.", false, &mut rdata).unwrap();
+        encode_name("admin.example.com.", false, &mut rdata).unwrap();
+        for value in [2024010101u32, 3600, 600, 604800, 86400] {
+            rdata.extend_from_slice(&value.to_be_bytes());
+        }
+        rdata
+    }
+
+    fn message(id: u16, rcode: u16, answers: &[(&str, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&id.to_be_bytes());
+        msg.extend_from_slice(&(0x8400u16 | rcode).to_be_bytes());
+        msg.extend_from_slice(&0u16.to_be_bytes());
+        msg.extend_from_slice(&(answers.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&[0, 0, 0, 0]);
+        for (name, rtype, rdata) in answers {
+            encode_name(name, false, &mut msg).unwrap();
+            msg.extend_from_slice(&rtype.to_be_bytes());
+            msg.extend_from_slice(&CLASS_IN.to_be_bytes());
+            msg.extend_from_slice(&3600u32.to_be_bytes());
+            msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            msg.extend_from_slice(rdata);
+        }
+        msg
+    }
+
+    /// Append a TSIG RR carrying `mac` and return the MAC.
+    fn append_tsig(key: &TsigKey, msg: &mut Vec<u8>, mac: Vec<u8>, now: u64) -> Vec<u8> {
+        let mut rdata = Vec::new();
+        encode_name("hmac-sha256.", true, &mut rdata).unwrap();
+        rdata.extend_from_slice(&now.to_be_bytes()[2..]);
+        rdata.extend_from_slice(&DEFAULT_TSIG_FUDGE.to_be_bytes());
+        rdata.extend_from_slice(&(mac.len() as u16).to_be_bytes());
+        rdata.extend_from_slice(&mac);
+        rdata.extend_from_slice(&msg[0..2]);
+        rdata.extend_from_slice(&[0, 0, 0, 0]);
+        encode_name(key.name(), true, msg).unwrap();
+        msg.extend_from_slice(&TYPE_TSIG.to_be_bytes());
+        msg.extend_from_slice(&CLASS_ANY.to_be_bytes());
+        msg.extend_from_slice(&0u32.to_be_bytes());
+        msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&rdata);
+        let arcount = u16::from_be_bytes([msg[10], msg[11]]) + 1;
+        msg[10..12].copy_from_slice(&arcount.to_be_bytes());
+        mac
+    }
+
+    async fn read_request(stream: &mut TcpStream) -> Vec<u8> {
+        let mut len = [0u8; 2];
+        stream.read_exact(&mut len).await.unwrap();
+        let mut buf = vec![0u8; u16::from_be_bytes(len) as usize];
+        stream.read_exact(&mut buf).await.unwrap();
+        buf
+    }
+
+    async fn send(stream: &mut TcpStream, msg: &[u8]) {
+        stream
+            .write_all(&(msg.len() as u16).to_be_bytes())
+            .await
+            .unwrap();
+        stream.write_all(msg).await.unwrap();
+    }
+
+    /// Serve one signed three-message transfer: a signed first message, an
+    /// unsigned middle message, and a signed final message.
+    async fn serve_signed_transfer(listener: TcpListener, corrupt_last: bool) {
+        let key = key();
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut stream).await;
+        let parsed = parse_message(&request).unwrap();
+        let request_mac = parsed.tsig.expect("signed request").mac;
+        let id = parsed.id;
+        let now = unix_time();
+
+        let mut first = message(
+            id,
+            0,
+            &[
+                ("example.com.", 6, soa_rdata()),
+                ("www.example.com.", 1, vec![192, 0, 2, 1]),
+            ],
+        );
+        let variables = key.variables(now, DEFAULT_TSIG_FUDGE, 0).unwrap();
+        let mac = key.digest(&[
+            &(request_mac.len() as u16).to_be_bytes(),
+            &request_mac,
+            &first,
+            &variables,
+        ]);
+        let prior = append_tsig(&key, &mut first, mac, now);
+
+        let middle = message(id, 0, &[("www.example.com.", 1, vec![192, 0, 2, 2])]);
+
+        let mut last = message(id, 0, &[("example.com.", 6, soa_rdata())]);
+        let mut timers = now.to_be_bytes()[2..].to_vec();
+        timers.extend_from_slice(&DEFAULT_TSIG_FUDGE.to_be_bytes());
+        let mut mac = key.digest(&[
+            &(prior.len() as u16).to_be_bytes(),
+            &prior,
+            &middle,
+            &last,
+            &timers,
+        ]);
+        if corrupt_last {
+            mac[0] ^= 0xff;
+        }
+        append_tsig(&key, &mut last, mac, now);
+
+        send(&mut stream, &first).await;
+        send(&mut stream, &middle).await;
+        send(&mut stream, &last).await;
+    }
+
+    #[tokio::test]
+    async fn test_signed_transfer_returns_records() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_signed_transfer(listener, false));
+
+        let key = key();
+        let records = transfer_zone(
+            "127.0.0.1",
+            port,
+            "example.com",
+            Some(&key),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        server.await.unwrap();
+
+        let summary: Vec<_> = records
+            .iter()
+            .map(|r| (r.name.as_str(), r.record_type.as_str(), r.data.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "example.com.",
+                    "SOA",
+                    "ns1.example.com. admin.example.com. 2024010101 3600 600 604800 86400"
+                ),
+                ("www.example.com.", "A", "192.0.2.1"),
+                ("www.example.com.", "A", "192.0.2.2"),
+            ]
+        );
+        assert!(records.iter().all(|r| r.ttl == Some(3600)));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_rejects_bad_signature() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_signed_transfer(listener, true));
+
+        let key = key();
+        let err = transfer_zone(
+            "127.0.0.1",
+            port,
+            "example.com",
+            Some(&key),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+        server.await.unwrap();
+
+        assert!(err.to_string().contains("BADSIG"));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_reports_refusal() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut stream).await;
+            let id = parse_message(&request).unwrap().id;
+            send(&mut stream, &message(id, 9, &[])).await;
+        });
+
+        let err = transfer_zone(
+            "127.0.0.1",
+            port,
+            "example.com",
+            None,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+        server.await.unwrap();
+
+        assert!(err.to_string().contains("NOTAUTH"));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_rejects_stream_ending_early() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut stream).await;
+            let id = parse_message(&request).unwrap().id;
+            send(
+                &mut stream,
+                &message(id, 0, &[("example.com.", 6, soa_rdata())]),
+            )
+            .await;
+        });
+
+        let err = transfer_zone(
+            "127.0.0.1",
+            port,
+            "example.com",
+            None,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+        server.await.unwrap();
+
+        assert!(err.to_string().contains("closing SOA"));
+    }
+}
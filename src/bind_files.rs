@@ -0,0 +1,173 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Files BIND writes into its working directory on request
+//!
+//! `rndc dumpdb` and `rndc stats` make BIND write `named_dump.db` and
+//! `named.stats` into its working directory (the `directory` option), which
+//! bindcar reads through a shared mount: `BIND_DIRECTORY`, or the zone
+//! directory when unset. Every dump overwrites the same file, so
+//! [`BindFiles`] runs them one at a time and removes the previous dump first,
+//! so that a file present afterwards is the new one.
+//!
+//! BIND has no command that lists its zones, and the only complete source, a
+//! zone dump, writes the contents of every zone to disk. The served zone names
+//! are therefore cached: concurrent listings and every page of one listing
+//! share a dump that is at most `ZONE_LIST_CACHE_SECONDS` old, and zones
+//! bindcar adds or deletes itself are applied to the cached set directly.
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::time::Instant;
+
+use crate::{rndc_parser, server};
+
+/// Environment variable naming BIND's working directory as mounted in bindcar
+pub const BIND_DIRECTORY_ENV: &str = "BIND_DIRECTORY";
+
+/// Environment variable with the lifetime of the served zone cache, in seconds
+pub const ZONE_LIST_CACHE_ENV: &str = "ZONE_LIST_CACHE_SECONDS";
+
+/// Default lifetime of the served zone cache
+pub const DEFAULT_ZONE_LIST_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Served zone names and when they were dumped
+struct ServedZones {
+    taken: Instant,
+    names: BTreeSet<String>,
+}
+
+/// Serialized access to BIND's dump and statistics files, with the served
+/// zone cache
+pub struct BindFiles {
+    directory: PathBuf,
+    ttl: Duration,
+    served: Mutex<Option<ServedZones>>,
+}
+
+/// Held while a dump or statistics file is written and read
+pub struct DumpGuard<'a>(#[allow(dead_code)] MutexGuard<'a, Option<ServedZones>>);
+
+impl BindFiles {
+    /// Files in `directory`, caching served zones for `ttl` (zero disables
+    /// the cache)
+    pub fn new(directory: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            directory: directory.into(),
+            ttl,
+            served: Mutex::new(None),
+        }
+    }
+
+    /// Configure from `BIND_DIRECTORY` and `ZONE_LIST_CACHE_SECONDS`.
+    ///
+    /// # Errors
+    /// Returns an error if `ZONE_LIST_CACHE_SECONDS` is not a number.
+    pub fn from_env(zone_dir: &str) -> Result<Self> {
+        let directory = std::env::var(BIND_DIRECTORY_ENV)
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .unwrap_or_else(|| zone_dir.to_string());
+        let ttl = match std::env::var(ZONE_LIST_CACHE_ENV) {
+            Ok(seconds) if !seconds.is_empty() => Duration::from_secs(
+                seconds
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid {}: {}", ZONE_LIST_CACHE_ENV, seconds))?,
+            ),
+            _ => DEFAULT_ZONE_LIST_CACHE_TTL,
+        };
+        Ok(Self::new(directory, ttl))
+    }
+
+    /// Path of the file `rndc dumpdb` writes
+    pub fn dump_file(&self) -> PathBuf {
+        self.directory.join(server::BIND_DUMP_FILE_NAME)
+    }
+
+    /// Path of the file `rndc stats` appends to
+    pub fn stats_file(&self) -> PathBuf {
+        self.directory.join(server::BIND_STATS_FILE_NAME)
+    }
+
+    /// Remove the previous dump so that the next one cannot be mistaken for it.
+    ///
+    /// # Errors
+    /// Returns an error if the file exists but cannot be removed.
+    pub async fn remove_dump_file(&self) -> std::io::Result<()> {
+        match tokio::fs::remove_file(self.dump_file()).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wait for any other dump to finish; no other starts until the guard is
+    /// dropped.
+    pub async fn exclusive(&self) -> DumpGuard<'_> {
+        DumpGuard(self.served.lock().await)
+    }
+
+    /// Names of the zones BIND serves in the default view, without built-in
+    /// empty zones or zones named ".".
+    ///
+    /// Returns the cached set while it is fresh. Otherwise `dump` is called to
+    /// run `rndc dumpdb -zones` and the dump file is read; callers arriving
+    /// meanwhile wait and get the same result.
+    ///
+    /// # Errors
+    /// Returns an error if the dump fails or its file is not written in time.
+    pub async fn served_zone_names<F, Fut>(&self, dump: F) -> Result<BTreeSet<String>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let mut served = self.served.lock().await;
+        if let Some(cached) = served.as_ref() {
+            if cached.taken.elapsed() < self.ttl {
+                return Ok(cached.names.clone());
+            }
+        }
+
+        let taken = Instant::now();
+        self.remove_dump_file().await?;
+        dump().await?;
+        let names: BTreeSet<String> = server::read_bind_file(&self.dump_file(), |dump| {
+            server::is_complete_dump(dump).then(|| {
+                rndc_parser::parse_dump_zones(dump)
+                    .into_iter()
+                    .filter(|z| z.class == "IN" && z.view.is_none() && !z.automatic_empty)
+                    .map(|z| z.name.trim_end_matches('.').to_string())
+                    // Hint and redirect zones ("."), which cannot be addressed
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            })
+        })
+        .await?;
+
+        *served = Some(ServedZones {
+            taken,
+            names: names.clone(),
+        });
+        Ok(names)
+    }
+
+    /// Record a zone bindcar has added
+    pub async fn zone_added(&self, zone_name: &str) {
+        if let Some(cached) = self.served.lock().await.as_mut() {
+            cached
+                .names
+                .insert(zone_name.trim_end_matches('.').to_string());
+        }
+    }
+
+    /// Record a zone bindcar has deleted
+    pub async fn zone_removed(&self, zone_name: &str) {
+        if let Some(cached) = self.served.lock().await.as_mut() {
+            cached.names.remove(zone_name.trim_end_matches('.'));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for BIND's dump files and the served zone cache
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::bind_files::*;
+
+    fn zone_dump(zones: &[&str]) -> String {
+        let mut dump = String::from(";\n; Start view _default\n;\n");
+        for zone in zones {
+            dump.push_str(&format!(
+                ";\n; Zone dump of '{zone}/IN'\n;\n{zone}.\t3600\tIN SOA\tns1.{zone}. admin.{zone}. 1 3600 600 604800 86400\n"
+            ));
+        }
+        dump.push_str("; Dump complete\n");
+        dump
+    }
+
+    /// Stand-in for `rndc dumpdb -zones` that writes `zones` and counts calls
+    async fn fake_dump(
+        path: PathBuf,
+        zones: &[&str],
+        calls: &AtomicUsize,
+    ) -> anyhow::Result<String> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::fs::write(path, zone_dump(zones)).await?;
+        Ok(String::new())
+    }
+
+    #[tokio::test]
+    async fn test_served_zone_names_are_cached() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files = BindFiles::new(dir.path(), Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        let first = files
+            .served_zone_names(|| fake_dump(files.dump_file(), &["example.com"], &calls))
+            .await
+            .unwrap();
+        let second = files
+            .served_zone_names(|| fake_dump(files.dump_file(), &["other.com"], &calls))
+            .await
+            .unwrap();
+
+        assert_eq!(first.into_iter().collect::<Vec<_>>(), vec!["example.com"]);
+        assert_eq!(second.into_iter().collect::<Vec<_>>(), vec!["example.com"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_listings_share_one_dump() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files = BindFiles::new(dir.path(), Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        let (a, b) = tokio::join!(
+            files.served_zone_names(|| fake_dump(files.dump_file(), &["a.com", "b.com"], &calls)),
+            files.served_zone_names(|| fake_dump(files.dump_file(), &["c.com"], &calls)),
+        );
+
+        assert_eq!(a.unwrap(), b.unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_cache_dumps_again() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files = BindFiles::new(dir.path(), Duration::ZERO);
+        let calls = AtomicUsize::new(0);
+
+        files
+            .served_zone_names(|| fake_dump(files.dump_file(), &["example.com"], &calls))
+            .await
+            .unwrap();
+        let names = files
+            .served_zone_names(|| fake_dump(files.dump_file(), &["other.com"], &calls))
+            .await
+            .unwrap();
+
+        assert_eq!(names.into_iter().collect::<Vec<_>>(), vec!["other.com"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_previous_dump_is_not_read() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files = BindFiles::new(dir.path(), Duration::ZERO);
+        std::fs::write(files.dump_file(), zone_dump(&["stale.com"])).unwrap();
+
+        // BIND writes the new dump some time after rndc returns.
+        let path = files.dump_file();
+        let names = files
+            .served_zone_names(|| async move {
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    tokio::fs::write(path, zone_dump(&["fresh.com"]))
+                        .await
+                        .unwrap();
+                });
+                Ok(String::new())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(names.into_iter().collect::<Vec<_>>(), vec!["fresh.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_added_and_removed_zones_update_the_cache() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files = BindFiles::new(dir.path(), Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        // Nothing is cached yet, so there is nothing to update.
+        files.zone_added("early.com").await;
+
+        files
+            .served_zone_names(|| fake_dump(files.dump_file(), &["a.com", "b.com"], &calls))
+            .await
+            .unwrap();
+        files.zone_added("c.com.").await;
+        files.zone_removed("a.com").await;
+
+        let names = files
+            .served_zone_names(|| fake_dump(files.dump_file(), &[], &calls))
+            .await
+            .unwrap();
+        assert_eq!(
+            names.into_iter().collect::<Vec<_>>(),
+            vec!["b.com", "c.com"]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_root_zones_are_not_listed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files = BindFiles::new(dir.path(), Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        let names = files
+            .served_zone_names(|| fake_dump(files.dump_file(), &["", "example.com"], &calls))
+            .await
+            .unwrap();
+        assert_eq!(names.into_iter().collect::<Vec<_>>(), vec!["example.com"]);
+    }
+
+    #[test]
+    fn test_file_paths() {
+        let files = BindFiles::new("/var/cache/bind", DEFAULT_ZONE_LIST_CACHE_TTL);
+        assert_eq!(
+            files.dump_file(),
+            PathBuf::from("/var/cache/bind/named_dump.db")
+        );
+        assert_eq!(
+            files.stats_file(),
+            PathBuf::from("/var/cache/bind/named.stats")
+        );
+    }
+}
@@ -0,0 +1,567 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! RFC 9432 catalog zone maintenance
+//!
+//! When `BIND_CATALOG_ZONE` names a catalog zone served by BIND9, every primary
+//! zone created through bindcar is added to it as a member and removed again
+//! when the zone is deleted. Secondaries that consume the catalog then
+//! provision and deprovision the member zones on their own. A failed catalog
+//! update is returned as a [`CatalogError`] and retried through
+//! [`put_member`] or [`delete_member`].
+//!
+//! The member's unique id is derived from its zone name, so a member's records
+//! can be replaced or removed without reading the catalog first:
+//!
+//! ```text
+//! <id>.zones.<catalog>.        IN PTR <member>.
+//! group.<id>.zones.<catalog>.  IN TXT "<group>"
+//! coo.<id>.zones.<catalog>.    IN PTR <new catalog>.
+//! ```
+//!
+//! The catalog zone itself (SOA, NS and `version` TXT "2") is created by the
+//! operator; bindcar only maintains its member records, through the same
+//! dynamic update path as record changes.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{error, info};
+use utoipa::ToSchema;
+
+use crate::{
+    metrics,
+    nsupdate::NsupdateExecutor,
+    types::{ApiError, AppState},
+    zone_file_parser::{zone_apex, ResourceRecord},
+    zones::{validate_zone_name, ZoneResponse},
+};
+
+/// Environment variable naming the catalog zone to maintain
+pub const CATALOG_ZONE_ENV: &str = "BIND_CATALOG_ZONE";
+
+/// TTL of member records; RFC 9432 gives catalog TTLs no meaning.
+const CATALOG_RECORD_TTL: u32 = 0;
+
+/// Number of SHA-256 bytes used for a member's unique id (40 hex characters).
+const MEMBER_ID_BYTES: usize = 20;
+
+/// Maximum length of a TXT character-string.
+const MAX_GROUP_LEN: usize = 255;
+
+/// Catalog properties of a member zone (RFC 9432 section 4.4)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogProperties {
+    /// Groups the member belongs to; consumers map these to zone options
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+
+    /// Change of ownership: the catalog zone the member is migrating to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coo: Option<String>,
+}
+
+/// A member zone as listed in a catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogMember {
+    /// Member zone name
+    pub zone: String,
+
+    /// Unique id label of the member
+    pub id: String,
+
+    /// Groups the member belongs to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+
+    /// Change of ownership target catalog, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coo: Option<String>,
+}
+
+/// Catalog zone membership
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogResponse {
+    /// Catalog zone name
+    pub catalog: String,
+
+    /// Schema version from the `version` TXT record ("2" for RFC 9432)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Number of members
+    pub count: usize,
+
+    /// Members, sorted by zone name
+    pub members: Vec<CatalogMember>,
+}
+
+/// Catalog membership change that a zone operation could not make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogOperation {
+    /// Adding a created zone
+    Add,
+    /// Removing a deleted zone
+    Remove,
+}
+
+/// A catalog update that failed after the zone itself was created or deleted
+///
+/// Secondaries consuming the catalog do not see the change until it is
+/// retried with `PUT` or `DELETE /api/v1/catalogs/{catalog}/members/{zone}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogError {
+    /// Catalog zone name
+    pub catalog: String,
+    /// Member zone name
+    pub zone: String,
+    /// The membership change that failed
+    pub operation: CatalogOperation,
+    /// What went wrong and how to retry
+    pub message: String,
+}
+
+impl CatalogError {
+    /// A failed add or remove of `zone_name` in `catalog`
+    pub fn new(catalog: &str, zone_name: &str, operation: CatalogOperation) -> Self {
+        let (verb, method) = match operation {
+            CatalogOperation::Add => ("added to", "PUT"),
+            CatalogOperation::Remove => ("removed from", "DELETE"),
+        };
+        Self {
+            catalog: catalog.to_string(),
+            zone: zone_name.to_string(),
+            operation,
+            message: format!(
+                "zone was not {} catalog zone {}; retry with {} /api/v1/catalogs/{}/members/{}",
+                verb, catalog, method, catalog, zone_name
+            ),
+        }
+    }
+}
+
+/// Read and validate the catalog zone from `BIND_CATALOG_ZONE`.
+///
+/// Returns `None` when the variable is unset or empty.
+///
+/// # Errors
+/// Returns an error if the value is not a valid zone name.
+pub fn catalog_zone_from_env() -> Result<Option<String>> {
+    match std::env::var(CATALOG_ZONE_ENV) {
+        Ok(zone) if !zone.is_empty() => {
+            validate_zone_name(&zone)
+                .map_err(|e| anyhow::anyhow!("invalid {}: {}", CATALOG_ZONE_ENV, e))?;
+            Ok(Some(zone.trim_end_matches('.').to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Unique id of a member zone: the leading hex of the SHA-256 of its
+/// lower-case name, so the same zone always maps to the same id.
+pub fn member_id(zone_name: &str) -> String {
+    let digest = Sha256::digest(zone_apex(zone_name).to_ascii_lowercase().as_bytes());
+    digest[..MEMBER_ID_BYTES]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Owner name of a member's PTR record
+fn member_owner(catalog: &str, zone_name: &str) -> String {
+    format!("{}.zones.{}", member_id(zone_name), zone_apex(catalog))
+}
+
+/// Validate member properties before they reach an nsupdate script.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] for an empty, overlong or non-printable
+/// group, or a `coo` that is not a valid zone name.
+pub fn validate_properties(properties: &CatalogProperties) -> Result<(), ApiError> {
+    for group in &properties.groups {
+        if group.is_empty()
+            || group.len() > MAX_GROUP_LEN
+            || !group
+                .chars()
+                .all(|c| c == ' ' || (c.is_ascii_graphic() && c != '"' && c != '\\'))
+        {
+            return Err(ApiError::InvalidRequest(format!(
+                "Invalid catalog group '{}': must be 1-{} printable ASCII characters without quotes or backslashes",
+                group, MAX_GROUP_LEN
+            )));
+        }
+    }
+    if let Some(coo) = &properties.coo {
+        validate_zone_name(coo)?;
+    }
+    Ok(())
+}
+
+/// Deletions that remove every record of a member from the catalog.
+///
+/// Records carry no data, so each deletes the whole RRset at its name.
+pub fn member_removals(catalog: &str, zone_name: &str) -> Vec<ResourceRecord> {
+    let owner = member_owner(catalog, zone_name);
+    [
+        (owner.clone(), "PTR"),
+        (format!("group.{}", owner), "TXT"),
+        (format!("coo.{}", owner), "PTR"),
+    ]
+    .into_iter()
+    .map(|(name, record_type)| ResourceRecord {
+        name,
+        ttl: None,
+        record_type: record_type.to_string(),
+        data: String::new(),
+    })
+    .collect()
+}
+
+/// Records that make `zone_name` a member of `catalog`.
+pub fn member_records(
+    catalog: &str,
+    zone_name: &str,
+    properties: &CatalogProperties,
+) -> Vec<ResourceRecord> {
+    let owner = member_owner(catalog, zone_name);
+    let record = |name: String, record_type: &str, data: String| ResourceRecord {
+        name,
+        ttl: Some(CATALOG_RECORD_TTL),
+        record_type: record_type.to_string(),
+        data,
+    };
+
+    let mut records = vec![record(owner.clone(), "PTR", zone_apex(zone_name))];
+    for group in &properties.groups {
+        records.push(record(
+            format!("group.{}", owner),
+            "TXT",
+            format!("\"{}\"", group),
+        ));
+    }
+    if let Some(coo) = &properties.coo {
+        records.push(record(format!("coo.{}", owner), "PTR", zone_apex(coo)));
+    }
+    records
+}
+
+/// nsupdate script that adds (or replaces) a member of `catalog`.
+pub fn add_member_script(
+    nsupdate: &NsupdateExecutor,
+    catalog: &str,
+    zone_name: &str,
+    properties: &CatalogProperties,
+) -> Result<String> {
+    nsupdate.apply_changes_script(
+        catalog,
+        &member_removals(catalog, zone_name),
+        &member_records(catalog, zone_name, properties),
+        CATALOG_RECORD_TTL,
+    )
+}
+
+/// nsupdate script that removes a member from `catalog`.
+pub fn remove_member_script(
+    nsupdate: &NsupdateExecutor,
+    catalog: &str,
+    zone_name: &str,
+) -> Result<String> {
+    nsupdate.apply_changes_script(
+        catalog,
+        &member_removals(catalog, zone_name),
+        &[],
+        CATALOG_RECORD_TTL,
+    )
+}
+
+/// Add `zone_name` to `catalog`, replacing any properties it had.
+///
+/// Stale records from an earlier membership are removed in the same update.
+pub async fn add_member(
+    nsupdate: &NsupdateExecutor,
+    catalog: &str,
+    zone_name: &str,
+    properties: &CatalogProperties,
+) -> Result<String> {
+    info!("Adding zone {} to catalog {}", zone_name, catalog);
+    nsupdate
+        .apply_changes(
+            catalog,
+            &member_removals(catalog, zone_name),
+            &member_records(catalog, zone_name, properties),
+            CATALOG_RECORD_TTL,
+        )
+        .await
+}
+
+/// Remove `zone_name` from `catalog`; a no-op for zones that are not members.
+pub async fn remove_member(
+    nsupdate: &NsupdateExecutor,
+    catalog: &str,
+    zone_name: &str,
+) -> Result<String> {
+    info!("Removing zone {} from catalog {}", zone_name, catalog);
+    nsupdate
+        .apply_changes(
+            catalog,
+            &member_removals(catalog, zone_name),
+            &[],
+            CATALOG_RECORD_TTL,
+        )
+        .await
+}
+
+/// Strip the quotes of a single TXT character-string.
+fn unquote(data: &str) -> String {
+    data.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(data)
+        .to_string()
+}
+
+/// Extract the schema version and members from a catalog zone's records.
+///
+/// Records that do not fit the RFC 9432 layout are ignored, as consumers
+/// ignore them. Members are sorted by zone name.
+pub fn parse_catalog(
+    catalog: &str,
+    records: &[ResourceRecord],
+) -> (Option<String>, Vec<CatalogMember>) {
+    let apex = zone_apex(catalog).to_ascii_lowercase();
+    let version_name = format!("version.{}", apex);
+    let zones_suffix = format!(".zones.{}", apex);
+
+    let mut version = None;
+    let mut members: BTreeMap<String, CatalogMember> = BTreeMap::new();
+    let mut properties: Vec<(String, &str, &ResourceRecord)> = Vec::new();
+
+    for record in records {
+        let name = record.name.to_ascii_lowercase();
+        if name == version_name && record.record_type == "TXT" {
+            version = Some(unquote(&record.data));
+            continue;
+        }
+        let Some(labels) = name.strip_suffix(&zones_suffix) else {
+            continue;
+        };
+        match labels.split_once('.') {
+            None if record.record_type == "PTR" => {
+                members.insert(
+                    labels.to_string(),
+                    CatalogMember {
+                        zone: record.data.trim_end_matches('.').to_string(),
+                        id: labels.to_string(),
+                        groups: Vec::new(),
+                        coo: None,
+                    },
+                );
+            }
+            Some(("group", id)) => properties.push((id.to_string(), "group", record)),
+            Some(("coo", id)) => properties.push((id.to_string(), "coo", record)),
+            _ => {}
+        }
+    }
+
+    for (id, property, record) in properties {
+        let Some(member) = members.get_mut(&id) else {
+            continue;
+        };
+        match (property, record.record_type.as_str()) {
+            ("group", "TXT") => member.groups.push(unquote(&record.data)),
+            ("coo", "PTR") => member.coo = Some(record.data.trim_end_matches('.').to_string()),
+            _ => {}
+        }
+    }
+
+    let mut members: Vec<_> = members.into_values().collect();
+    members.sort_by(|a, b| a.zone.cmp(&b.zone));
+    (version, members)
+}
+
+/// List the members of a catalog zone
+///
+/// Reads the catalog with a TSIG-signed AXFR and returns its schema version and
+/// member zones with their group and coo properties.
+#[utoipa::path(
+    get,
+    path = "/api/v1/catalogs/{name}",
+    params(
+        ("name" = String, Path, description = "Catalog zone name")
+    ),
+    responses(
+        (status = 200, description = "Catalog members", body = CatalogResponse),
+        (status = 400, description = "Invalid zone name"),
+        (status = 404, description = "Catalog zone not found"),
+        (status = 500, description = "Zone transfer failed"),
+    ),
+    tag = "catalogs"
+)]
+pub async fn get_catalog(
+    State(state): State<AppState>,
+    Path(catalog): Path<String>,
+) -> Result<Json<CatalogResponse>, ApiError> {
+    info!("Listing members of catalog {}", catalog);
+
+    if let Err(e) = validate_zone_name(&catalog) {
+        metrics::record_zone_operation("catalog", false);
+        return Err(e);
+    }
+
+    state.rndc.showzone(&catalog).await.map_err(|e| {
+        metrics::record_zone_operation("catalog", false);
+        if e.to_string().contains("not found") {
+            ApiError::ZoneNotFound(catalog.clone())
+        } else {
+            ApiError::RndcError(e.to_string())
+        }
+    })?;
+
+    let records = state.nsupdate.axfr(&catalog).await.map_err(|e| {
+        error!("AXFR of catalog {} failed: {:#}", catalog, e);
+        metrics::record_zone_operation("catalog", false);
+        ApiError::NsupdateError(format!("Failed to transfer zone: {}", e))
+    })?;
+
+    let (version, members) = parse_catalog(&catalog, &records);
+    metrics::record_zone_operation("catalog", true);
+
+    Ok(Json(CatalogResponse {
+        catalog,
+        version,
+        count: members.len(),
+        members,
+    }))
+}
+
+/// Check that `catalog` is the configured catalog zone and `zone_name` can be
+/// one of its members.
+fn validate_member_request(
+    state: &AppState,
+    catalog: &str,
+    zone_name: &str,
+) -> Result<String, ApiError> {
+    validate_zone_name(catalog)?;
+    validate_zone_name(zone_name)?;
+    let configured = state
+        .catalog_zone
+        .as_deref()
+        .filter(|configured| zone_apex(configured).eq_ignore_ascii_case(&zone_apex(catalog)))
+        .ok_or_else(|| {
+            ApiError::InvalidRequest(format!(
+                "{} is not the configured catalog zone ({})",
+                catalog, CATALOG_ZONE_ENV
+            ))
+        })?;
+    if zone_apex(zone_name).eq_ignore_ascii_case(&zone_apex(configured)) {
+        return Err(ApiError::InvalidRequest(
+            "The catalog zone cannot be its own member".to_string(),
+        ));
+    }
+    Ok(configured.to_string())
+}
+
+/// Add a zone to the catalog
+///
+/// Writes the member's records, replacing any properties it had. Use it to
+/// retry a `catalogError` with operation `add` from a zone create.
+#[utoipa::path(
+    put,
+    path = "/api/v1/catalogs/{name}/members/{zone}",
+    request_body = CatalogProperties,
+    params(
+        ("name" = String, Path, description = "Catalog zone name"),
+        ("zone" = String, Path, description = "Member zone name")
+    ),
+    responses(
+        (status = 200, description = "Zone is a member of the catalog", body = ZoneResponse),
+        (status = 400, description = "Invalid name or properties, or not the configured catalog zone"),
+        (status = 500, description = "Catalog update failed"),
+    ),
+    tag = "catalogs"
+)]
+pub async fn put_member(
+    State(state): State<AppState>,
+    Path((catalog, zone_name)): Path<(String, String)>,
+    Json(properties): Json<CatalogProperties>,
+) -> Result<Json<ZoneResponse>, ApiError> {
+    let catalog = validate_member_request(&state, &catalog, &zone_name)?;
+    validate_properties(&properties)?;
+
+    let output = add_member(&state.nsupdate, &catalog, &zone_name, &properties)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to add zone {} to catalog {}: {:#}",
+                zone_name, catalog, e
+            );
+            metrics::record_zone_operation("catalog_add", false);
+            ApiError::NsupdateError(format!("Failed to update catalog: {}", e))
+        })?;
+    metrics::record_zone_operation("catalog_add", true);
+
+    Ok(Json(ZoneResponse {
+        success: true,
+        message: format!("Zone {} added to catalog {}", zone_name, catalog),
+        details: (!output.trim().is_empty()).then_some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
+    }))
+}
+
+/// Remove a zone from the catalog
+///
+/// Deletes the member's records; a zone that is not a member is left as is.
+/// Use it to retry a `catalogError` with operation `remove` from a zone delete.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/catalogs/{name}/members/{zone}",
+    params(
+        ("name" = String, Path, description = "Catalog zone name"),
+        ("zone" = String, Path, description = "Member zone name")
+    ),
+    responses(
+        (status = 200, description = "Zone is not a member of the catalog", body = ZoneResponse),
+        (status = 400, description = "Invalid name, or not the configured catalog zone"),
+        (status = 500, description = "Catalog update failed"),
+    ),
+    tag = "catalogs"
+)]
+pub async fn delete_member(
+    State(state): State<AppState>,
+    Path((catalog, zone_name)): Path<(String, String)>,
+) -> Result<Json<ZoneResponse>, ApiError> {
+    let catalog = validate_member_request(&state, &catalog, &zone_name)?;
+
+    let output = remove_member(&state.nsupdate, &catalog, &zone_name)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to remove zone {} from catalog {}: {:#}",
+                zone_name, catalog, e
+            );
+            metrics::record_zone_operation("catalog_remove", false);
+            ApiError::NsupdateError(format!("Failed to update catalog: {}", e))
+        })?;
+    metrics::record_zone_operation("catalog_remove", true);
+
+    Ok(Json(ZoneResponse {
+        success: true,
+        message: format!("Zone {} removed from catalog {}", zone_name, catalog),
+        details: (!output.trim().is_empty()).then_some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
+    }))
+}
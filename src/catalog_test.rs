@@ -0,0 +1,275 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for catalog zone maintenance
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        extract::{Path, State},
+        Json,
+    };
+
+    use crate::catalog::*;
+    use crate::nsupdate::NsupdateExecutor;
+    use crate::rndc::RndcExecutor;
+    use crate::types::{ApiError, AppState};
+    use crate::zone_file_parser::ResourceRecord;
+    use crate::zones::ZoneResponse;
+
+    const CATALOG: &str = "catalog.invalid";
+
+    fn executor() -> NsupdateExecutor {
+        NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap()
+    }
+
+    fn app_state(catalog_zone: Option<&str>) -> AppState {
+        let rndc = RndcExecutor::new(
+            "127.0.0.1:953".to_string(),
+            "sha256".to_string(),
+            "dGVzdC1zZWNyZXQtaGVyZQ==".to_string(),
+        )
+        .unwrap();
+        AppState {
+            rndc: Arc::new(rndc),
+            nsupdate: Arc::new(executor()),
+            zone_dir: "/tmp".to_string(),
+            catalog_zone: catalog_zone.map(str::to_string),
+            bind_files: Arc::new(crate::bind_files::BindFiles::new(
+                "/tmp",
+                crate::bind_files::DEFAULT_ZONE_LIST_CACHE_TTL,
+            )),
+        }
+    }
+
+    fn member_path(catalog: &str, zone: &str) -> Path<(String, String)> {
+        Path((catalog.to_string(), zone.to_string()))
+    }
+
+    fn record(name: &str, record_type: &str, data: &str) -> ResourceRecord {
+        ResourceRecord {
+            name: name.to_string(),
+            ttl: Some(0),
+            record_type: record_type.to_string(),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_member_id_is_stable_and_case_insensitive() {
+        let id = member_id("example.com");
+        assert_eq!(id.len(), 40);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(id, member_id("Example.COM."));
+        assert_ne!(id, member_id("example.org"));
+    }
+
+    #[test]
+    fn test_member_records_layout() {
+        let properties = CatalogProperties {
+            groups: vec!["signed".to_string()],
+            coo: Some("new-catalog.invalid".to_string()),
+        };
+        let records = member_records(CATALOG, "example.com", &properties);
+        let owner = format!("{}.zones.catalog.invalid.", member_id("example.com"));
+
+        assert_eq!(
+            records,
+            vec![
+                record(&owner, "PTR", "example.com."),
+                record(&format!("group.{}", owner), "TXT", "\"signed\""),
+                record(&format!("coo.{}", owner), "PTR", "new-catalog.invalid."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_member_script_replaces_existing_membership() {
+        let properties = CatalogProperties::default();
+        let script = add_member_script(&executor(), CATALOG, "example.com", &properties).unwrap();
+        let owner = format!("{}.zones.catalog.invalid.", member_id("example.com"));
+
+        assert_eq!(
+            script,
+            format!(
+                "server 127.0.0.1 53\nzone catalog.invalid\n\
+                 update delete {owner} IN PTR\n\
+                 update delete group.{owner} IN TXT\n\
+                 update delete coo.{owner} IN PTR\n\
+                 update add {owner} 0 IN PTR example.com.\n\
+                 send\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_remove_member_script_only_deletes() {
+        let script = remove_member_script(&executor(), CATALOG, "example.com").unwrap();
+        assert_eq!(script.matches("update delete").count(), 3);
+        assert!(!script.contains("update add"));
+    }
+
+    #[test]
+    fn test_validate_properties() {
+        let valid = CatalogProperties {
+            groups: vec!["internal zones".to_string()],
+            coo: Some("other-catalog.example".to_string()),
+        };
+        assert!(validate_properties(&valid).is_ok());
+
+        for group in ["", "quo\"te", "back\\slash", "new\nline", &"x".repeat(256)] {
+            let properties = CatalogProperties {
+                groups: vec![group.to_string()],
+                coo: None,
+            };
+            assert!(validate_properties(&properties).is_err(), "{:?}", group);
+        }
+
+        let bad_coo = CatalogProperties {
+            groups: vec![],
+            coo: Some("../etc".to_string()),
+        };
+        assert!(validate_properties(&bad_coo).is_err());
+    }
+
+    #[test]
+    fn test_parse_catalog_round_trips_member_records() {
+        let mut records = vec![
+            record(
+                "catalog.invalid.",
+                "SOA",
+                "invalid. invalid. 1 3600 600 86400 0",
+            ),
+            record("catalog.invalid.", "NS", "invalid."),
+            record("version.catalog.invalid.", "TXT", "\"2\""),
+        ];
+        records.extend(member_records(
+            CATALOG,
+            "example.org",
+            &CatalogProperties::default(),
+        ));
+        records.extend(member_records(
+            CATALOG,
+            "example.com",
+            &CatalogProperties {
+                groups: vec!["a".to_string(), "b".to_string()],
+                coo: Some("next.invalid".to_string()),
+            },
+        ));
+        // Properties of an unknown member and unrelated records are ignored
+        records.push(record(
+            "group.deadbeef.zones.catalog.invalid.",
+            "TXT",
+            "\"orphan\"",
+        ));
+        records.push(record("ext.catalog.invalid.", "TXT", "\"x\""));
+
+        let (version, members) = parse_catalog("Catalog.Invalid", &records);
+
+        assert_eq!(version.as_deref(), Some("2"));
+        assert_eq!(
+            members,
+            vec![
+                CatalogMember {
+                    zone: "example.com".to_string(),
+                    id: member_id("example.com"),
+                    groups: vec!["a".to_string(), "b".to_string()],
+                    coo: Some("next.invalid".to_string()),
+                },
+                CatalogMember {
+                    zone: "example.org".to_string(),
+                    id: member_id("example.org"),
+                    groups: vec![],
+                    coo: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_catalog_error_names_the_retry() {
+        let error = CatalogError::new(CATALOG, "example.com", CatalogOperation::Add);
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "catalog": CATALOG,
+                "zone": "example.com",
+                "operation": "add",
+                "message": "zone was not added to catalog zone catalog.invalid; retry with PUT /api/v1/catalogs/catalog.invalid/members/example.com",
+            })
+        );
+
+        let error = CatalogError::new(CATALOG, "example.com", CatalogOperation::Remove);
+        assert!(error.message.contains("removed from catalog zone"));
+        assert!(error.message.contains("retry with DELETE"));
+    }
+
+    #[test]
+    fn test_zone_response_carries_catalog_error() {
+        let mut response = ZoneResponse {
+            success: true,
+            message: "Zone example.com created successfully".to_string(),
+            details: None,
+            warnings: Vec::new(),
+            catalog_error: None,
+            plan: None,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("catalogError").is_none());
+
+        response.catalog_error = Some(CatalogError::new(
+            CATALOG,
+            "example.com",
+            CatalogOperation::Add,
+        ));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["catalogError"]["operation"], "add");
+        assert_eq!(json["catalogError"]["zone"], "example.com");
+    }
+
+    #[tokio::test]
+    async fn test_member_endpoints_require_the_configured_catalog() {
+        for state in [app_state(None), app_state(Some("other.invalid"))] {
+            let result = put_member(
+                State(state.clone()),
+                member_path(CATALOG, "example.com"),
+                Json(CatalogProperties::default()),
+            )
+            .await;
+            assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+
+            let result = delete_member(State(state), member_path(CATALOG, "example.com")).await;
+            assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_member_endpoints_reject_invalid_members() {
+        let state = app_state(Some(CATALOG));
+        for zone in ["Catalog.Invalid.", "../../etc/passwd"] {
+            let result = put_member(
+                State(state.clone()),
+                member_path(CATALOG, zone),
+                Json(CatalogProperties::default()),
+            )
+            .await;
+            assert!(matches!(result, Err(ApiError::InvalidRequest(_))), "{zone}");
+
+            let result = delete_member(State(state.clone()), member_path(CATALOG, zone)).await;
+            assert!(matches!(result, Err(ApiError::InvalidRequest(_))), "{zone}");
+        }
+
+        let result = put_member(
+            State(state),
+            member_path(CATALOG, "example.com"),
+            Json(CatalogProperties {
+                groups: vec!["bad\"group".to_string()],
+                ..Default::default()
+            }),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+    }
+}
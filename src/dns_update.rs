@@ -0,0 +1,457 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Dynamic update (RFC 2136) client
+//!
+//! Builds DNS UPDATE messages and sends them straight to BIND9, replacing the
+//! `nsupdate` subprocess. An UPDATE reuses the four message sections:
+//!
+//! ```text
+//! zone           the zone, as a single SOA question
+//! prerequisites  RRs whose class and type encode the condition (section 2.4)
+//! updates        RRs to add (class IN), RRsets to delete (class ANY) and
+//!                single RRs to delete (class NONE) (section 2.5)
+//! additional     the TSIG RR, when a key is configured
+//! ```
+//!
+//! Requests go over UDP, sent up to three times, and are retried over TCP only
+//! when the response is truncated; requests larger than a UDP message, and
+//! unsigned requests with prerequisites, always use TCP. Signed requests must
+//! get a signed response that verifies (RFC 8945 §5.3), and every non-NOERROR
+//! rcode becomes a typed error: [`PrerequisiteFailed`] for the four
+//! prerequisite rcodes in a trusted response, [`UpdateOutcomeUnknown`] for
+//! those answering a retransmission, [`UpdateRejected`] for everything else.
+//!
+//! A response is trusted if it verified under TSIG or, without a key, arrived
+//! over TCP: an unsigned UDP response cannot be told from a spoofed one, while
+//! a spoofer would have to take over the TCP connection bindcar opened to the
+//! configured server. A sent UPDATE may also have been applied with only its
+//! response lost. Neither an untrusted response nor an answer to a
+//! retransmission is therefore reported as a prerequisite failure: a caller
+//! doing compare-and-swap would conclude that its change was not applied.
+
+use anyhow::{Context, Result};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tracing::debug;
+
+use crate::dns_wire::{
+    self, TsigKey, TsigVerifier, CLASS_ANY, CLASS_IN, CLASS_NONE, TYPE_ANY, TYPE_SOA,
+};
+use crate::nsupdate::{Prerequisite, PrerequisiteFailed, UpdateCommand};
+
+/// UPDATE opcode (RFC 2136 section 1.3)
+const OPCODE_UPDATE: u16 = 5;
+
+/// Header flags of a request: opcode UPDATE, every other bit clear
+const UPDATE_FLAGS: u16 = OPCODE_UPDATE << 11;
+
+/// QR bit, set in responses
+const FLAG_RESPONSE: u16 = 0x8000;
+
+/// Largest request sent over UDP (RFC 1035 section 4.2.1; updates carry no EDNS)
+const MAX_UDP_MESSAGE: usize = 512;
+
+/// Largest message, limited by the TCP length prefix
+const MAX_MESSAGE: usize = u16::MAX as usize;
+
+/// Room left for the TSIG record: a key name of up to 255 bytes, the
+/// algorithm name and a SHA-512 MAC
+const TSIG_ALLOWANCE: usize = 512;
+
+/// Largest unsigned update, so that the signed message still fits
+const MAX_UPDATE_MESSAGE: usize = MAX_MESSAGE - TSIG_ALLOWANCE;
+
+/// Number of UDP sends before giving up
+const UDP_ATTEMPTS: u32 = 3;
+
+/// Time to wait for a UDP response before sending again
+const UDP_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Default time limit for an update, including retries and the TCP fallback
+pub const DEFAULT_UPDATE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An update the server refused with an rcode other than a prerequisite failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("update rejected ({}): {}", dns_wire::rcode_name(*.rcode), rejection_hint(*.rcode))]
+pub struct UpdateRejected {
+    /// Response code, or the TSIG error (BADSIG, BADKEY, BADTIME) when the
+    /// server could not authenticate the request
+    pub rcode: u16,
+}
+
+/// A prerequisite failure answering a retransmitted update
+///
+/// An earlier copy of the update may have been applied with only its response
+/// lost, in which case the prerequisites were checked against the update's
+/// own result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{failure} after the update was retransmitted; an earlier attempt may have been applied")]
+pub struct UpdateOutcomeUnknown {
+    /// The failure the server reported
+    pub failure: PrerequisiteFailed,
+}
+
+/// What an rcode most likely means for a dynamic update
+fn rejection_hint(rcode: u16) -> &'static str {
+    match rcode {
+        1 => "format error (check record syntax)",
+        2 => "server failure (check BIND9 logs)",
+        3 | 6..=8 => "prerequisite failure in an unsigned UDP response (not trusted)",
+        4 => "server does not support dynamic updates",
+        5 => "zone refused the update (check allow-update configuration)",
+        9 => "not authorized (check TSIG key configuration)",
+        10 => "name outside the zone, or zone not found on server",
+        16..=18 => "TSIG key rejected (check key name, secret and clock)",
+        _ => "unexpected response code",
+    }
+}
+
+fn type_code(record_type: &str) -> Result<u16> {
+    dns_wire::record_type_code(record_type)
+        .with_context(|| format!("unknown record type: {}", record_type))
+}
+
+fn push_record(
+    msg: &mut Vec<u8>,
+    name: &str,
+    rtype: u16,
+    class: u16,
+    ttl: u32,
+    rdata: &[u8],
+) -> Result<()> {
+    dns_wire::encode_name(name, false, msg).with_context(|| format!("invalid name: {}", name))?;
+    msg.extend_from_slice(&rtype.to_be_bytes());
+    msg.extend_from_slice(&class.to_be_bytes());
+    msg.extend_from_slice(&ttl.to_be_bytes());
+    msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+    msg.extend_from_slice(rdata);
+    Ok(())
+}
+
+fn rdata(rtype: u16, record_type: &str, value: &str) -> Result<Vec<u8>> {
+    dns_wire::encode_rdata(rtype, value)
+        .with_context(|| format!("invalid {} value: {}", record_type, value))
+}
+
+fn section_count(section: &str, len: usize) -> Result<[u8; 2]> {
+    u16::try_from(len)
+        .map(u16::to_be_bytes)
+        .map_err(|_| anyhow::anyhow!("too many {} for one update", section))
+}
+
+/// Build an unsigned UPDATE message.
+///
+/// # Errors
+/// Returns an error if there are no changes, a name, record type or value
+/// cannot be encoded, or the message would be too large for 65535 bytes once
+/// signed.
+pub fn build_update(
+    id: u16,
+    zone: &str,
+    prerequisites: &[Prerequisite],
+    commands: &[UpdateCommand],
+) -> Result<Vec<u8>> {
+    if commands.is_empty() {
+        anyhow::bail!("an update needs at least one change");
+    }
+
+    let mut msg = Vec::with_capacity(MAX_UDP_MESSAGE);
+    msg.extend_from_slice(&id.to_be_bytes());
+    msg.extend_from_slice(&UPDATE_FLAGS.to_be_bytes());
+    msg.extend_from_slice(&1u16.to_be_bytes()); // ZOCOUNT
+    msg.extend_from_slice(&section_count("prerequisites", prerequisites.len())?);
+    msg.extend_from_slice(&section_count("changes", commands.len())?);
+    msg.extend_from_slice(&0u16.to_be_bytes()); // ADCOUNT
+    dns_wire::encode_name(zone, false, &mut msg)
+        .with_context(|| format!("invalid zone name: {}", zone))?;
+    msg.extend_from_slice(&TYPE_SOA.to_be_bytes());
+    msg.extend_from_slice(&CLASS_IN.to_be_bytes());
+
+    for prerequisite in prerequisites {
+        match prerequisite {
+            Prerequisite::NameInUse { name } => {
+                push_record(&mut msg, name, TYPE_ANY, CLASS_ANY, 0, &[])?
+            }
+            Prerequisite::NameNotInUse { name } => {
+                push_record(&mut msg, name, TYPE_ANY, CLASS_NONE, 0, &[])?
+            }
+            Prerequisite::RrsetExists {
+                name,
+                record_type,
+                value,
+            } if value.is_empty() => {
+                push_record(&mut msg, name, type_code(record_type)?, CLASS_ANY, 0, &[])?
+            }
+            Prerequisite::RrsetExists {
+                name,
+                record_type,
+                value,
+            } => {
+                let rtype = type_code(record_type)?;
+                let rdata = rdata(rtype, record_type, value)?;
+                push_record(&mut msg, name, rtype, CLASS_IN, 0, &rdata)?
+            }
+            Prerequisite::RrsetNotExists { name, record_type } => {
+                push_record(&mut msg, name, type_code(record_type)?, CLASS_NONE, 0, &[])?
+            }
+        }
+    }
+
+    for command in commands {
+        match command {
+            UpdateCommand::Add {
+                name,
+                ttl,
+                record_type,
+                value,
+            } => {
+                let rtype = type_code(record_type)?;
+                let rdata = rdata(rtype, record_type, value)?;
+                push_record(&mut msg, name, rtype, CLASS_IN, *ttl, &rdata)?
+            }
+            UpdateCommand::Delete {
+                name,
+                record_type,
+                value,
+            } if value.is_empty() => {
+                push_record(&mut msg, name, type_code(record_type)?, CLASS_ANY, 0, &[])?
+            }
+            UpdateCommand::Delete {
+                name,
+                record_type,
+                value,
+            } => {
+                let rtype = type_code(record_type)?;
+                let rdata = rdata(rtype, record_type, value)?;
+                push_record(&mut msg, name, rtype, CLASS_NONE, 0, &rdata)?
+            }
+        }
+    }
+
+    if msg.len() > MAX_UPDATE_MESSAGE {
+        anyhow::bail!(
+            "update is {} bytes, more than the {} that fit in a DNS message",
+            msg.len(),
+            MAX_UPDATE_MESSAGE
+        );
+    }
+    Ok(msg)
+}
+
+/// Sign `message` with `key` (if any), send it to `server:port` and check the
+/// response.
+///
+/// UDP is used unless `force_tcp` is set, the signed message is too large for
+/// a datagram, or the message has prerequisites and no key signs it.
+///
+/// # Errors
+/// Returns [`PrerequisiteFailed`], [`UpdateOutcomeUnknown`] or
+/// [`UpdateRejected`] if the server refused the update, and an error if the
+/// server cannot be reached or does not answer, the response is malformed or
+/// fails TSIG verification, or the timeout elapses.
+pub async fn send_update(
+    server: &str,
+    port: u16,
+    message: Vec<u8>,
+    key: Option<&TsigKey>,
+    force_tcp: bool,
+    timeout: Duration,
+) -> Result<()> {
+    tokio::time::timeout(timeout, send(server, port, message, key, force_tcp))
+        .await
+        .map_err(|_| anyhow::anyhow!("update to {}:{} timed out", server, port))?
+}
+
+async fn send(
+    server: &str,
+    port: u16,
+    mut message: Vec<u8>,
+    key: Option<&TsigKey>,
+    force_tcp: bool,
+) -> Result<()> {
+    let id = message
+        .get(..2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .context("UPDATE message has no header")?;
+    let request_mac = match key {
+        Some(key) => Some(key.sign(
+            &mut message,
+            dns_wire::unix_time(),
+            dns_wire::DEFAULT_TSIG_FUDGE,
+        )?),
+        None => None,
+    };
+
+    // Only a TCP response can be trusted without a signature
+    let unsigned_prerequisites = key.is_none() && prerequisite_count(&message) > 0;
+    let udp_response = if force_tcp || unsigned_prerequisites || message.len() > MAX_UDP_MESSAGE {
+        None
+    } else {
+        exchange_udp(server, port, &message, id).await?
+    };
+    let (response, retransmitted, over_tcp) = match udp_response {
+        Some((response, retransmitted)) => (response, retransmitted, false),
+        None => (exchange_tcp(server, port, &message).await?, false, true),
+    };
+
+    let signed = key.zip(request_mac);
+    let trust_unsigned = signed.is_none() && over_tcp;
+    check_response(&response, id, signed, trust_unsigned).map_err(|e| {
+        match e.downcast_ref::<PrerequisiteFailed>() {
+            Some(&failure) if retransmitted => UpdateOutcomeUnknown { failure }.into(),
+            _ => e,
+        }
+    })
+}
+
+/// Number of prerequisite RRs in an UPDATE message (the answer section count)
+fn prerequisite_count(message: &[u8]) -> u16 {
+    message
+        .get(6..8)
+        .map_or(0, |count| u16::from_be_bytes([count[0], count[1]]))
+}
+
+/// Send a request over UDP, retrying on silence.
+///
+/// Returns the response and whether the request was sent more than once, or
+/// `None` if the response was truncated, so the caller retries over TCP.
+/// Silence is not retried over TCP: the update may have been applied.
+async fn exchange_udp(
+    server: &str,
+    port: u16,
+    request: &[u8],
+    id: u16,
+) -> Result<Option<(Vec<u8>, bool)>> {
+    let addr = tokio::net::lookup_host((server, port))
+        .await
+        .with_context(|| format!("Failed to resolve {}:{}", server, port))?
+        .next()
+        .with_context(|| format!("No address for {}", server))?;
+    let local: SocketAddr = if addr.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local)
+        .await
+        .context("Failed to open UDP socket")?;
+    socket
+        .connect(addr)
+        .await
+        .with_context(|| format!("Failed to connect to {}", addr))?;
+
+    let mut buf = vec![0u8; MAX_MESSAGE];
+    for attempt in 1..=UDP_ATTEMPTS {
+        socket
+            .send(request)
+            .await
+            .context("Failed to send UPDATE request")?;
+        let deadline = tokio::time::Instant::now() + UDP_RETRY_INTERVAL;
+        loop {
+            let received = match tokio::time::timeout_at(deadline, socket.recv(&mut buf)).await {
+                Ok(received) => received.context("Failed to receive UPDATE response")?,
+                Err(_) => break,
+            };
+            let response = &buf[..received];
+            // Ignore stray datagrams, e.g. a late answer to an earlier attempt
+            // of another update
+            if response.len() < 12 || response[..2] != id.to_be_bytes() {
+                continue;
+            }
+            if response[2] & 0x02 != 0 {
+                debug!("UDP response to UPDATE is truncated, retrying over TCP");
+                return Ok(None);
+            }
+            return Ok(Some((response.to_vec(), attempt > 1)));
+        }
+        debug!(
+            "No UDP response to UPDATE (attempt {}/{})",
+            attempt, UDP_ATTEMPTS
+        );
+    }
+
+    anyhow::bail!(
+        "no response to UPDATE from {} after {} attempts; it may have been applied",
+        addr,
+        UDP_ATTEMPTS
+    )
+}
+
+/// Send a request over TCP and read one length-prefixed response.
+async fn exchange_tcp(server: &str, port: u16, request: &[u8]) -> Result<Vec<u8>> {
+    let mut stream = TcpStream::connect((server, port))
+        .await
+        .with_context(|| format!("Failed to connect to {}:{}", server, port))?;
+
+    let mut framed = Vec::with_capacity(request.len() + 2);
+    framed.extend_from_slice(&(request.len() as u16).to_be_bytes());
+    framed.extend_from_slice(request);
+    stream
+        .write_all(&framed)
+        .await
+        .context("Failed to send UPDATE request")?;
+
+    let mut len = [0u8; 2];
+    stream
+        .read_exact(&mut len)
+        .await
+        .context("Connection closed before the UPDATE response")?;
+    let mut response = vec![0u8; u16::from_be_bytes(len) as usize];
+    stream
+        .read_exact(&mut response)
+        .await
+        .context("Truncated UPDATE response")?;
+    Ok(response)
+}
+
+/// Check that `raw` answers request `id`, verify its signature and turn its
+/// rcode into a result.
+///
+/// Prerequisite rcodes only become [`PrerequisiteFailed`] in a response that
+/// verified under TSIG, or an unsigned one if `trust_unsigned` is set (an
+/// unsigned request answered over TCP); otherwise they are an
+/// [`UpdateRejected`].
+fn check_response(
+    raw: &[u8],
+    id: u16,
+    signed: Option<(&TsigKey, Vec<u8>)>,
+    trust_unsigned: bool,
+) -> Result<()> {
+    let message = dns_wire::parse_message(raw).context("Malformed UPDATE response")?;
+    if message.id != id {
+        anyhow::bail!("UPDATE response ID mismatch");
+    }
+    if message.flags & FLAG_RESPONSE == 0 || (message.flags >> 11) & 0xF != OPCODE_UPDATE {
+        anyhow::bail!("response is not an UPDATE response");
+    }
+
+    let mut trusted = trust_unsigned;
+    if let Some((key, request_mac)) = signed {
+        match &message.tsig {
+            // The server could not authenticate the request; its TSIG RR
+            // carries the reason and no MAC (RFC 8945 §5.3.2)
+            Some(tsig) if tsig.error != 0 => {
+                return Err(UpdateRejected { rcode: tsig.error }.into());
+            }
+            Some(_) => {
+                TsigVerifier::new(key, request_mac).verify(raw, &message, dns_wire::unix_time())?;
+                trusted = true;
+            }
+            // An unsigned error can only make the update fail, never succeed
+            None if message.rcode() != 0 => {}
+            None => anyhow::bail!("UPDATE response is not TSIG-signed"),
+        }
+    }
+
+    let rcode = message.rcode();
+    if rcode == 0 {
+        return Ok(());
+    }
+    match PrerequisiteFailed::from_rcode(&dns_wire::rcode_name(rcode)) {
+        Some(failure) if trusted => Err(failure.into()),
+        _ => Err(UpdateRejected { rcode }.into()),
+    }
+}
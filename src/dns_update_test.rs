@@ -0,0 +1,840 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for UPDATE message building, RDATA encoding and the update client
+//! against an in-process UDP/TCP server
+
+#[cfg(test)]
+mod tests {
+    use crate::dns_update::*;
+    use crate::dns_wire::*;
+    use crate::nsupdate::{NsupdateExecutor, Prerequisite, PrerequisiteFailed, UpdateCommand};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream, UdpSocket};
+
+    const SECRET: &str = "dGVzdC1zZWNyZXQtaGVyZQ==";
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn key() -> TsigKey {
+        TsigKey::new("update-key", "hmac-sha256", SECRET).unwrap()
+    }
+
+    fn add_a(value: &str) -> UpdateCommand {
+        UpdateCommand::Add {
+            name: "www.example.com.".to_string(),
+            ttl: 300,
+            record_type: "A".to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn encode(record_type: &str, text: &str) -> Vec<u8> {
+        encode_rdata(record_type_code(record_type).unwrap(), text).unwrap()
+    }
+
+    /// Encode RDATA and render it back through the message decoder
+    fn round_trip(record_type: &str, text: &str) -> String {
+        let rtype = record_type_code(record_type).unwrap();
+        let rdata = encode_rdata(rtype, text).unwrap();
+        let mut rr = Vec::new();
+        encode_name("x.example.com.", false, &mut rr).unwrap();
+        rr.extend_from_slice(&rtype.to_be_bytes());
+        rr.extend_from_slice(&CLASS_IN.to_be_bytes());
+        rr.extend_from_slice(&300u32.to_be_bytes());
+        rr.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        rr.extend_from_slice(&rdata);
+        decode_record(&rr, 0).unwrap().0.data
+    }
+
+    #[test]
+    fn test_build_update_sections() {
+        let prerequisites = [
+            Prerequisite::NameInUse {
+                name: "www.example.com.".to_string(),
+            },
+            Prerequisite::NameNotInUse {
+                name: "new.example.com.".to_string(),
+            },
+            Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "TXT".to_string(),
+                value: String::new(),
+            },
+            Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: "192.0.2.1".to_string(),
+            },
+            Prerequisite::RrsetNotExists {
+                name: "www.example.com.".to_string(),
+                record_type: "AAAA".to_string(),
+            },
+        ];
+        let commands = [
+            UpdateCommand::Delete {
+                name: "www.example.com.".to_string(),
+                record_type: "TXT".to_string(),
+                value: String::new(),
+            },
+            UpdateCommand::Delete {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: "192.0.2.1".to_string(),
+            },
+            add_a("192.0.2.2"),
+        ];
+
+        let msg = build_update(0x1234, "example.com", &prerequisites, &commands).unwrap();
+        assert_eq!(&msg[..4], &[0x12, 0x34, 0x28, 0x00]);
+        assert_eq!(u16::from_be_bytes([msg[4], msg[5]]), 1); // ZOCOUNT
+        assert_eq!(u16::from_be_bytes([msg[10], msg[11]]), 0); // ADCOUNT
+
+        // Prerequisites are in the answer section, updates in the authority section
+        let parsed = parse_message(&msg).unwrap();
+        let classes: Vec<_> = parsed
+            .answers
+            .iter()
+            .map(|rr| (rr.rtype, rr.class))
+            .collect();
+        assert_eq!(
+            classes,
+            [
+                (TYPE_ANY, CLASS_ANY),
+                (TYPE_ANY, CLASS_NONE),
+                (16, CLASS_ANY),
+                (1, CLASS_IN),
+                (28, CLASS_NONE),
+            ]
+        );
+        assert!(parsed.answers.iter().all(|rr| rr.ttl == 0));
+        assert_eq!(parsed.answers[3].data, "192.0.2.1");
+
+        let updates: Vec<_> = parsed
+            .authority
+            .iter()
+            .map(|rr| (rr.rtype, rr.class, rr.ttl, rr.data.as_str()))
+            .collect();
+        assert_eq!(
+            updates,
+            [
+                (16, CLASS_ANY, 0, ""),
+                (1, CLASS_NONE, 0, "192.0.2.1"),
+                (1, CLASS_IN, 300, "192.0.2.2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_record_rrset_prerequisite() {
+        // A value-dependent RRset prerequisite is one record per value; the
+        // server compares them together with the whole RRset (RFC 2136 3.2.3)
+        let prerequisites: Vec<_> = ["192.0.2.1", "192.0.2.2"]
+            .into_iter()
+            .map(|value| Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: value.to_string(),
+            })
+            .collect();
+
+        let msg = build_update(1, "example.com", &prerequisites, &[add_a("192.0.2.3")]).unwrap();
+        let parsed = parse_message(&msg).unwrap();
+        let records: Vec<_> = parsed
+            .answers
+            .iter()
+            .map(|rr| (rr.name.as_str(), rr.rtype, rr.class, rr.data.as_str()))
+            .collect();
+        assert_eq!(
+            records,
+            [
+                ("www.example.com.", 1, CLASS_IN, "192.0.2.1"),
+                ("www.example.com.", 1, CLASS_IN, "192.0.2.2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_update_rejects_bad_input() {
+        assert!(build_update(1, "example.com", &[], &[]).is_err());
+        assert!(build_update(1, "example.com", &[], &[add_a("not-an-address")]).is_err());
+
+        let unknown_type = UpdateCommand::Add {
+            name: "www.example.com.".to_string(),
+            ttl: 300,
+            record_type: "BOGUS".to_string(),
+            value: "x".to_string(),
+        };
+        assert!(build_update(1, "example.com", &[], &[unknown_type]).is_err());
+
+        let bad_name = Prerequisite::NameInUse {
+            name: "a..example.com.".to_string(),
+        };
+        assert!(build_update(1, "example.com", &[bad_name], &[add_a("192.0.2.1")]).is_err());
+    }
+
+    #[test]
+    fn test_encode_rdata_round_trips_through_the_decoder() {
+        for (record_type, text, rendered) in [
+            ("A", "192.0.2.1", "192.0.2.1"),
+            ("AAAA", "2001:db8::1", "2001:db8::1"),
+            ("CNAME", "web.example.com.", "web.example.com."),
+            ("NS", "ns1.example.com.", "ns1.example.com."),
+            ("MX", "10 mail.example.com.", "10 mail.example.com."),
+            ("MX", "0 .", "0 ."),
+            (
+                "SRV",
+                "10 5 443 web.example.com.",
+                "10 5 443 web.example.com.",
+            ),
+            ("TXT", "\"v=spf1 -all\"", "\"v=spf1 -all\""),
+            ("TXT", "\"one\" \"two\"", "\"one\" \"two\""),
+            ("TXT", "unquoted words", "\"unquoted\" \"words\""),
+            ("TXT", r#""say \"hi\"\059""#, r#""say \"hi\";""#),
+            (
+                "CAA",
+                "0 issue \"letsencrypt.org\"",
+                "0 issue \"letsencrypt.org\"",
+            ),
+            (
+                "SOA",
+                "ns1.example.com. admin.example.com. 2024010101 1h 600 1w 1d",
+                "ns1.example.com. admin.example.com. 2024010101 3600 600 604800 86400",
+            ),
+        ] {
+            assert_eq!(round_trip(record_type, text), rendered, "{}", text);
+        }
+    }
+
+    /// Check that `text` renders as `rendered`, and that the rendered form
+    /// encodes back to the same bytes.
+    fn assert_round_trip(record_type: &str, text: &str, rendered: &str) {
+        assert_eq!(round_trip(record_type, text), rendered, "{}", text);
+        assert_eq!(encode(record_type, rendered), encode(record_type, text));
+    }
+
+    #[test]
+    fn test_ds_round_trip() {
+        assert_round_trip("DS", "60485 RSASHA256 2 0102ab", "60485 8 2 0102AB");
+        assert_round_trip("CDS", "60485 13 2 01 02", "60485 13 2 0102");
+    }
+
+    #[test]
+    fn test_dnskey_round_trip() {
+        assert_round_trip("DNSKEY", "257 3 ED25519 AQID BAU=", "257 3 15 AQIDBAU=");
+        assert_round_trip("CDNSKEY", "256 3 8 AQID", "256 3 8 AQID");
+    }
+
+    #[test]
+    fn test_tlsa_round_trip() {
+        assert_round_trip("TLSA", "3 1 1 abcdef", "3 1 1 ABCDEF");
+    }
+
+    #[test]
+    fn test_sshfp_round_trip() {
+        assert_round_trip("SSHFP", "4 2 01 02", "4 2 0102");
+    }
+
+    #[test]
+    fn test_naptr_round_trip() {
+        assert_round_trip(
+            "NAPTR",
+            r#"100 10 U E2U+sip "!^.*$!sip:info@example.com!" ."#,
+            r#"100 10 "U" "E2U+sip" "!^.*$!sip:info@example.com!" ."#,
+        );
+        assert_round_trip(
+            "NAPTR",
+            r#"100 10 "" "" "" _sip._udp.example.com."#,
+            r#"100 10 "" "" "" _sip._udp.example.com."#,
+        );
+    }
+
+    #[test]
+    fn test_svcb_round_trip() {
+        assert_round_trip("SVCB", "0 svc.example.com.", "0 svc.example.com.");
+        assert_round_trip(
+            "SVCB",
+            "1 . ipv6hint=2001:db8::1,2001:db8::2 mandatory=port,alpn key65000=\"x y\" port=53",
+            "1 . mandatory=alpn,port port=53 ipv6hint=2001:db8::1,2001:db8::2 key65000=\"x y\"",
+        );
+        assert_round_trip(
+            "HTTPS",
+            "1 . alpn=h2,h3 no-default-alpn ipv4hint=192.0.2.1 ech=AQID key7",
+            "1 . alpn=\"h2,h3\" no-default-alpn ipv4hint=192.0.2.1 ech=AQID key7",
+        );
+    }
+
+    #[test]
+    fn test_loc_round_trip() {
+        // RFC 1876 example
+        assert_round_trip(
+            "LOC",
+            "52 22 23.000 N 4 53 32.000 E -2.00m 0.00m 10000m 10m",
+            "52 22 23.000 N 4 53 32.000 E -2m 0m 10000m 10m",
+        );
+        assert_round_trip(
+            "LOC",
+            "42 21 54.5 S 71 6 W 24.5m",
+            "42 21 54.500 S 71 6 0.000 W 24.50m 1m 10000m 10m",
+        );
+    }
+
+    #[test]
+    fn test_uri_round_trip() {
+        assert_round_trip(
+            "URI",
+            "10 1 \"ftp://ftp1.example.com/public\"",
+            "10 1 \"ftp://ftp1.example.com/public\"",
+        );
+    }
+
+    #[test]
+    fn test_hinfo_round_trip() {
+        assert_round_trip("HINFO", "INTEL \"Linux 6\"", "\"INTEL\" \"Linux 6\"");
+    }
+
+    #[test]
+    fn test_cert_round_trip() {
+        assert_round_trip("CERT", "3 0 0 AQID", "PGP 0 0 AQID");
+        assert_round_trip("CERT", "65000 1 8 AQID", "65000 1 8 AQID");
+    }
+
+    #[test]
+    fn test_malformed_rdata_falls_back_to_generic_form() {
+        for (record_type, rdata, rendered) in [
+            // Empty digest, LOC version 1, SVCB keys out of order
+            ("DS", &[0, 1, 8, 2][..], "\\# 4 00010802"),
+            (
+                "LOC",
+                &[1; 16][..],
+                "\\# 16 01010101010101010101010101010101",
+            ),
+            (
+                "SVCB",
+                &[0, 1, 0, 0, 3, 0, 2, 0, 53, 0, 1, 0, 0][..],
+                "\\# 13 00010000030002003500010000",
+            ),
+        ] {
+            let rtype = record_type_code(record_type).unwrap();
+            let mut rr = Vec::new();
+            encode_name("x.example.com.", false, &mut rr).unwrap();
+            rr.extend_from_slice(&rtype.to_be_bytes());
+            rr.extend_from_slice(&CLASS_IN.to_be_bytes());
+            rr.extend_from_slice(&300u32.to_be_bytes());
+            rr.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            rr.extend_from_slice(rdata);
+            assert_eq!(decode_record(&rr, 0).unwrap().0.data, rendered);
+        }
+    }
+
+    #[test]
+    fn test_encode_rdata_wire_formats() {
+        assert_eq!(
+            encode("DS", "60485 8 2 0102AB"),
+            [0xec, 0x45, 8, 2, 0x01, 0x02, 0xab]
+        );
+        assert_eq!(
+            encode("DS", "60485 RSASHA256 2 01 02"),
+            encode("DS", "60485 8 2 0102")
+        );
+        assert_eq!(
+            encode("DNSKEY", "257 3 ED25519 AQID"),
+            [1, 1, 3, 15, 1, 2, 3]
+        );
+        assert_eq!(encode("TLSA", "3 1 1 abcdef"), [3, 1, 1, 0xab, 0xcd, 0xef]);
+        assert_eq!(encode("SSHFP", "4 2 0102"), [4, 2, 1, 2]);
+        assert_eq!(
+            encode("HINFO", "\"INTEL\" Linux"),
+            b"\x05INTEL\x05Linux".to_vec()
+        );
+        assert_eq!(
+            encode("URI", "10 1 \"https://example.com/\""),
+            b"\x00\x0a\x00\x01https://example.com/".to_vec()
+        );
+        assert_eq!(encode("CERT", "PGP 0 0 AQID"), [0, 3, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(encode("DNAME", "example.net."), b"\x07example\x03net\x00");
+
+        let naptr = encode(
+            "NAPTR",
+            r#"100 10 "U" "E2U+sip" "!^.*$!sip:info@example.com!" ."#,
+        );
+        assert_eq!(&naptr[..7], &[0, 100, 0, 10, 1, b'U', 7]);
+        assert_eq!(naptr.last(), Some(&0));
+
+        // Keys are sorted, whatever order they are given in
+        assert_eq!(
+            encode("HTTPS", "1 . port=443 alpn=h2"),
+            [0, 1, 0, 0, 1, 0, 3, 2, b'h', b'2', 0, 3, 0, 2, 0x01, 0xbb]
+        );
+        assert_eq!(
+            encode(
+                "SVCB",
+                "1 svc.example.com. mandatory=port no-default-alpn port=53 ipv4hint=192.0.2.1"
+            ),
+            [
+                &[0, 1][..],
+                b"\x03svc\x07example\x03com\x00",
+                &[0, 0, 0, 2, 0, 3],
+                &[0, 2, 0, 0],
+                &[0, 3, 0, 2, 0, 53],
+                &[0, 4, 0, 4, 192, 0, 2, 1],
+            ]
+            .concat()
+        );
+
+        // RFC 1876 example
+        let loc = encode(
+            "LOC",
+            "52 22 23.000 N 4 53 32.000 E -2.00m 0.00m 10000m 10m",
+        );
+        assert_eq!(&loc[..4], &[0, 0x00, 0x16, 0x13]);
+        assert_eq!(&loc[4..8], &2_336_026_648u32.to_be_bytes());
+        assert_eq!(&loc[8..12], &2_165_095_648u32.to_be_bytes());
+        assert_eq!(&loc[12..16], &9_999_800u32.to_be_bytes());
+        let loc = encode("LOC", "0 S 0 W 0m");
+        assert_eq!(&loc[..4], &[0, 0x12, 0x16, 0x13]);
+        assert_eq!(&loc[4..12], &[0x80, 0, 0, 0, 0x80, 0, 0, 0]);
+
+        // RFC 3597 form works for every type
+        assert_eq!(encode("A", "\\# 4 C0000201"), [192, 0, 2, 1]);
+        assert_eq!(encode("TYPE65280", "\\# 3 01 0203"), [1, 2, 3]);
+        assert!(encode("TXT", "\\# 0").is_empty());
+    }
+
+    #[test]
+    fn test_encode_rdata_rejects_malformed_text() {
+        let long = format!("\"{}\"", "x".repeat(256));
+        for (record_type, text) in [
+            ("A", "192.0.2"),
+            ("A", "192.0.2.1 192.0.2.2"),
+            ("MX", "mail.example.com."),
+            ("TXT", &long),
+            ("TXT", "\"unterminated"),
+            ("TXT", "\"bad \\256\""),
+            ("TXT", "\"tab\there\""),
+            ("CAA", "0 issue"),
+            ("DS", "1 8 2 abc"),
+            ("DNSKEY", "257 3 8 !!!"),
+            ("HTTPS", "1 . colour=blue"),
+            ("HTTPS", "1 . port=1 port=2"),
+            ("LOC", "91 N 0 E 0m"),
+            ("CERT", "NOPE 0 0 AQID"),
+            ("A", "\\# 5 C0000201"),
+            ("TYPE65280", "opaque"),
+            // Names must be absolute and unquoted
+            ("CNAME", "relative.example.com"),
+            ("SRV", "0 5 443 \"web.example.com.\""),
+            ("NAPTR", "100 10 S SIP+D2U \"\" _sip._udp"),
+            // Zone-file metacharacters outside quotes
+            ("HINFO", "cpu ;os"),
+            ("HINFO", "(cpu os"),
+        ] {
+            let rtype = record_type_code(record_type).unwrap();
+            assert!(
+                encode_rdata(rtype, text).is_err(),
+                "{} {} should be rejected",
+                record_type,
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_rejected_messages() {
+        let refused = UpdateRejected { rcode: 5 }.to_string();
+        assert_eq!(
+            refused,
+            "update rejected (REFUSED): zone refused the update (check allow-update configuration)"
+        );
+        assert!(UpdateRejected { rcode: 16 }
+            .to_string()
+            .starts_with("update rejected (BADSIG): TSIG key rejected"));
+    }
+
+    // ========== Client against a mock server ==========
+
+    /// A response to `request` with the given rcode and extra header flags
+    fn response(request: &[u8], rcode: u16, flags: u16) -> Vec<u8> {
+        let mut msg = request[..2].to_vec();
+        msg.extend_from_slice(&(0xa800u16 | flags | rcode).to_be_bytes());
+        msg.extend_from_slice(&[0; 8]);
+        msg
+    }
+
+    /// Append a TSIG RR carrying `mac` and TSIG error `error`.
+    fn append_tsig(key: &TsigKey, msg: &mut Vec<u8>, mac: &[u8], now: u64, error: u16) {
+        let mut rdata = Vec::new();
+        encode_name("hmac-sha256.", true, &mut rdata).unwrap();
+        rdata.extend_from_slice(&now.to_be_bytes()[2..]);
+        rdata.extend_from_slice(&DEFAULT_TSIG_FUDGE.to_be_bytes());
+        rdata.extend_from_slice(&(mac.len() as u16).to_be_bytes());
+        rdata.extend_from_slice(mac);
+        rdata.extend_from_slice(&msg[0..2]);
+        rdata.extend_from_slice(&error.to_be_bytes());
+        rdata.extend_from_slice(&[0, 0]);
+        encode_name(key.name(), true, msg).unwrap();
+        msg.extend_from_slice(&TYPE_TSIG.to_be_bytes());
+        msg.extend_from_slice(&CLASS_ANY.to_be_bytes());
+        msg.extend_from_slice(&0u32.to_be_bytes());
+        msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&rdata);
+        let arcount = u16::from_be_bytes([msg[10], msg[11]]) + 1;
+        msg[10..12].copy_from_slice(&arcount.to_be_bytes());
+    }
+
+    /// A response with `rcode` signed with the request's MAC
+    fn signed_response(request: &[u8], rcode: u16, corrupt: bool) -> Vec<u8> {
+        let key = key();
+        let request_mac = parse_message(request)
+            .unwrap()
+            .tsig
+            .expect("signed request")
+            .mac;
+        let now = unix_time();
+        let mut msg = response(request, rcode, 0);
+        let variables = key.variables(now, DEFAULT_TSIG_FUDGE, 0).unwrap();
+        let mut mac = key.digest(&[
+            &(request_mac.len() as u16).to_be_bytes(),
+            &request_mac,
+            &msg,
+            &variables,
+        ]);
+        if corrupt {
+            mac[0] ^= 0xff;
+        }
+        append_tsig(&key, &mut msg, &mac, now, 0);
+        msg
+    }
+
+    /// Answer one UDP request with `reply(request)`.
+    async fn serve_udp(socket: UdpSocket, reply: impl Fn(&[u8]) -> Vec<u8>) {
+        let mut buf = vec![0u8; 65535];
+        let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+        socket.send_to(&reply(&buf[..len]), peer).await.unwrap();
+    }
+
+    /// Answer one TCP request with `reply(request)`.
+    async fn serve_tcp(listener: TcpListener, reply: impl Fn(&[u8]) -> Vec<u8>) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut stream).await;
+        let msg = reply(&request);
+        stream
+            .write_all(&(msg.len() as u16).to_be_bytes())
+            .await
+            .unwrap();
+        stream.write_all(&msg).await.unwrap();
+    }
+
+    async fn read_request(stream: &mut TcpStream) -> Vec<u8> {
+        let mut len = [0u8; 2];
+        stream.read_exact(&mut len).await.unwrap();
+        let mut buf = vec![0u8; u16::from_be_bytes(len) as usize];
+        stream.read_exact(&mut buf).await.unwrap();
+        buf
+    }
+
+    /// UDP and TCP sockets bound to the same local port
+    async fn udp_and_tcp() -> (UdpSocket, TcpListener, u16) {
+        loop {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            if let Ok(socket) = UdpSocket::bind(("127.0.0.1", port)).await {
+                return (socket, listener, port);
+            }
+        }
+    }
+
+    fn request() -> Vec<u8> {
+        build_update(
+            random_message_id(),
+            "example.com.",
+            &[],
+            &[add_a("192.0.2.1")],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_signed_update_over_udp() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_udp(socket, |request| {
+            signed_response(request, 0, false)
+        }));
+
+        let key = key();
+        send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT)
+            .await
+            .unwrap();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_truncated_udp_response_falls_back_to_tcp() {
+        let (socket, listener, port) = udp_and_tcp().await;
+        let udp = tokio::spawn(serve_udp(socket, |request| response(request, 0, 0x0200)));
+        let tcp = tokio::spawn(serve_tcp(listener, |request| {
+            signed_response(request, 0, false)
+        }));
+
+        let key = key();
+        send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT)
+            .await
+            .unwrap();
+        udp.await.unwrap();
+        tcp.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_forced_tcp_skips_udp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_tcp(listener, |request| response(request, 0, 0)));
+
+        send_update("127.0.0.1", port, request(), None, true, TIMEOUT)
+            .await
+            .unwrap();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_udp_silence_is_not_retried_over_tcp() {
+        let (socket, listener, port) = udp_and_tcp().await;
+        let sent = tokio::spawn(async move {
+            let mut buf = vec![0u8; 65535];
+            let mut datagrams = 0;
+            while tokio::time::timeout(Duration::from_secs(3), socket.recv(&mut buf))
+                .await
+                .is_ok()
+            {
+                datagrams += 1;
+            }
+            datagrams
+        });
+
+        let err = send_update("127.0.0.1", port, request(), None, false, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no response"), "{:#}", err);
+        assert_eq!(sent.await.unwrap(), 3);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), listener.accept())
+                .await
+                .is_err(),
+            "update was retried over TCP"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prerequisite_rcode_is_typed() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        tokio::spawn(serve_udp(socket, |request| {
+            signed_response(request, 8, false)
+        }));
+
+        let key = key();
+        let err = send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PrerequisiteFailed>(),
+            Some(&PrerequisiteFailed::RrsetMissing)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_prerequisite_rcode_is_not_trusted() {
+        let key = key();
+        for key in [None, Some(&key)] {
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let port = socket.local_addr().unwrap().port();
+            tokio::spawn(serve_udp(socket, |request| response(request, 7, 0)));
+
+            let err = send_update("127.0.0.1", port, request(), key, false, TIMEOUT)
+                .await
+                .unwrap_err();
+            assert!(err.downcast_ref::<PrerequisiteFailed>().is_none());
+            assert_eq!(
+                err.downcast_ref::<UpdateRejected>(),
+                Some(&UpdateRejected { rcode: 7 })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_prerequisites_are_trusted_over_tcp() {
+        let (socket, listener, port) = udp_and_tcp().await;
+        let tcp = tokio::spawn(serve_tcp(listener, |request| response(request, 8, 0)));
+        let request = build_update(
+            random_message_id(),
+            "example.com.",
+            &[Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: "192.0.2.1".to_string(),
+            }],
+            &[add_a("192.0.2.2")],
+        )
+        .unwrap();
+
+        // Without a key, an update with prerequisites skips UDP
+        let err = send_update("127.0.0.1", port, request, None, false, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PrerequisiteFailed>(),
+            Some(&PrerequisiteFailed::RrsetMissing)
+        );
+        tcp.await.unwrap();
+        let mut buf = [0u8; 512];
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), socket.recv(&mut buf))
+                .await
+                .is_err(),
+            "update was sent over UDP"
+        );
+
+        // With a key, an unsigned answer is not trusted even over TCP
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_tcp(listener, |request| response(request, 8, 0)));
+        let key = key();
+        let err = send_update(
+            "127.0.0.1",
+            port,
+            self::request(),
+            Some(&key),
+            true,
+            TIMEOUT,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<UpdateRejected>(),
+            Some(&UpdateRejected { rcode: 8 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prerequisite_failure_after_retransmission_is_ambiguous() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        // The first copy is "applied" but its response is lost
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 65535];
+            socket.recv_from(&mut buf).await.unwrap();
+            serve_udp(socket, |request| signed_response(request, 8, false)).await;
+        });
+
+        let key = key();
+        let err = send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<PrerequisiteFailed>().is_none());
+        assert_eq!(
+            err.downcast_ref::<UpdateOutcomeUnknown>(),
+            Some(&UpdateOutcomeUnknown {
+                failure: PrerequisiteFailed::RrsetMissing
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refused_update_is_typed() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        tokio::spawn(serve_udp(socket, |request| response(request, 5, 0)));
+
+        let key = key();
+        let err = send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<UpdateRejected>(),
+            Some(&UpdateRejected { rcode: 5 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tsig_error_is_reported() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        tokio::spawn(serve_udp(socket, |request| {
+            let mut msg = response(request, 9, 0);
+            append_tsig(&key(), &mut msg, &[], unix_time(), 16);
+            msg
+        }));
+
+        let key = key();
+        let err = send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<UpdateRejected>(),
+            Some(&UpdateRejected { rcode: 16 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signed_update_rejects_bad_or_missing_signature() {
+        let key = key();
+        for corrupt in [true, false] {
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let port = socket.local_addr().unwrap().port();
+            tokio::spawn(serve_udp(socket, move |request| {
+                if corrupt {
+                    signed_response(request, 0, true)
+                } else {
+                    response(request, 0, 0)
+                }
+            }));
+
+            let result =
+                send_update("127.0.0.1", port, request(), Some(&key), false, TIMEOUT).await;
+            assert!(result.is_err(), "corrupt = {}", corrupt);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_sends_update_as_delete_then_add() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_udp(socket, |request| {
+            let parsed = parse_message(request).unwrap();
+            let updates: Vec<_> = parsed
+                .authority
+                .iter()
+                .map(|rr| (rr.class, rr.data.as_str()))
+                .collect();
+            assert_eq!(
+                updates,
+                [(CLASS_NONE, "192.0.2.1"), (CLASS_IN, "192.0.2.2")]
+            );
+            signed_response(request, 0, false)
+        }));
+
+        let executor = NsupdateExecutor::new(
+            "127.0.0.1".to_string(),
+            port,
+            Some("update-key".to_string()),
+            Some("hmac-sha256".to_string()),
+            Some(SECRET.to_string()),
+        )
+        .unwrap();
+        let rcode = executor
+            .update_record(
+                "example.com.",
+                &[],
+                "www.example.com.",
+                300,
+                "A",
+                "192.0.2.1",
+                "192.0.2.2",
+            )
+            .await
+            .unwrap();
+        assert_eq!(rcode, "NOERROR");
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,1714 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! DNS wire-format encoding, decoding and TSIG authentication
+//!
+//! This module implements the subset of RFC 1035 message handling that bindcar
+//! needs to talk to BIND9 directly over DNS, together with RFC 8945 TSIG
+//! signing and verification:
+//! - Domain name encoding and (compressed) decoding
+//! - Query construction and response parsing
+//! - RDATA rendering to presentation format for every record type bindcar
+//!   manages, with the RFC 3597 `\# <len> <hex>` form for everything else
+//! - RDATA encoding from presentation format for every record type bindcar
+//!   manages, and from the `\#` form for any type
+//! - HMAC-SHA2 TSIG signing of requests and verification of single responses
+//!   and multi-message (AXFR) response streams
+//!
+//! Only the SHA-2 HMAC family is accepted for TSIG; HMAC-MD5 / HMAC-SHA1 are
+//! deprecated and rejected, matching the RNDC control-channel posture (A12).
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use std::hash::{BuildHasher, Hasher};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use subtle::ConstantTimeEq;
+
+/// DNS class IN
+pub const CLASS_IN: u16 = 1;
+/// DNS class NONE (RFC 2136 delete-specific-RR)
+pub const CLASS_NONE: u16 = 254;
+/// DNS class ANY
+pub const CLASS_ANY: u16 = 255;
+
+/// Record type SOA
+pub const TYPE_SOA: u16 = 6;
+/// Record type TSIG
+pub const TYPE_TSIG: u16 = 250;
+/// Query type AXFR
+pub const TYPE_AXFR: u16 = 252;
+/// Query type ANY (RFC 2136 name prerequisites and deletes)
+pub const TYPE_ANY: u16 = 255;
+
+/// Maximum length of an encoded domain name (RFC 1035 §2.3.4)
+const MAX_NAME_WIRE_LEN: usize = 255;
+
+/// Maximum length of a single label (RFC 1035 §2.3.4)
+const MAX_LABEL_LEN: usize = 63;
+
+/// Maximum number of compression pointers followed while decoding one name.
+/// Bounds the work an adversarial message can cause with pointer loops.
+const MAX_POINTER_HOPS: usize = 64;
+
+/// Default TSIG fudge (allowed clock skew) in seconds, as used by BIND
+pub const DEFAULT_TSIG_FUDGE: u16 = 300;
+
+/// Maximum number of consecutive unsigned messages permitted in a TSIG-signed
+/// response stream (RFC 8945 §5.3.1).
+const MAX_UNSIGNED_MESSAGES: usize = 99;
+
+/// Record type mnemonics and their numeric codes
+const RECORD_TYPES: &[(&str, u16)] = &[
+    ("A", 1),
+    ("NS", 2),
+    ("CNAME", 5),
+    ("SOA", 6),
+    ("PTR", 12),
+    ("HINFO", 13),
+    ("MX", 15),
+    ("TXT", 16),
+    ("AAAA", 28),
+    ("LOC", 29),
+    ("SRV", 33),
+    ("NAPTR", 35),
+    ("CERT", 37),
+    ("DNAME", 39),
+    ("OPT", 41),
+    ("DS", 43),
+    ("SSHFP", 44),
+    ("RRSIG", 46),
+    ("NSEC", 47),
+    ("DNSKEY", 48),
+    ("NSEC3", 50),
+    ("NSEC3PARAM", 51),
+    ("TLSA", 52),
+    ("CDS", 59),
+    ("CDNSKEY", 60),
+    ("SVCB", 64),
+    ("HTTPS", 65),
+    ("TSIG", 250),
+    ("IXFR", 251),
+    ("AXFR", 252),
+    ("ANY", 255),
+    ("URI", 256),
+    ("CAA", 257),
+];
+
+/// DNSSEC algorithm mnemonics and their numbers (RFC 4034 appendix A.1)
+const DNSSEC_ALGORITHMS: &[(&str, u8)] = &[
+    ("RSAMD5", 1),
+    ("DH", 2),
+    ("DSA", 3),
+    ("RSASHA1", 5),
+    ("DSA-NSEC3-SHA1", 6),
+    ("RSASHA1-NSEC3-SHA1", 7),
+    ("RSASHA256", 8),
+    ("RSASHA512", 10),
+    ("ECC-GOST", 12),
+    ("ECDSAP256SHA256", 13),
+    ("ECDSAP384SHA384", 14),
+    ("ED25519", 15),
+    ("ED448", 16),
+    ("INDIRECT", 252),
+    ("PRIVATEDNS", 253),
+    ("PRIVATEOID", 254),
+];
+
+/// CERT certificate type mnemonics and their numbers (RFC 4398 section 2.1)
+const CERT_TYPES: &[(&str, u16)] = &[
+    ("PKIX", 1),
+    ("SPKI", 2),
+    ("PGP", 3),
+    ("IPKIX", 4),
+    ("ISPKI", 5),
+    ("IPGP", 6),
+    ("ACPKIX", 7),
+    ("IACPKIX", 8),
+    ("URI", 253),
+    ("OID", 254),
+];
+
+/// SVCB service parameter keys, indexed by their number (RFC 9460 section 14.3.2)
+const SVC_PARAM_KEYS: &[&str] = &[
+    "mandatory",
+    "alpn",
+    "no-default-alpn",
+    "port",
+    "ipv4hint",
+    "ech",
+    "ipv6hint",
+];
+
+/// Number of a DNSSEC algorithm mnemonic (case-insensitive)
+pub fn dnssec_algorithm_number(name: &str) -> Option<u8> {
+    DNSSEC_ALGORITHMS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, number)| *number)
+}
+
+/// Number of a CERT certificate type mnemonic (case-insensitive)
+pub fn cert_type_number(name: &str) -> Option<u16> {
+    CERT_TYPES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, number)| *number)
+}
+
+/// Number of an SVCB service parameter key (case-insensitive, accepts `key<n>`)
+pub fn svc_param_key_number(name: &str) -> Option<u16> {
+    let lower = name.to_ascii_lowercase();
+    SVC_PARAM_KEYS
+        .iter()
+        .position(|k| *k == lower)
+        .map(|i| i as u16)
+        .or_else(|| {
+            lower
+                .strip_prefix("key")
+                .filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|n| n.parse().ok())
+        })
+}
+
+/// Mnemonic for a record type code (`TYPE<n>` for unknown codes, RFC 3597)
+pub fn record_type_name(code: u16) -> String {
+    RECORD_TYPES
+        .iter()
+        .find(|(_, c)| *c == code)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| format!("TYPE{}", code))
+}
+
+/// Numeric code for a record type mnemonic (case-insensitive, accepts `TYPE<n>`)
+pub fn record_type_code(name: &str) -> Option<u16> {
+    let upper = name.to_ascii_uppercase();
+    RECORD_TYPES
+        .iter()
+        .find(|(n, _)| *n == upper)
+        .map(|(_, c)| *c)
+        .or_else(|| upper.strip_prefix("TYPE").and_then(|n| n.parse().ok()))
+}
+
+/// DNS response code mnemonic (including the TSIG extended error codes)
+pub fn rcode_name(rcode: u16) -> String {
+    match rcode {
+        0 => "NOERROR".to_string(),
+        1 => "FORMERR".to_string(),
+        2 => "SERVFAIL".to_string(),
+        3 => "NXDOMAIN".to_string(),
+        4 => "NOTIMP".to_string(),
+        5 => "REFUSED".to_string(),
+        6 => "YXDOMAIN".to_string(),
+        7 => "YXRRSET".to_string(),
+        8 => "NXRRSET".to_string(),
+        9 => "NOTAUTH".to_string(),
+        10 => "NOTZONE".to_string(),
+        16 => "BADSIG".to_string(),
+        17 => "BADKEY".to_string(),
+        18 => "BADTIME".to_string(),
+        22 => "BADTRUNC".to_string(),
+        other => format!("RCODE{}", other),
+    }
+}
+
+/// Generate a random 16-bit message ID.
+///
+/// `RandomState` is seeded from the OS random source, which is sufficient for a
+/// query ID; bindcar's DNS traffic is TSIG-authenticated, not ID-authenticated.
+pub fn random_message_id() -> u16 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default(),
+    );
+    hasher.finish() as u16
+}
+
+// ========== Names ==========
+
+/// Append the uncompressed wire form of `name` to `buf`.
+///
+/// Names are treated as absolute whether or not they carry a trailing dot.
+/// Labels are lower-cased when `canonical` is set (the TSIG digest requires the
+/// canonical form of the key and algorithm names, RFC 8945 §4.3.3).
+///
+/// # Errors
+/// Returns an error for an empty label, a label over 63 octets, or a name over
+/// 255 octets.
+pub fn encode_name(name: &str, canonical: bool, buf: &mut Vec<u8>) -> Result<()> {
+    let trimmed = name.strip_suffix('.').unwrap_or(name);
+    let start = buf.len();
+
+    if !trimmed.is_empty() {
+        for label in trimmed.split('.') {
+            if label.is_empty() || label.len() > MAX_LABEL_LEN {
+                return Err(anyhow::anyhow!("invalid label in domain name {:?}", name));
+            }
+            buf.push(label.len() as u8);
+            if canonical {
+                buf.extend(label.bytes().map(|b| b.to_ascii_lowercase()));
+            } else {
+                buf.extend_from_slice(label.as_bytes());
+            }
+        }
+    }
+    buf.push(0);
+
+    if buf.len() - start > MAX_NAME_WIRE_LEN {
+        return Err(anyhow::anyhow!("domain name too long: {:?}", name));
+    }
+
+    Ok(())
+}
+
+/// Escape one label for presentation format (RFC 1035 §5.1).
+fn escape_label(label: &[u8], out: &mut String) {
+    for &b in label {
+        match b {
+            b'.' | b'\\' | b'"' | b'(' | b')' | b';' | b'@' | b'$' => {
+                out.push('\\');
+                out.push(b as char);
+            }
+            0x21..=0x7e => out.push(b as char),
+            _ => out.push_str(&format!("\\{:03}", b)),
+        }
+    }
+}
+
+/// Decode a (possibly compressed) domain name starting at `offset`.
+///
+/// Returns the absolute presentation-form name (with trailing dot) and the
+/// offset just past the name in the original position.
+///
+/// # Errors
+/// Returns an error on truncation, a pointer loop, or an over-long name.
+pub fn decode_name(msg: &[u8], offset: usize) -> Result<(String, usize)> {
+    let mut name = String::new();
+    let mut pos = offset;
+    let mut end = None;
+    let mut hops = 0;
+    let mut wire_len = 0;
+
+    loop {
+        let len = *msg.get(pos).context("truncated domain name")? as usize;
+        match len & 0xC0 {
+            0x00 => {
+                if len == 0 {
+                    pos += 1;
+                    break;
+                }
+                let label = msg
+                    .get(pos + 1..pos + 1 + len)
+                    .context("truncated domain name label")?;
+                wire_len += len + 1;
+                if wire_len > MAX_NAME_WIRE_LEN {
+                    return Err(anyhow::anyhow!("domain name too long"));
+                }
+                escape_label(label, &mut name);
+                name.push('.');
+                pos += len + 1;
+            }
+            0xC0 => {
+                let low = *msg.get(pos + 1).context("truncated compression pointer")? as usize;
+                hops += 1;
+                if hops > MAX_POINTER_HOPS {
+                    return Err(anyhow::anyhow!("domain name compression loop"));
+                }
+                if end.is_none() {
+                    end = Some(pos + 2);
+                }
+                pos = ((len & 0x3F) << 8) | low;
+            }
+            _ => return Err(anyhow::anyhow!("unsupported label type")),
+        }
+    }
+
+    if name.is_empty() {
+        name.push('.');
+    }
+
+    Ok((name, end.unwrap_or(pos)))
+}
+
+// ========== Message Construction ==========
+
+/// Build a query message with a single question (no RD flag).
+///
+/// # Errors
+/// Returns an error if `qname` is not a valid domain name.
+pub fn build_query(id: u16, qname: &str, qtype: u16) -> Result<Vec<u8>> {
+    let mut msg = Vec::with_capacity(64);
+    msg.extend_from_slice(&id.to_be_bytes());
+    msg.extend_from_slice(&0u16.to_be_bytes()); // QUERY, no flags
+    msg.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+    msg.extend_from_slice(&[0, 0, 0, 0, 0, 0]); // AN/NS/AR
+    encode_name(qname, false, &mut msg)?;
+    msg.extend_from_slice(&qtype.to_be_bytes());
+    msg.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(msg)
+}
+
+/// Increment the 16-bit header counter at `offset` (e.g. ARCOUNT at 10).
+pub(crate) fn increment_count(msg: &mut [u8], offset: usize) {
+    let count = u16::from_be_bytes([msg[offset], msg[offset + 1]]).wrapping_add(1);
+    msg[offset..offset + 2].copy_from_slice(&count.to_be_bytes());
+}
+
+// ========== Message Parsing ==========
+
+/// A resource record decoded from a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireRecord {
+    /// Absolute owner name with trailing dot
+    pub name: String,
+    /// Record type code
+    pub rtype: u16,
+    /// Record class
+    pub class: u16,
+    /// Time-to-live in seconds
+    pub ttl: u32,
+    /// RDATA rendered in presentation format
+    pub data: String,
+}
+
+/// TSIG record found at the end of a message's additional section
+#[derive(Debug, Clone)]
+pub struct TsigRecord {
+    /// Offset of the TSIG RR within the message
+    pub offset: usize,
+    /// Key name (owner name of the TSIG RR)
+    pub key_name: String,
+    /// Algorithm name (e.g., "hmac-sha256.")
+    pub algorithm: String,
+    /// Signing time, seconds since the Unix epoch
+    pub time_signed: u64,
+    /// Permitted clock skew in seconds
+    pub fudge: u16,
+    /// Message authentication code
+    pub mac: Vec<u8>,
+    /// Message ID at signing time
+    pub original_id: u16,
+    /// TSIG extended error code
+    pub error: u16,
+}
+
+/// A decoded DNS message
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub id: u16,
+    pub flags: u16,
+    pub answers: Vec<WireRecord>,
+    pub authority: Vec<WireRecord>,
+    pub additional: Vec<WireRecord>,
+    pub tsig: Option<TsigRecord>,
+}
+
+impl Message {
+    /// Response code from the header
+    pub fn rcode(&self) -> u16 {
+        self.flags & 0x000F
+    }
+
+    /// Whether the TC (truncated) bit is set
+    pub fn truncated(&self) -> bool {
+        self.flags & 0x0200 != 0
+    }
+}
+
+/// Cursor-style reader over a message buffer
+struct Reader<'a> {
+    msg: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn u8(&mut self) -> Result<u8> {
+        let v = *self.msg.get(self.pos).context("truncated message")?;
+        self.pos += 1;
+        Ok(v)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u48(&mut self) -> Result<u64> {
+        let b = self.bytes(6)?;
+        Ok(b.iter().fold(0u64, |acc, &x| (acc << 8) | x as u64))
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        let b = self
+            .msg
+            .get(self.pos..self.pos + n)
+            .context("truncated message")?;
+        self.pos += n;
+        Ok(b)
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let (name, next) = decode_name(self.msg, self.pos)?;
+        self.pos = next;
+        Ok(name)
+    }
+
+    fn char_string(&mut self) -> Result<&'a [u8]> {
+        let n = self.u8()? as usize;
+        self.bytes(n)
+    }
+
+    /// The rest of the buffer, which must not be empty
+    fn payload(&mut self, what: &str) -> Result<&'a [u8]> {
+        let rest = self.bytes(self.msg.len().saturating_sub(self.pos))?;
+        if rest.is_empty() {
+            return Err(anyhow::anyhow!("{} is empty", what));
+        }
+        Ok(rest)
+    }
+}
+
+/// Quote a character-string for presentation format.
+pub(crate) fn quote_char_string(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() + 2);
+    out.push('"');
+    for &b in bytes {
+        match b {
+            b'"' | b'\\' => {
+                out.push('\\');
+                out.push(b as char);
+            }
+            0x20..=0x7e => out.push(b as char),
+            _ => out.push_str(&format!("\\{:03}", b)),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Lower-case hex encoding
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Render RDATA at `msg[start..start + len]` in presentation format.
+fn render_rdata(msg: &[u8], start: usize, len: usize, rtype: u16) -> Result<String> {
+    let end = start + len;
+    let rdata = msg.get(start..end).context("truncated RDATA")?;
+    let mut r = Reader { msg, pos: start };
+
+    let text = match record_type_name(rtype).as_str() {
+        "A" if len == 4 => Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3]).to_string(),
+        "AAAA" if len == 16 => {
+            let octets: [u8; 16] = rdata.try_into().expect("length checked");
+            Ipv6Addr::from(octets).to_string()
+        }
+        "NS" | "CNAME" | "PTR" | "DNAME" => r.name()?,
+        "SOA" => {
+            let mname = r.name()?;
+            let rname = r.name()?;
+            format!(
+                "{} {} {} {} {} {} {}",
+                mname,
+                rname,
+                r.u32()?,
+                r.u32()?,
+                r.u32()?,
+                r.u32()?,
+                r.u32()?
+            )
+        }
+        "MX" => {
+            let preference = r.u16()?;
+            format!("{} {}", preference, r.name()?)
+        }
+        "SRV" => {
+            let priority = r.u16()?;
+            let weight = r.u16()?;
+            let port = r.u16()?;
+            format!("{} {} {} {}", priority, weight, port, r.name()?)
+        }
+        "TXT" => {
+            let mut parts = Vec::new();
+            while r.pos < end {
+                parts.push(quote_char_string(r.char_string()?));
+            }
+            parts.join(" ")
+        }
+        "CAA" => {
+            let flags = r.u8()?;
+            let tag_len = r.u8()? as usize;
+            let tag = String::from_utf8_lossy(r.bytes(tag_len)?).to_string();
+            let value = r.bytes(end.saturating_sub(r.pos))?;
+            format!("{} {} {}", flags, tag, quote_char_string(value))
+        }
+        other => render_uncompressed(other, rdata)
+            .unwrap_or_else(|_| format!("\\# {} {}", len, hex(rdata))),
+    };
+
+    if r.pos > end {
+        return Err(anyhow::anyhow!("RDATA overruns its length"));
+    }
+
+    Ok(text)
+}
+
+/// Render RDATA of the types that never use name compression, from the RDATA
+/// alone.
+///
+/// Fails for other types and for RDATA that does not parse exactly, which the
+/// caller then shows in the `\#` form; rendered text always encodes back to
+/// the same bytes.
+fn render_uncompressed(type_name: &str, rdata: &[u8]) -> Result<String> {
+    let mut r = Reader { msg: rdata, pos: 0 };
+    let base64 = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+
+    let text = match type_name {
+        "DS" | "CDS" => format!(
+            "{} {} {} {}",
+            r.u16()?,
+            r.u8()?,
+            r.u8()?,
+            hex(r.payload("digest")?).to_ascii_uppercase()
+        ),
+        "DNSKEY" | "CDNSKEY" => format!(
+            "{} {} {} {}",
+            r.u16()?,
+            r.u8()?,
+            r.u8()?,
+            base64(r.payload("public key")?)
+        ),
+        "TLSA" => format!(
+            "{} {} {} {}",
+            r.u8()?,
+            r.u8()?,
+            r.u8()?,
+            hex(r.payload("certificate data")?).to_ascii_uppercase()
+        ),
+        "SSHFP" => format!(
+            "{} {} {}",
+            r.u8()?,
+            r.u8()?,
+            hex(r.payload("fingerprint")?).to_ascii_uppercase()
+        ),
+        "NAPTR" => format!(
+            "{} {} {} {} {} {}",
+            r.u16()?,
+            r.u16()?,
+            quote_char_string(r.char_string()?),
+            quote_char_string(r.char_string()?),
+            quote_char_string(r.char_string()?),
+            r.name()?
+        ),
+        "SVCB" | "HTTPS" => {
+            let priority = r.u16()?;
+            let target = r.name()?;
+            format!("{} {}{}", priority, target, render_svc_params(&mut r)?)
+        }
+        "LOC" => render_loc(&mut r)?,
+        "URI" => format!(
+            "{} {} {}",
+            r.u16()?,
+            r.u16()?,
+            quote_char_string(r.payload("target")?)
+        ),
+        "HINFO" => format!(
+            "{} {}",
+            quote_char_string(r.char_string()?),
+            quote_char_string(r.char_string()?)
+        ),
+        "CERT" => {
+            let cert_type = r.u16()?;
+            let cert_type = CERT_TYPES
+                .iter()
+                .find(|(_, number)| *number == cert_type)
+                .map_or_else(|| cert_type.to_string(), |(name, _)| name.to_string());
+            format!(
+                "{} {} {} {}",
+                cert_type,
+                r.u16()?,
+                r.u8()?,
+                base64(r.payload("certificate")?)
+            )
+        }
+        other => return Err(anyhow::anyhow!("no presentation format for {}", other)),
+    };
+
+    if r.pos != rdata.len() {
+        return Err(anyhow::anyhow!("trailing bytes in {} RDATA", type_name));
+    }
+    Ok(text)
+}
+
+/// SVCB and HTTPS service parameters as ` key=value` pairs, each with a
+/// leading space (RFC 9460 section 2.1)
+fn render_svc_params(r: &mut Reader) -> Result<String> {
+    let mut out = String::new();
+    let mut last = None;
+
+    while r.pos < r.msg.len() {
+        let key = r.u16()?;
+        if last.is_some_and(|last| key <= last) {
+            return Err(anyhow::anyhow!("service parameters are not in key order"));
+        }
+        last = Some(key);
+        let len = r.u16()? as usize;
+        let value = r.bytes(len)?;
+        let nonempty = |size: usize| {
+            if value.is_empty() || !value.len().is_multiple_of(size) {
+                Err(anyhow::anyhow!("malformed service parameter key{}", key))
+            } else {
+                Ok(value.chunks(size))
+            }
+        };
+        let key_name = |key: u16| {
+            SVC_PARAM_KEYS
+                .get(key as usize)
+                .map_or_else(|| format!("key{}", key), |name| name.to_string())
+        };
+
+        let rendered = match key {
+            0 => Some(
+                nonempty(2)?
+                    .map(|k| key_name(u16::from_be_bytes([k[0], k[1]])))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            1 => {
+                let mut ids = Reader { msg: value, pos: 0 };
+                let mut list = Vec::new();
+                while ids.pos < value.len() {
+                    let id = ids.char_string()?;
+                    if id.is_empty()
+                        || !id
+                            .iter()
+                            .all(|&b| b.is_ascii_graphic() && !b",\"\\".contains(&b))
+                    {
+                        return Err(anyhow::anyhow!("alpn identifier needs escaping"));
+                    }
+                    list.push(String::from_utf8_lossy(id).to_string());
+                }
+                if list.is_empty() {
+                    return Err(anyhow::anyhow!("alpn is empty"));
+                }
+                Some(format!("\"{}\"", list.join(",")))
+            }
+            2 if value.is_empty() => None,
+            3 if len == 2 => Some(u16::from_be_bytes([value[0], value[1]]).to_string()),
+            4 => Some(
+                nonempty(4)?
+                    .map(|ip| Ipv4Addr::new(ip[0], ip[1], ip[2], ip[3]).to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            5 if !value.is_empty() => Some(base64::engine::general_purpose::STANDARD.encode(value)),
+            6 => Some(
+                nonempty(16)?
+                    .map(|ip| {
+                        let octets: [u8; 16] = ip.try_into().expect("chunk of 16");
+                        Ipv6Addr::from(octets).to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            2 | 3 | 5 => return Err(anyhow::anyhow!("malformed service parameter key{}", key)),
+            _ if value.is_empty() => None,
+            _ => Some(quote_char_string(value)),
+        };
+
+        out.push(' ');
+        out.push_str(&key_name(key));
+        if let Some(rendered) = rendered {
+            out.push('=');
+            out.push_str(&rendered);
+        }
+    }
+
+    Ok(out)
+}
+
+/// A LOC distance in centimeters as meters, e.g. `-24m` or `0.50m`
+fn loc_meters(cm: i64) -> String {
+    let sign = if cm < 0 { "-" } else { "" };
+    let cm = cm.unsigned_abs();
+    if cm.is_multiple_of(100) {
+        format!("{}{}m", sign, cm / 100)
+    } else {
+        format!("{}{}.{:02}m", sign, cm / 100, cm % 100)
+    }
+}
+
+/// A LOC latitude or longitude as `degrees minutes seconds hemisphere`
+fn loc_angle(value: u32, hemispheres: [&str; 2], max_degrees: u64) -> Result<String> {
+    let offset = value as i64 - LOC_EQUATOR;
+    let hemisphere = if offset < 0 {
+        hemispheres[1]
+    } else {
+        hemispheres[0]
+    };
+    let thousandths = offset.unsigned_abs();
+    if thousandths > max_degrees * 3_600_000 {
+        return Err(anyhow::anyhow!("LOC coordinate is out of range"));
+    }
+    Ok(format!(
+        "{} {} {}.{:03} {}",
+        thousandths / 3_600_000,
+        thousandths / 60_000 % 60,
+        thousandths / 1000 % 60,
+        thousandths % 1000,
+        hemisphere
+    ))
+}
+
+/// LOC (RFC 1876 section 3), always with all three precisions
+fn render_loc(r: &mut Reader) -> Result<String> {
+    if r.u8()? != 0 {
+        return Err(anyhow::anyhow!("unsupported LOC version"));
+    }
+    let mut precision = Vec::with_capacity(3);
+    for _ in 0..3 {
+        let byte = r.u8()?;
+        let (mantissa, exponent) = (byte >> 4, byte & 0x0f);
+        if mantissa > 9 || exponent > 9 {
+            return Err(anyhow::anyhow!("invalid LOC precision"));
+        }
+        precision.push(loc_meters(mantissa as i64 * 10i64.pow(exponent as u32)));
+    }
+    let latitude = loc_angle(r.u32()?, ["N", "S"], 90)?;
+    let longitude = loc_angle(r.u32()?, ["E", "W"], 180)?;
+    let altitude = loc_meters(r.u32()? as i64 - LOC_ALTITUDE_BASE_CM);
+    Ok(format!(
+        "{} {} {} {}",
+        latitude,
+        longitude,
+        altitude,
+        precision.join(" ")
+    ))
+}
+
+/// Parse one resource record, returning it (or the TSIG record) and the next offset.
+fn parse_record(msg: &[u8], offset: usize) -> Result<(Option<WireRecord>, Option<TsigRecord>)> {
+    let mut r = Reader { msg, pos: offset };
+    let name = r.name()?;
+    let rtype = r.u16()?;
+    let class = r.u16()?;
+    let ttl = r.u32()?;
+    let rdlen = r.u16()? as usize;
+    let rdata_start = r.pos;
+
+    if rtype == TYPE_TSIG {
+        let algorithm = r.name()?;
+        let time_signed = r.u48()?;
+        let fudge = r.u16()?;
+        let mac_len = r.u16()? as usize;
+        let mac = r.bytes(mac_len)?.to_vec();
+        let original_id = r.u16()?;
+        let error = r.u16()?;
+        let other_len = r.u16()? as usize;
+        r.bytes(other_len)?;
+        return Ok((
+            None,
+            Some(TsigRecord {
+                offset,
+                key_name: name,
+                algorithm,
+                time_signed,
+                fudge,
+                mac,
+                original_id,
+                error,
+            }),
+        ));
+    }
+
+    let data = render_rdata(msg, rdata_start, rdlen, rtype)?;
+    Ok((
+        Some(WireRecord {
+            name,
+            rtype,
+            class,
+            ttl,
+            data,
+        }),
+        None,
+    ))
+}
+
+/// Offset just past the resource record starting at `offset`.
+fn skip_record(msg: &[u8], offset: usize) -> Result<usize> {
+    let mut r = Reader { msg, pos: offset };
+    r.name()?;
+    r.bytes(8)?;
+    let rdlen = r.u16()? as usize;
+    r.bytes(rdlen)?;
+    Ok(r.pos)
+}
+
+/// Decode one resource record outside a message (e.g. from a BIND journal),
+/// returning it and the offset just past it.
+///
+/// # Errors
+/// Returns an error if the record is truncated or malformed, or is a TSIG
+/// record.
+pub fn decode_record(buf: &[u8], offset: usize) -> Result<(WireRecord, usize)> {
+    let next = skip_record(buf, offset)?;
+    match parse_record(buf, offset)? {
+        (Some(record), _) => Ok((record, next)),
+        _ => Err(anyhow::anyhow!("unexpected TSIG record")),
+    }
+}
+
+/// Parse a complete DNS message.
+///
+/// # Errors
+/// Returns an error if the message is truncated or malformed, or if a TSIG
+/// record appears anywhere other than last in the additional section.
+pub fn parse_message(msg: &[u8]) -> Result<Message> {
+    let mut r = Reader { msg, pos: 0 };
+    let id = r.u16()?;
+    let flags = r.u16()?;
+    let qdcount = r.u16()?;
+    let ancount = r.u16()? as usize;
+    let nscount = r.u16()? as usize;
+    let arcount = r.u16()? as usize;
+
+    for _ in 0..qdcount {
+        r.name()?;
+        r.bytes(4)?;
+    }
+
+    let mut sections: [Vec<WireRecord>; 3] = Default::default();
+    let mut tsig = None;
+    let counts = [ancount, nscount, arcount];
+    let total = ancount + nscount + arcount;
+    let mut seen = 0;
+
+    for (section, count) in counts.iter().enumerate() {
+        for _ in 0..*count {
+            let offset = r.pos;
+            let (record, tsig_record) = parse_record(msg, offset)?;
+            r.pos = skip_record(msg, offset)?;
+            seen += 1;
+
+            if let Some(t) = tsig_record {
+                if section != 2 || seen != total {
+                    return Err(anyhow::anyhow!("TSIG record is not last in message"));
+                }
+                tsig = Some(t);
+            } else if let Some(rec) = record {
+                sections[section].push(rec);
+            }
+        }
+    }
+
+    let [answers, authority, additional] = sections;
+    Ok(Message {
+        id,
+        flags,
+        answers,
+        authority,
+        additional,
+        tsig,
+    })
+}
+
+// ========== TSIG ==========
+
+/// TSIG HMAC algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsigAlgorithm {
+    HmacSha224,
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+}
+
+impl TsigAlgorithm {
+    /// Algorithm domain name as used in the TSIG RR
+    pub fn wire_name(&self) -> &'static str {
+        match self {
+            TsigAlgorithm::HmacSha224 => "hmac-sha224.",
+            TsigAlgorithm::HmacSha256 => "hmac-sha256.",
+            TsigAlgorithm::HmacSha384 => "hmac-sha384.",
+            TsigAlgorithm::HmacSha512 => "hmac-sha512.",
+        }
+    }
+
+    /// Parse "sha256", "hmac-sha256", "HMAC-SHA256." and similar forms.
+    pub fn parse(s: &str) -> Option<Self> {
+        let lower = s.trim_end_matches('.').to_ascii_lowercase();
+        match lower.strip_prefix("hmac-").unwrap_or(&lower) {
+            "sha224" => Some(TsigAlgorithm::HmacSha224),
+            "sha256" => Some(TsigAlgorithm::HmacSha256),
+            "sha384" => Some(TsigAlgorithm::HmacSha384),
+            "sha512" => Some(TsigAlgorithm::HmacSha512),
+            _ => None,
+        }
+    }
+
+    fn mac(&self, key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+        fn compute<M: Mac + KeyInit>(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+            let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts any key length");
+            for part in parts {
+                mac.update(part);
+            }
+            mac.finalize().into_bytes().to_vec()
+        }
+
+        match self {
+            TsigAlgorithm::HmacSha224 => compute::<Hmac<Sha224>>(key, parts),
+            TsigAlgorithm::HmacSha256 => compute::<Hmac<Sha256>>(key, parts),
+            TsigAlgorithm::HmacSha384 => compute::<Hmac<Sha384>>(key, parts),
+            TsigAlgorithm::HmacSha512 => compute::<Hmac<Sha512>>(key, parts),
+        }
+    }
+}
+
+/// TSIG key material
+#[derive(Clone)]
+pub struct TsigKey {
+    name: String,
+    algorithm: TsigAlgorithm,
+    secret: Vec<u8>,
+}
+
+// Manual `Debug` that redacts the secret (A4), as for `RndcConfig`.
+impl std::fmt::Debug for TsigKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TsigKey")
+            .field("name", &self.name)
+            .field("algorithm", &self.algorithm)
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl TsigKey {
+    /// Build a key from its name, algorithm string and base64 secret.
+    ///
+    /// # Errors
+    /// Returns an error for an unsupported algorithm (anything outside the
+    /// SHA-2 HMAC family), an invalid key name, or a secret that is not valid
+    /// base64.
+    pub fn new(name: &str, algorithm: &str, secret_b64: &str) -> Result<Self> {
+        let algorithm = TsigAlgorithm::parse(algorithm).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unsupported TSIG algorithm for native DNS: {} (allowed: hmac-sha224, hmac-sha256, hmac-sha384, hmac-sha512)",
+                algorithm
+            )
+        })?;
+        encode_name(name, true, &mut Vec::new()).context("invalid TSIG key name")?;
+        let secret = base64::engine::general_purpose::STANDARD
+            .decode(secret_b64.trim())
+            .context("TSIG secret is not valid base64")?;
+        if secret.is_empty() {
+            return Err(anyhow::anyhow!("TSIG secret cannot be empty"));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            algorithm,
+            secret,
+        })
+    }
+
+    /// Key name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// TSIG variables (RFC 8945 §4.3.3) for the digest.
+    pub(crate) fn variables(&self, time_signed: u64, fudge: u16, error: u16) -> Result<Vec<u8>> {
+        let mut v = Vec::with_capacity(64);
+        encode_name(&self.name, true, &mut v)?;
+        v.extend_from_slice(&CLASS_ANY.to_be_bytes());
+        v.extend_from_slice(&0u32.to_be_bytes());
+        encode_name(self.algorithm.wire_name(), true, &mut v)?;
+        v.extend_from_slice(&time_signed.to_be_bytes()[2..]);
+        v.extend_from_slice(&fudge.to_be_bytes());
+        v.extend_from_slice(&error.to_be_bytes());
+        v.extend_from_slice(&0u16.to_be_bytes()); // other len
+        Ok(v)
+    }
+
+    /// HMAC over the concatenation of `parts` with this key.
+    pub(crate) fn digest(&self, parts: &[&[u8]]) -> Vec<u8> {
+        self.algorithm.mac(&self.secret, parts)
+    }
+
+    /// Sign `msg` in place by appending a TSIG RR and bumping ARCOUNT.
+    ///
+    /// Returns the request MAC, which is needed to verify the response.
+    ///
+    /// # Errors
+    /// Returns an error if the key or algorithm name cannot be encoded.
+    pub fn sign(&self, msg: &mut Vec<u8>, time_signed: u64, fudge: u16) -> Result<Vec<u8>> {
+        let variables = self.variables(time_signed, fudge, 0)?;
+        let mac = self.digest(&[msg, &variables]);
+        let id = [msg[0], msg[1]];
+
+        let mut rdata = Vec::with_capacity(64 + mac.len());
+        encode_name(self.algorithm.wire_name(), true, &mut rdata)?;
+        rdata.extend_from_slice(&time_signed.to_be_bytes()[2..]);
+        rdata.extend_from_slice(&fudge.to_be_bytes());
+        rdata.extend_from_slice(&(mac.len() as u16).to_be_bytes());
+        rdata.extend_from_slice(&mac);
+        rdata.extend_from_slice(&id);
+        rdata.extend_from_slice(&0u16.to_be_bytes()); // error
+        rdata.extend_from_slice(&0u16.to_be_bytes()); // other len
+
+        encode_name(&self.name, true, msg)?;
+        msg.extend_from_slice(&TYPE_TSIG.to_be_bytes());
+        msg.extend_from_slice(&CLASS_ANY.to_be_bytes());
+        msg.extend_from_slice(&0u32.to_be_bytes());
+        msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&rdata);
+        increment_count(msg, 10);
+
+        Ok(mac)
+    }
+}
+
+/// Current time as seconds since the Unix epoch
+pub fn unix_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Verifies the TSIG signatures on a response or a multi-message response
+/// stream (RFC 8945 §5.3).
+pub struct TsigVerifier<'a> {
+    key: &'a TsigKey,
+    prior_mac: Vec<u8>,
+    first: bool,
+    pending: Vec<u8>,
+    unsigned: usize,
+}
+
+impl<'a> TsigVerifier<'a> {
+    /// Create a verifier for responses to a request signed with `request_mac`.
+    pub fn new(key: &'a TsigKey, request_mac: Vec<u8>) -> Self {
+        Self {
+            key,
+            prior_mac: request_mac,
+            first: true,
+            pending: Vec::new(),
+            unsigned: 0,
+        }
+    }
+
+    /// Verify one response message.
+    ///
+    /// # Errors
+    /// Returns an error if the first message is unsigned, too many consecutive
+    /// messages are unsigned, the key or algorithm does not match, the server
+    /// reported a TSIG error, the time is outside the fudge window, or the MAC
+    /// does not verify.
+    pub fn verify(&mut self, raw: &[u8], message: &Message, now: u64) -> Result<()> {
+        let Some(tsig) = &message.tsig else {
+            if self.first {
+                return Err(anyhow::anyhow!("response is not TSIG-signed"));
+            }
+            self.unsigned += 1;
+            if self.unsigned > MAX_UNSIGNED_MESSAGES {
+                return Err(anyhow::anyhow!("too many unsigned messages in response"));
+            }
+            self.pending.extend_from_slice(raw);
+            return Ok(());
+        };
+
+        if !tsig
+            .key_name
+            .trim_end_matches('.')
+            .eq_ignore_ascii_case(self.key.name.trim_end_matches('.'))
+            || TsigAlgorithm::parse(&tsig.algorithm) != Some(self.key.algorithm)
+        {
+            return Err(anyhow::anyhow!("response signed with an unexpected key"));
+        }
+        if tsig.error != 0 {
+            return Err(anyhow::anyhow!(
+                "server reported TSIG error {}",
+                rcode_name(tsig.error)
+            ));
+        }
+        if now.abs_diff(tsig.time_signed) > tsig.fudge as u64 {
+            return Err(anyhow::anyhow!("TSIG time outside fudge window (BADTIME)"));
+        }
+
+        // The message as it was before the TSIG RR was added: TSIG stripped,
+        // ARCOUNT decremented, ID restored to the original ID.
+        let mut stripped = raw[..tsig.offset].to_vec();
+        let arcount = u16::from_be_bytes([stripped[10], stripped[11]]).wrapping_sub(1);
+        stripped[10..12].copy_from_slice(&arcount.to_be_bytes());
+        stripped[0..2].copy_from_slice(&tsig.original_id.to_be_bytes());
+
+        let prior_len = (self.prior_mac.len() as u16).to_be_bytes();
+        let expected = if self.first {
+            let variables = self
+                .key
+                .variables(tsig.time_signed, tsig.fudge, tsig.error)?;
+            self.key
+                .digest(&[&prior_len, &self.prior_mac, &stripped, &variables])
+        } else {
+            let mut timers = tsig.time_signed.to_be_bytes()[2..].to_vec();
+            timers.extend_from_slice(&tsig.fudge.to_be_bytes());
+            self.key.digest(&[
+                &prior_len,
+                &self.prior_mac,
+                &self.pending,
+                &stripped,
+                &timers,
+            ])
+        };
+
+        if tsig.mac.len() != expected.len() || !bool::from(tsig.mac.ct_eq(&expected)) {
+            return Err(anyhow::anyhow!(
+                "TSIG signature verification failed (BADSIG)"
+            ));
+        }
+
+        self.prior_mac = tsig.mac.clone();
+        self.first = false;
+        self.pending.clear();
+        self.unsigned = 0;
+        Ok(())
+    }
+
+    /// Whether the last message seen carried a verified signature.
+    ///
+    /// The final message of a stream must be signed (RFC 8945 §5.3.1).
+    pub fn is_complete(&self) -> bool {
+        !self.first && self.unsigned == 0
+    }
+}
+
+// ========== RDATA Encoding ==========
+
+/// Largest RDATA that fits in a resource record
+const MAX_RDATA_LEN: usize = u16::MAX as usize;
+
+/// LOC defaults: 1 m size, 10 km horizontal and 10 m vertical precision, as
+/// the precision byte (RFC 1876 section 3)
+const LOC_DEFAULT_PRECISION: [u8; 3] = [0x12, 0x16, 0x13];
+
+/// Equator, prime meridian and 100 km below the WGS 84 spheroid: the zero
+/// points of LOC latitude, longitude and altitude
+const LOC_EQUATOR: i64 = 1 << 31;
+const LOC_ALTITUDE_BASE_CM: i64 = 10_000_000;
+
+/// One whitespace-separated field of presentation-format RDATA
+struct Field<'a> {
+    /// The field as written, for names and numbers
+    text: &'a str,
+    /// Contents with quotes removed and `\X` / `\DDD` escapes resolved, for
+    /// character-strings and other binary data
+    bytes: Vec<u8>,
+}
+
+/// Split presentation-format RDATA into fields.
+///
+/// A quoted string is one field even if it contains spaces, also when it
+/// follows other text (e.g. `alpn="h2,h3"`). Outside quotes `;`, `(` and `)`
+/// are rejected: in a zone file they would start a comment or a multi-line
+/// record and change what the line means.
+fn rdata_fields(text: &str) -> Result<Vec<Field<'_>>> {
+    let raw = text.as_bytes();
+    let mut fields = Vec::new();
+    let mut i = 0;
+
+    while i < raw.len() {
+        if raw[i] == b' ' || raw[i] == b'\t' {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut bytes = Vec::new();
+        let mut in_quotes = false;
+        while let Some(&b) = raw.get(i) {
+            match b {
+                b'"' => {
+                    in_quotes = !in_quotes;
+                    i += 1;
+                }
+                b' ' | b'\t' if !in_quotes => break,
+                b';' | b'(' | b')' if !in_quotes => {
+                    return Err(anyhow::anyhow!("unquoted {:?} in RDATA", b as char));
+                }
+                b'\\' => {
+                    let digits = raw
+                        .get(i + 1..i + 4)
+                        .filter(|d| d.iter().all(u8::is_ascii_digit));
+                    if let Some(digits) = digits {
+                        let value: u16 = std::str::from_utf8(digits)
+                            .expect("ASCII digits")
+                            .parse()
+                            .expect("three digits");
+                        bytes.push(
+                            u8::try_from(value).map_err(|_| {
+                                anyhow::anyhow!("escape \\{} is out of range", value)
+                            })?,
+                        );
+                        i += 4;
+                    } else {
+                        let escaped = *raw.get(i + 1).context("RDATA ends with a backslash")?;
+                        bytes.push(escaped);
+                        i += 2;
+                    }
+                }
+                _ if b.is_ascii_control() => {
+                    return Err(anyhow::anyhow!("RDATA contains a control character"));
+                }
+                _ => {
+                    bytes.push(b);
+                    i += 1;
+                }
+            }
+        }
+        if in_quotes {
+            return Err(anyhow::anyhow!("unterminated quoted string in RDATA"));
+        }
+
+        fields.push(Field {
+            text: &text[start..i],
+            bytes,
+        });
+    }
+
+    Ok(fields)
+}
+
+fn expect_fields(fields: &[Field], min: usize, max: Option<usize>) -> Result<()> {
+    if fields.len() < min || max.is_some_and(|max| fields.len() > max) {
+        return Err(anyhow::anyhow!(
+            "expected {} fields, found {}",
+            match max {
+                Some(max) if max == min => min.to_string(),
+                Some(max) => format!("{} to {}", min, max),
+                None => format!("at least {}", min),
+            },
+            fields.len()
+        ));
+    }
+    Ok(())
+}
+
+fn number<T: std::str::FromStr>(what: &str, field: &Field) -> Result<T> {
+    field
+        .text
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid {}: {}", what, field.text))
+}
+
+fn push_name(field: &Field, buf: &mut Vec<u8>) -> Result<()> {
+    if field.text.contains('\\') || field.text.contains('"') {
+        return Err(anyhow::anyhow!(
+            "quoted or escaped domain names are not supported: {}",
+            field.text
+        ));
+    }
+    if !field.text.ends_with('.') {
+        return Err(anyhow::anyhow!(
+            "domain name must be fully qualified, ending with '.': {}",
+            field.text
+        ));
+    }
+    encode_name(field.text, false, buf)
+}
+
+fn push_char_string(bytes: &[u8], buf: &mut Vec<u8>) -> Result<()> {
+    let len = u8::try_from(bytes.len())
+        .map_err(|_| anyhow::anyhow!("character-string is longer than 255 bytes"))?;
+    buf.push(len);
+    buf.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn push_hex(what: &str, fields: &[Field], buf: &mut Vec<u8>) -> Result<()> {
+    let digits: String = fields.iter().map(|f| f.text).collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return Err(anyhow::anyhow!(
+            "{} must be an even number of hex digits",
+            what
+        ));
+    }
+    for pair in digits.as_bytes().chunks(2) {
+        let pair = std::str::from_utf8(pair).unwrap_or("");
+        buf.push(
+            u8::from_str_radix(pair, 16)
+                .map_err(|_| anyhow::anyhow!("{} is not hexadecimal", what))?,
+        );
+    }
+    Ok(())
+}
+
+fn push_base64(what: &str, fields: &[Field], buf: &mut Vec<u8>) -> Result<()> {
+    let text: String = fields.iter().map(|f| f.text).collect();
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(&text)
+        .map_err(|_| anyhow::anyhow!("{} is not valid base64", what))?;
+    buf.extend(decoded);
+    Ok(())
+}
+
+fn dnssec_algorithm(field: &Field) -> Result<u8> {
+    dnssec_algorithm_number(field.text).map_or_else(|| number("algorithm", field), Ok)
+}
+
+/// Encode the `\# <len> <hex>` form (RFC 3597 section 5).
+fn encode_generic(fields: &[Field], buf: &mut Vec<u8>) -> Result<()> {
+    expect_fields(fields, 2, None)?;
+    let len: usize = number("RDATA length", &fields[1])?;
+    if len == 0 {
+        expect_fields(fields, 2, Some(2))?;
+        return Ok(());
+    }
+    push_hex("RDATA", &fields[2..], buf)?;
+    if buf.len() != len {
+        return Err(anyhow::anyhow!(
+            "RDATA length {} does not match its {} bytes",
+            len,
+            buf.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Whole centimeters in a LOC distance such as `10m` or `-24.5`.
+fn loc_centimeters(field: &Field) -> Result<i64> {
+    let meters: f64 = field
+        .text
+        .strip_suffix(['m', 'M'])
+        .unwrap_or(field.text)
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid LOC distance: {}", field.text))?;
+    Ok((meters * 100.0).round() as i64)
+}
+
+/// A LOC precision as `mantissa << 4 | exponent`, in centimeters.
+fn loc_precision(field: &Field) -> Result<u8> {
+    let mut mantissa = loc_centimeters(field)?;
+    if mantissa < 0 {
+        return Err(anyhow::anyhow!("LOC precision cannot be negative"));
+    }
+    let mut exponent = 0u8;
+    while mantissa >= 10 && exponent < 9 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    if mantissa >= 10 {
+        return Err(anyhow::anyhow!("LOC precision is out of range"));
+    }
+    Ok((mantissa as u8) << 4 | exponent)
+}
+
+/// A LOC latitude or longitude, `degrees [minutes [seconds]] hemisphere`, in
+/// thousandths of a second of arc from the equator or prime meridian.
+fn loc_coordinate(
+    fields: &[Field],
+    pos: &mut usize,
+    hemispheres: [&str; 2],
+    max_degrees: i64,
+) -> Result<u32> {
+    let start = *pos;
+    let sign = loop {
+        let field = fields
+            .get(*pos)
+            .with_context(|| format!("LOC is missing the {} hemisphere", hemispheres.join("/")))?;
+        *pos += 1;
+        if field.text.eq_ignore_ascii_case(hemispheres[0]) {
+            break 1;
+        }
+        if field.text.eq_ignore_ascii_case(hemispheres[1]) {
+            break -1;
+        }
+        if *pos - start > 3 {
+            return Err(anyhow::anyhow!("invalid LOC coordinate"));
+        }
+    };
+
+    let parts = &fields[start..*pos - 1];
+    let degrees: i64 = number("LOC degrees", parts.first().context("LOC degrees missing")?)?;
+    let minutes: i64 = parts.get(1).map_or(Ok(0), |f| number("LOC minutes", f))?;
+    let seconds: f64 = parts.get(2).map_or(Ok(0.0), |f| number("LOC seconds", f))?;
+    let thousandths = degrees * 3_600_000 + minutes * 60_000 + (seconds * 1000.0).round() as i64;
+    if !(0..60).contains(&minutes)
+        || !(0.0..60.0).contains(&seconds)
+        || thousandths > max_degrees * 3_600_000
+    {
+        return Err(anyhow::anyhow!("LOC coordinate is out of range"));
+    }
+    Ok((LOC_EQUATOR + sign * thousandths) as u32)
+}
+
+/// LOC (RFC 1876 section 3)
+fn encode_loc(fields: &[Field], buf: &mut Vec<u8>) -> Result<()> {
+    let mut pos = 0;
+    let latitude = loc_coordinate(fields, &mut pos, ["N", "S"], 90)?;
+    let longitude = loc_coordinate(fields, &mut pos, ["E", "W"], 180)?;
+    let altitude = LOC_ALTITUDE_BASE_CM
+        + loc_centimeters(fields.get(pos).context("LOC is missing the altitude")?)?;
+    pos += 1;
+    let altitude =
+        u32::try_from(altitude).map_err(|_| anyhow::anyhow!("LOC altitude is out of range"))?;
+
+    let mut precision = LOC_DEFAULT_PRECISION;
+    for slot in precision.iter_mut() {
+        if let Some(field) = fields.get(pos) {
+            *slot = loc_precision(field)?;
+            pos += 1;
+        }
+    }
+    if pos != fields.len() {
+        return Err(anyhow::anyhow!("too many LOC fields"));
+    }
+
+    buf.push(0); // version
+    buf.extend_from_slice(&precision);
+    buf.extend_from_slice(&latitude.to_be_bytes());
+    buf.extend_from_slice(&longitude.to_be_bytes());
+    buf.extend_from_slice(&altitude.to_be_bytes());
+    Ok(())
+}
+
+/// SVCB and HTTPS service parameters, in key order (RFC 9460 section 2.2)
+fn encode_svc_params(fields: &[Field], buf: &mut Vec<u8>) -> Result<()> {
+    let mut params = std::collections::BTreeMap::new();
+    for field in fields {
+        let (key, value) = match field.bytes.iter().position(|&b| b == b'=') {
+            Some(eq) => (&field.bytes[..eq], Some(&field.bytes[eq + 1..])),
+            None => (&field.bytes[..], None),
+        };
+        let key = String::from_utf8_lossy(key);
+        let number = svc_param_key_number(&key)
+            .with_context(|| format!("unknown service parameter: {}", key))?;
+        let text = value.map(String::from_utf8_lossy).unwrap_or_default();
+        let list = || text.split(',').filter(|item| !item.is_empty());
+
+        let mut encoded = Vec::new();
+        match number {
+            0 => {
+                let mut keys = list()
+                    .map(|k| {
+                        svc_param_key_number(k)
+                            .with_context(|| format!("unknown mandatory key: {}", k))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                keys.sort_unstable();
+                keys.iter()
+                    .for_each(|k| encoded.extend_from_slice(&k.to_be_bytes()));
+            }
+            1 => {
+                for id in list() {
+                    push_char_string(id.as_bytes(), &mut encoded)?;
+                }
+            }
+            2 => {}
+            3 => encoded.extend_from_slice(
+                &text
+                    .parse::<u16>()
+                    .map_err(|_| anyhow::anyhow!("invalid port: {}", text))?
+                    .to_be_bytes(),
+            ),
+            4 => {
+                for ip in list() {
+                    let ip: Ipv4Addr = ip
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("invalid ipv4hint: {}", ip))?;
+                    encoded.extend_from_slice(&ip.octets());
+                }
+            }
+            5 => {
+                encoded = base64::engine::general_purpose::STANDARD
+                    .decode(text.as_bytes())
+                    .map_err(|_| anyhow::anyhow!("ech is not valid base64"))?;
+            }
+            6 => {
+                for ip in list() {
+                    let ip: Ipv6Addr = ip
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("invalid ipv6hint: {}", ip))?;
+                    encoded.extend_from_slice(&ip.octets());
+                }
+            }
+            _ => encoded.extend_from_slice(value.unwrap_or_default()),
+        }
+
+        if params.insert(number, encoded).is_some() {
+            return Err(anyhow::anyhow!("duplicate service parameter: {}", key));
+        }
+    }
+
+    for (key, value) in params {
+        buf.extend_from_slice(&key.to_be_bytes());
+        buf.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        buf.extend(value);
+    }
+    Ok(())
+}
+
+/// Encode presentation-format RDATA, as used in zone files and nsupdate
+/// commands, to wire format.
+///
+/// Domain names must be absolute; they are never compressed. Any type may be
+/// given in the RFC 3597 `\# <len> <hex>` form.
+///
+/// # Errors
+/// Returns an error if the text does not match the type's format, or if the
+/// type is one bindcar cannot encode from presentation format.
+pub fn encode_rdata(rtype: u16, text: &str) -> Result<Vec<u8>> {
+    let fields = rdata_fields(text)?;
+    let type_name = record_type_name(rtype);
+    let mut buf = Vec::with_capacity(text.len());
+
+    if fields.first().is_some_and(|f| f.text == "\\#") {
+        encode_generic(&fields, &mut buf)?;
+        return Ok(buf);
+    }
+
+    match type_name.as_str() {
+        "A" => {
+            expect_fields(&fields, 1, Some(1))?;
+            let ip: Ipv4Addr = number("IPv4 address", &fields[0])?;
+            buf.extend_from_slice(&ip.octets());
+        }
+        "AAAA" => {
+            expect_fields(&fields, 1, Some(1))?;
+            let ip: Ipv6Addr = number("IPv6 address", &fields[0])?;
+            buf.extend_from_slice(&ip.octets());
+        }
+        "NS" | "CNAME" | "PTR" | "DNAME" => {
+            expect_fields(&fields, 1, Some(1))?;
+            push_name(&fields[0], &mut buf)?;
+        }
+        "SOA" => {
+            expect_fields(&fields, 7, Some(7))?;
+            push_name(&fields[0], &mut buf)?;
+            push_name(&fields[1], &mut buf)?;
+            for field in &fields[2..] {
+                let value = crate::zone_file_parser::parse_ttl(field.text)
+                    .with_context(|| format!("invalid SOA value: {}", field.text))?;
+                buf.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        "MX" => {
+            expect_fields(&fields, 2, Some(2))?;
+            buf.extend_from_slice(&number::<u16>("preference", &fields[0])?.to_be_bytes());
+            push_name(&fields[1], &mut buf)?;
+        }
+        "SRV" => {
+            expect_fields(&fields, 4, Some(4))?;
+            for (what, field) in ["priority", "weight", "port"].iter().zip(&fields) {
+                buf.extend_from_slice(&number::<u16>(what, field)?.to_be_bytes());
+            }
+            push_name(&fields[3], &mut buf)?;
+        }
+        "TXT" | "SPF" => {
+            expect_fields(&fields, 1, None)?;
+            for field in &fields {
+                push_char_string(&field.bytes, &mut buf)?;
+            }
+        }
+        "HINFO" => {
+            expect_fields(&fields, 2, Some(2))?;
+            push_char_string(&fields[0].bytes, &mut buf)?;
+            push_char_string(&fields[1].bytes, &mut buf)?;
+        }
+        "CAA" => {
+            expect_fields(&fields, 3, Some(3))?;
+            buf.push(number("flags", &fields[0])?);
+            push_char_string(fields[1].text.as_bytes(), &mut buf)?;
+            buf.extend_from_slice(&fields[2].bytes);
+        }
+        "DS" | "CDS" => {
+            expect_fields(&fields, 4, None)?;
+            buf.extend_from_slice(&number::<u16>("key tag", &fields[0])?.to_be_bytes());
+            buf.push(dnssec_algorithm(&fields[1])?);
+            buf.push(number("digest type", &fields[2])?);
+            push_hex("digest", &fields[3..], &mut buf)?;
+        }
+        "DNSKEY" | "CDNSKEY" => {
+            expect_fields(&fields, 4, None)?;
+            buf.extend_from_slice(&number::<u16>("flags", &fields[0])?.to_be_bytes());
+            buf.push(number("protocol", &fields[1])?);
+            buf.push(dnssec_algorithm(&fields[2])?);
+            push_base64("public key", &fields[3..], &mut buf)?;
+        }
+        "TLSA" => {
+            expect_fields(&fields, 4, None)?;
+            buf.push(number("usage", &fields[0])?);
+            buf.push(number("selector", &fields[1])?);
+            buf.push(number("matching type", &fields[2])?);
+            push_hex("certificate data", &fields[3..], &mut buf)?;
+        }
+        "SSHFP" => {
+            expect_fields(&fields, 3, None)?;
+            buf.push(number("algorithm", &fields[0])?);
+            buf.push(number("fingerprint type", &fields[1])?);
+            push_hex("fingerprint", &fields[2..], &mut buf)?;
+        }
+        "NAPTR" => {
+            expect_fields(&fields, 6, Some(6))?;
+            buf.extend_from_slice(&number::<u16>("order", &fields[0])?.to_be_bytes());
+            buf.extend_from_slice(&number::<u16>("preference", &fields[1])?.to_be_bytes());
+            for field in &fields[2..5] {
+                push_char_string(&field.bytes, &mut buf)?;
+            }
+            push_name(&fields[5], &mut buf)?;
+        }
+        "SVCB" | "HTTPS" => {
+            expect_fields(&fields, 2, None)?;
+            buf.extend_from_slice(&number::<u16>("priority", &fields[0])?.to_be_bytes());
+            push_name(&fields[1], &mut buf)?;
+            encode_svc_params(&fields[2..], &mut buf)?;
+        }
+        "LOC" => encode_loc(&fields, &mut buf)?,
+        "URI" => {
+            expect_fields(&fields, 3, Some(3))?;
+            buf.extend_from_slice(&number::<u16>("priority", &fields[0])?.to_be_bytes());
+            buf.extend_from_slice(&number::<u16>("weight", &fields[1])?.to_be_bytes());
+            buf.extend_from_slice(&fields[2].bytes);
+        }
+        "CERT" => {
+            expect_fields(&fields, 4, None)?;
+            let cert_type = cert_type_number(fields[0].text)
+                .map_or_else(|| number("certificate type", &fields[0]), Ok)?;
+            buf.extend_from_slice(&cert_type.to_be_bytes());
+            buf.extend_from_slice(&number::<u16>("key tag", &fields[1])?.to_be_bytes());
+            buf.push(dnssec_algorithm(&fields[2])?);
+            push_base64("certificate", &fields[3..], &mut buf)?;
+        }
+        other => {
+            return Err(anyhow::anyhow!(
+                "cannot encode {} RDATA from presentation format; use the \\# form",
+                other
+            ))
+        }
+    }
+
+    if buf.len() > MAX_RDATA_LEN {
+        return Err(anyhow::anyhow!("{} RDATA is too long", type_name));
+    }
+    Ok(buf)
+}
+
+/// Canonical presentation form of RDATA: the text encoded to wire format and
+/// rendered back, so that spelling differences (mnemonics, hex case, base64
+/// spacing, parameter order, the `\#` form) disappear.
+///
+/// Returns `None` if the text cannot be encoded.
+pub fn canonical_rdata(rtype: u16, text: &str) -> Option<String> {
+    let rdata = encode_rdata(rtype, text).ok()?;
+    render_rdata(&rdata, 0, rdata.len(), rtype).ok()
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for DNS wire-format handling and TSIG
+
+#[cfg(test)]
+mod tests {
+    use crate::dns_wire::*;
+
+    const SECRET: &str = "dGVzdC1zZWNyZXQtaGVyZQ==";
+
+    fn key() -> TsigKey {
+        TsigKey::new("update-key", "HMAC-SHA256", SECRET).unwrap()
+    }
+
+    /// Build a response to `request_id` carrying the given answer RRs
+    /// (owner name, type, RDATA).
+    fn response(id: u16, answers: &[(&str, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&id.to_be_bytes());
+        msg.extend_from_slice(&0x8400u16.to_be_bytes()); // QR AA
+        msg.extend_from_slice(&0u16.to_be_bytes());
+        msg.extend_from_slice(&(answers.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&[0, 0, 0, 0]);
+        for (name, rtype, rdata) in answers {
+            encode_name(name, false, &mut msg).unwrap();
+            msg.extend_from_slice(&rtype.to_be_bytes());
+            msg.extend_from_slice(&CLASS_IN.to_be_bytes());
+            msg.extend_from_slice(&300u32.to_be_bytes());
+            msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            msg.extend_from_slice(rdata);
+        }
+        msg
+    }
+
+    /// Sign a response the way a server does for the first message of a reply.
+    fn sign_first_response(key: &TsigKey, msg: &mut Vec<u8>, request_mac: &[u8], now: u64) {
+        let variables = key.variables(now, DEFAULT_TSIG_FUDGE, 0).unwrap();
+        let prior_len = (request_mac.len() as u16).to_be_bytes();
+        let mac = key.digest(&[&prior_len, request_mac, msg, &variables]);
+        append_tsig(key, msg, &mac, now);
+    }
+
+    fn append_tsig(key: &TsigKey, msg: &mut Vec<u8>, mac: &[u8], now: u64) {
+        let mut rdata = Vec::new();
+        encode_name("hmac-sha256.", true, &mut rdata).unwrap();
+        rdata.extend_from_slice(&now.to_be_bytes()[2..]);
+        rdata.extend_from_slice(&DEFAULT_TSIG_FUDGE.to_be_bytes());
+        rdata.extend_from_slice(&(mac.len() as u16).to_be_bytes());
+        rdata.extend_from_slice(mac);
+        rdata.extend_from_slice(&msg[0..2]);
+        rdata.extend_from_slice(&[0, 0, 0, 0]);
+        encode_name(key.name(), true, msg).unwrap();
+        msg.extend_from_slice(&TYPE_TSIG.to_be_bytes());
+        msg.extend_from_slice(&CLASS_ANY.to_be_bytes());
+        msg.extend_from_slice(&0u32.to_be_bytes());
+        msg.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&rdata);
+        let arcount = u16::from_be_bytes([msg[10], msg[11]]) + 1;
+        msg[10..12].copy_from_slice(&arcount.to_be_bytes());
+    }
+
+    #[test]
+    fn test_record_type_mapping() {
+        assert_eq!(record_type_code("mx"), Some(15));
+        assert_eq!(record_type_code("TYPE65280"), Some(65280));
+        assert_eq!(record_type_code("BOGUS"), None);
+        assert_eq!(record_type_name(257), "CAA");
+        assert_eq!(record_type_name(65280), "TYPE65280");
+    }
+
+    #[test]
+    fn test_encode_name_rejects_bad_labels() {
+        assert!(encode_name("a..b", false, &mut Vec::new()).is_err());
+        let long_label = "a".repeat(64);
+        assert!(encode_name(&long_label, false, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_decode_compressed_name() {
+        let mut msg = Vec::new();
+        encode_name("example.com.", false, &mut msg).unwrap();
+        msg.extend_from_slice(&[3, b'w', b'w', b'w', 0xC0, 0x00]);
+
+        let (name, next) = decode_name(&msg, 13).unwrap();
+        assert_eq!(name, "www.example.com.");
+        assert_eq!(next, msg.len());
+    }
+
+    #[test]
+    fn test_decode_name_rejects_pointer_loop() {
+        let msg = [0xC0, 0x00];
+        assert!(decode_name(&msg, 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_root_and_escaped_names() {
+        assert_eq!(decode_name(&[0], 0).unwrap().0, ".");
+        let msg = [3, b'a', b'.', 0x01, 0];
+        assert_eq!(decode_name(&msg, 0).unwrap().0, "a\\.\\001.");
+    }
+
+    #[test]
+    fn test_parse_message_renders_rdata() {
+        let mut mx = 10u16.to_be_bytes().to_vec();
+        encode_name("mail.example.com.", false, &mut mx).unwrap();
+        let txt = b"\x05hello\x08say \"hi\"".to_vec();
+        let msg = response(
+            1,
+            &[
+                ("example.com.", 1, vec![192, 0, 2, 1]),
+                ("example.com.", 15, mx),
+                ("example.com.", 16, txt),
+                ("example.com.", 99, vec![0xde, 0xad]),
+            ],
+        );
+
+        let parsed = parse_message(&msg).unwrap();
+        let data: Vec<_> = parsed.answers.iter().map(|r| r.data.as_str()).collect();
+        assert_eq!(
+            data,
+            vec![
+                "192.0.2.1",
+                "10 mail.example.com.",
+                "\"hello\" \"say \\\"hi\\\"\"",
+                "\\# 2 dead"
+            ]
+        );
+        assert!(parsed.tsig.is_none());
+    }
+
+    #[test]
+    fn test_parse_message_rejects_truncation() {
+        let msg = response(1, &[("example.com.", 1, vec![192, 0, 2, 1])]);
+        assert!(parse_message(&msg[..msg.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_tsig_key_rejects_weak_algorithms() {
+        assert!(TsigKey::new("k", "hmac-md5", SECRET).is_err());
+        assert!(TsigKey::new("k", "hmac-sha1", SECRET).is_err());
+        assert!(TsigKey::new("k", "sha512", SECRET).is_ok());
+        assert!(TsigKey::new("k", "hmac-sha256", "not base64!").is_err());
+    }
+
+    #[test]
+    fn test_tsig_key_debug_redacts_secret() {
+        let debug = format!("{:?}", key());
+        assert!(debug.contains("REDACTED"));
+        assert!(!debug.contains(SECRET));
+    }
+
+    #[test]
+    fn test_sign_appends_tsig_record() {
+        let key = key();
+        let mut query = build_query(42, "example.com", TYPE_AXFR).unwrap();
+        let unsigned_len = query.len();
+        let mac = key.sign(&mut query, 1_700_000_000, 300).unwrap();
+
+        assert_eq!(mac.len(), 32);
+        let parsed = parse_message(&query).unwrap();
+        let tsig = parsed.tsig.expect("TSIG record");
+        assert_eq!(tsig.offset, unsigned_len);
+        assert_eq!(tsig.key_name, "update-key.");
+        assert_eq!(tsig.algorithm, "hmac-sha256.");
+        assert_eq!(tsig.time_signed, 1_700_000_000);
+        assert_eq!(tsig.mac, mac);
+        assert_eq!(tsig.original_id, 42);
+    }
+
+    #[test]
+    fn test_verify_signed_response() {
+        let key = key();
+        let now = 1_700_000_000;
+        let mut query = build_query(7, "example.com", TYPE_AXFR).unwrap();
+        let request_mac = key.sign(&mut query, now, 300).unwrap();
+
+        let mut reply = response(7, &[("example.com.", 1, vec![192, 0, 2, 1])]);
+        sign_first_response(&key, &mut reply, &request_mac, now);
+
+        let mut verifier = TsigVerifier::new(&key, request_mac);
+        let parsed = parse_message(&reply).unwrap();
+        verifier.verify(&reply, &parsed, now + 10).unwrap();
+        assert!(verifier.is_complete());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_response() {
+        let key = key();
+        let now = 1_700_000_000;
+        let mut query = build_query(7, "example.com", TYPE_AXFR).unwrap();
+        let request_mac = key.sign(&mut query, now, 300).unwrap();
+
+        let mut reply = response(7, &[("example.com.", 1, vec![192, 0, 2, 1])]);
+        sign_first_response(&key, &mut reply, &request_mac, now);
+        // Change the A record address after signing
+        reply[12 + 13 + 10 + 3] = 99;
+
+        let mut verifier = TsigVerifier::new(&key, request_mac);
+        let parsed = parse_message(&reply).unwrap();
+        let err = verifier.verify(&reply, &parsed, now).unwrap_err();
+        assert!(err.to_string().contains("BADSIG"));
+    }
+
+    #[test]
+    fn test_verify_rejects_unsigned_and_stale_responses() {
+        let key = key();
+        let now = 1_700_000_000;
+        let mut query = build_query(7, "example.com", TYPE_AXFR).unwrap();
+        let request_mac = key.sign(&mut query, now, 300).unwrap();
+
+        let unsigned = response(7, &[]);
+        let mut verifier = TsigVerifier::new(&key, request_mac.clone());
+        let parsed = parse_message(&unsigned).unwrap();
+        assert!(verifier.verify(&unsigned, &parsed, now).is_err());
+
+        let mut reply = response(7, &[]);
+        sign_first_response(&key, &mut reply, &request_mac, now);
+        let mut verifier = TsigVerifier::new(&key, request_mac);
+        let parsed = parse_message(&reply).unwrap();
+        let err = verifier.verify(&reply, &parsed, now + 301).unwrap_err();
+        assert!(err.to_string().contains("BADTIME"));
+    }
+}
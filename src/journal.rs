@@ -0,0 +1,219 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! BIND9 zone journal (`.jnl`) decoding
+//!
+//! BIND records every change to a dynamic zone in `<zone file>.jnl` as an
+//! IXFR-style difference between two SOA serials. A journal is laid out as:
+//!
+//! ```text
+//! header      64 bytes: format string, first and next transaction
+//!             positions (serial + offset), index size, source serial
+//! index       index size x 8 bytes (serial + offset), used by BIND only
+//! transaction size, [record count,] serial from, serial to
+//!             then records, each a 4-byte length and an uncompressed RR
+//! ```
+//!
+//! Format 1 (`;BIND LOG V9`) has no record count in the transaction header;
+//! format 2 (`;BIND LOG V9.2`, BIND 9.16.13 and later) adds it. Within a
+//! transaction the old SOA starts the removed records and the new SOA starts
+//! the added records.
+//!
+//! Only the committed part of the file (up to the header's next-transaction
+//! offset) is read, so a journal BIND is appending to can be decoded safely.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::dns_wire::{self, TYPE_SOA};
+
+/// Size of the journal header in bytes
+const HEADER_SIZE: usize = 64;
+
+/// Size of one index entry (serial + offset)
+const INDEX_ENTRY_SIZE: usize = 8;
+
+/// Format strings at the start of the header
+const FORMAT_V1: &[u8] = b";BIND LOG V9\n";
+const FORMAT_V2: &[u8] = b";BIND LOG V9.2\n";
+
+/// Header flag: the source serial is set
+const FLAG_SOURCE_SERIAL_VALID: u8 = 0x01;
+
+/// Journal header
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalHeader {
+    /// Journal format: 1 (`;BIND LOG V9`) or 2 (`;BIND LOG V9.2`)
+    pub version: u8,
+    /// Serial the first transaction starts from
+    pub first_serial: u32,
+    /// Serial the last transaction ends at
+    pub last_serial: u32,
+    /// Serial of the zone file the journal applies to, for inline-signed zones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_serial: Option<u32>,
+    #[serde(skip)]
+    begin_offset: u32,
+    #[serde(skip)]
+    end_offset: u32,
+}
+
+/// A record added or removed by a journal transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct JournalRecord {
+    /// Absolute owner name
+    pub name: String,
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub ttl: u32,
+    /// RDATA in presentation format
+    pub value: String,
+}
+
+/// One change to the zone, from one SOA serial to the next
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalTransaction {
+    pub serial_from: u32,
+    pub serial_to: u32,
+    /// Records removed, without the old SOA
+    pub removed: Vec<JournalRecord>,
+    /// Records added, without the new SOA
+    pub added: Vec<JournalRecord>,
+}
+
+/// A decoded journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Journal {
+    pub header: JournalHeader,
+    /// Transactions, oldest first
+    pub transactions: Vec<JournalTransaction>,
+}
+
+fn be_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    let b = bytes.get(offset..offset + 4).context("truncated journal")?;
+    Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Decode the journal header.
+///
+/// # Errors
+/// Returns an error if the data is shorter than a header or does not start
+/// with a known format string.
+pub fn parse_header(bytes: &[u8]) -> Result<JournalHeader> {
+    let header = bytes
+        .get(..HEADER_SIZE)
+        .context("file is shorter than a journal header")?;
+    let version = if header.starts_with(FORMAT_V2) {
+        2
+    } else if header.starts_with(FORMAT_V1) {
+        1
+    } else {
+        anyhow::bail!("not a BIND journal (unknown format string)");
+    };
+
+    let flags = header[40];
+    Ok(JournalHeader {
+        version,
+        first_serial: be_u32(header, 16)?,
+        begin_offset: be_u32(header, 20)?,
+        last_serial: be_u32(header, 24)?,
+        end_offset: be_u32(header, 28)?,
+        source_serial: (flags & FLAG_SOURCE_SERIAL_VALID != 0)
+            .then(|| be_u32(header, 36))
+            .transpose()?,
+    })
+}
+
+/// Decode the records of one transaction, split at the SOAs.
+fn parse_records(
+    bytes: &[u8],
+    mut pos: usize,
+    end: usize,
+) -> Result<(Vec<JournalRecord>, Vec<JournalRecord>)> {
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let mut soas = 0;
+
+    while pos < end {
+        let size = be_u32(bytes, pos)? as usize;
+        let start = pos + 4;
+        let record_end = start
+            .checked_add(size)
+            .filter(|&e| e <= end)
+            .context("journal record overruns its transaction")?;
+        let (record, next) = dns_wire::decode_record(&bytes[..record_end], start)
+            .context("malformed journal record")?;
+        if next != record_end {
+            anyhow::bail!("journal record length does not match its contents");
+        }
+        pos = record_end;
+
+        if record.rtype == TYPE_SOA {
+            soas += 1;
+            continue;
+        }
+        let record = JournalRecord {
+            name: record.name,
+            record_type: dns_wire::record_type_name(record.rtype),
+            ttl: record.ttl,
+            value: record.data,
+        };
+        // Records after the first SOA are removed, after the second added
+        match soas {
+            1 => removed.push(record),
+            2 => added.push(record),
+            _ => anyhow::bail!("journal transaction does not start with an SOA"),
+        }
+    }
+
+    Ok((removed, added))
+}
+
+/// Decode a journal file.
+///
+/// # Errors
+/// Returns an error if the header is invalid or a committed transaction is
+/// truncated or malformed.
+pub fn parse_journal(bytes: &[u8]) -> Result<Journal> {
+    let header = parse_header(bytes)?;
+    let transaction_header_size = if header.version == 2 { 16 } else { 12 };
+
+    let index_size = be_u32(bytes, 32)? as usize;
+    let first = HEADER_SIZE + index_size * INDEX_ENTRY_SIZE;
+    let mut pos = (header.begin_offset as usize).max(first);
+    let end = header.end_offset as usize;
+    if end > bytes.len() {
+        anyhow::bail!("journal is shorter than its header says");
+    }
+
+    let mut transactions = Vec::new();
+    while pos < end {
+        let size = be_u32(bytes, pos)? as usize;
+        let serial_offset = transaction_header_size - 8;
+        let serial_from = be_u32(bytes, pos + serial_offset)?;
+        let serial_to = be_u32(bytes, pos + serial_offset + 4)?;
+        let start = pos + transaction_header_size;
+        let transaction_end = start
+            .checked_add(size)
+            .filter(|&e| e <= end)
+            .context("journal transaction overruns the journal")?;
+
+        let (removed, added) = parse_records(bytes, start, transaction_end)
+            .with_context(|| format!("transaction {} -> {}", serial_from, serial_to))?;
+        transactions.push(JournalTransaction {
+            serial_from,
+            serial_to,
+            removed,
+            added,
+        });
+        pos = transaction_end;
+    }
+
+    Ok(Journal {
+        header,
+        transactions,
+    })
+}
@@ -0,0 +1,168 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for BIND journal decoding
+
+#[cfg(test)]
+mod tests {
+    use crate::dns_wire::encode_name;
+    use crate::journal::*;
+
+    const TYPE_A: u16 = 1;
+    const TYPE_SOA: u16 = 6;
+    const TYPE_TXT: u16 = 16;
+
+    /// An uncompressed RR preceded by its 4-byte length, as BIND stores it
+    fn record(name: &str, rtype: u16, ttl: u32, rdata: &[u8]) -> Vec<u8> {
+        let mut rr = Vec::new();
+        encode_name(name, false, &mut rr).unwrap();
+        rr.extend_from_slice(&rtype.to_be_bytes());
+        rr.extend_from_slice(&1u16.to_be_bytes());
+        rr.extend_from_slice(&ttl.to_be_bytes());
+        rr.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        rr.extend_from_slice(rdata);
+
+        let mut out = (rr.len() as u32).to_be_bytes().to_vec();
+        out.extend(rr);
+        out
+    }
+
+    fn soa(serial: u32) -> Vec<u8> {
+        let mut rdata = Vec::new();
+        encode_name("ns1.example.com.", false, &mut rdata).unwrap();
+        encode_name("hostmaster.example.com.", false, &mut rdata).unwrap();
+        for value in [serial, 3600, 600, 604800, 86400] {
+            rdata.extend_from_slice(&value.to_be_bytes());
+        }
+        record("example.com.", TYPE_SOA, 3600, &rdata)
+    }
+
+    fn txt(value: &str) -> Vec<u8> {
+        let mut rdata = vec![value.len() as u8];
+        rdata.extend_from_slice(value.as_bytes());
+        record("_acme-challenge.example.com.", TYPE_TXT, 60, &rdata)
+    }
+
+    /// Serial from, serial to, removed records and added records
+    type Transaction = (u32, u32, Vec<Vec<u8>>, Vec<Vec<u8>>);
+
+    /// Build a journal with the given transactions
+    fn journal(version: u8, transactions: &[Transaction]) -> Vec<u8> {
+        let index_size = 2u32;
+        let begin = 64 + index_size as usize * 8;
+
+        let mut body = Vec::new();
+        for (from, to, removed, added) in transactions {
+            let mut records = soa(*from);
+            removed.iter().for_each(|r| records.extend(r));
+            records.extend(soa(*to));
+            added.iter().for_each(|r| records.extend(r));
+
+            body.extend_from_slice(&(records.len() as u32).to_be_bytes());
+            if version == 2 {
+                let count = 2 + removed.len() + added.len();
+                body.extend_from_slice(&(count as u32).to_be_bytes());
+            }
+            body.extend_from_slice(&from.to_be_bytes());
+            body.extend_from_slice(&to.to_be_bytes());
+            body.extend(records);
+        }
+
+        let mut header = vec![0u8; 64];
+        let format: &[u8] = if version == 2 {
+            b";BIND LOG V9.2\n"
+        } else {
+            b";BIND LOG V9\n"
+        };
+        header[..format.len()].copy_from_slice(format);
+        let first = transactions.first().map_or(0, |t| t.0);
+        let last = transactions.last().map_or(0, |t| t.1);
+        header[16..20].copy_from_slice(&first.to_be_bytes());
+        header[20..24].copy_from_slice(&(begin as u32).to_be_bytes());
+        header[24..28].copy_from_slice(&last.to_be_bytes());
+        header[28..32].copy_from_slice(&((begin + body.len()) as u32).to_be_bytes());
+        header[32..36].copy_from_slice(&index_size.to_be_bytes());
+
+        let mut bytes = header;
+        bytes.extend(vec![0u8; index_size as usize * 8]);
+        bytes.extend(body);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_journal_v2() {
+        let bytes = journal(
+            2,
+            &[
+                (
+                    10,
+                    11,
+                    vec![],
+                    vec![record("www.example.com.", TYPE_A, 300, &[192, 0, 2, 1])],
+                ),
+                (11, 12, vec![txt("old")], vec![txt("new")]),
+            ],
+        );
+        let parsed = parse_journal(&bytes).unwrap();
+
+        assert_eq!(parsed.header.version, 2);
+        assert_eq!(parsed.header.first_serial, 10);
+        assert_eq!(parsed.header.last_serial, 12);
+        assert_eq!(parsed.header.source_serial, None);
+        assert_eq!(parsed.transactions.len(), 2);
+
+        let first = &parsed.transactions[0];
+        assert_eq!((first.serial_from, first.serial_to), (10, 11));
+        assert!(first.removed.is_empty());
+        assert_eq!(
+            first.added,
+            [JournalRecord {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                ttl: 300,
+                value: "192.0.2.1".to_string(),
+            }]
+        );
+
+        let second = &parsed.transactions[1];
+        assert_eq!(second.removed[0].value, r#""old""#);
+        assert_eq!(second.added[0].value, r#""new""#);
+        assert_eq!(second.added[0].record_type, "TXT");
+    }
+
+    #[test]
+    fn test_parse_journal_v1() {
+        let bytes = journal(1, &[(7, 8, vec![txt("a")], vec![])]);
+        let parsed = parse_journal(&bytes).unwrap();
+        assert_eq!(parsed.header.version, 1);
+        assert_eq!(parsed.transactions[0].removed.len(), 1);
+        assert!(parsed.transactions[0].added.is_empty());
+    }
+
+    #[test]
+    fn test_parse_journal_ignores_uncommitted_data() {
+        let mut bytes = journal(2, &[(1, 2, vec![], vec![txt("x")])]);
+        // A transaction BIND is still writing, past the header's end offset
+        bytes.extend_from_slice(&[0, 0, 0, 200, 0, 0]);
+        assert_eq!(parse_journal(&bytes).unwrap().transactions.len(), 1);
+
+        let empty = journal(2, &[]);
+        assert!(parse_journal(&empty).unwrap().transactions.is_empty());
+    }
+
+    #[test]
+    fn test_parse_journal_rejects_bad_input() {
+        assert!(parse_journal(b"short").is_err());
+        assert!(parse_journal(&[b'x'; 64]).is_err());
+
+        let bytes = journal(2, &[(1, 2, vec![], vec![txt("x")])]);
+        assert!(parse_journal(&bytes[..bytes.len() - 3]).is_err());
+
+        // Transaction that does not start with an SOA
+        let mut bytes = journal(2, &[(1, 2, vec![], vec![])]);
+        let start = 64 + 16 + 16;
+        let txt = txt("y");
+        bytes.splice(start..start + txt.len(), txt);
+        assert!(parse_journal(&bytes).is_err());
+    }
+}
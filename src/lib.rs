@@ -76,6 +76,7 @@
 //!                 value: "192.0.2.1".to_string(),
 //!                 ttl: None,
 //!                 priority: None,
+//!                 data: None,
 //!             },
 //!         ],
 //!         also_notify: None,
@@ -83,8 +84,13 @@
 //!         primaries: None,
 //!         dnssec_policy: None,
 //!         inline_signing: None,
+//!         forwarders: None,
+//!         forward: None,
 //!     },
 //!     update_key_name: None,
+//!     update_policy: None,
+//!     serial_strategy: None,
+//!     catalog: None,
 //! };
 //!
 //! // Serialize to JSON for API requests
@@ -137,18 +143,36 @@
 
 // Re-export public modules
 pub mod auth;
+pub mod axfr;
+pub mod bind_files;
+pub mod catalog;
 pub mod cli;
+pub mod dns_update;
+pub mod dns_wire;
+pub mod journal;
 pub mod metrics;
 pub mod middleware;
 pub mod nsupdate;
 pub mod rate_limit;
+pub mod record_batch;
+pub mod record_data;
 pub mod records;
 pub mod rndc;
 pub mod rndc_conf_parser;
 pub mod rndc_conf_types;
 pub mod rndc_parser;
 pub mod rndc_types;
+pub mod server;
+pub mod soa_serial;
 pub mod types;
+pub mod zone_batch;
+pub mod zone_check;
+pub mod zone_clone;
+pub mod zone_diff;
+pub mod zone_export;
+pub mod zone_file_parser;
+pub mod zone_files;
+pub mod zone_options;
 pub mod zones;
 
 // Re-export commonly used types
@@ -156,7 +180,7 @@ pub mod zones;
 // RNDC executor
 pub use rndc::RndcExecutor;
 
-// nsupdate executor
+// Dynamic update (RFC 2136) executor
 pub use nsupdate::NsupdateExecutor;
 
 // Error types
@@ -168,7 +192,7 @@ pub use zones::{DnsRecord, SoaRecord, ZoneConfig};
 // Request/Response types for API operations
 pub use zones::{
     CreateZoneRequest, ServerStatusResponse, ZoneInfo, ZoneListResponse, ZoneResponse,
-    ZONE_TYPE_PRIMARY, ZONE_TYPE_SECONDARY,
+    ZoneStatusResponse, ZONE_TYPE_PRIMARY, ZONE_TYPE_SECONDARY,
 };
 
 // Record management types
@@ -183,12 +207,27 @@ pub use rndc_conf_parser::{parse_rndc_conf_file, parse_rndc_conf_str};
 // RNDC configuration types
 pub use rndc_conf_types::{KeyBlock, OptionsBlock, RndcConfFile, ServerAddress, ServerBlock};
 
+// Zone file parser
+pub use zone_file_parser::{parse_zone_file, ZoneFileParseError};
+
 // Test modules
 #[cfg(test)]
 mod auth_test;
 #[cfg(test)]
+mod axfr_test;
+#[cfg(test)]
+mod bind_files_test;
+#[cfg(test)]
+mod catalog_test;
+#[cfg(test)]
 mod cli_test;
 #[cfg(test)]
+mod dns_update_test;
+#[cfg(test)]
+mod dns_wire_test;
+#[cfg(test)]
+mod journal_test;
+#[cfg(test)]
 mod metrics_test;
 #[cfg(test)]
 mod middleware_test;
@@ -197,6 +236,10 @@ mod nsupdate_test;
 #[cfg(test)]
 mod rate_limit_test;
 #[cfg(test)]
+mod record_batch_test;
+#[cfg(test)]
+mod record_data_test;
+#[cfg(test)]
 mod records_test;
 #[cfg(test)]
 mod rndc_parser_tests;
@@ -205,6 +248,26 @@ mod rndc_test;
 #[cfg(test)]
 mod rndc_types_tests;
 #[cfg(test)]
+mod server_test;
+#[cfg(test)]
+mod soa_serial_test;
+#[cfg(test)]
 mod types_test;
 #[cfg(test)]
+mod zone_batch_test;
+#[cfg(test)]
+mod zone_check_test;
+#[cfg(test)]
+mod zone_clone_test;
+#[cfg(test)]
+mod zone_diff_test;
+#[cfg(test)]
+mod zone_export_test;
+#[cfg(test)]
+mod zone_file_parser_tests;
+#[cfg(test)]
+mod zone_files_test;
+#[cfg(test)]
+mod zone_options_test;
+#[cfg(test)]
 mod zones_test;
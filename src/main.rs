@@ -10,11 +10,11 @@
 
 use anyhow::Context;
 use axum::{
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     http::StatusCode,
     middleware as axum_middleware,
     response::{IntoResponse, Response},
-    routing::{get, post},
+    routing::{get, post, put},
     Json, Router,
 };
 use clap::Parser;
@@ -50,45 +50,110 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
         zones::create_zone,
         zones::delete_zone,
         zones::modify_zone,
+        zones::replace_zone,
         zones::reload_zone,
         zones::zone_status,
         zones::freeze_zone,
         zones::thaw_zone,
         zones::notify_zone,
         zones::retransfer_zone,
+        zones::clone_zone,
+        zones::import_zone,
+        zones::export_zone,
+        zones::sync_zone,
+        zones::get_zone_journal,
+        bindcar::zone_batch::batch_zones,
         zones::server_status,
+        bindcar::server::reconfig,
+        bindcar::server::reload_server,
+        bindcar::server::flush_cache,
+        bindcar::server::sync_zones,
+        bindcar::server::dump_database,
+        bindcar::server::server_stats,
         zones::list_zones,
         zones::get_zone,
         bindcar::records::add_record,
         bindcar::records::remove_record,
         bindcar::records::update_record,
+        bindcar::records::list_records,
+        bindcar::record_batch::batch_records,
+        bindcar::catalog::get_catalog,
+        bindcar::catalog::put_member,
+        bindcar::catalog::delete_member,
     ),
     components(
         schemas(
             zones::CreateZoneRequest,
             zones::ModifyZoneRequest,
+            zones::CloneZoneRequest,
+            bindcar::zone_export::ZoneExport,
+            bindcar::zone_export::ExportFormat,
+            bindcar::zone_batch::BatchZoneRequest,
+            bindcar::zone_batch::BatchZoneResponse,
+            bindcar::zone_batch::BatchZoneResult,
+            bindcar::zone_batch::BatchOperation,
+            bindcar::zone_batch::ZoneSelector,
+            bindcar::zone_options::ZoneOption,
+            bindcar::rndc_types::UpdatePolicyRule,
+            bindcar::rndc_types::UpdatePolicyAction,
+            bindcar::rndc_types::UpdatePolicyRuleType,
             zones::ZoneResponse,
             zones::ServerStatusResponse,
+            zones::ZoneStatusResponse,
+            zones::ZoneJournalResponse,
+            bindcar::journal::JournalHeader,
+            bindcar::journal::JournalTransaction,
+            bindcar::journal::JournalRecord,
+            bindcar::rndc_types::ServerStatus,
+            bindcar::rndc_types::ClientCount,
+            bindcar::rndc_types::ZoneStatus,
+            bindcar::server::ServerCommandResponse,
+            bindcar::server::FlushRequest,
+            bindcar::server::DumpResponse,
+            bindcar::rndc_types::DumpScope,
+            bindcar::rndc_types::DumpedZone,
+            bindcar::rndc_types::StatsDump,
+            bindcar::rndc_types::StatsSection,
             zones::ZoneInfo,
             zones::ZoneListResponse,
+            zones::ZoneSummary,
+            zones::ZoneDrift,
             zones::ZoneConfig,
             zones::SoaRecord,
             zones::DnsRecord,
+            bindcar::record_data::RecordData,
+            bindcar::soa_serial::SerialStrategy,
+            bindcar::types::OperationPlan,
+            bindcar::zone_check::ZoneCheckIssue,
+            bindcar::zone_check::ZoneCheckSeverity,
             bindcar::records::AddRecordRequest,
             bindcar::records::RemoveRecordRequest,
             bindcar::records::UpdateRecordRequest,
+            bindcar::records::RecordPrerequisite,
+            bindcar::records::PrerequisiteCondition,
             bindcar::records::RecordResponse,
+            bindcar::records::RecordSet,
+            bindcar::records::RecordSetListResponse,
+            bindcar::record_batch::BatchRecordRequest,
+            bindcar::record_batch::RecordChange,
+            bindcar::record_batch::RecordOperation,
+            bindcar::catalog::CatalogProperties,
+            bindcar::catalog::CatalogMember,
+            bindcar::catalog::CatalogResponse,
+            bindcar::catalog::CatalogError,
+            bindcar::catalog::CatalogOperation,
         )
     ),
     tags(
         (name = "zones", description = "Zone management endpoints"),
         (name = "records", description = "DNS record management endpoints"),
-        (name = "server", description = "Server status endpoints")
+        (name = "catalogs", description = "Catalog zone (RFC 9432) endpoints"),
+        (name = "server", description = "Server status and maintenance endpoints")
     ),
     info(
         title = "Bindcar API",
         version = VERSION,
-        description = "HTTP REST API for managing BIND9 zones and DNS records via RNDC and nsupdate",
+        description = "HTTP REST API for managing BIND9 zones and DNS records via RNDC and RFC 2136 dynamic updates",
         license(name = "MIT")
     )
 )]
@@ -150,6 +215,7 @@ async fn metrics_handler() -> Response {
                 Json(ErrorResponse {
                     error: "Failed to gather metrics".to_string(),
                     details: None,
+                    issues: None,
                 }),
             )
                 .into_response()
@@ -438,7 +504,7 @@ async fn start_server(command: &Commands, insecure_override: bool) -> anyhow::Re
         .context("failed to create rndc client")?,
     );
 
-    // Configure nsupdate executor (hybrid approach: env vars → rndc credentials)
+    // Configure the dynamic update client (hybrid approach: env vars → rndc credentials)
     let nsupdate_key_name = std::env::var("NSUPDATE_KEY_NAME")
         .ok()
         .or_else(|| std::env::var("RNDC_KEY_NAME").ok())
@@ -461,11 +527,11 @@ async fn start_server(command: &Commands, insecure_override: bool) -> anyhow::Re
         .and_then(|p| p.parse().ok())
         .unwrap_or(53);
 
-    info!("nsupdate executor configuration:");
+    info!("dynamic update client configuration:");
     info!("  server: {}:{}", nsupdate_server, nsupdate_port);
     info!("  TSIG key: {:?}", nsupdate_key_name);
 
-    // create nsupdate executor
+    // create dynamic update executor
     let nsupdate = Arc::new(
         bindcar::nsupdate::NsupdateExecutor::new(
             nsupdate_server,
@@ -477,11 +543,21 @@ async fn start_server(command: &Commands, insecure_override: bool) -> anyhow::Re
         .context("failed to create nsupdate executor")?,
     );
 
+    let catalog_zone = bindcar::catalog::catalog_zone_from_env()?;
+    match &catalog_zone {
+        Some(catalog) => info!("maintaining catalog zone: {}", catalog),
+        None => debug!("no catalog zone configured"),
+    }
+
+    let bind_files = Arc::new(bindcar::bind_files::BindFiles::from_env(&zone_dir)?);
+
     // create application state
     let state = AppState {
         rndc,
         nsupdate,
         zone_dir: zone_dir.clone(),
+        catalog_zone,
+        bind_files,
     };
 
     // build api routes
@@ -490,22 +566,48 @@ async fn start_server(command: &Commands, insecure_override: bool) -> anyhow::Re
         .route(
             "/zones/{name}",
             get(zones::get_zone)
+                .put(zones::replace_zone)
                 .delete(zones::delete_zone)
                 .patch(zones::modify_zone),
         )
+        .route("/zones:batch", post(bindcar::zone_batch::batch_zones))
         .route("/zones/{name}/reload", post(zones::reload_zone))
         .route("/zones/{name}/status", get(zones::zone_status))
         .route("/zones/{name}/freeze", post(zones::freeze_zone))
         .route("/zones/{name}/thaw", post(zones::thaw_zone))
         .route("/zones/{name}/notify", post(zones::notify_zone))
         .route("/zones/{name}/retransfer", post(zones::retransfer_zone))
+        .route("/zones/{name}/clone", post(zones::clone_zone))
+        .route("/zones/{name}/export", get(zones::export_zone))
+        .route("/zones/{name}/sync", post(zones::sync_zone))
+        .route("/zones/{name}/journal", get(zones::get_zone_journal))
+        .route(
+            "/zones/{name}/import",
+            post(zones::import_zone).layer(DefaultBodyLimit::max(zones::MAX_ZONE_IMPORT_BYTES)),
+        )
         .route(
             "/zones/{name}/records",
-            post(bindcar::records::add_record)
+            get(bindcar::records::list_records)
+                .post(bindcar::records::add_record)
                 .delete(bindcar::records::remove_record)
                 .put(bindcar::records::update_record),
         )
+        .route(
+            "/zones/{name}/records:batch",
+            post(bindcar::record_batch::batch_records),
+        )
+        .route("/catalogs/{name}", get(bindcar::catalog::get_catalog))
+        .route(
+            "/catalogs/{name}/members/{zone}",
+            put(bindcar::catalog::put_member).delete(bindcar::catalog::delete_member),
+        )
         .route("/server/status", get(zones::server_status))
+        .route("/server/reconfig", post(bindcar::server::reconfig))
+        .route("/server/reload", post(bindcar::server::reload_server))
+        .route("/server/flush", post(bindcar::server::flush_cache))
+        .route("/server/sync", post(bindcar::server::sync_zones))
+        .route("/server/dumpdb", post(bindcar::server::dump_database))
+        .route("/server/stats", get(bindcar::server::server_stats))
         .with_state(state.clone());
 
     // conditionally apply authentication middleware
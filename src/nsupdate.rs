@@ -1,65 +1,170 @@
 // Copyright (c) 2025 Erick Bourgeois, firestoned
 // SPDX-License-Identifier: MIT
 
-//! nsupdate command executor for dynamic DNS updates
+//! Dynamic DNS updates (RFC 2136)
 //!
-//! This module provides programmatic access to BIND9's nsupdate utility for
-//! performing dynamic DNS record updates using the DNS UPDATE protocol (RFC 2136).
+//! This module performs record updates against BIND9 with the DNS UPDATE
+//! protocol. Messages are built, signed and sent natively by
+//! [`dns_update`](crate::dns_update); no `nsupdate` binary is needed. The
+//! `*_script` builders render the same changes in nsupdate syntax, for dry
+//! runs and logs.
 //!
 //! # Features
 //!
-//! - TSIG authentication support
+//! - TSIG authentication (HMAC-SHA224/256/384/512)
 //! - Add, remove, and update individual DNS records
-//! - Async command execution with tokio
-//! - Comprehensive error handling and parsing
+//! - Ordered multi-record changes applied atomically in one UPDATE
+//! - RFC 2136 prerequisites, for compare-and-swap style conditional updates
+//! - UDP with TCP fallback, or TCP only (`NSUPDATE_TCP`)
+//! - Typed errors for prerequisite failures and refused updates
 
 use anyhow::{Context, Result};
-use std::ffi::OsString;
-use std::io::Write;
-use std::path::Path;
-use std::process::Stdio;
 use std::time::Instant;
-use tokio::io::AsyncWriteExt;
 use tracing::{debug, error, info};
 
+use crate::axfr;
+use crate::dns_update;
+use crate::dns_wire::{self, TsigKey};
 use crate::metrics;
+use crate::zone_file_parser::ResourceRecord;
+
+/// One change in an ordered, atomic update
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateCommand {
+    /// Add a record
+    Add {
+        name: String,
+        ttl: u32,
+        record_type: String,
+        value: String,
+    },
+    /// Delete one record, or the whole RRset when `value` is empty
+    Delete {
+        name: String,
+        record_type: String,
+        value: String,
+    },
+}
+
+/// A condition the zone must meet for an update to be applied (RFC 2136 section 2.4)
+///
+/// The server checks every prerequisite before making any change; if one does
+/// not hold, the whole update is refused and [`PrerequisiteFailed`] is returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prerequisite {
+    /// The name owns at least one record (`prereq yxdomain`)
+    NameInUse { name: String },
+    /// The name owns no records (`prereq nxdomain`)
+    NameNotInUse { name: String },
+    /// The RRset exists (`prereq yxrrset`). With a value, the RRset must hold
+    /// exactly that one record; an empty value matches any contents. The
+    /// server combines all valued prerequisites for one RRset, which must then
+    /// hold exactly those records.
+    RrsetExists {
+        name: String,
+        record_type: String,
+        value: String,
+    },
+    /// The RRset does not exist (`prereq nxrrset`)
+    RrsetNotExists { name: String, record_type: String },
+}
+
+/// An update refused because one of its prerequisites did not hold
+///
+/// Each variant is named after the rcode the server answers with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PrerequisiteFailed {
+    /// A name that must not exist does (YXDOMAIN)
+    #[error("prerequisite failed (YXDOMAIN): the name already exists")]
+    NameExists,
+    /// A name that must exist does not (NXDOMAIN)
+    #[error("prerequisite failed (NXDOMAIN): the name does not exist")]
+    NameMissing,
+    /// An RRset that must not exist does (YXRRSET)
+    #[error("prerequisite failed (YXRRSET): the RRset already exists")]
+    RrsetExists,
+    /// An RRset that must exist is missing or holds other records (NXRRSET)
+    #[error("prerequisite failed (NXRRSET): the RRset does not exist or holds other records")]
+    RrsetMissing,
+}
 
-/// Maximum length of a TSIG key name (matches the DNS name length limit).
-const MAX_TSIG_KEY_NAME_LEN: usize = 253;
+impl PrerequisiteFailed {
+    /// Map an update rcode mnemonic (e.g. "NXRRSET") to the failure it reports
+    pub fn from_rcode(rcode: &str) -> Option<Self> {
+        match rcode {
+            "YXDOMAIN" => Some(Self::NameExists),
+            "NXDOMAIN" => Some(Self::NameMissing),
+            "YXRRSET" => Some(Self::RrsetExists),
+            "NXRRSET" => Some(Self::RrsetMissing),
+            _ => None,
+        }
+    }
 
-/// HMAC algorithms accepted in a TSIG key file.
-const ALLOWED_TSIG_ALGORITHMS: &[&str] = &[
-    "hmac-md5",
-    "hmac-sha1",
-    "hmac-sha224",
-    "hmac-sha256",
-    "hmac-sha384",
-    "hmac-sha512",
-];
+    /// Whether something exists that must not (YXDOMAIN, YXRRSET), rather
+    /// than something required being missing
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::NameExists | Self::RrsetExists)
+    }
+}
+
+/// Render prerequisites as nsupdate `prereq` lines.
+///
+/// # Errors
+/// Returns an error if any field contains a control character.
+fn prerequisite_lines(prerequisites: &[Prerequisite]) -> Result<String> {
+    let mut lines = String::new();
+    for prerequisite in prerequisites {
+        let line = match prerequisite {
+            Prerequisite::NameInUse { name } => {
+                reject_injection_chars("prerequisite name", name)?;
+                format!("prereq yxdomain {}\n", name)
+            }
+            Prerequisite::NameNotInUse { name } => {
+                reject_injection_chars("prerequisite name", name)?;
+                format!("prereq nxdomain {}\n", name)
+            }
+            Prerequisite::RrsetExists {
+                name,
+                record_type,
+                value,
+            } => {
+                reject_injection_chars("prerequisite name", name)?;
+                reject_injection_chars("prerequisite value", value)?;
+                if value.is_empty() {
+                    format!("prereq yxrrset {} IN {}\n", name, record_type)
+                } else {
+                    format!("prereq yxrrset {} IN {} {}\n", name, record_type, value)
+                }
+            }
+            Prerequisite::RrsetNotExists { name, record_type } => {
+                reject_injection_chars("prerequisite name", name)?;
+                format!("prereq nxrrset {} IN {}\n", name, record_type)
+            }
+        };
+        lines.push_str(&line);
+    }
+    Ok(lines)
+}
 
-/// nsupdate command executor
+/// Dynamic update client
 ///
-/// Manages dynamic DNS updates via the nsupdate command-line tool.
-/// Supports TSIG authentication for secure updates.
+/// Sends DNS UPDATE messages to one server, signed with the configured TSIG
+/// key if any.
 #[derive(Clone)]
 pub struct NsupdateExecutor {
-    /// Optional TSIG key name for authentication
-    tsig_key_name: Option<String>,
-    /// Optional TSIG algorithm (e.g., "HMAC-SHA256")
-    tsig_algorithm: Option<String>,
-    /// Optional TSIG secret (base64-encoded)
-    tsig_secret: Option<String>,
+    /// Optional TSIG key for authentication, decoded once at startup
+    tsig_key: Option<TsigKey>,
     /// DNS server address
     server: String,
     /// DNS server port
     port: u16,
-    /// Force TCP transport (nsupdate -v); required in environments where
-    /// UDP is unreliable, e.g., Docker Desktop on macOS.
+    /// Force TCP transport; for environments where UDP is unreliable, e.g.,
+    /// Docker Desktop on macOS.
     use_tcp: bool,
 }
 
 impl NsupdateExecutor {
-    /// Create a new nsupdate executor
+    /// Create a new update client
     ///
     /// # Arguments
     ///
@@ -69,6 +174,13 @@ impl NsupdateExecutor {
     /// * `tsig_algorithm` - Optional TSIG algorithm
     /// * `tsig_secret` - Optional TSIG secret (base64-encoded)
     ///
+    /// TSIG is used only when the key name, algorithm and secret are all set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key name is invalid, the algorithm is outside
+    /// the SHA-2 HMAC family, or the secret is not valid base64.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -90,137 +202,95 @@ impl NsupdateExecutor {
         tsig_secret: Option<String>,
     ) -> Result<Self> {
         info!(
-            "Creating nsupdate executor for {}:{} with TSIG: {}",
+            "Creating DNS update client for {}:{} with TSIG: {}",
             server,
             port,
             tsig_key_name.is_some()
         );
 
+        let tsig_key = match (
+            tsig_key_name.as_deref(),
+            tsig_algorithm.as_deref(),
+            tsig_secret.as_deref(),
+        ) {
+            (Some(key_name), Some(algorithm), Some(secret)) => {
+                Some(TsigKey::new(key_name, algorithm, secret).context("invalid TSIG key")?)
+            }
+            _ => None,
+        };
+
         let use_tcp = std::env::var("NSUPDATE_TCP")
             .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
             .unwrap_or(false);
 
         Ok(Self {
-            tsig_key_name,
-            tsig_algorithm,
-            tsig_secret,
+            tsig_key,
             server,
             port,
             use_tcp,
         })
     }
 
-    /// Create a private (mode `0600`) temporary key file holding the TSIG key,
-    /// for use with `nsupdate -k`.
+    /// Send one UPDATE with the given prerequisites and changes
     ///
-    /// Returns `None` when TSIG is not configured. The file is deleted when the
-    /// returned guard is dropped — callers must keep it alive until nsupdate has
-    /// finished reading it.
+    /// # Returns
     ///
-    /// Passing the key via `-k <file>` instead of `-y algo:name:secret` keeps the
-    /// secret out of the process argument vector, which is world-readable through
-    /// `/proc/<pid>/cmdline` and process listings (B-7).
+    /// The response code of the accepted update ("NOERROR")
     ///
     /// # Errors
-    /// Returns an error if the key material fails validation or the file cannot
-    /// be created or written.
-    pub(crate) fn create_tsig_key_file(&self) -> Result<Option<tempfile::NamedTempFile>> {
-        let (Some(key_name), Some(algorithm), Some(secret)) = (
-            self.tsig_key_name.as_deref(),
-            self.tsig_algorithm.as_deref(),
-            self.tsig_secret.as_deref(),
-        ) else {
-            return Ok(None);
-        };
-
-        let content = build_tsig_key_file_content(key_name, algorithm, secret)?;
-
-        // NamedTempFile is created with mode 0600 on Unix.
-        let mut keyfile =
-            tempfile::NamedTempFile::new().context("Failed to create temporary TSIG key file")?;
-        keyfile
-            .write_all(content.as_bytes())
-            .context("Failed to write TSIG key file")?;
-        keyfile.flush().context("Failed to flush TSIG key file")?;
-
-        debug!("Using TSIG authentication with key: {}", key_name);
-        Ok(Some(keyfile))
-    }
-
-    /// Execute nsupdate commands
-    ///
-    /// # Arguments
-    ///
-    /// * `commands` - nsupdate commands as a string (one command per line)
-    ///
-    /// # Returns
     ///
-    /// Success or error message from nsupdate
-    async fn execute(&self, commands: &str) -> Result<String> {
+    /// Returns [`PrerequisiteFailed`] if a prerequisite does not hold,
+    /// [`UpdateOutcomeUnknown`](dns_update::UpdateOutcomeUnknown) if that was
+    /// reported for a retransmission, and
+    /// [`UpdateRejected`](dns_update::UpdateRejected) if the server refused the
+    /// update for another reason or sent an unsigned prerequisite failure.
+    async fn send(
+        &self,
+        zone: &str,
+        prerequisites: &[Prerequisite],
+        commands: &[UpdateCommand],
+    ) -> Result<String> {
         let start = Instant::now();
 
-        debug!("Executing nsupdate commands:\n{}", commands);
-
-        // TSIG key goes into a 0600 temp file passed via -k, never into argv
-        // (B-7). The guard keeps the file alive until nsupdate has completed.
-        let keyfile = self.create_tsig_key_file()?;
-
-        let mut cmd = tokio::process::Command::new("nsupdate");
-        cmd.args(build_nsupdate_args(
-            self.use_tcp,
-            keyfile.as_ref().map(tempfile::NamedTempFile::path),
-        ));
-
-        // Scrub the child's environment (A-5). The API process holds
-        // NSUPDATE_SECRET / RNDC_SECRET in its own environment, and a spawned
-        // child inherits the parent environment by default — exposing the
-        // base64 secrets via /proc/<pid>/environ to any co-located principal for
-        // the lifetime of the nsupdate process. nsupdate needs none of those; we
-        // pass only an allowlisted PATH so the binary can still be resolved.
-        cmd.env_clear().envs(minimal_child_env());
-
-        cmd.stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        let mut child = cmd.spawn().context("Failed to spawn nsupdate process")?;
-
-        // Write commands to stdin
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(commands.as_bytes())
-                .await
-                .context("Failed to write to nsupdate stdin")?;
-            stdin.flush().await.context("Failed to flush stdin")?;
-        }
-
-        // Wait for completion and capture output
-        let output = child
-            .wait_with_output()
+        let result = async {
+            let message = dns_update::build_update(
+                dns_wire::random_message_id(),
+                zone,
+                prerequisites,
+                commands,
+            )?;
+            debug!(
+                "Sending UPDATE for zone {} ({} bytes) to {}:{}",
+                zone,
+                message.len(),
+                self.server,
+                self.port
+            );
+            dns_update::send_update(
+                &self.server,
+                self.port,
+                message,
+                self.tsig_key(),
+                self.use_tcp,
+                dns_update::DEFAULT_UPDATE_TIMEOUT,
+            )
             .await
-            .context("Failed to wait for nsupdate")?;
-
-        // nsupdate has exited — remove the key file immediately rather than
-        // waiting for scope end.
-        drop(keyfile);
+        }
+        .await;
 
         let duration = start.elapsed().as_secs_f64();
-
-        // Handle errors
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let error_msg = parse_nsupdate_error(&stderr);
-            error!("nsupdate failed: {}", error_msg);
-            metrics::record_nsupdate_command("update", false, duration);
-            return Err(anyhow::anyhow!("nsupdate failed: {}", error_msg));
+        metrics::record_nsupdate_command("update", result.is_ok(), duration);
+
+        match result {
+            Ok(()) => {
+                debug!("UPDATE for zone {} completed in {:.3}s", zone, duration);
+                Ok("NOERROR".to_string())
+            }
+            Err(e) => {
+                error!("UPDATE for zone {} failed: {:#}", zone, e);
+                Err(e)
+            }
         }
-
-        metrics::record_nsupdate_command("update", true, duration);
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        debug!("nsupdate completed successfully in {:.3}s", duration);
-
-        Ok(stdout)
     }
 
     /// Add a DNS record
@@ -228,16 +298,22 @@ impl NsupdateExecutor {
     /// # Arguments
     ///
     /// * `zone` - Zone name (e.g., "example.com")
+    /// * `prerequisites` - Conditions the zone must meet, checked by the server
     /// * `name` - Record name (FQDN, e.g., "www.example.com.")
     /// * `ttl` - Time-to-live in seconds
     /// * `record_type` - Record type (e.g., "A", "AAAA", "CNAME")
     /// * `value` - Record value (e.g., "192.0.2.1")
     ///
+    /// # Errors
+    ///
+    /// Returns [`PrerequisiteFailed`] if a prerequisite does not hold.
+    ///
     /// # Example
     ///
     /// ```ignore
     /// executor.add_record(
     ///     "example.com",
+    ///     &[],
     ///     "www.example.com.",
     ///     3600,
     ///     "A",
@@ -247,6 +323,7 @@ impl NsupdateExecutor {
     pub async fn add_record(
         &self,
         zone: &str,
+        prerequisites: &[Prerequisite],
         name: &str,
         ttl: u32,
         record_type: &str,
@@ -257,18 +334,45 @@ impl NsupdateExecutor {
             record_type, name, value, ttl
         );
 
+        let commands = [UpdateCommand::Add {
+            name: name.to_string(),
+            ttl,
+            record_type: record_type.to_string(),
+            value: value.to_string(),
+        }];
+        self.send(zone, prerequisites, &commands).await
+    }
+
+    /// Build the nsupdate script for [`add_record`](Self::add_record) without executing it
+    ///
+    /// # Errors
+    /// Returns an error if any field contains a control character.
+    pub fn add_record_script(
+        &self,
+        zone: &str,
+        prerequisites: &[Prerequisite],
+        name: &str,
+        ttl: u32,
+        record_type: &str,
+        value: &str,
+    ) -> Result<String> {
         // Defense-in-depth: reject control characters before assembling the
         // newline-delimited nsupdate command script.
         reject_injection_chars("zone", zone)?;
         reject_injection_chars("name", name)?;
         reject_injection_chars("value", value)?;
 
-        let commands = format!(
-            "server {} {}\nzone {}\nupdate add {} {} IN {} {}\nsend\n",
-            self.server, self.port, zone, name, ttl, record_type, value
-        );
-
-        self.execute(&commands).await
+        Ok(format!(
+            "server {} {}\nzone {}\n{}update add {} {} IN {} {}\nsend\n",
+            self.server,
+            self.port,
+            zone,
+            prerequisite_lines(prerequisites)?,
+            name,
+            ttl,
+            record_type,
+            value
+        ))
     }
 
     /// Remove a DNS record
@@ -276,16 +380,22 @@ impl NsupdateExecutor {
     /// # Arguments
     ///
     /// * `zone` - Zone name (e.g., "example.com")
+    /// * `prerequisites` - Conditions the zone must meet, checked by the server
     /// * `name` - Record name (FQDN, e.g., "www.example.com.")
     /// * `record_type` - Record type (e.g., "A")
     /// * `value` - Record value to remove (e.g., "192.0.2.1"). If empty, removes all records of this type.
     ///
+    /// # Errors
+    ///
+    /// Returns [`PrerequisiteFailed`] if a prerequisite does not hold.
+    ///
     /// # Example
     ///
     /// ```ignore
     /// // Remove specific record
     /// executor.remove_record(
     ///     "example.com",
+    ///     &[],
     ///     "www.example.com.",
     ///     "A",
     ///     "192.0.2.1"
@@ -294,6 +404,7 @@ impl NsupdateExecutor {
     /// // Remove all A records for www
     /// executor.remove_record(
     ///     "example.com",
+    ///     &[],
     ///     "www.example.com.",
     ///     "A",
     ///     ""
@@ -302,6 +413,7 @@ impl NsupdateExecutor {
     pub async fn remove_record(
         &self,
         zone: &str,
+        prerequisites: &[Prerequisite],
         name: &str,
         record_type: &str,
         value: &str,
@@ -314,6 +426,26 @@ impl NsupdateExecutor {
             ""
         );
 
+        let commands = [UpdateCommand::Delete {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            value: value.to_string(),
+        }];
+        self.send(zone, prerequisites, &commands).await
+    }
+
+    /// Build the nsupdate script for [`remove_record`](Self::remove_record) without executing it
+    ///
+    /// # Errors
+    /// Returns an error if any field contains a control character.
+    pub fn remove_record_script(
+        &self,
+        zone: &str,
+        prerequisites: &[Prerequisite],
+        name: &str,
+        record_type: &str,
+        value: &str,
+    ) -> Result<String> {
         // Defense-in-depth: reject control characters before assembling the
         // newline-delimited nsupdate command script.
         reject_injection_chars("zone", zone)?;
@@ -329,12 +461,14 @@ impl NsupdateExecutor {
             format!("update delete {} {} {}", name, record_type, value)
         };
 
-        let commands = format!(
-            "server {} {}\nzone {}\n{}\nsend\n",
-            self.server, self.port, zone, delete_cmd
-        );
-
-        self.execute(&commands).await
+        Ok(format!(
+            "server {} {}\nzone {}\n{}{}\nsend\n",
+            self.server,
+            self.port,
+            zone,
+            prerequisite_lines(prerequisites)?,
+            delete_cmd
+        ))
     }
 
     /// Update a DNS record (atomic delete + add)
@@ -342,17 +476,25 @@ impl NsupdateExecutor {
     /// # Arguments
     ///
     /// * `zone` - Zone name (e.g., "example.com")
+    /// * `prerequisites` - Conditions the zone must meet, checked by the server;
+    ///   e.g. [`Prerequisite::RrsetExists`] with `old_value` to make the update
+    ///   a compare-and-swap
     /// * `name` - Record name (FQDN, e.g., "www.example.com.")
     /// * `ttl` - New time-to-live in seconds
     /// * `record_type` - Record type (e.g., "A")
     /// * `old_value` - Current record value (e.g., "192.0.2.1")
     /// * `new_value` - New record value (e.g., "192.0.2.2")
     ///
+    /// # Errors
+    ///
+    /// Returns [`PrerequisiteFailed`] if a prerequisite does not hold.
+    ///
     /// # Example
     ///
     /// ```ignore
     /// executor.update_record(
     ///     "example.com",
+    ///     &[],
     ///     "www.example.com.",
     ///     3600,
     ///     "A",
@@ -360,9 +502,11 @@ impl NsupdateExecutor {
     ///     "192.0.2.2"
     /// ).await?;
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_record(
         &self,
         zone: &str,
+        prerequisites: &[Prerequisite],
         name: &str,
         ttl: u32,
         record_type: &str,
@@ -374,6 +518,38 @@ impl NsupdateExecutor {
             record_type, name, old_value, new_value, ttl
         );
 
+        // Atomic update: delete old, add new in one message
+        let commands = [
+            UpdateCommand::Delete {
+                name: name.to_string(),
+                record_type: record_type.to_string(),
+                value: old_value.to_string(),
+            },
+            UpdateCommand::Add {
+                name: name.to_string(),
+                ttl,
+                record_type: record_type.to_string(),
+                value: new_value.to_string(),
+            },
+        ];
+        self.send(zone, prerequisites, &commands).await
+    }
+
+    /// Build the nsupdate script for [`update_record`](Self::update_record) without executing it
+    ///
+    /// # Errors
+    /// Returns an error if any field contains a control character.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_record_script(
+        &self,
+        zone: &str,
+        prerequisites: &[Prerequisite],
+        name: &str,
+        ttl: u32,
+        record_type: &str,
+        old_value: &str,
+        new_value: &str,
+    ) -> Result<String> {
         // Defense-in-depth: reject control characters before assembling the
         // newline-delimited nsupdate command script.
         reject_injection_chars("zone", zone)?;
@@ -382,11 +558,12 @@ impl NsupdateExecutor {
         reject_injection_chars("new_value", new_value)?;
 
         // Atomic update: delete old, add new in single transaction
-        let commands = format!(
-            "server {} {}\nzone {}\nupdate delete {} {} {}\nupdate add {} {} IN {} {}\nsend\n",
+        Ok(format!(
+            "server {} {}\nzone {}\n{}update delete {} {} {}\nupdate add {} {} IN {} {}\nsend\n",
             self.server,
             self.port,
             zone,
+            prerequisite_lines(prerequisites)?,
             name,
             record_type,
             old_value,
@@ -394,127 +571,252 @@ impl NsupdateExecutor {
             ttl,
             record_type,
             new_value
+        ))
+    }
+
+    /// Apply a set of record deletions and additions as one transaction
+    ///
+    /// All changes are sent in a single UPDATE message, so BIND applies them
+    /// atomically: either every change lands or none does.
+    ///
+    /// # Arguments
+    ///
+    /// * `zone` - Zone name (e.g., "example.com")
+    /// * `removed` - Records to delete (matched on name, type and value); a
+    ///   record with empty data deletes the whole RRset
+    /// * `added` - Records to add; a record without a TTL is added with `default_ttl`
+    /// * `default_ttl` - TTL for added records that carry none
+    pub async fn apply_changes(
+        &self,
+        zone: &str,
+        removed: &[ResourceRecord],
+        added: &[ResourceRecord],
+        default_ttl: u32,
+    ) -> Result<String> {
+        info!(
+            "Applying {} deletions and {} additions to zone {}",
+            removed.len(),
+            added.len(),
+            zone
         );
 
-        self.execute(&commands).await
+        let commands: Vec<UpdateCommand> = removed
+            .iter()
+            .map(|record| UpdateCommand::Delete {
+                name: record.name.clone(),
+                record_type: record.record_type.clone(),
+                value: record.data.clone(),
+            })
+            .chain(added.iter().map(|record| UpdateCommand::Add {
+                name: record.name.clone(),
+                ttl: record.ttl.unwrap_or(default_ttl),
+                record_type: record.record_type.clone(),
+                value: record.data.clone(),
+            }))
+            .collect();
+        if commands.is_empty() {
+            return Ok(String::new());
+        }
+        self.send(zone, &[], &commands).await
     }
-}
 
-/// Environment variables propagated to the spawned `nsupdate` child.
-///
-/// Deliberately minimal: only `PATH`, so the `nsupdate` binary can still be
-/// resolved. Everything else (notably `NSUPDATE_SECRET` / `RNDC_SECRET`, which
-/// live in the API process environment) is withheld so the child's
-/// `/proc/<pid>/environ` never exposes a secret (A-5).
-const CHILD_ENV_ALLOWLIST: &[&str] = &["PATH"];
+    /// Build the nsupdate script for [`apply_changes`](Self::apply_changes) without executing it
+    ///
+    /// # Errors
+    /// Returns an error if any field contains a control character.
+    pub fn apply_changes_script(
+        &self,
+        zone: &str,
+        removed: &[ResourceRecord],
+        added: &[ResourceRecord],
+        default_ttl: u32,
+    ) -> Result<String> {
+        reject_injection_chars("zone", zone)?;
 
-/// Build the allowlisted environment for the `nsupdate` child process.
-///
-/// Reads only the variables named in [`CHILD_ENV_ALLOWLIST`] from the parent
-/// environment; any that are unset are simply omitted. Used together with
-/// `Command::env_clear()` so the child starts from an empty environment plus
-/// this allowlist.
-pub(crate) fn minimal_child_env() -> Vec<(OsString, OsString)> {
-    CHILD_ENV_ALLOWLIST
-        .iter()
-        .filter_map(|name| std::env::var_os(name).map(|value| (OsString::from(name), value)))
-        .collect()
-}
+        let mut commands = format!("server {} {}\nzone {}\n", self.server, self.port, zone);
+        for record in removed {
+            reject_injection_chars("name", &record.name)?;
+            reject_injection_chars("value", &record.data)?;
+            if record.data.is_empty() {
+                commands.push_str(&format!(
+                    "update delete {} IN {}\n",
+                    record.name, record.record_type
+                ));
+            } else {
+                commands.push_str(&format!(
+                    "update delete {} IN {} {}\n",
+                    record.name, record.record_type, record.data
+                ));
+            }
+        }
+        for record in added {
+            reject_injection_chars("name", &record.name)?;
+            reject_injection_chars("value", &record.data)?;
+            commands.push_str(&format!(
+                "update add {} {} IN {} {}\n",
+                record.name,
+                record.ttl.unwrap_or(default_ttl),
+                record.record_type,
+                record.data
+            ));
+        }
+        commands.push_str("send\n");
 
-/// Build the nsupdate argument vector.
-///
-/// The TSIG key is referenced by file path (`-k <keyfile>`), never embedded in
-/// the arguments — argv is world-readable via `/proc/<pid>/cmdline` (B-7).
-pub(crate) fn build_nsupdate_args(use_tcp: bool, keyfile: Option<&Path>) -> Vec<OsString> {
-    let mut args: Vec<OsString> = Vec::new();
-    if use_tcp {
-        args.push("-v".into());
-    }
-    if let Some(path) = keyfile {
-        args.push("-k".into());
-        args.push(path.as_os_str().to_owned());
+        Ok(commands)
     }
-    args
-}
 
-/// Render a BIND TSIG key file for `nsupdate -k`.
-///
-/// Produces:
-/// ```text
-/// key "<name>" {
-///     algorithm <hmac-...>;
-///     secret "<base64>";
-/// };
-/// ```
-///
-/// All three fields are validated before interpolation since they land in a
-/// quoted BIND configuration literal:
-/// - `key_name` — safe identifier set (`[A-Za-z0-9._-]`, max 253 chars)
-/// - `algorithm` — normalized to lowercase, `hmac-` prefix added if missing,
-///   then checked against the known HMAC algorithm list
-/// - `secret` — base64 character set only
-///
-/// # Errors
-/// Returns an error when any field is empty or fails validation.
-pub(crate) fn build_tsig_key_file_content(
-    key_name: &str,
-    algorithm: &str,
-    secret: &str,
-) -> Result<String> {
-    if key_name.is_empty() || key_name.len() > MAX_TSIG_KEY_NAME_LEN {
-        return Err(anyhow::anyhow!(
-            "TSIG key name must be 1-{} characters",
-            MAX_TSIG_KEY_NAME_LEN
-        ));
-    }
-    if let Some(bad) = key_name
-        .chars()
-        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
-    {
-        return Err(anyhow::anyhow!(
-            "TSIG key name contains invalid character: {:?}",
-            bad
-        ));
-    }
+    /// Apply an ordered list of changes as one transaction
+    ///
+    /// The changes go out as a single UPDATE message in the given order, so
+    /// BIND applies all of them or none.
+    ///
+    /// # Arguments
+    ///
+    /// * `zone` - Zone name (e.g., "example.com")
+    /// * `prerequisites` - Conditions the zone must meet, checked by the server
+    ///   before any change is made
+    /// * `commands` - Changes, applied in order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrerequisiteFailed`] if a prerequisite does not hold.
+    pub async fn apply_updates(
+        &self,
+        zone: &str,
+        prerequisites: &[Prerequisite],
+        commands: &[UpdateCommand],
+    ) -> Result<String> {
+        info!(
+            "Applying {} changes to zone {} in one update",
+            commands.len(),
+            zone
+        );
 
-    // Accept "SHA256", "hmac-sha256", "HMAC-SHA256", etc.
-    let mut normalized_algorithm = algorithm.to_ascii_lowercase();
-    if !normalized_algorithm.starts_with("hmac-") {
-        normalized_algorithm = format!("hmac-{}", normalized_algorithm);
-    }
-    if !ALLOWED_TSIG_ALGORITHMS.contains(&normalized_algorithm.as_str()) {
-        return Err(anyhow::anyhow!(
-            "Unsupported TSIG algorithm: {} (allowed: {})",
-            algorithm,
-            ALLOWED_TSIG_ALGORITHMS.join(", ")
-        ));
+        self.send(zone, prerequisites, commands).await
     }
 
-    if secret.is_empty() {
-        return Err(anyhow::anyhow!("TSIG secret cannot be empty"));
+    /// Build the nsupdate script for [`apply_updates`](Self::apply_updates) without executing it
+    ///
+    /// # Errors
+    /// Returns an error if there are no changes or any field contains a
+    /// control character.
+    pub fn apply_updates_script(
+        &self,
+        zone: &str,
+        prerequisites: &[Prerequisite],
+        commands: &[UpdateCommand],
+    ) -> Result<String> {
+        if commands.is_empty() {
+            anyhow::bail!("an update needs at least one change");
+        }
+        reject_injection_chars("zone", zone)?;
+
+        let mut script = format!(
+            "server {} {}\nzone {}\n{}",
+            self.server,
+            self.port,
+            zone,
+            prerequisite_lines(prerequisites)?
+        );
+        for command in commands {
+            match command {
+                UpdateCommand::Add {
+                    name,
+                    ttl,
+                    record_type,
+                    value,
+                } => {
+                    reject_injection_chars("name", name)?;
+                    reject_injection_chars("value", value)?;
+                    script.push_str(&format!(
+                        "update add {} {} IN {} {}\n",
+                        name, ttl, record_type, value
+                    ));
+                }
+                UpdateCommand::Delete {
+                    name,
+                    record_type,
+                    value,
+                } if value.is_empty() => {
+                    reject_injection_chars("name", name)?;
+                    script.push_str(&format!("update delete {} IN {}\n", name, record_type));
+                }
+                UpdateCommand::Delete {
+                    name,
+                    record_type,
+                    value,
+                } => {
+                    reject_injection_chars("name", name)?;
+                    reject_injection_chars("value", value)?;
+                    script.push_str(&format!(
+                        "update delete {} IN {} {}\n",
+                        name, record_type, value
+                    ));
+                }
+            }
+        }
+        script.push_str("send\n");
+
+        Ok(script)
     }
-    if let Some(bad) = secret
-        .chars()
-        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')))
-    {
-        return Err(anyhow::anyhow!(
-            "TSIG secret contains non-base64 character: {:?}",
-            bad
-        ));
+
+    /// The TSIG key, or `None` when TSIG is not configured.
+    pub(crate) fn tsig_key(&self) -> Option<&TsigKey> {
+        self.tsig_key.as_ref()
     }
 
-    Ok(format!(
-        "key \"{key_name}\" {{\n    algorithm {normalized_algorithm};\n    secret \"{secret}\";\n}};\n"
-    ))
+    /// Transfer a zone (AXFR) from the configured server
+    ///
+    /// Uses the same server and TSIG key as dynamic updates, so the key must be
+    /// permitted by the zone's `allow-transfer` as well as its update policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `zone` - Zone name (e.g., "example.com")
+    ///
+    /// # Returns
+    ///
+    /// Every record in the zone, leading SOA first
+    pub async fn axfr(&self, zone: &str) -> Result<Vec<ResourceRecord>> {
+        info!(
+            "Transferring zone {} from {}:{}",
+            zone, self.server, self.port
+        );
+
+        let start = Instant::now();
+        let result = axfr::transfer_zone(
+            &self.server,
+            self.port,
+            zone,
+            self.tsig_key(),
+            axfr::DEFAULT_AXFR_TIMEOUT,
+        )
+        .await;
+        let duration = start.elapsed().as_secs_f64();
+
+        match &result {
+            Ok(records) => {
+                metrics::record_nsupdate_command("axfr", true, duration);
+                debug!("AXFR of {} returned {} records", zone, records.len());
+            }
+            Err(e) => {
+                metrics::record_nsupdate_command("axfr", false, duration);
+                error!("AXFR of {} failed: {:#}", zone, e);
+            }
+        }
+
+        result
+    }
 }
 
 /// Reject a DNS update field containing control characters.
 ///
-/// nsupdate reads its command script as newline-delimited lines from stdin, so a
-/// `\n`, `\r`, or NUL embedded in a zone, name, or value would be interpreted as a
-/// command separator and allow injection of arbitrary update commands (B-2). This
-/// is a defense-in-depth check at the sink; the HTTP handlers in `records.rs` also
-/// reject these characters and return HTTP 400.
+/// The `*_script` builders render newline-delimited nsupdate commands, so a `\n`,
+/// `\r`, or NUL embedded in a zone, name, or value would be read as a command
+/// separator and make a script show commands that are not part of the update
+/// (B-2). This is a defense-in-depth check at the sink; the HTTP handlers in
+/// `records.rs` also reject these characters and return HTTP 400, and the wire
+/// encoder refuses them in RDATA.
 ///
 /// # Errors
 /// Returns an error if `value` contains any control character.
@@ -530,71 +832,10 @@ pub(crate) fn reject_injection_chars(field: &str, value: &str) -> Result<()> {
     Ok(())
 }
 
-/// Parse nsupdate error messages into human-readable format
-///
-/// Maps common nsupdate error codes to helpful messages
-fn parse_nsupdate_error(stderr: &str) -> String {
-    if stderr.contains("REFUSED") {
-        "Zone refused the update (check allow-update configuration)".to_string()
-    } else if stderr.contains("NOTAUTH") {
-        "Not authorized (check TSIG key configuration)".to_string()
-    } else if stderr.contains("SERVFAIL") {
-        "Server failure (check BIND9 logs)".to_string()
-    } else if stderr.contains("NOTZONE") {
-        "Zone not found on server".to_string()
-    } else if stderr.contains("FORMERR") {
-        "Format error (check record syntax)".to_string()
-    } else if stderr.contains("NXDOMAIN") {
-        "Domain name does not exist".to_string()
-    } else {
-        // Return raw stderr if no specific error matched
-        stderr.trim().to_string()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse_nsupdate_error_refused() {
-        let stderr = "update failed: REFUSED\n";
-        assert_eq!(
-            parse_nsupdate_error(stderr),
-            "Zone refused the update (check allow-update configuration)"
-        );
-    }
-
-    #[test]
-    fn test_parse_nsupdate_error_notauth() {
-        let stderr = "update failed: NOTAUTH\n";
-        assert_eq!(
-            parse_nsupdate_error(stderr),
-            "Not authorized (check TSIG key configuration)"
-        );
-    }
-
-    #[test]
-    fn test_parse_nsupdate_error_servfail() {
-        let stderr = "update failed: SERVFAIL\n";
-        assert_eq!(
-            parse_nsupdate_error(stderr),
-            "Server failure (check BIND9 logs)"
-        );
-    }
-
-    #[test]
-    fn test_parse_nsupdate_error_notzone() {
-        let stderr = "update failed: NOTZONE\n";
-        assert_eq!(parse_nsupdate_error(stderr), "Zone not found on server");
-    }
-
-    #[test]
-    fn test_parse_nsupdate_error_unknown() {
-        let stderr = "some other error\n";
-        assert_eq!(parse_nsupdate_error(stderr), "some other error");
-    }
-
     #[test]
     fn test_new_executor_with_tsig() {
         let executor = NsupdateExecutor::new(
@@ -40,12 +40,186 @@ mod tests {
         assert!(executor.is_ok());
     }
 
-    // Note: We cannot easily test the execute() method and record operations
-    // without a real BIND9 server, so these are integration tests that should
-    // be run separately with a test environment.
-    //
-    // For unit testing, we validate the struct creation and error parsing logic,
-    // which are in the main nsupdate.rs file.
+    #[test]
+    fn test_script_builders_render_without_executing() {
+        let executor =
+            NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap();
+
+        let script = executor
+            .add_record_script(
+                "example.com",
+                &[],
+                "www.example.com.",
+                300,
+                "A",
+                "192.0.2.1",
+            )
+            .unwrap();
+        assert_eq!(
+            script,
+            "server 127.0.0.1 53\nzone example.com\nupdate add www.example.com. 300 IN A 192.0.2.1\nsend\n"
+        );
+
+        let script = executor
+            .remove_record_script("example.com", &[], "www.example.com.", "A", "")
+            .unwrap();
+        assert!(script.contains("update delete www.example.com. A\n"));
+
+        assert!(executor
+            .update_record_script(
+                "example.com",
+                &[],
+                "www.example.com.",
+                300,
+                "A",
+                "1.2.3.4",
+                "x\nsend"
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_apply_updates_script_keeps_order_in_one_send() {
+        use crate::nsupdate::UpdateCommand;
+
+        let executor =
+            NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap();
+        let commands = [
+            UpdateCommand::Delete {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: String::new(),
+            },
+            UpdateCommand::Add {
+                name: "www.example.com.".to_string(),
+                ttl: 60,
+                record_type: "A".to_string(),
+                value: "192.0.2.2".to_string(),
+            },
+            UpdateCommand::Delete {
+                name: "old.example.com.".to_string(),
+                record_type: "CNAME".to_string(),
+                value: "www.example.com.".to_string(),
+            },
+        ];
+
+        let script = executor
+            .apply_updates_script("example.com", &[], &commands)
+            .unwrap();
+        assert_eq!(
+            script,
+            "server 127.0.0.1 53\nzone example.com\n\
+             update delete www.example.com. IN A\n\
+             update add www.example.com. 60 IN A 192.0.2.2\n\
+             update delete old.example.com. IN CNAME www.example.com.\n\
+             send\n"
+        );
+        assert_eq!(script.matches("send").count(), 1);
+
+        assert!(executor
+            .apply_updates_script("example.com", &[], &[])
+            .is_err());
+        let injected = [UpdateCommand::Add {
+            name: "www.example.com.".to_string(),
+            ttl: 60,
+            record_type: "A".to_string(),
+            value: "192.0.2.2\nsend".to_string(),
+        }];
+        assert!(executor
+            .apply_updates_script("example.com", &[], &injected)
+            .is_err());
+    }
+
+    #[test]
+    fn test_prerequisites_precede_the_update() {
+        use crate::nsupdate::Prerequisite;
+
+        let executor =
+            NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap();
+        let prerequisites = [
+            Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: "192.0.2.1".to_string(),
+            },
+            Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "AAAA".to_string(),
+                value: String::new(),
+            },
+            Prerequisite::RrsetNotExists {
+                name: "www.example.com.".to_string(),
+                record_type: "CNAME".to_string(),
+            },
+            Prerequisite::NameInUse {
+                name: "example.com.".to_string(),
+            },
+            Prerequisite::NameNotInUse {
+                name: "new.example.com.".to_string(),
+            },
+        ];
+
+        let script = executor
+            .update_record_script(
+                "example.com",
+                &prerequisites,
+                "www.example.com.",
+                300,
+                "A",
+                "192.0.2.1",
+                "192.0.2.2",
+            )
+            .unwrap();
+        assert_eq!(
+            script,
+            "server 127.0.0.1 53\nzone example.com\n\
+             prereq yxrrset www.example.com. IN A 192.0.2.1\n\
+             prereq yxrrset www.example.com. IN AAAA\n\
+             prereq nxrrset www.example.com. IN CNAME\n\
+             prereq yxdomain example.com.\n\
+             prereq nxdomain new.example.com.\n\
+             update delete www.example.com. A 192.0.2.1\n\
+             update add www.example.com. 300 IN A 192.0.2.2\n\
+             send\n"
+        );
+
+        let injected = [Prerequisite::NameInUse {
+            name: "www.example.com.\nupdate add evil".to_string(),
+        }];
+        assert!(executor
+            .remove_record_script("example.com", &injected, "www.example.com.", "A", "")
+            .is_err());
+    }
+
+    #[test]
+    fn test_prerequisite_failures_map_from_rcodes() {
+        use crate::nsupdate::PrerequisiteFailed;
+
+        assert_eq!(
+            PrerequisiteFailed::from_rcode("YXRRSET"),
+            Some(PrerequisiteFailed::RrsetExists)
+        );
+        assert_eq!(
+            PrerequisiteFailed::from_rcode("NXRRSET"),
+            Some(PrerequisiteFailed::RrsetMissing)
+        );
+        assert_eq!(
+            PrerequisiteFailed::from_rcode("YXDOMAIN"),
+            Some(PrerequisiteFailed::NameExists)
+        );
+        assert_eq!(
+            PrerequisiteFailed::from_rcode("NXDOMAIN"),
+            Some(PrerequisiteFailed::NameMissing)
+        );
+        assert_eq!(PrerequisiteFailed::from_rcode("REFUSED"), None);
+
+        assert!(PrerequisiteFailed::RrsetExists.is_conflict());
+        assert!(PrerequisiteFailed::NameExists.is_conflict());
+        assert!(!PrerequisiteFailed::RrsetMissing.is_conflict());
+        assert!(!PrerequisiteFailed::NameMissing.is_conflict());
+    }
+
+    // Sending updates is covered against a mock server in dns_update_test.rs.
 }
 
 #[cfg(test)]
@@ -67,128 +241,56 @@ mod injection_tests {
     }
 }
 
-/// B-7: the TSIG secret must never appear in the nsupdate argument vector —
-/// argv is world-readable via /proc/<pid>/cmdline. The key is passed via a
-/// 0600 temp key file (-k) instead.
+/// The TSIG key is used in-process only; it is never written to disk or
+/// passed to a child process.
 #[cfg(test)]
-mod tsig_keyfile_tests {
-    use crate::nsupdate::{build_nsupdate_args, build_tsig_key_file_content, NsupdateExecutor};
+mod tsig_key_tests {
+    use crate::nsupdate::NsupdateExecutor;
 
     const SECRET: &str = "dGVzdC1zZWNyZXQ=";
 
-    fn executor_with_tsig() -> NsupdateExecutor {
+    fn executor_with_key(algorithm: &str, secret: &str) -> anyhow::Result<NsupdateExecutor> {
         NsupdateExecutor::new(
             "127.0.0.1".to_string(),
             53,
             Some("update-key".to_string()),
-            Some("HMAC-SHA256".to_string()),
-            Some(SECRET.to_string()),
+            Some(algorithm.to_string()),
+            Some(secret.to_string()),
         )
-        .unwrap()
     }
 
     #[test]
-    fn test_args_never_contain_secret() {
-        let executor = executor_with_tsig();
-        let keyfile = executor.create_tsig_key_file().unwrap().unwrap();
-        let args = build_nsupdate_args(false, Some(keyfile.path()));
-
-        for arg in &args {
-            let s = arg.to_string_lossy();
-            assert!(
-                !s.contains(SECRET),
-                "argv must not contain the TSIG secret, found in {s:?}"
-            );
+    fn test_tsig_key_accepts_sha2_algorithms() {
+        for algorithm in ["HMAC-SHA256", "hmac-sha512", "SHA384", "hmac-sha224"] {
+            let executor = executor_with_key(algorithm, SECRET).unwrap();
+            assert_eq!(executor.tsig_key().unwrap().name(), "update-key");
         }
-        // -y must be gone entirely
-        assert!(args.iter().all(|a| a != "-y"));
-        // -k <path> must be present
-        assert!(args.iter().any(|a| a == "-k"));
-    }
-
-    #[test]
-    fn test_args_without_tsig_or_tcp_are_empty() {
-        assert!(build_nsupdate_args(false, None).is_empty());
     }
 
     #[test]
-    fn test_args_include_tcp_flag() {
-        let args = build_nsupdate_args(true, None);
-        assert_eq!(args, vec![std::ffi::OsString::from("-v")]);
+    fn test_new_rejects_legacy_algorithms() {
+        // HMAC-MD5 and HMAC-SHA1 are not supported by the native client
+        assert!(executor_with_key("hmac-md5", SECRET).is_err());
+        assert!(executor_with_key("hmac-sha1", SECRET).is_err());
+        assert!(executor_with_key("rot13", SECRET).is_err());
     }
 
     #[test]
-    fn test_minimal_child_env_excludes_secrets() {
-        use crate::nsupdate::minimal_child_env;
-
-        let env = minimal_child_env();
-        // Every propagated variable must be in the PATH-only allowlist; in
-        // particular no SECRET-bearing variable may ever appear (A-5).
-        for (key, _) in &env {
-            let key = key.to_string_lossy();
-            assert_eq!(key, "PATH", "unexpected env var propagated to child: {key}");
-            assert!(
-                !key.to_uppercase().contains("SECRET"),
-                "secret-bearing env var leaked to child: {key}"
-            );
-        }
+    fn test_new_rejects_invalid_secret() {
+        assert!(executor_with_key("hmac-sha256", "not base64!").is_err());
+        assert!(executor_with_key("hmac-sha256", "").is_err());
     }
 
     #[test]
-    fn test_keyfile_is_created_with_owner_only_permissions() {
-        use std::os::unix::fs::PermissionsExt;
-
-        let executor = executor_with_tsig();
-        let keyfile = executor.create_tsig_key_file().unwrap().unwrap();
-        let mode = keyfile.path().metadata().unwrap().permissions().mode();
-        assert_eq!(
-            mode & 0o777,
-            0o600,
-            "TSIG key file must be readable by owner only"
-        );
+    fn test_tsig_key_debug_hides_secret() {
+        let executor = executor_with_key("hmac-sha256", SECRET).unwrap();
+        assert!(!format!("{:?}", executor.tsig_key().unwrap()).contains(SECRET));
     }
 
     #[test]
-    fn test_keyfile_content_and_cleanup() {
-        let executor = executor_with_tsig();
-        let keyfile = executor.create_tsig_key_file().unwrap().unwrap();
-        let path = keyfile.path().to_path_buf();
-
-        let content = std::fs::read_to_string(&path).unwrap();
-        assert!(content.contains("key \"update-key\""));
-        assert!(content.contains("algorithm hmac-sha256;"));
-        assert!(content.contains(&format!("secret \"{SECRET}\";")));
-
-        // Dropping the guard unlinks the file.
-        drop(keyfile);
-        assert!(!path.exists(), "key file must be removed after use");
-    }
-
-    #[test]
-    fn test_no_keyfile_without_tsig() {
+    fn test_no_tsig_key_without_tsig() {
         let executor =
             NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap();
-        assert!(executor.create_tsig_key_file().unwrap().is_none());
-    }
-
-    #[test]
-    fn test_key_file_content_normalizes_algorithm() {
-        let content = build_tsig_key_file_content("k", "SHA256", "YWJj").unwrap();
-        assert!(content.contains("algorithm hmac-sha256;"));
-        let content = build_tsig_key_file_content("k", "hmac-sha512", "YWJj").unwrap();
-        assert!(content.contains("algorithm hmac-sha512;"));
-    }
-
-    #[test]
-    fn test_key_file_content_rejects_bad_inputs() {
-        // Key name breaking out of the quoted literal
-        assert!(build_tsig_key_file_content("k\"; };", "sha256", "YWJj").is_err());
-        assert!(build_tsig_key_file_content("k name", "sha256", "YWJj").is_err());
-        assert!(build_tsig_key_file_content("", "sha256", "YWJj").is_err());
-        // Unknown algorithm
-        assert!(build_tsig_key_file_content("k", "rot13", "YWJj").is_err());
-        // Non-base64 secret (quote breakout)
-        assert!(build_tsig_key_file_content("k", "sha256", "abc\"; };").is_err());
-        assert!(build_tsig_key_file_content("k", "sha256", "").is_err());
+        assert!(executor.tsig_key().is_none());
     }
 }
@@ -0,0 +1,288 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Atomic multi-record change sets
+//!
+//! `POST /api/v1/zones/{name}/records:batch` applies an ordered list of
+//! `add`, `delete` and `replace` operations to a zone in a single DNS UPDATE
+//! message. BIND applies an UPDATE all-or-nothing, so a blue/green cutover
+//! that flips several A and CNAME records either lands completely or not at
+//! all.
+//!
+//! Every change is validated before anything is sent; one invalid change
+//! rejects the whole batch. Optional prerequisites are checked by the server
+//! first, so a batch can be made conditional on the zone's current state.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use utoipa::ToSchema;
+
+use crate::{
+    dns_update, metrics,
+    nsupdate::{Prerequisite, UpdateCommand},
+    record_data::RecordData,
+    records::{
+        dry_run_response, normalize_record_name, resolve_prerequisites, resolve_record_value,
+        update_error, validate_record_name, validate_record_type, validate_zone_for_updates,
+        RecordPrerequisite, RecordResponse,
+    },
+    types::{ApiError, AppState, DryRunQuery},
+};
+
+/// Largest number of changes in one batch; the UPDATE must fit in one message
+pub const MAX_BATCH_RECORD_CHANGES: usize = 500;
+
+/// What a change does to its record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordOperation {
+    /// Add the record to its RRset
+    Add,
+    /// Delete the record, or the whole RRset if no value is given
+    Delete,
+    /// Delete the RRset, then add the record in its place
+    Replace,
+}
+
+/// One change in a batch
+///
+/// The record is given like in the single-record endpoints: a `type` and
+/// `value` (with `priority` for MX and SRV), or structured `data`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordChange {
+    pub operation: RecordOperation,
+
+    /// Record name (e.g., "www", "@" for apex)
+    pub name: String,
+
+    /// Record type (e.g., "A", "CNAME"); optional with `data`
+    #[serde(rename = "type", default)]
+    pub record_type: String,
+
+    /// Record value; optional for `delete` to remove the whole RRset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    /// Structured record data, instead of `value` and `priority`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
+
+    /// TTL in seconds for `add` and `replace` (default: 3600)
+    #[serde(default = "default_ttl")]
+    pub ttl: u32,
+
+    /// Priority (for MX and SRV records)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u16>,
+}
+
+fn default_ttl() -> u32 {
+    3600
+}
+
+/// Request to apply several record changes atomically
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchRecordRequest {
+    /// Changes, applied in order
+    pub changes: Vec<RecordChange>,
+
+    /// Conditions the zone must meet before any change is applied; each
+    /// needs its own name, and a type for `yxrrset` and `nxrrset`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prerequisites: Vec<RecordPrerequisite>,
+}
+
+fn invalid_change(index: usize, e: ApiError) -> ApiError {
+    match e {
+        ApiError::InvalidRecord(message) => {
+            ApiError::InvalidRecord(format!("changes[{}]: {}", index, message))
+        }
+        other => other,
+    }
+}
+
+/// Turn one change into the update commands it stands for.
+fn change_commands(change: &RecordChange, zone_name: &str) -> Result<Vec<UpdateCommand>, ApiError> {
+    validate_record_name(&change.name)?;
+    let name = normalize_record_name(&change.name, zone_name);
+    let value = change.value.as_deref().unwrap_or("");
+
+    if change.operation == RecordOperation::Delete && change.data.is_none() && value.is_empty() {
+        if change.priority.is_some() {
+            return Err(ApiError::InvalidRecord(
+                "priority requires a value".to_string(),
+            ));
+        }
+        validate_record_type(&change.record_type)?;
+        return Ok(vec![UpdateCommand::Delete {
+            name,
+            record_type: change.record_type.to_uppercase(),
+            value: String::new(),
+        }]);
+    }
+    let (record_type, value) = resolve_record_value(
+        &change.record_type,
+        value,
+        change.priority,
+        change.data.as_ref(),
+    )?;
+    let record_type = record_type.to_uppercase();
+
+    Ok(match change.operation {
+        RecordOperation::Add => vec![UpdateCommand::Add {
+            name,
+            ttl: change.ttl,
+            record_type,
+            value,
+        }],
+        RecordOperation::Delete => vec![UpdateCommand::Delete {
+            name,
+            record_type,
+            value,
+        }],
+        RecordOperation::Replace => vec![
+            UpdateCommand::Delete {
+                name: name.clone(),
+                record_type: record_type.clone(),
+                value: String::new(),
+            },
+            UpdateCommand::Add {
+                name,
+                ttl: change.ttl,
+                record_type,
+                value,
+            },
+        ],
+    })
+}
+
+/// Validate a batch and turn it into update commands, in order.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] for an empty or oversized batch and
+/// [`ApiError::InvalidRecord`], prefixed with the change's index, for the
+/// first invalid change.
+pub(crate) fn batch_commands(
+    request: &BatchRecordRequest,
+    zone_name: &str,
+) -> Result<Vec<UpdateCommand>, ApiError> {
+    if request.changes.is_empty() {
+        return Err(ApiError::InvalidRequest(
+            "changes cannot be empty".to_string(),
+        ));
+    }
+    if request.changes.len() > MAX_BATCH_RECORD_CHANGES {
+        return Err(ApiError::InvalidRequest(format!(
+            "A batch can hold at most {} changes",
+            MAX_BATCH_RECORD_CHANGES
+        )));
+    }
+
+    let mut commands = Vec::with_capacity(request.changes.len());
+    for (index, change) in request.changes.iter().enumerate() {
+        commands.extend(change_commands(change, zone_name).map_err(|e| invalid_change(index, e))?);
+    }
+    Ok(commands)
+}
+
+/// Check that a validated batch fits in one UPDATE message.
+///
+/// Fewer than [`MAX_BATCH_RECORD_CHANGES`] changes can still exceed the
+/// 65535-byte message limit when the values are long, so the message is
+/// encoded here rather than failing later in the executor.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] if the encoded update is too large.
+pub(crate) fn check_batch_size(
+    zone_name: &str,
+    prerequisites: &[Prerequisite],
+    commands: &[UpdateCommand],
+) -> Result<(), ApiError> {
+    dns_update::build_update(0, zone_name, prerequisites, commands)
+        .map(|_| ())
+        .map_err(|e| ApiError::InvalidRequest(format!("Batch does not fit in one update: {}", e)))
+}
+
+/// Apply several record changes atomically
+///
+/// Sends every change in one DNS UPDATE, in request order. BIND applies all
+/// of them or none: if the update fails, the zone is unchanged.
+#[utoipa::path(
+    post,
+    path = "/api/v1/zones/{zone_name}/records:batch",
+    request_body = BatchRecordRequest,
+    params(
+        ("zone_name" = String, Path, description = "Zone name"),
+        DryRunQuery
+    ),
+    responses(
+        (status = 200, description = "Changes applied (or planned, with dryRun)", body = RecordResponse),
+        (status = 400, description = "Invalid change, batch too large for one update, or zone not configured for updates"),
+        (status = 404, description = "Zone not found"),
+        (status = 409, description = "A prerequisite failed: a name or RRset exists that must not"),
+        (status = 412, description = "A prerequisite failed: a required name or RRset is missing"),
+        (status = 504, description = "A prerequisite failed after the update was retransmitted; it may have been applied"),
+        (status = 500, description = "Update rejected with no change applied, or no response"),
+    ),
+    tag = "records"
+)]
+pub async fn batch_records(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
+    Json(request): Json<BatchRecordRequest>,
+) -> Result<Json<RecordResponse>, ApiError> {
+    info!(
+        "Applying {} record changes to zone {}",
+        request.changes.len(),
+        zone_name
+    );
+
+    validate_zone_for_updates(&state, &zone_name).await?;
+    let commands = batch_commands(&request, &zone_name)?;
+    let prerequisites = resolve_prerequisites(&request.prerequisites, &zone_name, None, None)?;
+    check_batch_size(&zone_name, &prerequisites, &commands)?;
+
+    if query.dry_run {
+        let script = state
+            .nsupdate
+            .apply_updates_script(&zone_name, &prerequisites, &commands);
+        return Ok(Json(dry_run_response(&zone_name, "changed in", script)?));
+    }
+
+    state
+        .nsupdate
+        .apply_updates(&zone_name, &prerequisites, &commands)
+        .await
+        .map_err(|e| {
+            error!("nsupdate batch failed for zone {}: {}", zone_name, e);
+            metrics::record_record_operation("batch", false);
+            update_error("apply record changes", e)
+        })?;
+
+    info!(
+        "Applied {} record changes to zone {}",
+        request.changes.len(),
+        zone_name
+    );
+    metrics::record_record_operation("batch", true);
+
+    Ok(Json(RecordResponse {
+        success: true,
+        message: format!(
+            "{} record changes applied to zone {}",
+            request.changes.len(),
+            zone_name
+        ),
+        details: Some(serde_json::json!({
+            "zone": zone_name,
+            "changes": request.changes.len(),
+        })),
+        plan: None,
+    }))
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for atomic multi-record change sets
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    use crate::nsupdate::UpdateCommand;
+    use crate::record_batch::*;
+    use crate::types::{ApiError, AppState};
+    use crate::{NsupdateExecutor, RndcExecutor};
+
+    fn request(json: &str) -> BatchRecordRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn add(name: &str, ttl: u32, record_type: &str, value: &str) -> UpdateCommand {
+        UpdateCommand::Add {
+            name: name.to_string(),
+            ttl,
+            record_type: record_type.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn delete(name: &str, record_type: &str, value: &str) -> UpdateCommand {
+        UpdateCommand::Delete {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_batch_commands_keep_order_and_expand_replace() {
+        let commands = batch_commands(
+            &request(
+                r#"{"changes": [
+                    {"operation": "replace", "name": "www", "type": "a", "value": "192.0.2.2", "ttl": 60},
+                    {"operation": "add", "name": "www", "type": "A", "value": "192.0.2.3", "ttl": 60},
+                    {"operation": "delete", "name": "old", "type": "CNAME"},
+                    {"operation": "delete", "name": "@", "type": "MX", "value": "mail.example.com.", "priority": 10},
+                    {"operation": "add", "name": "_sip._tcp", "data": {"type": "SRV", "priority": 1, "weight": 2, "port": 5060, "target": "sip.example.com."}}
+                ]}"#,
+            ),
+            "example.com",
+        )
+        .unwrap();
+
+        assert_eq!(
+            commands,
+            [
+                delete("www.example.com.", "A", ""),
+                add("www.example.com.", 60, "A", "192.0.2.2"),
+                add("www.example.com.", 60, "A", "192.0.2.3"),
+                delete("old.example.com.", "CNAME", ""),
+                delete("example.com.", "MX", "10 mail.example.com."),
+                add(
+                    "_sip._tcp.example.com.",
+                    3600,
+                    "SRV",
+                    "1 2 5060 sip.example.com."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_commands_reject_invalid_changes() {
+        let cases = [
+            (r#"{"changes": []}"#, "cannot be empty"),
+            (
+                r#"{"changes": [{"operation": "add", "name": "www", "type": "A"}]}"#,
+                "changes[0]",
+            ),
+            (
+                r#"{"changes": [
+                    {"operation": "add", "name": "ok", "type": "A", "value": "192.0.2.1"},
+                    {"operation": "replace", "name": "www", "type": "A", "value": "not-an-ip"}
+                ]}"#,
+                "changes[1]",
+            ),
+            (
+                r#"{"changes": [{"operation": "delete", "name": "www", "type": "BOGUS"}]}"#,
+                "changes[0]",
+            ),
+            (
+                r#"{"changes": [{"operation": "delete", "name": "www", "type": "MX", "priority": 10}]}"#,
+                "priority",
+            ),
+            (
+                r#"{"changes": [{"operation": "add", "name": "a b", "type": "A", "value": "192.0.2.1"}]}"#,
+                "changes[0]",
+            ),
+        ];
+        for (json, expected) in cases {
+            match batch_commands(&request(json), "example.com") {
+                Err(ApiError::InvalidRequest(msg)) | Err(ApiError::InvalidRecord(msg)) => {
+                    assert!(msg.contains(expected), "{json}: unexpected message {msg}")
+                }
+                other => panic!("{json}: expected a 400 error, got {:?}", other),
+            }
+        }
+
+        let change =
+            request(r#"{"changes": [{"operation": "delete", "name": "www", "type": "A"}]}"#)
+                .changes
+                .remove(0);
+        let too_many = BatchRecordRequest {
+            changes: vec![change; MAX_BATCH_RECORD_CHANGES + 1],
+            prerequisites: Vec::new(),
+        };
+        assert!(batch_commands(&too_many, "example.com").is_err());
+    }
+
+    #[test]
+    fn test_check_batch_size_rejects_oversized_update() {
+        let txt = |count: usize| {
+            let value = format!("\"{}\"", "x".repeat(255));
+            let changes: Vec<String> = (0..count)
+                .map(|i| {
+                    format!(
+                        r#"{{"operation": "add", "name": "txt{i}", "type": "TXT", "value": {}}}"#,
+                        serde_json::to_string(&value).unwrap()
+                    )
+                })
+                .collect();
+            request(&format!(r#"{{"changes": [{}]}}"#, changes.join(",")))
+        };
+
+        // 10 changes of ~300 bytes each fit easily
+        let commands = batch_commands(&txt(10), "example.com").unwrap();
+        assert!(check_batch_size("example.com", &[], &commands).is_ok());
+
+        // 300 changes are under the change limit but exceed 65535 bytes
+        let commands = batch_commands(&txt(300), "example.com").unwrap();
+        match check_batch_size("example.com", &[], &commands) {
+            Err(ApiError::InvalidRequest(msg)) => assert!(msg.contains("bytes"), "{msg}"),
+            other => panic!("expected a 400 error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_operation_rejected_by_serde() {
+        let result: Result<BatchRecordRequest, _> = serde_json::from_str(
+            r#"{"changes": [{"operation": "upsert", "name": "www", "type": "A", "value": "192.0.2.1"}]}"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_route_is_not_the_records_route() {
+        let state = AppState {
+            rndc: Arc::new(
+                RndcExecutor::new(
+                    "127.0.0.1:953".to_string(),
+                    "sha256".to_string(),
+                    "dGVzdC1zZWNyZXQtaGVyZQ==".to_string(),
+                )
+                .unwrap(),
+            ),
+            nsupdate: Arc::new(
+                NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap(),
+            ),
+            zone_dir: "/tmp".to_string(),
+            catalog_zone: None,
+            bind_files: Arc::new(crate::bind_files::BindFiles::new(
+                "/tmp",
+                crate::bind_files::DEFAULT_ZONE_LIST_CACHE_TTL,
+            )),
+        };
+        async fn records_handler() -> &'static str {
+            "records"
+        }
+        let app = Router::new()
+            .route("/zones/{name}/records:batch", post(batch_records))
+            .route(
+                "/zones/{name}/records",
+                get(records_handler).post(records_handler),
+            )
+            .with_state(state);
+
+        // An invalid zone name fails validation before any rndc call
+        let request = Request::builder()
+            .method("POST")
+            .uri("/zones/bad..zone/records:batch")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"changes": []}"#))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Structured, type-aware record data
+//!
+//! [`RecordData`] is the typed alternative to a record's free-form `value`
+//! string: each field of the RDATA is its own JSON member, checked on its own,
+//! e.g.
+//!
+//! ```json
+//! {"type": "SRV", "priority": 10, "weight": 5, "port": 443, "target": "web.example.com."}
+//! ```
+//!
+//! Only the common types have a structured form; DS, TLSA, SVCB and the other
+//! DNSSEC and service types are given as a presentation-format `value`.
+//!
+//! [`RecordData::rdata`] renders the canonical presentation format, which is
+//! what both nsupdate commands and zone-file lines carry. TXT text longer than
+//! 255 bytes is split into several character-strings, and every quoted string
+//! is escaped, so no field can change the meaning of the line it lands in.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{dns_wire, types::ApiError};
+
+/// Longest character-string in RDATA (RFC 1035 section 3.3)
+const MAX_CHARACTER_STRING: usize = 255;
+
+/// Longest TXT text accepted; a TXT record must fit in a 64 KiB message
+const MAX_TXT_LENGTH: usize = 4000;
+
+/// Record data, tagged with its record type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "UPPERCASE", deny_unknown_fields)]
+pub enum RecordData {
+    /// IPv4 address
+    A {
+        #[schema(value_type = String, example = "192.0.2.1")]
+        address: Ipv4Addr,
+    },
+    /// IPv6 address
+    #[serde(rename = "AAAA")]
+    Aaaa {
+        #[schema(value_type = String, example = "2001:db8::1")]
+        address: Ipv6Addr,
+    },
+    /// Alias to another name
+    #[serde(rename = "CNAME")]
+    Cname { target: String },
+    /// Alias for every name below the owner
+    #[serde(rename = "DNAME")]
+    Dname { target: String },
+    /// Name server
+    #[serde(rename = "NS")]
+    Ns { target: String },
+    /// Reverse pointer
+    #[serde(rename = "PTR")]
+    Ptr { target: String },
+    /// Mail exchange; an exchange of "." with preference 0 is a null MX
+    #[serde(rename = "MX")]
+    Mx { preference: u16, exchange: String },
+    /// Text, split into 255-byte character-strings when rendered
+    #[serde(rename = "TXT")]
+    Txt { text: String },
+    /// Service location
+    #[serde(rename = "SRV")]
+    Srv {
+        priority: u16,
+        weight: u16,
+        port: u16,
+        /// Target host, or "." if the service is not available
+        target: String,
+    },
+    /// Certification authority authorization
+    #[serde(rename = "CAA")]
+    Caa {
+        /// 0, or 128 for critical
+        #[serde(default)]
+        flags: u8,
+        /// Property tag, e.g. "issue", "issuewild" or "iodef"
+        tag: String,
+        /// Property value, unquoted
+        value: String,
+    },
+}
+
+fn invalid(record_type: &str, message: String) -> ApiError {
+    ApiError::InvalidRecord(format!("Invalid {} record data: {}", record_type, message))
+}
+
+/// Check an absolute domain name, or the root when `allow_root` is set.
+fn check_fqdn(
+    record_type: &str,
+    field: &str,
+    name: &str,
+    allow_root: bool,
+) -> Result<(), ApiError> {
+    if name == "." {
+        return if allow_root {
+            Ok(())
+        } else {
+            Err(invalid(
+                record_type,
+                format!("{} cannot be the root", field),
+            ))
+        };
+    }
+    if !name.ends_with('.') {
+        return Err(invalid(
+            record_type,
+            format!(
+                "{} must be a fully qualified domain name ending with '.': {}",
+                field, name
+            ),
+        ));
+    }
+    if name.len() > 254 {
+        return Err(invalid(
+            record_type,
+            format!("{} is longer than 253 characters", field),
+        ));
+    }
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid(
+                record_type,
+                format!("{} has an empty or over-long label: {}", field, name),
+            ));
+        }
+        if let Some(bad) = label
+            .chars()
+            .find(|&c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '*')))
+        {
+            return Err(invalid(
+                record_type,
+                format!("{} contains illegal character {:?}", field, bad),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Quote and escape text as one or more character-strings of at most 255 bytes.
+fn quote_text(text: &str) -> String {
+    text.as_bytes()
+        .chunks(MAX_CHARACTER_STRING)
+        .map(dns_wire::quote_char_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl RecordData {
+    /// Record type mnemonic
+    pub fn record_type(&self) -> &'static str {
+        match self {
+            RecordData::A { .. } => "A",
+            RecordData::Aaaa { .. } => "AAAA",
+            RecordData::Cname { .. } => "CNAME",
+            RecordData::Dname { .. } => "DNAME",
+            RecordData::Ns { .. } => "NS",
+            RecordData::Ptr { .. } => "PTR",
+            RecordData::Mx { .. } => "MX",
+            RecordData::Txt { .. } => "TXT",
+            RecordData::Srv { .. } => "SRV",
+            RecordData::Caa { .. } => "CAA",
+        }
+    }
+
+    /// Validate every field.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::InvalidRecord`] (HTTP 400) naming the first field
+    /// that is out of range or malformed.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let record_type = self.record_type();
+        match self {
+            RecordData::A { .. } | RecordData::Aaaa { .. } => Ok(()),
+            RecordData::Cname { target }
+            | RecordData::Dname { target }
+            | RecordData::Ns { target }
+            | RecordData::Ptr { target } => check_fqdn(record_type, "target", target, false),
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => {
+                check_fqdn(record_type, "exchange", exchange, true)?;
+                if exchange == "." && *preference != 0 {
+                    return Err(invalid(
+                        record_type,
+                        "a null MX (exchange \".\") must have preference 0".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            RecordData::Txt { text } => {
+                if text.len() > MAX_TXT_LENGTH {
+                    return Err(invalid(
+                        record_type,
+                        format!("text is longer than {} bytes", MAX_TXT_LENGTH),
+                    ));
+                }
+                if let Some(bad) = text.chars().find(|c| c.is_control()) {
+                    return Err(invalid(
+                        record_type,
+                        format!("text contains control character {:?}", bad),
+                    ));
+                }
+                Ok(())
+            }
+            RecordData::Srv { target, .. } => check_fqdn(record_type, "target", target, true),
+            RecordData::Caa { flags, tag, value } => {
+                if *flags != 0 && *flags != 128 {
+                    return Err(invalid(
+                        record_type,
+                        "flags must be 0 or 128 (critical)".to_string(),
+                    ));
+                }
+                if tag.is_empty()
+                    || tag.len() > 15
+                    || !tag.chars().all(|c| c.is_ascii_alphanumeric())
+                {
+                    return Err(invalid(
+                        record_type,
+                        format!("tag must be 1 to 15 letters and digits: {:?}", tag),
+                    ));
+                }
+                if let Some(bad) = value.chars().find(|c| c.is_control()) {
+                    return Err(invalid(
+                        record_type,
+                        format!("value contains control character {:?}", bad),
+                    ));
+                }
+                let is_url = ["mailto:", "http://", "https://"]
+                    .iter()
+                    .any(|scheme| value.starts_with(scheme));
+                if tag.eq_ignore_ascii_case("iodef") && !is_url {
+                    return Err(invalid(
+                        record_type,
+                        "iodef value must be a mailto:, http:// or https:// URL".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// RDATA in canonical presentation format, as used in nsupdate commands
+    /// and zone files
+    pub fn rdata(&self) -> String {
+        match self {
+            RecordData::A { address } => address.to_string(),
+            RecordData::Aaaa { address } => address.to_string(),
+            RecordData::Cname { target }
+            | RecordData::Dname { target }
+            | RecordData::Ns { target }
+            | RecordData::Ptr { target } => target.clone(),
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => format!("{} {}", preference, exchange),
+            RecordData::Txt { text } if text.is_empty() => "\"\"".to_string(),
+            RecordData::Txt { text } => quote_text(text),
+            RecordData::Srv {
+                priority,
+                weight,
+                port,
+                target,
+            } => format!("{} {} {} {}", priority, weight, port, target),
+            RecordData::Caa { flags, tag, value } => format!(
+                "{} {} {}",
+                flags,
+                tag.to_ascii_lowercase(),
+                dns_wire::quote_char_string(value.as_bytes())
+            ),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for structured record data
+
+#[cfg(test)]
+mod tests {
+    use crate::record_data::*;
+
+    fn data(json: &str) -> RecordData {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_tagged_record_data() {
+        assert_eq!(
+            data(
+                r#"{"type": "SRV", "priority": 10, "weight": 5, "port": 443, "target": "web.example.com."}"#
+            ),
+            RecordData::Srv {
+                priority: 10,
+                weight: 5,
+                port: 443,
+                target: "web.example.com.".to_string(),
+            }
+        );
+        assert_eq!(
+            data(r#"{"type": "AAAA", "address": "2001:db8::1"}"#).record_type(),
+            "AAAA"
+        );
+        assert_eq!(
+            data(r#"{"type": "CAA", "tag": "issue", "value": "letsencrypt.org"}"#),
+            RecordData::Caa {
+                flags: 0,
+                tag: "issue".to_string(),
+                value: "letsencrypt.org".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_bad_fields() {
+        for json in [
+            r#"{"type": "A", "address": "999.0.0.1"}"#,
+            r#"{"type": "SRV", "priority": 10, "weight": 5, "port": 70000, "target": "x."}"#,
+            r#"{"type": "MX", "preference": 10}"#,
+            r#"{"type": "MX", "preference": 10, "exchange": "mail.", "extra": 1}"#,
+            r#"{"type": "SPF", "text": "v=spf1"}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<RecordData>(json).is_err(),
+                "expected {json} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_record_data() {
+        let valid = [
+            r#"{"type": "CNAME", "target": "www.example.com."}"#,
+            r#"{"type": "MX", "preference": 0, "exchange": "."}"#,
+            r#"{"type": "SRV", "priority": 0, "weight": 0, "port": 0, "target": "."}"#,
+            r#"{"type": "CAA", "flags": 128, "tag": "iodef", "value": "mailto:security@example.com"}"#,
+            r#"{"type": "TXT", "text": "v=spf1 -all"}"#,
+            r#"{"type": "PTR", "target": "_sip._tcp.example.com."}"#,
+        ];
+        for json in valid {
+            assert!(data(json).validate().is_ok(), "expected {json} to be valid");
+        }
+
+        let invalid = [
+            r#"{"type": "CNAME", "target": "www.example.com"}"#,
+            r#"{"type": "CNAME", "target": "."}"#,
+            r#"{"type": "NS", "target": "ns1..example.com."}"#,
+            r#"{"type": "NS", "target": "ns1 example.com."}"#,
+            r#"{"type": "MX", "preference": 10, "exchange": "."}"#,
+            r#"{"type": "CAA", "flags": 1, "tag": "issue", "value": "ca.example"}"#,
+            r#"{"type": "CAA", "tag": "is sue", "value": "ca.example"}"#,
+            r#"{"type": "CAA", "tag": "iodef", "value": "security@example.com"}"#,
+            r#"{"type": "TXT", "text": "a\nupdate add evil"}"#,
+        ];
+        for json in invalid {
+            assert!(
+                data(json).validate().is_err(),
+                "expected {json} to be invalid"
+            );
+        }
+
+        let long = RecordData::Txt {
+            text: "x".repeat(4001),
+        };
+        assert!(long.validate().is_err());
+    }
+
+    #[test]
+    fn test_rdata_renders_canonical_presentation_format() {
+        assert_eq!(
+            data(r#"{"type": "AAAA", "address": "2001:0db8:0000::0001"}"#).rdata(),
+            "2001:db8::1"
+        );
+        assert_eq!(
+            data(r#"{"type": "MX", "preference": 10, "exchange": "mail.example.com."}"#).rdata(),
+            "10 mail.example.com."
+        );
+        assert_eq!(
+            data(r#"{"type": "SRV", "priority": 10, "weight": 5, "port": 443, "target": "web.example.com."}"#)
+                .rdata(),
+            "10 5 443 web.example.com."
+        );
+        assert_eq!(
+            data(r#"{"type": "CAA", "tag": "ISSUE", "value": "ca.example; account=\"1\""}"#)
+                .rdata(),
+            r#"0 issue "ca.example; account=\"1\"""#
+        );
+        assert_eq!(data(r#"{"type": "TXT", "text": ""}"#).rdata(), r#""""#);
+    }
+
+    #[test]
+    fn test_txt_is_split_into_character_strings() {
+        let text = format!("{}{}", "a".repeat(255), "b".repeat(10));
+        let rdata = RecordData::Txt { text }.rdata();
+        assert_eq!(
+            rdata,
+            format!("\"{}\" \"{}\"", "a".repeat(255), "b".repeat(10))
+        );
+
+        // Quotes, backslashes and non-ASCII bytes are escaped
+        let rdata = RecordData::Txt {
+            text: r#"say "hi" \ café"#.to_string(),
+        }
+        .rdata();
+        assert_eq!(rdata, r#""say \"hi\" \\ caf\195\169""#);
+    }
+}
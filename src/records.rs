@@ -7,21 +7,40 @@
 //! - Adding records to existing zones
 //! - Removing records from existing zones
 //! - Updating existing records
+//! - Listing the live records of a zone
 //!
-//! All operations use nsupdate for dynamic DNS updates with TSIG authentication.
+//! All write operations use nsupdate for dynamic DNS updates with TSIG
+//! authentication. Listing reads the zone from the server with a TSIG-signed
+//! AXFR using the same key.
+//!
+//! A record's content is given either as a `type` and free-form `value` string
+//! or as structured [`RecordData`] in `data`.
+//!
+//! Every write can carry RFC 2136 `prerequisites` that the server checks before
+//! applying it. An update that requires its current value to be present is a
+//! compare-and-swap: when another writer got there first, the request fails with
+//! 409 or 412 instead of silently overwriting or recreating the record. The
+//! server's answer is trusted when it is TSIG-signed; without a key, updates
+//! with prerequisites are sent over TCP and the unsigned answer is trusted.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::{debug, error, info};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    metrics, rndc_parser, rndc_types,
-    types::{ApiError, AppState},
+    dns_update::UpdateOutcomeUnknown,
+    dns_wire, metrics,
+    nsupdate::{Prerequisite, PrerequisiteFailed},
+    record_data::RecordData,
+    rndc_parser, rndc_types,
+    types::{ApiError, AppState, DryRunQuery, OperationPlan},
+    zone_file_parser::ResourceRecord,
 };
 
 /// Request to add a new DNS record
@@ -31,11 +50,12 @@ pub struct AddRecordRequest {
     /// Record name (e.g., "www", "@" for apex)
     pub name: String,
 
-    /// Record type (e.g., "A", "AAAA", "CNAME", "MX", "TXT")
-    #[serde(rename = "type")]
+    /// Record type (e.g., "A", "AAAA", "CNAME", "MX", "TXT"); optional with `data`
+    #[serde(rename = "type", default)]
     pub record_type: String,
 
-    /// Record value (e.g., "192.0.2.1" for A record)
+    /// Record value (e.g., "192.0.2.1" for A record); omitted with `data`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub value: String,
 
     /// TTL in seconds (default: 3600)
@@ -45,6 +65,14 @@ pub struct AddRecordRequest {
     /// Priority (for MX and SRV records)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u16>,
+
+    /// Structured record data, instead of `value` and `priority`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
+
+    /// Conditions the zone must meet for the change to be applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prerequisites: Vec<RecordPrerequisite>,
 }
 
 /// Request to remove a DNS record
@@ -54,13 +82,21 @@ pub struct RemoveRecordRequest {
     /// Record name (e.g., "www", "@" for apex)
     pub name: String,
 
-    /// Record type (e.g., "A", "AAAA", "CNAME")
-    #[serde(rename = "type")]
+    /// Record type (e.g., "A", "AAAA", "CNAME"); optional with `data`
+    #[serde(rename = "type", default)]
     pub record_type: String,
 
     /// Record value to remove (optional - if omitted, removes all records of this type)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
+
+    /// Structured data of the record to remove, instead of `value`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
+
+    /// Conditions the zone must meet for the change to be applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prerequisites: Vec<RecordPrerequisite>,
 }
 
 /// Request to update a DNS record
@@ -70,14 +106,16 @@ pub struct UpdateRecordRequest {
     /// Record name (e.g., "www", "@" for apex)
     pub name: String,
 
-    /// Record type (e.g., "A", "AAAA", "CNAME")
-    #[serde(rename = "type")]
+    /// Record type (e.g., "A", "AAAA", "CNAME"); optional with structured data
+    #[serde(rename = "type", default)]
     pub record_type: String,
 
-    /// Current record value
+    /// Current record value; omitted with `currentData`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub current_value: String,
 
-    /// New record value
+    /// New record value; omitted with `newData`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub new_value: String,
 
     /// TTL in seconds (default: 3600)
@@ -87,6 +125,65 @@ pub struct UpdateRecordRequest {
     /// Priority (for MX and SRV records)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u16>,
+
+    /// Structured data of the current record, instead of `currentValue`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_data: Option<RecordData>,
+
+    /// Structured data of the new record, instead of `newValue`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_data: Option<RecordData>,
+
+    /// Conditions the zone must meet for the change to be applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prerequisites: Vec<RecordPrerequisite>,
+}
+
+/// Condition a prerequisite checks, named after its nsupdate `prereq` form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PrerequisiteCondition {
+    /// The name owns at least one record
+    Yxdomain,
+    /// The name owns no records
+    Nxdomain,
+    /// The RRset exists; with values, it holds exactly those records
+    Yxrrset,
+    /// The RRset does not exist
+    Nxrrset,
+}
+
+/// Condition the server checks before applying a change (RFC 2136)
+///
+/// If any prerequisite does not hold, nothing is changed and the request
+/// fails with 409 (something exists that must not) or 412 (something
+/// required is missing). Without a TSIG key, changes with prerequisites are
+/// sent over TCP.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordPrerequisite {
+    pub condition: PrerequisiteCondition,
+
+    /// Name the condition applies to (e.g., "www", "@" for apex); defaults to
+    /// the record's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Record type for `yxrrset` and `nxrrset`; defaults to the record's type
+    #[serde(rename = "type", default, skip_serializing_if = "String::is_empty")]
+    pub record_type: String,
+
+    /// For `yxrrset`: the one record the RRset must hold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    /// Structured data of that record, instead of `value`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
+
+    /// For `yxrrset`: every record the RRset must hold, instead of `value`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
 }
 
 /// Response from record operations
@@ -96,14 +193,61 @@ pub struct RecordResponse {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
+    /// Planned actions, present only for `dryRun=true` requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<OperationPlan>,
+}
+
+/// Query parameters for listing records
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListRecordsQuery {
+    /// Only return RRsets with this owner name (relative, "@", or FQDN)
+    pub name: Option<String>,
+
+    /// Only return RRsets of this type (e.g., "A", "MX")
+    #[serde(rename = "type")]
+    #[param(rename = "type")]
+    pub record_type: Option<String>,
+}
+
+/// A set of records sharing owner name, class and type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RecordSet {
+    /// Absolute owner name (e.g., "www.example.com.")
+    pub name: String,
+
+    /// Record type (e.g., "A", "MX")
+    #[serde(rename = "type")]
+    pub record_type: String,
+
+    /// TTL in seconds
+    pub ttl: u32,
+
+    /// Presentation-format RDATA of each record in the set
+    pub values: Vec<String>,
+}
+
+/// Response listing the RRsets of a zone
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecordSetListResponse {
+    pub zone: String,
+    pub rrsets: Vec<RecordSet>,
+    pub count: usize,
 }
 
 fn default_ttl() -> u32 {
     3600
 }
 
+/// Largest number of prerequisites on one change, counting each of `values`
+pub const MAX_PREREQUISITES: usize = 100;
+
 /// Supported DNS record types
-const VALID_RECORD_TYPES: &[&str] = &["A", "AAAA", "CNAME", "MX", "TXT", "NS", "PTR", "SRV", "CAA"];
+const VALID_RECORD_TYPES: &[&str] = &[
+    "A", "AAAA", "CNAME", "MX", "TXT", "NS", "PTR", "SRV", "CAA", "DS", "DNSKEY", "TLSA", "SSHFP",
+    "NAPTR", "SVCB", "HTTPS", "LOC", "URI", "DNAME", "HINFO", "CERT",
+];
 
 /// Validate that a zone exists and supports dynamic updates
 ///
@@ -115,7 +259,10 @@ const VALID_RECORD_TYPES: &[&str] = &["A", "AAAA", "CNAME", "MX", "TXT", "NS", "
 /// # Returns
 ///
 /// Ok if zone exists and has allow-update configured, Err otherwise
-async fn validate_zone_for_updates(state: &AppState, zone_name: &str) -> Result<(), ApiError> {
+pub(crate) async fn validate_zone_for_updates(
+    state: &AppState,
+    zone_name: &str,
+) -> Result<(), ApiError> {
     // Validate zone name against the strict DNS grammar. This also rejects
     // empty names and any control characters that could inject extra nsupdate
     // commands once the zone is assembled into a request.
@@ -144,10 +291,10 @@ async fn validate_zone_for_updates(state: &AppState, zone_name: &str) -> Result<
         )));
     }
 
-    // Zone must have allow-update configured
-    if zone_config.allow_update.is_none() && zone_config.allow_update_raw.is_none() {
+    // Zone must have allow-update or update-policy configured
+    if zone_config.allow_update.is_none() && zone_config.update_policy.is_none() {
         return Err(ApiError::DynamicUpdatesNotEnabled(format!(
-            "Zone {} does not have allow-update configured. \
+            "Zone {} does not have allow-update or update-policy configured. \
             Create zone with updateKeyName or modify zone to enable dynamic updates",
             zone_name
         )));
@@ -249,7 +396,7 @@ pub(crate) fn validate_record_value(record_type: &str, value: &str) -> Result<()
                 .parse::<std::net::Ipv6Addr>()
                 .map_err(|_| ApiError::InvalidRecord(format!("Invalid IPv6 address: {}", value)))?;
         }
-        "CNAME" | "NS" | "PTR" | "MX" => {
+        "CNAME" | "NS" | "PTR" | "MX" | "DNAME" => {
             // Must be FQDN with trailing dot
             if !value.ends_with('.') {
                 return Err(ApiError::InvalidRecord(format!(
@@ -258,16 +405,305 @@ pub(crate) fn validate_record_value(record_type: &str, value: &str) -> Result<()
                 )));
             }
         }
-        "TXT" | "CAA" | "SRV" => {
-            // No further format constraint beyond the non-empty and
-            // control-character checks performed above.
+        upper => {
+            // Checked by the encoder that builds the UPDATE, so a value that
+            // validates can be sent. SRV and CAA are also held to the checks
+            // of their structured form.
+            let code = dns_wire::record_type_code(upper).ok_or_else(|| {
+                ApiError::InvalidRecord(format!("Unsupported record type: {}", upper))
+            })?;
+            let rdata = dns_wire::encode_rdata(code, value)
+                .map_err(|e| rdata_error(upper, value, &format!("{:#}", e)))?;
+            if let Some(data) = structured_data(upper, &rdata) {
+                data.validate()?;
+            }
         }
-        _ => {}
     }
 
     Ok(())
 }
 
+/// Resolve a record's type and presentation-format value.
+///
+/// The record is given either as structured `data`, or as `record_type` and
+/// `value` with an optional `priority` that is prepended to MX and SRV values.
+/// With `data`, `record_type` may be empty but must otherwise match it.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRecord`] (HTTP 400) if `data` is combined with a
+/// value or priority, the types disagree, or the content fails validation.
+pub(crate) fn resolve_record_value(
+    record_type: &str,
+    value: &str,
+    priority: Option<u16>,
+    data: Option<&RecordData>,
+) -> Result<(String, String), ApiError> {
+    let Some(data) = data else {
+        validate_record_type(record_type)?;
+        let upper = record_type.to_uppercase();
+        let value = match priority {
+            Some(priority) if (upper == "MX" || upper == "SRV") && !value.is_empty() => {
+                format!("{} {}", priority, value)
+            }
+            _ => value.to_string(),
+        };
+        validate_record_value(record_type, &value)?;
+        return Ok((record_type.to_string(), value));
+    };
+
+    if !value.is_empty() || priority.is_some() {
+        return Err(ApiError::InvalidRecord(
+            "A value or priority cannot be combined with structured data".to_string(),
+        ));
+    }
+    if !record_type.is_empty() && !record_type.eq_ignore_ascii_case(data.record_type()) {
+        return Err(ApiError::InvalidRecord(format!(
+            "Record type {} does not match data of type {}",
+            record_type,
+            data.record_type()
+        )));
+    }
+    data.validate()?;
+    Ok((data.record_type().to_string(), data.rdata()))
+}
+
+/// Validate prerequisites and resolve them to absolute names, in order.
+///
+/// A prerequisite without a name or type takes the record's: `record_name`
+/// and `record_type`, when the change is about a single record. A `yxrrset`
+/// with several `values` resolves to one [`Prerequisite::RrsetExists`] per
+/// value; the server compares them together with the whole RRset.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRecord`] (HTTP 400) if there are too many
+/// prerequisites or values, one lacks a name or type it needs, carries a type
+/// or value its condition does not take, or its value fails validation.
+pub(crate) fn resolve_prerequisites(
+    prerequisites: &[RecordPrerequisite],
+    zone_name: &str,
+    record_name: Option<&str>,
+    record_type: Option<&str>,
+) -> Result<Vec<Prerequisite>, ApiError> {
+    if prerequisites.len() > MAX_PREREQUISITES {
+        return Err(ApiError::InvalidRecord(format!(
+            "A change can have at most {} prerequisites",
+            MAX_PREREQUISITES
+        )));
+    }
+
+    let mut resolved = Vec::with_capacity(prerequisites.len());
+    for (index, prerequisite) in prerequisites.iter().enumerate() {
+        let records = resolve_prerequisite(prerequisite, zone_name, record_name, record_type)
+            .map_err(|e| match e {
+                ApiError::InvalidRecord(message) => {
+                    ApiError::InvalidRecord(format!("prerequisites[{}]: {}", index, message))
+                }
+                other => other,
+            })?;
+        resolved.extend(records);
+    }
+    if resolved.len() > MAX_PREREQUISITES {
+        return Err(ApiError::InvalidRecord(format!(
+            "A change can have at most {} prerequisite values",
+            MAX_PREREQUISITES
+        )));
+    }
+    Ok(resolved)
+}
+
+fn resolve_prerequisite(
+    prerequisite: &RecordPrerequisite,
+    zone_name: &str,
+    record_name: Option<&str>,
+    record_type: Option<&str>,
+) -> Result<Vec<Prerequisite>, ApiError> {
+    let condition = prerequisite.condition;
+    let name = prerequisite
+        .name
+        .as_deref()
+        .or(record_name)
+        .ok_or_else(|| ApiError::InvalidRecord("a prerequisite needs a name".to_string()))?;
+    validate_record_name(name)?;
+    let name = normalize_record_name(name, zone_name);
+
+    let has_value = prerequisite.value.is_some() || prerequisite.data.is_some();
+    let has_values = !prerequisite.values.is_empty();
+    if (has_value || has_values) && condition != PrerequisiteCondition::Yxrrset {
+        return Err(ApiError::InvalidRecord(
+            "only a yxrrset prerequisite can have a value".to_string(),
+        ));
+    }
+    if has_value && has_values {
+        return Err(ApiError::InvalidRecord(
+            "a prerequisite has either 'values' or 'value'/'data', not both".to_string(),
+        ));
+    }
+
+    // The type is given, implied by the data, or the record's
+    let rrset_type = || -> Result<String, ApiError> {
+        let explicit = if prerequisite.record_type.is_empty() {
+            prerequisite
+                .data
+                .as_ref()
+                .map_or("", RecordData::record_type)
+        } else {
+            &prerequisite.record_type
+        };
+        let record_type = Some(explicit)
+            .filter(|t| !t.is_empty())
+            .or(record_type)
+            .ok_or_else(|| {
+                ApiError::InvalidRecord(
+                    "a yxrrset or nxrrset prerequisite needs a type".to_string(),
+                )
+            })?;
+        validate_record_type(record_type)?;
+        Ok(record_type.to_string())
+    };
+
+    Ok(match condition {
+        PrerequisiteCondition::Yxdomain | PrerequisiteCondition::Nxdomain => {
+            if !prerequisite.record_type.is_empty() {
+                return Err(ApiError::InvalidRecord(
+                    "a yxdomain or nxdomain prerequisite cannot have a type".to_string(),
+                ));
+            }
+            if condition == PrerequisiteCondition::Yxdomain {
+                vec![Prerequisite::NameInUse { name }]
+            } else {
+                vec![Prerequisite::NameNotInUse { name }]
+            }
+        }
+        PrerequisiteCondition::Yxrrset => {
+            let record_type = rrset_type()?;
+            let values = if has_values {
+                prerequisite
+                    .values
+                    .iter()
+                    .map(|value| Ok(resolve_record_value(&record_type, value, None, None)?.1))
+                    .collect::<Result<Vec<_>, ApiError>>()?
+            } else if has_value {
+                vec![
+                    resolve_record_value(
+                        &record_type,
+                        prerequisite.value.as_deref().unwrap_or(""),
+                        None,
+                        prerequisite.data.as_ref(),
+                    )?
+                    .1,
+                ]
+            } else {
+                vec![String::new()]
+            };
+            values
+                .into_iter()
+                .map(|value| Prerequisite::RrsetExists {
+                    name: name.clone(),
+                    record_type: record_type.clone(),
+                    value,
+                })
+                .collect()
+        }
+        PrerequisiteCondition::Nxrrset => vec![Prerequisite::RrsetNotExists {
+            name,
+            record_type: rrset_type()?,
+        }],
+    })
+}
+
+/// Map a failed update to an API error.
+///
+/// A prerequisite the server found unmet is a problem with the caller's view
+/// of the zone: 409 if something exists that must not, 412 if something
+/// required is missing. The same failure answering a retransmitted update may
+/// be the update's own effect, so its outcome is unknown (504). Anything else
+/// is an nsupdate failure (500).
+pub(crate) fn update_error(action: &str, e: anyhow::Error) -> ApiError {
+    if let Some(unknown) = e.downcast_ref::<UpdateOutcomeUnknown>() {
+        return ApiError::UpdateOutcomeUnknown(unknown.to_string());
+    }
+    match e.downcast_ref::<PrerequisiteFailed>() {
+        Some(failure) if failure.is_conflict() => ApiError::RecordConflict(failure.to_string()),
+        Some(failure) => ApiError::PreconditionFailed(failure.to_string()),
+        None => ApiError::NsupdateError(format!("Failed to {}: {:#}", action, e)),
+    }
+}
+
+/// A record's value for logs and responses: the rendered data if given
+fn display_value(value: &str, data: Option<&RecordData>) -> String {
+    data.map_or_else(|| value.to_string(), RecordData::rdata)
+}
+
+fn rdata_error(record_type: &str, value: &str, reason: &str) -> ApiError {
+    ApiError::InvalidRecord(format!(
+        "Invalid {} record value {:?}: {}",
+        record_type, value, reason
+    ))
+}
+
+/// The structured form of encoded SRV or CAA RDATA, so that string values
+/// are held to the same checks as [`RecordData`].
+fn structured_data(record_type: &str, rdata: &[u8]) -> Option<RecordData> {
+    let u16_at = |i: usize| Some(u16::from_be_bytes([*rdata.get(i)?, *rdata.get(i + 1)?]));
+    match record_type {
+        "SRV" => Some(RecordData::Srv {
+            priority: u16_at(0)?,
+            weight: u16_at(2)?,
+            port: u16_at(4)?,
+            target: dns_wire::decode_name(rdata, 6).ok()?.0,
+        }),
+        "CAA" => {
+            let (&flags, rest) = rdata.split_first()?;
+            let (&tag_len, rest) = rest.split_first()?;
+            let (tag, value) = rest.split_at_checked(tag_len as usize)?;
+            Some(RecordData::Caa {
+                flags,
+                tag: String::from_utf8_lossy(tag).into_owned(),
+                value: String::from_utf8_lossy(value).into_owned(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Group transferred records into RRsets, keeping transfer order, and apply
+/// the optional owner-name (absolute) and type filters.
+///
+/// RRsets are keyed by lower-cased owner name and type (every transferred
+/// record is class IN), so grouping is linear in the number of records.
+pub(crate) fn group_rrsets(
+    records: Vec<ResourceRecord>,
+    name: Option<&str>,
+    record_type: Option<&str>,
+) -> Vec<RecordSet> {
+    let mut rrsets: Vec<RecordSet> = Vec::new();
+    let mut index: HashMap<(String, String), usize> = HashMap::new();
+
+    for record in records {
+        if name.is_some_and(|n| !n.eq_ignore_ascii_case(&record.name))
+            || record_type.is_some_and(|t| !t.eq_ignore_ascii_case(&record.record_type))
+        {
+            continue;
+        }
+
+        let key = (record.name.to_ascii_lowercase(), record.record_type.clone());
+        match index.get(&key) {
+            Some(&i) => rrsets[i].values.push(record.data),
+            None => {
+                index.insert(key, rrsets.len());
+                rrsets.push(RecordSet {
+                    name: record.name,
+                    record_type: record.record_type,
+                    ttl: record.ttl.unwrap_or_default(),
+                    values: vec![record.data],
+                });
+            }
+        }
+    }
+
+    rrsets
+}
+
 /// Normalize record name to FQDN
 ///
 /// # Arguments
@@ -278,7 +714,7 @@ pub(crate) fn validate_record_value(record_type: &str, value: &str) -> Result<()
 /// # Returns
 ///
 /// Fully qualified domain name with trailing dot
-fn normalize_record_name(name: &str, zone: &str) -> String {
+pub(crate) fn normalize_record_name(name: &str, zone: &str) -> String {
     if name == "@" {
         // Apex record - use zone name
         format!("{}.", zone)
@@ -294,18 +730,43 @@ fn normalize_record_name(name: &str, zone: &str) -> String {
     }
 }
 
+/// Build the response for a dry run from the nsupdate script that would be sent.
+pub(crate) fn dry_run_response(
+    zone_name: &str,
+    action: &str,
+    script: anyhow::Result<String>,
+) -> Result<RecordResponse, ApiError> {
+    let script = script.map_err(|e| ApiError::InvalidRecord(e.to_string()))?;
+    info!("Dry run: record would be {} zone {}", action, zone_name);
+
+    Ok(RecordResponse {
+        success: true,
+        message: format!("Dry run: record would be {} zone {}", action, zone_name),
+        details: None,
+        plan: Some(OperationPlan {
+            nsupdate_script: Some(script),
+            ..Default::default()
+        }),
+    })
+}
+
 /// Add a DNS record to an existing zone
 #[utoipa::path(
     post,
     path = "/api/v1/zones/{zone_name}/records",
     request_body = AddRecordRequest,
     params(
-        ("zone_name" = String, Path, description = "Zone name")
+        ("zone_name" = String, Path, description = "Zone name"),
+        DryRunQuery
     ),
     responses(
+        (status = 200, description = "Dry run: planned nsupdate script", body = RecordResponse),
         (status = 201, description = "Record added successfully", body = RecordResponse),
         (status = 400, description = "Invalid request or zone not configured for updates"),
         (status = 404, description = "Zone not found"),
+        (status = 409, description = "A prerequisite failed: a name or RRset exists that must not"),
+        (status = 412, description = "A prerequisite failed: a required name or RRset is missing"),
+        (status = 504, description = "A prerequisite failed after the update was retransmitted; it may have been applied"),
         (status = 500, description = "Update failed"),
     ),
     tag = "records"
@@ -313,51 +774,71 @@ fn normalize_record_name(name: &str, zone: &str) -> String {
 pub async fn add_record(
     State(state): State<AppState>,
     Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
     Json(request): Json<AddRecordRequest>,
 ) -> Result<(StatusCode, Json<RecordResponse>), ApiError> {
     info!(
         "Adding record to zone {}: {} {} {} (TTL: {})",
-        zone_name, request.name, request.record_type, request.value, request.ttl
+        zone_name,
+        request.name,
+        request.record_type,
+        display_value(&request.value, request.data.as_ref()),
+        request.ttl
     );
 
     // Early return pattern: validate all prerequisites
     validate_zone_for_updates(&state, &zone_name).await?;
-    validate_record_type(&request.record_type)?;
     validate_record_name(&request.name)?;
-    validate_record_value(&request.record_type, &request.value)?;
+    let (record_type, value_with_priority) = resolve_record_value(
+        &request.record_type,
+        &request.value,
+        request.priority,
+        request.data.as_ref(),
+    )?;
 
     // Normalize record name to FQDN
     let fqdn = normalize_record_name(&request.name, &zone_name);
 
     debug!("Normalized record name: {} -> {}", request.name, fqdn);
 
-    // For MX and SRV records, prepend priority to value
-    let value_with_priority = if let Some(priority) = request.priority {
-        if request.record_type.to_uppercase() == "MX" || request.record_type.to_uppercase() == "SRV"
-        {
-            format!("{} {}", priority, request.value)
-        } else {
-            request.value.clone()
-        }
-    } else {
-        request.value.clone()
-    };
+    let prerequisites = resolve_prerequisites(
+        &request.prerequisites,
+        &zone_name,
+        Some(&request.name),
+        Some(&record_type),
+    )?;
+
+    if query.dry_run {
+        let script = state.nsupdate.add_record_script(
+            &zone_name,
+            &prerequisites,
+            &fqdn,
+            request.ttl,
+            &record_type,
+            &value_with_priority,
+        );
+        return Ok((
+            StatusCode::OK,
+            Json(dry_run_response(&zone_name, "added to", script)?),
+        ));
+    }
 
     // Execute nsupdate
     let _output = state
         .nsupdate
         .add_record(
             &zone_name,
+            &prerequisites,
             &fqdn,
             request.ttl,
-            &request.record_type,
+            &record_type,
             &value_with_priority,
         )
         .await
         .map_err(|e| {
             error!("nsupdate add failed: {}", e);
             metrics::record_record_operation("add", false);
-            ApiError::NsupdateError(format!("Failed to add record: {}", e))
+            update_error("add record", e)
         })?;
 
     info!("Record added successfully to zone {}", zone_name);
@@ -372,11 +853,12 @@ pub async fn add_record(
                 "zone": zone_name,
                 "record": {
                     "name": request.name,
-                    "type": request.record_type,
-                    "value": request.value,
+                    "type": record_type,
+                    "value": display_value(&request.value, request.data.as_ref()),
                     "ttl": request.ttl,
                 }
             })),
+            plan: None,
         }),
     ))
 }
@@ -387,12 +869,16 @@ pub async fn add_record(
     path = "/api/v1/zones/{zone_name}/records",
     request_body = RemoveRecordRequest,
     params(
-        ("zone_name" = String, Path, description = "Zone name")
+        ("zone_name" = String, Path, description = "Zone name"),
+        DryRunQuery
     ),
     responses(
-        (status = 200, description = "Record removed successfully", body = RecordResponse),
+        (status = 200, description = "Record removed successfully (or planned, with dryRun)", body = RecordResponse),
         (status = 400, description = "Invalid request or zone not configured for updates"),
         (status = 404, description = "Zone not found"),
+        (status = 409, description = "A prerequisite failed: a name or RRset exists that must not"),
+        (status = 412, description = "A prerequisite failed: a required name or RRset is missing"),
+        (status = 504, description = "A prerequisite failed after the update was retransmitted; it may have been applied"),
         (status = 500, description = "Update failed"),
     ),
     tag = "records"
@@ -400,38 +886,79 @@ pub async fn add_record(
 pub async fn remove_record(
     State(state): State<AppState>,
     Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
     Json(request): Json<RemoveRecordRequest>,
 ) -> Result<Json<RecordResponse>, ApiError> {
     info!(
         "Removing record from zone {}: {} {} {:?}",
-        zone_name, request.name, request.record_type, request.value
+        zone_name,
+        request.name,
+        request.record_type,
+        request
+            .data
+            .as_ref()
+            .map(RecordData::rdata)
+            .or(request.value.clone())
     );
 
     // Early return pattern: validate all prerequisites
     validate_zone_for_updates(&state, &zone_name).await?;
-    validate_record_type(&request.record_type)?;
     validate_record_name(&request.name)?;
 
     // Validate value if provided
-    if let Some(ref value) = request.value {
-        validate_record_value(&request.record_type, value)?;
-    }
+    let (record_type, value) = match (&request.data, &request.value) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::InvalidRecord(
+                "value and data cannot both be given".to_string(),
+            ))
+        }
+        (Some(data), None) => {
+            let (record_type, value) =
+                resolve_record_value(&request.record_type, "", None, Some(data))?;
+            (record_type, Some(value))
+        }
+        (None, value) => {
+            validate_record_type(&request.record_type)?;
+            if let Some(value) = value {
+                validate_record_value(&request.record_type, value)?;
+            }
+            (request.record_type.clone(), value.clone())
+        }
+    };
 
     // Normalize record name to FQDN
     let fqdn = normalize_record_name(&request.name, &zone_name);
 
     debug!("Normalized record name: {} -> {}", request.name, fqdn);
 
+    let prerequisites = resolve_prerequisites(
+        &request.prerequisites,
+        &zone_name,
+        Some(&request.name),
+        Some(&record_type),
+    )?;
+
+    let value_str = value.as_deref().unwrap_or("");
+    if query.dry_run {
+        let script = state.nsupdate.remove_record_script(
+            &zone_name,
+            &prerequisites,
+            &fqdn,
+            &record_type,
+            value_str,
+        );
+        return Ok(Json(dry_run_response(&zone_name, "removed from", script)?));
+    }
+
     // Execute nsupdate
-    let value_str = request.value.as_deref().unwrap_or("");
     let _output = state
         .nsupdate
-        .remove_record(&zone_name, &fqdn, &request.record_type, value_str)
+        .remove_record(&zone_name, &prerequisites, &fqdn, &record_type, value_str)
         .await
         .map_err(|e| {
             error!("nsupdate remove failed: {}", e);
             metrics::record_record_operation("remove", false);
-            ApiError::NsupdateError(format!("Failed to remove record: {}", e))
+            update_error("remove record", e)
         })?;
 
     info!("Record removed successfully from zone {}", zone_name);
@@ -444,10 +971,11 @@ pub async fn remove_record(
             "zone": zone_name,
             "record": {
                 "name": request.name,
-                "type": request.record_type,
-                "value": request.value,
+                "type": record_type,
+                "value": value,
             }
         })),
+        plan: None,
     }))
 }
 
@@ -457,12 +985,16 @@ pub async fn remove_record(
     path = "/api/v1/zones/{zone_name}/records",
     request_body = UpdateRecordRequest,
     params(
-        ("zone_name" = String, Path, description = "Zone name")
+        ("zone_name" = String, Path, description = "Zone name"),
+        DryRunQuery
     ),
     responses(
-        (status = 200, description = "Record updated successfully", body = RecordResponse),
+        (status = 200, description = "Record updated successfully (or planned, with dryRun)", body = RecordResponse),
         (status = 400, description = "Invalid request or zone not configured for updates"),
         (status = 404, description = "Zone not found"),
+        (status = 409, description = "A prerequisite failed: a name or RRset exists that must not"),
+        (status = 412, description = "A prerequisite failed: a required name or RRset is missing"),
+        (status = 504, description = "A prerequisite failed after the update was retransmitted; it may have been applied"),
         (status = 500, description = "Update failed"),
     ),
     tag = "records"
@@ -470,53 +1002,72 @@ pub async fn remove_record(
 pub async fn update_record(
     State(state): State<AppState>,
     Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
     Json(request): Json<UpdateRecordRequest>,
 ) -> Result<Json<RecordResponse>, ApiError> {
+    let current_value = display_value(&request.current_value, request.current_data.as_ref());
+    let new_value = display_value(&request.new_value, request.new_data.as_ref());
     info!(
         "Updating record in zone {}: {} {} from {} to {} (TTL: {})",
-        zone_name,
-        request.name,
-        request.record_type,
-        request.current_value,
-        request.new_value,
-        request.ttl
+        zone_name, request.name, request.record_type, current_value, new_value, request.ttl
     );
 
     // Early return pattern: validate all prerequisites
     validate_zone_for_updates(&state, &zone_name).await?;
-    validate_record_type(&request.record_type)?;
     validate_record_name(&request.name)?;
-    validate_record_value(&request.record_type, &request.current_value)?;
-    validate_record_value(&request.record_type, &request.new_value)?;
+    let (record_type, current_with_priority) = resolve_record_value(
+        &request.record_type,
+        &request.current_value,
+        request.priority,
+        request.current_data.as_ref(),
+    )?;
+    let (new_type, new_with_priority) = resolve_record_value(
+        &request.record_type,
+        &request.new_value,
+        request.priority,
+        request.new_data.as_ref(),
+    )?;
+    if !new_type.eq_ignore_ascii_case(&record_type) {
+        return Err(ApiError::InvalidRecord(format!(
+            "Current ({}) and new ({}) records must have the same type",
+            record_type, new_type
+        )));
+    }
 
     // Normalize record name to FQDN
     let fqdn = normalize_record_name(&request.name, &zone_name);
 
     debug!("Normalized record name: {} -> {}", request.name, fqdn);
 
-    // For MX and SRV records, prepend priority to values
-    let (current_with_priority, new_with_priority) = if let Some(priority) = request.priority {
-        if request.record_type.to_uppercase() == "MX" || request.record_type.to_uppercase() == "SRV"
-        {
-            (
-                format!("{} {}", priority, request.current_value),
-                format!("{} {}", priority, request.new_value),
-            )
-        } else {
-            (request.current_value.clone(), request.new_value.clone())
-        }
-    } else {
-        (request.current_value.clone(), request.new_value.clone())
-    };
+    let prerequisites = resolve_prerequisites(
+        &request.prerequisites,
+        &zone_name,
+        Some(&request.name),
+        Some(&record_type),
+    )?;
+
+    if query.dry_run {
+        let script = state.nsupdate.update_record_script(
+            &zone_name,
+            &prerequisites,
+            &fqdn,
+            request.ttl,
+            &record_type,
+            &current_with_priority,
+            &new_with_priority,
+        );
+        return Ok(Json(dry_run_response(&zone_name, "updated in", script)?));
+    }
 
     // Execute nsupdate
     let _output = state
         .nsupdate
         .update_record(
             &zone_name,
+            &prerequisites,
             &fqdn,
             request.ttl,
-            &request.record_type,
+            &record_type,
             &current_with_priority,
             &new_with_priority,
         )
@@ -524,7 +1075,7 @@ pub async fn update_record(
         .map_err(|e| {
             error!("nsupdate update failed: {}", e);
             metrics::record_record_operation("update", false);
-            ApiError::NsupdateError(format!("Failed to update record: {}", e))
+            update_error("update record", e)
         })?;
 
     info!("Record updated successfully in zone {}", zone_name);
@@ -537,11 +1088,88 @@ pub async fn update_record(
             "zone": zone_name,
             "record": {
                 "name": request.name,
-                "type": request.record_type,
-                "currentValue": request.current_value,
-                "newValue": request.new_value,
+                "type": record_type,
+                "currentValue": current_value,
+                "newValue": new_value,
                 "ttl": request.ttl,
             }
         })),
+        plan: None,
+    }))
+}
+
+/// List the records of a zone as served by BIND9
+///
+/// Reads the zone with a TSIG-signed AXFR, so the result includes changes made
+/// by dynamic updates that have not yet been written back to the zone file.
+#[utoipa::path(
+    get,
+    path = "/api/v1/zones/{zone_name}/records",
+    params(
+        ("zone_name" = String, Path, description = "Zone name"),
+        ListRecordsQuery
+    ),
+    responses(
+        (status = 200, description = "Zone records", body = RecordSetListResponse),
+        (status = 400, description = "Invalid zone name or filter"),
+        (status = 404, description = "Zone not found"),
+        (status = 500, description = "Zone transfer failed"),
+    ),
+    tag = "records"
+)]
+pub async fn list_records(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<ListRecordsQuery>,
+) -> Result<Json<RecordSetListResponse>, ApiError> {
+    info!(
+        "Listing records in zone {} (name: {:?}, type: {:?})",
+        zone_name, query.name, query.record_type
+    );
+
+    crate::zones::validate_zone_name(&zone_name)?;
+
+    let name_filter = match query.name.as_deref() {
+        Some(name) => {
+            validate_record_name(name)?;
+            Some(normalize_record_name(name, &zone_name))
+        }
+        None => None,
+    };
+    let type_filter = match query.record_type.as_deref() {
+        Some(record_type) => Some(
+            dns_wire::record_type_code(record_type)
+                .map(dns_wire::record_type_name)
+                .ok_or_else(|| {
+                    ApiError::InvalidRecord(format!("Invalid record type: {}", record_type))
+                })?,
+        ),
+        None => None,
+    };
+
+    // Confirm the zone exists so an unknown zone is a 404 rather than a
+    // transfer failure (BIND answers NOTAUTH for both).
+    state.rndc.showzone(&zone_name).await.map_err(|e| {
+        metrics::record_record_operation("list", false);
+        if e.to_string().contains("not found") {
+            ApiError::ZoneNotFound(zone_name.clone())
+        } else {
+            ApiError::RndcError(e.to_string())
+        }
+    })?;
+
+    let records = state.nsupdate.axfr(&zone_name).await.map_err(|e| {
+        error!("AXFR of zone {} failed: {:#}", zone_name, e);
+        metrics::record_record_operation("list", false);
+        ApiError::NsupdateError(format!("Failed to transfer zone: {}", e))
+    })?;
+
+    let rrsets = group_rrsets(records, name_filter.as_deref(), type_filter.as_deref());
+    metrics::record_record_operation("list", true);
+
+    Ok(Json(RecordSetListResponse {
+        zone: zone_name,
+        count: rrsets.len(),
+        rrsets,
     }))
 }
@@ -15,6 +15,8 @@ mod tests {
             value: "192.0.2.1".to_string(),
             ttl: 3600,
             priority: None,
+            data: None,
+            prerequisites: Vec::new(),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -30,6 +32,8 @@ mod tests {
             name: "www".to_string(),
             record_type: "A".to_string(),
             value: Some("192.0.2.1".to_string()),
+            data: None,
+            prerequisites: Vec::new(),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -44,6 +48,8 @@ mod tests {
             name: "www".to_string(),
             record_type: "A".to_string(),
             value: None,
+            data: None,
+            prerequisites: Vec::new(),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -62,6 +68,9 @@ mod tests {
             new_value: "192.0.2.2".to_string(),
             ttl: 7200,
             priority: None,
+            current_data: None,
+            new_data: None,
+            prerequisites: Vec::new(),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -70,6 +79,8 @@ mod tests {
         assert!(json.contains("\"currentValue\":\"192.0.2.1\""));
         assert!(json.contains("\"newValue\":\"192.0.2.2\""));
         assert!(json.contains("\"ttl\":7200"));
+        // No prerequisites, no field
+        assert!(!json.contains("prerequisites"));
     }
 
     #[test]
@@ -80,6 +91,8 @@ mod tests {
             value: "mail.example.com.".to_string(),
             ttl: 3600,
             priority: Some(10),
+            data: None,
+            prerequisites: Vec::new(),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -174,4 +187,656 @@ mod validation_security_tests {
             );
         }
     }
+
+    #[test]
+    fn test_group_rrsets_groups_and_filters() {
+        use crate::records::group_rrsets;
+        use crate::zone_file_parser::ResourceRecord;
+
+        let rr = |name: &str, record_type: &str, data: &str| ResourceRecord {
+            name: name.to_string(),
+            ttl: Some(300),
+            record_type: record_type.to_string(),
+            data: data.to_string(),
+        };
+        let records = vec![
+            rr(
+                "example.com.",
+                "SOA",
+                "ns1.example.com. admin.example.com. 1 2 3 4 5",
+            ),
+            rr("www.example.com.", "A", "192.0.2.1"),
+            rr("mail.example.com.", "A", "192.0.2.9"),
+            rr("WWW.example.com.", "A", "192.0.2.2"),
+            rr("www.example.com.", "AAAA", "2001:db8::1"),
+        ];
+
+        let all = group_rrsets(records.clone(), None, None);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[1].values, vec!["192.0.2.1", "192.0.2.2"]);
+
+        let filtered = group_rrsets(records, Some("www.example.com."), Some("A"));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].ttl, 300);
+        assert_eq!(filtered[0].values.len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod resolve_record_value_tests {
+    use crate::record_data::RecordData;
+    use crate::records::{resolve_record_value, AddRecordRequest};
+
+    #[test]
+    fn test_string_form_prepends_priority_for_mx_and_srv() {
+        assert_eq!(
+            resolve_record_value("MX", "mail.example.com.", Some(10), None).unwrap(),
+            ("MX".to_string(), "10 mail.example.com.".to_string())
+        );
+        assert_eq!(
+            resolve_record_value("A", "192.0.2.1", Some(10), None).unwrap(),
+            ("A".to_string(), "192.0.2.1".to_string())
+        );
+        assert!(resolve_record_value("A", "not-an-ip", None, None).is_err());
+        assert!(resolve_record_value("", "", None, None).is_err());
+    }
+
+    #[test]
+    fn test_string_form_srv_is_checked_with_its_priority() {
+        assert_eq!(
+            resolve_record_value("SRV", "5 443 web.example.com.", Some(10), None).unwrap(),
+            ("SRV".to_string(), "10 5 443 web.example.com.".to_string())
+        );
+        // Three fields are only complete with the priority
+        assert!(resolve_record_value("SRV", "5 443 web.example.com.", None, None).is_err());
+        assert!(resolve_record_value("SRV", "10 5 443 web.example.com.", Some(10), None).is_err());
+    }
+
+    #[test]
+    fn test_structured_form_renders_data() {
+        let request: AddRecordRequest = serde_json::from_str(
+            r#"{"name": "_https._tcp", "data": {"type": "SRV", "priority": 10, "weight": 5, "port": 443, "target": "web.example.com."}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.record_type, "");
+        assert_eq!(
+            resolve_record_value(
+                &request.record_type,
+                &request.value,
+                request.priority,
+                request.data.as_ref()
+            )
+            .unwrap(),
+            ("SRV".to_string(), "10 5 443 web.example.com.".to_string())
+        );
+
+        let data = RecordData::Cname {
+            target: "www.example.com.".to_string(),
+        };
+        assert!(resolve_record_value("cname", "", None, Some(&data)).is_ok());
+    }
+
+    #[test]
+    fn test_structured_form_rejects_conflicts() {
+        let data = RecordData::Mx {
+            preference: 10,
+            exchange: "mail.example.com.".to_string(),
+        };
+        // Type mismatch
+        assert!(resolve_record_value("A", "", None, Some(&data)).is_err());
+        // Value or priority alongside data
+        assert!(resolve_record_value("MX", "mail.example.com.", None, Some(&data)).is_err());
+        assert!(resolve_record_value("MX", "", Some(10), Some(&data)).is_err());
+        // Field validation
+        let bad = RecordData::Mx {
+            preference: 10,
+            exchange: "mail.example.com".to_string(),
+        };
+        assert!(resolve_record_value("", "", None, Some(&bad)).is_err());
+    }
+}
+
+#[cfg(test)]
+mod record_type_validation_tests {
+    use crate::records::{validate_record_type, validate_record_value};
+
+    const SHA256: &str = "2bb183af5f22588179a53b0a98631fad1a292118ef7a4a2b4e5b4c4d5e6f7a8b";
+
+    fn assert_valid(record_type: &str, values: &[&str]) {
+        for value in values {
+            assert!(
+                validate_record_value(record_type, value).is_ok(),
+                "expected {record_type} value {value:?} to be accepted: {:?}",
+                validate_record_value(record_type, value)
+            );
+        }
+    }
+
+    fn assert_invalid(record_type: &str, values: &[&str]) {
+        for value in values {
+            assert!(
+                validate_record_value(record_type, value).is_err(),
+                "expected {record_type} value {value:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_record_types_are_supported() {
+        for record_type in [
+            "DS", "DNSKEY", "TLSA", "SSHFP", "NAPTR", "SVCB", "HTTPS", "LOC", "URI", "DNAME",
+            "HINFO", "CERT", "https",
+        ] {
+            assert!(validate_record_type(record_type).is_ok(), "{record_type}");
+        }
+        assert!(validate_record_type("RRSIG").is_err());
+    }
+
+    #[test]
+    fn test_validate_ds() {
+        assert_valid(
+            "DS",
+            &[
+                &format!("60485 13 2 {}", SHA256),
+                &format!(
+                    "60485 ECDSAP256SHA256 2 {} {}",
+                    &SHA256[..32],
+                    &SHA256[32..]
+                ),
+                "2371 13 1 2bb183af5f22588179a53b0a98631fad1a292118",
+            ],
+        );
+        assert_invalid(
+            "DS",
+            &[
+                "60485 13 2",
+                "60485 13 2 abc",
+                &format!("70000 13 2 {}", SHA256),
+                &format!("60485 13 256 {}", SHA256),
+                &format!("60485 13 2 {}zz", &SHA256[..62]),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_dnskey() {
+        assert_valid("DNSKEY", &["257 3 13 mdsswUyr3DPW132mOi8V9xESWE8jTo0d xCjjnopKl+GqJxpVXckHAeF+KkxLbxILfDLUT0rAK9iUzy1L53eKGQ=="]);
+        assert_invalid(
+            "DNSKEY",
+            &[
+                "257 300 13 mdsswUyr3DPW132mOi8V9xESWE8jTo0d",
+                "257 3 13 not*base64",
+                "257 3 13",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_tlsa_and_sshfp() {
+        assert_valid("TLSA", &[&format!("3 1 1 {}", SHA256), "3 0 0 308201a2"]);
+        assert_invalid("TLSA", &["256 1 1 00", "3 1 1", "3 1 1 abc", "3 1 1 zz"]);
+
+        assert_valid(
+            "SSHFP",
+            &[
+                &format!("4 2 {}", SHA256),
+                "1 1 dd465c09cfa51fb45020cc83316fff21b9ec74ac",
+            ],
+        );
+        assert_invalid("SSHFP", &["256 2 00", "4 2", "4 2 0g"]);
+    }
+
+    #[test]
+    fn test_validate_naptr() {
+        assert_valid(
+            "NAPTR",
+            &[
+                r#"100 10 "S" "SIP+D2U" "" _sip._udp.example.com."#,
+                r#"100 10 "U" "E2U+sip" "!^.*$!sip:info@example.com!" ."#,
+            ],
+        );
+        assert_invalid(
+            "NAPTR",
+            &[
+                r#"100 10 "S" "SIP+D2U" "" _sip._udp.example.com"#,
+                r#"100 10 "S" "SIP+D2U" "" "_sip._udp.example.com.""#,
+                r#"100 10 "S" "SIP+D2U" "" . extra"#,
+                r#"100 10 "S" "SIP+D2U" """#,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_svcb_and_https() {
+        assert_valid(
+            "HTTPS",
+            &[
+                "1 . alpn=h2,h3 ipv4hint=192.0.2.1,192.0.2.2 ipv6hint=2001:db8::1",
+                "0 cdn.example.net.",
+                r#"1 svc.example.com. alpn="h3" port=8443 ech=AEX+DQBBAA== no-default-alpn"#,
+            ],
+        );
+        assert_valid(
+            "SVCB",
+            &["16 foo.example.org. mandatory=alpn alpn=h2 key65333=ex"],
+        );
+        assert_invalid(
+            "HTTPS",
+            &[
+                "1 svc.example.com alpn=h2",
+                "1 . port=99999",
+                "1 . ipv4hint=2001:db8::1",
+                "1 . alpn=h2 alpn=h3",
+                "1 . bogus=1",
+                "1 . mandatory=bogus",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_loc() {
+        assert_valid(
+            "LOC",
+            &[
+                "52 22 23.000 N 4 53 32.000 E -2.00m 0.00m 10000m 10m",
+                "42 21 54 N 71 06 18 W -24m 30m",
+                "90 S 180 W 0",
+            ],
+        );
+        assert_invalid(
+            "LOC",
+            &[
+                "91 N 0 E 0m",
+                "52 60 N 4 E 0m",
+                "52 N 4 E",
+                "52 N 181 E 0m",
+                "52 N 4 E 0m 1m 1m 1m 1m",
+                "52 X 4 E 0m",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_uri_dname_hinfo_cert() {
+        assert_valid("URI", &[r#"10 1 "https://www.example.com/path""#]);
+        assert_invalid(
+            "URI",
+            &[
+                r#"10 1 "https://x" extra"#,
+                r#"70000 1 "https://x""#,
+                r#"10 "https://x""#,
+            ],
+        );
+
+        assert_valid("DNAME", &["target.example.net."]);
+        assert_invalid("DNAME", &["target.example.net"]);
+
+        assert_valid("HINFO", &[r#""INTEL-386" "Linux""#, "RFC8482 \"\""]);
+        assert_invalid("HINFO", &[r#""INTEL-386""#, "a b c"]);
+
+        assert_valid(
+            "CERT",
+            &[
+                "PKIX 0 0 MIIBIjANBgkqhkiG9w0B",
+                "1 12345 RSASHA256 MIIB IjAN",
+            ],
+        );
+        assert_invalid("CERT", &["BOGUS 0 0 MIIB", "PKIX 0 0 !!", "PKIX 0 0"]);
+    }
+
+    #[test]
+    fn test_transferred_values_are_accepted() {
+        // What an AXFR renders (and an export returns) can be posted back
+        for (record_type, value) in [
+            ("DS", format!("60485 ECDSAP256SHA256 2 {}", SHA256)),
+            (
+                "DNSKEY",
+                "257 3 13 mdsswUyr3DPW132mOi8V9xESWE8jTo0d".to_string(),
+            ),
+            ("TLSA", format!("3 1 1 {}", SHA256)),
+            ("SSHFP", format!("4 2 {}", SHA256)),
+            (
+                "NAPTR",
+                r#"100 10 "U" "E2U+sip" "!^.*$!sip:info@example.com!" ."#.to_string(),
+            ),
+            (
+                "HTTPS",
+                "1 . alpn=h2,h3 port=8443 ech=AEX+DQBBAA== key65333=ex".to_string(),
+            ),
+            ("LOC", "42 21 54 N 71 06 18 W -24m 30m".to_string()),
+            ("URI", r#"10 1 "https://www.example.com/path""#.to_string()),
+            ("HINFO", "RFC8482 \"\"".to_string()),
+            ("CERT", "1 12345 RSASHA256 MIIB IjAN".to_string()),
+        ] {
+            let code = crate::dns_wire::record_type_code(record_type).unwrap();
+            let transferred = crate::dns_wire::canonical_rdata(code, &value).unwrap();
+            assert!(!transferred.starts_with("\\#"), "{}", transferred);
+            assert_valid(record_type, &[&transferred]);
+        }
+    }
+
+    #[test]
+    fn test_validate_srv_string_form() {
+        assert_valid(
+            "SRV",
+            &[
+                "10 5 443 web.example.com.",
+                "0 0 0 .",
+                "65535 65535 65535 x.example.",
+            ],
+        );
+        assert_invalid(
+            "SRV",
+            &[
+                "foo",
+                "10 5 443",
+                "10 5 443 web.example.com",
+                "10 5 65536 web.example.com.",
+                "-1 5 443 web.example.com.",
+                "10 5 443 \"web.example.com.\"",
+                "10 5 443 web.example.com. extra",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_caa_string_form() {
+        assert_valid(
+            "CAA",
+            &[
+                "0 issue \"letsencrypt.org\"",
+                "128 issuewild \";\"",
+                "0 iodef \"mailto:security@example.com\"",
+                "0 issue letsencrypt.org",
+            ],
+        );
+        assert_invalid(
+            "CAA",
+            &[
+                "7 issue \"letsencrypt.org\"",
+                "256 issue \"ca\"",
+                "0 \"issue\" \"ca\"",
+                "0 is-sue \"ca\"",
+                "0 iodef \"security@example.com\"",
+                "0 issue",
+                "0 issue \"ca\" extra",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_txt_string_form() {
+        let long = "x".repeat(256);
+        assert_valid(
+            "TXT",
+            &[
+                "v=spf1 -all",
+                "\"v=DKIM1; k=rsa\" \"p=MIIB\"",
+                &format!("\"{}\" \"{}\"", &long[1..], &long[1..]),
+            ],
+        );
+        assert_invalid("TXT", &["\"unterminated", "dangling\\", &long]);
+    }
+
+    #[test]
+    fn test_structured_values_reject_zone_file_metacharacters() {
+        // An unquoted `;` or parenthesis would comment out or extend the
+        // zone-file line the record is rendered into.
+        assert_invalid("HINFO", &["cpu ;os", "(cpu os", r#""cpu" "os"#, "cpu os\\"]);
+        assert_valid("HINFO", &[r#""cpu;(x)" "os""#]);
+    }
+}
+
+#[cfg(test)]
+mod prerequisite_tests {
+    use crate::nsupdate::{NsupdateExecutor, Prerequisite, PrerequisiteFailed};
+    use crate::records::{
+        resolve_prerequisites, update_error, RecordPrerequisite, UpdateRecordRequest,
+        MAX_PREREQUISITES,
+    };
+    use crate::types::ApiError;
+
+    fn prerequisites(json: &str) -> Vec<RecordPrerequisite> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_prerequisites_default_to_the_record() {
+        let request: UpdateRecordRequest = serde_json::from_str(
+            r#"{"name": "www", "type": "A", "currentValue": "192.0.2.1", "newValue": "192.0.2.2",
+                "prerequisites": [{"condition": "yxrrset", "value": "192.0.2.1"}]}"#,
+        )
+        .unwrap();
+        let resolved = resolve_prerequisites(
+            &request.prerequisites,
+            "example.com",
+            Some("www"),
+            Some("A"),
+        )
+        .unwrap();
+        assert_eq!(
+            resolved,
+            [Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: "192.0.2.1".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_prerequisites_with_explicit_name_type_and_data() {
+        let resolved = resolve_prerequisites(
+            &prerequisites(
+                r#"[
+                    {"condition": "nxrrset", "name": "www", "type": "CNAME"},
+                    {"condition": "yxrrset", "name": "@", "data": {"type": "MX", "preference": 10, "exchange": "mail.example.com."}},
+                    {"condition": "yxrrset", "name": "@", "type": "NS"},
+                    {"condition": "yxdomain", "name": "api.example.com."},
+                    {"condition": "nxdomain"}
+                ]"#,
+            ),
+            "example.com",
+            Some("new"),
+            Some("A"),
+        )
+        .unwrap();
+        assert_eq!(
+            resolved,
+            [
+                Prerequisite::RrsetNotExists {
+                    name: "www.example.com.".to_string(),
+                    record_type: "CNAME".to_string(),
+                },
+                Prerequisite::RrsetExists {
+                    name: "example.com.".to_string(),
+                    record_type: "MX".to_string(),
+                    value: "10 mail.example.com.".to_string(),
+                },
+                Prerequisite::RrsetExists {
+                    name: "example.com.".to_string(),
+                    record_type: "NS".to_string(),
+                    value: String::new(),
+                },
+                Prerequisite::NameInUse {
+                    name: "api.example.com.".to_string(),
+                },
+                Prerequisite::NameNotInUse {
+                    name: "new.example.com.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yxrrset_values_cover_a_multi_record_rrset() {
+        // Swap one address of a three-address RRset, provided the RRset
+        // still holds exactly the addresses the caller last read
+        let request: UpdateRecordRequest = serde_json::from_str(
+            r#"{"name": "www", "type": "A", "currentValue": "192.0.2.2", "newValue": "192.0.2.4",
+                "prerequisites": [{"condition": "yxrrset",
+                                   "values": ["192.0.2.1", "192.0.2.2", "192.0.2.3"]}]}"#,
+        )
+        .unwrap();
+        let resolved = resolve_prerequisites(
+            &request.prerequisites,
+            "example.com",
+            Some("www"),
+            Some("A"),
+        )
+        .unwrap();
+        let expected: Vec<_> = ["192.0.2.1", "192.0.2.2", "192.0.2.3"]
+            .into_iter()
+            .map(|value| Prerequisite::RrsetExists {
+                name: "www.example.com.".to_string(),
+                record_type: "A".to_string(),
+                value: value.to_string(),
+            })
+            .collect();
+        assert_eq!(resolved, expected);
+
+        // Each value becomes one prerequisite record of the same RRset
+        let executor =
+            NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap();
+        let script = executor
+            .update_record_script(
+                "example.com",
+                &resolved,
+                "www.example.com.",
+                300,
+                "A",
+                "192.0.2.2",
+                "192.0.2.4",
+            )
+            .unwrap();
+        assert!(script.contains(
+            "prereq yxrrset www.example.com. IN A 192.0.2.1\n\
+             prereq yxrrset www.example.com. IN A 192.0.2.2\n\
+             prereq yxrrset www.example.com. IN A 192.0.2.3\n\
+             update delete www.example.com. A 192.0.2.2\n"
+        ));
+
+        let too_many = prerequisites(&format!(
+            r#"[{{"condition": "yxrrset", "values": [{}]}}]"#,
+            vec![r#""192.0.2.1""#; MAX_PREREQUISITES + 1].join(", ")
+        ));
+        assert!(resolve_prerequisites(&too_many, "example.com", Some("www"), Some("A")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_prerequisites_are_rejected() {
+        for (json, record_name, record_type) in [
+            // No name to default to, as in a batch
+            (r#"[{"condition": "yxdomain"}]"#, None, None),
+            // No type to default to
+            (r#"[{"condition": "nxrrset", "name": "www"}]"#, None, None),
+            // Only yxrrset takes a value
+            (
+                r#"[{"condition": "nxrrset", "value": "192.0.2.1"}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            // Name conditions take no type
+            (
+                r#"[{"condition": "nxdomain", "type": "A"}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            (
+                r#"[{"condition": "nxrrset", "values": ["192.0.2.1"]}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            // Either one record or the whole RRset
+            (
+                r#"[{"condition": "yxrrset", "value": "192.0.2.1", "values": ["192.0.2.2"]}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            // Values are validated like record values
+            (
+                r#"[{"condition": "yxrrset", "value": "not-an-ip"}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            (
+                r#"[{"condition": "yxrrset", "values": ["192.0.2.1", "not-an-ip"]}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            (
+                r#"[{"condition": "yxrrset", "value": "192.0.2.1\nupdate add evil"}]"#,
+                Some("www"),
+                Some("A"),
+            ),
+            (
+                r#"[{"condition": "yxdomain", "name": "bad name"}]"#,
+                None,
+                None,
+            ),
+        ] {
+            let result = resolve_prerequisites(
+                &prerequisites(json),
+                "example.com",
+                record_name,
+                record_type,
+            );
+            assert!(
+                matches!(result, Err(ApiError::InvalidRecord(_))),
+                "{} should be rejected",
+                json
+            );
+        }
+
+        let too_many =
+            vec![prerequisites(r#"[{"condition": "yxdomain"}]"#)[0].clone(); MAX_PREREQUISITES + 1];
+        assert!(resolve_prerequisites(&too_many, "example.com", Some("www"), None).is_err());
+    }
+
+    #[test]
+    fn test_errors_name_the_prerequisite() {
+        let Err(ApiError::InvalidRecord(message)) = resolve_prerequisites(
+            &prerequisites(r#"[{"condition": "yxdomain"}, {"condition": "nxrrset"}]"#),
+            "example.com",
+            Some("www"),
+            None,
+        ) else {
+            panic!("expected an invalid record error");
+        };
+        assert!(message.starts_with("prerequisites[1]: "), "{}", message);
+    }
+
+    #[test]
+    fn test_update_error_maps_prerequisite_failures() {
+        assert!(matches!(
+            update_error("update record", PrerequisiteFailed::RrsetExists.into()),
+            ApiError::RecordConflict(_)
+        ));
+        assert!(matches!(
+            update_error("update record", PrerequisiteFailed::NameExists.into()),
+            ApiError::RecordConflict(_)
+        ));
+        assert!(matches!(
+            update_error("update record", PrerequisiteFailed::RrsetMissing.into()),
+            ApiError::PreconditionFailed(_)
+        ));
+        assert!(matches!(
+            update_error("update record", PrerequisiteFailed::NameMissing.into()),
+            ApiError::PreconditionFailed(_)
+        ));
+        assert!(matches!(
+            update_error("update record", anyhow::anyhow!("nsupdate failed: REFUSED")),
+            ApiError::NsupdateError(_)
+        ));
+        assert!(matches!(
+            update_error(
+                "update record",
+                crate::dns_update::UpdateOutcomeUnknown {
+                    failure: PrerequisiteFailed::RrsetMissing
+                }
+                .into()
+            ),
+            ApiError::UpdateOutcomeUnknown(_)
+        ));
+    }
 }
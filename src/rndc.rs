@@ -15,7 +15,7 @@ use rndc::RndcClient;
 use std::time::Instant;
 use tracing::{debug, error, info};
 
-use crate::metrics;
+use crate::{metrics, rndc_types::DumpScope};
 
 /// RNDC configuration parsed from rndc.conf
 #[derive(Clone)]
@@ -185,6 +185,75 @@ impl RndcExecutor {
         self.execute("status").await
     }
 
+    /// Dump the contents of all zones to BIND's dump file (`rndc dumpdb -zones`)
+    ///
+    /// BIND writes the file asynchronously; it is complete once it ends with
+    /// `; Dump complete`.
+    pub async fn dumpdb_zones(&self) -> Result<String> {
+        self.execute("dumpdb -zones").await
+    }
+
+    /// Dump the server's state to BIND's dump file (`rndc dumpdb`)
+    ///
+    /// Like [`Self::dumpdb_zones`], the file is written asynchronously.
+    pub async fn dumpdb(&self, scope: DumpScope) -> Result<String> {
+        let command = format!("dumpdb {}", scope.flag());
+        self.execute(&command).await
+    }
+
+    /// Append the server statistics to BIND's statistics file (`rndc stats`)
+    pub async fn stats(&self) -> Result<String> {
+        self.execute("stats").await
+    }
+
+    /// Reload the configuration file and load new zones (`rndc reconfig`)
+    pub async fn reconfig(&self) -> Result<String> {
+        self.execute("reconfig").await
+    }
+
+    /// Reload the configuration file and all zones (`rndc reload`)
+    pub async fn reload_all(&self) -> Result<String> {
+        self.execute("reload").await
+    }
+
+    /// Flush the server's caches, or those of one view
+    pub async fn flush(&self, view: Option<&str>) -> Result<String> {
+        self.execute(&with_view("flush".to_string(), view)?).await
+    }
+
+    /// Flush a name from the cache, or from one view's cache
+    pub async fn flushname(&self, name: &str, view: Option<&str>) -> Result<String> {
+        validate_rndc_zone_name(name)?;
+        self.execute(&with_view(format!("flushname {}", name), view)?)
+            .await
+    }
+
+    /// Flush a name and everything below it from the cache
+    pub async fn flushtree(&self, name: &str, view: Option<&str>) -> Result<String> {
+        validate_rndc_zone_name(name)?;
+        self.execute(&with_view(format!("flushtree {}", name), view)?)
+            .await
+    }
+
+    /// Write a zone's journal to its zone file (`rndc sync [-clean] <zone>`)
+    ///
+    /// With `clean`, the journal file is removed afterwards.
+    pub async fn sync(&self, zone_name: &str, clean: bool) -> Result<String> {
+        validate_rndc_zone_name(zone_name)?;
+        let command = if clean {
+            format!("sync -clean {}", zone_name)
+        } else {
+            format!("sync {}", zone_name)
+        };
+        self.execute(&command).await
+    }
+
+    /// Write the journals of all dynamic zones to their zone files and remove
+    /// the journals (`rndc sync -clean`)
+    pub async fn sync_clean(&self) -> Result<String> {
+        self.execute("sync -clean").await
+    }
+
     /// Add a zone
     ///
     /// # Arguments
@@ -273,6 +342,17 @@ impl RndcExecutor {
     }
 }
 
+/// Append an optional view name to a command, validated like a zone name.
+fn with_view(command: String, view: Option<&str>) -> Result<String> {
+    match view {
+        Some(view) => {
+            validate_rndc_zone_name(view)?;
+            Ok(format!("{} {}", command, view))
+        }
+        None => Ok(command),
+    }
+}
+
 impl Clone for RndcExecutor {
     fn clone(&self) -> Self {
         Self {
@@ -16,14 +16,16 @@
 //! ```
 
 use crate::rndc_types::{
-    AutoDnssecMode, CheckNamesMode, DnsClass, ForwardMode, ForwarderSpec, MasterfileFormat,
-    NotifyMode, PrimarySpec, ZoneConfig, ZoneType,
+    AddressMatchElement, AutoDnssecMode, CheckNamesMode, ClientCount, DnsClass, DumpedZone,
+    ForwardMode, ForwarderSpec, MasterfileFormat, NotifyMode, PrimarySpec, ServerStatus, StatsDump,
+    StatsSection, UpdatePolicy, UpdatePolicyAction, UpdatePolicyRule, UpdatePolicyRuleType,
+    ZoneConfig, ZoneStatus, ZoneType,
 };
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until, take_while1},
-    character::complete::{char, multispace0},
-    combinator::{map, opt, recognize},
+    character::complete::{char, digit1, multispace0},
+    combinator::{all_consuming, map, opt, recognize},
     multi::many0,
     sequence::{delimited, preceded, terminated},
     IResult, Parser,
@@ -128,11 +130,83 @@ pub(crate) fn ip_with_port(input: &str) -> IResult<&str, PrimarySpec> {
     ))
 }
 
-/// Parse a list of IP addresses: { addr; addr; }
-fn ip_list(input: &str) -> IResult<&str, Vec<IpAddr>> {
+/// Parse an address or CIDR prefix: 192.0.2.1, 10.244.0.0/16
+///
+/// A prefix must not have host bits set, as BIND rejects `10.1.2.3/8`.
+fn address_prefix(input: &str) -> IResult<&str, AddressMatchElement> {
+    let verify_error =
+        || nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify));
+
+    let (rest, addr_str) =
+        take_while1(|c: char| c.is_ascii_hexdigit() || c == '.' || c == ':')(input)?;
+    let address: IpAddr = addr_str.parse().map_err(|_| verify_error())?;
+
+    let (rest, len) = opt(preceded(char('/'), digit1)).parse(rest)?;
+    let Some(len) = len else {
+        return Ok((rest, AddressMatchElement::Address(address)));
+    };
+    let len: u8 = len.parse().map_err(|_| verify_error())?;
+    let host_bits_clear = match address {
+        IpAddr::V4(v4) if len <= 32 => u32::from(v4).checked_shl(u32::from(len)).unwrap_or(0) == 0,
+        IpAddr::V6(v6) if len <= 128 => {
+            u128::from(v6).checked_shl(u32::from(len)).unwrap_or(0) == 0
+        }
+        _ => false,
+    };
+    if !host_bits_clear {
+        return Err(verify_error());
+    }
+    Ok((rest, AddressMatchElement::Prefix { address, len }))
+}
+
+/// Parse a key name (`"name"` or `name`) or a named ACL
+fn key_or_acl(input: &str) -> IResult<&str, AddressMatchElement> {
+    let (rest, name) = identifier(input)?;
+    if name != "key" {
+        return Ok((rest, AddressMatchElement::Acl(name.to_string())));
+    }
+
+    let (rest, key) = ws(alt((
+        quoted_string,
+        map(
+            take_while1(|c: char| c.is_alphanumeric() || "._-".contains(c)),
+            str::to_string,
+        ),
+    )))
+    .parse(rest)?;
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+    {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            rest,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    Ok((rest, AddressMatchElement::Key(key)))
+}
+
+/// Parse an address match list element: 10.0.0.0/8, !192.0.2.1, key "name",
+/// any, or a nested { ... } list
+fn address_match_element(input: &str) -> IResult<&str, AddressMatchElement> {
+    alt((
+        map(
+            preceded(ws(char('!')), address_match_element),
+            AddressMatchElement::negated,
+        ),
+        map(address_match_list, AddressMatchElement::List),
+        ws(address_prefix),
+        ws(key_or_acl),
+    ))
+    .parse(input)
+}
+
+/// Parse an address match list: { 10.0.0.0/8; !192.0.2.1; key "name"; }
+fn address_match_list(input: &str) -> IResult<&str, Vec<AddressMatchElement>> {
     delimited(
         ws(char('{')),
-        many0(terminated(ws(ip_addr), semicolon)),
+        many0(terminated(address_match_element, semicolon)),
         ws(char('}')),
     )
     .parse(input)
@@ -160,16 +234,15 @@ enum ZoneStatement {
 
     // Primary/Secondary
     Primaries(Vec<PrimarySpec>),
-    AlsoNotify(Vec<IpAddr>),
+    AlsoNotify(Vec<PrimarySpec>),
     Notify(NotifyMode),
 
     // Access Control
-    AllowQuery(Vec<IpAddr>),
-    AllowTransfer(Vec<IpAddr>),
-    AllowUpdate(Vec<IpAddr>),
-    AllowUpdateRaw(String),
-    AllowUpdateForwarding(Vec<IpAddr>),
-    AllowNotify(Vec<IpAddr>),
+    AllowQuery(Vec<AddressMatchElement>),
+    AllowTransfer(Vec<AddressMatchElement>),
+    AllowUpdate(Vec<AddressMatchElement>),
+    AllowUpdateForwarding(Vec<AddressMatchElement>),
+    AllowNotify(Vec<AddressMatchElement>),
 
     // Transfer Control
     MaxTransferTimeIn(u32),
@@ -182,7 +255,7 @@ enum ZoneStatement {
     NotifySourceV6(IpAddr),
 
     // Dynamic Updates
-    UpdatePolicy(String),
+    UpdatePolicy(UpdatePolicy),
     Journal(String),
     IxfrFromDifferences(bool),
 
@@ -249,83 +322,147 @@ fn parse_primaries_statement(input: &str) -> IResult<&str, ZoneStatement> {
     Ok((input, ZoneStatement::Primaries(primaries)))
 }
 
-/// Parse also-notify statement: also-notify { addr; addr; };
+/// Parse also-notify statement: also-notify { addr; addr port 5353; };
 fn parse_also_notify_statement(input: &str) -> IResult<&str, ZoneStatement> {
     let (input, _) = ws(tag("also-notify")).parse(input)?;
-    let (input, addrs) = ip_list(input)?;
+    let (input, addrs) = primary_list(input)?;
     let (input, _) = semicolon(input)?;
     Ok((input, ZoneStatement::AlsoNotify(addrs)))
 }
 
-/// Parse allow-transfer statement: allow-transfer { addr; addr; };
-fn parse_allow_transfer_statement(input: &str) -> IResult<&str, ZoneStatement> {
-    let (input, _) = ws(tag("allow-transfer")).parse(input)?;
-    let (input, addrs) = ip_list(input)?;
-    let (input, _) = semicolon(input)?;
-    Ok((input, ZoneStatement::AllowTransfer(addrs)))
+/// Strip the quotes BIND may print around a string
+fn unquote(token: &str) -> String {
+    token
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(token)
+        .to_string()
 }
 
-/// Parse allow-update statement: allow-update { addr; addr; }; or allow-update { key "name"; };
-/// Captures both IP addresses and raw directive for key-based updates
-fn parse_allow_update_statement(input: &str) -> IResult<&str, ZoneStatement> {
-    let (input, _) = ws(tag("allow-update")).parse(input)?;
-
-    // Capture the start position to extract raw content
-    let start_input = input;
+/// Build an update-policy rule from its tokens:
+/// `( grant | deny ) identity ruletype [ name ] [ types ]`
+fn update_policy_rule_from_tokens(tokens: &[&str]) -> Option<UpdatePolicyRule> {
+    let [action, identity, rule_type, rest @ ..] = tokens else {
+        return None;
+    };
+    let rule_type = UpdatePolicyRuleType::parse(rule_type)?;
+    let (name, types) = if rule_type.takes_name() {
+        let (name, types) = rest.split_first()?;
+        (Some(unquote(name)), types)
+    } else {
+        (None, rest)
+    };
 
-    // Parse the content between braces
-    let (input, _) = ws(char('{')).parse(input)?;
+    Some(UpdatePolicyRule {
+        action: UpdatePolicyAction::parse(action)?,
+        identity: unquote(identity),
+        rule_type,
+        name,
+        types: types.iter().map(|t| t.to_string()).collect(),
+    })
+}
 
-    // Collect IP addresses and check for key references
-    let mut addrs = Vec::new();
-    let mut has_key_ref = false;
-    let mut remaining = input;
+/// Parse one update-policy rule: grant key-name name host.example.com. A TXT
+fn update_policy_rule(input: &str) -> IResult<&str, UpdatePolicyRule> {
+    let (rest, body) = ws(take_while1(|c: char| c != ';' && c != '}')).parse(input)?;
+    let tokens: Vec<&str> = body.split_whitespace().collect();
+    let rule = update_policy_rule_from_tokens(&tokens).ok_or_else(|| {
+        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+    })?;
+    Ok((rest, rule))
+}
 
-    loop {
-        // Skip whitespace
-        let (input, _) = multispace0(remaining)?;
+/// Parse update-policy statement: update-policy local; or
+/// update-policy { grant key-name zonesub TXT; };
+fn parse_update_policy_statement(input: &str) -> IResult<&str, ZoneStatement> {
+    let (input, _) = ws(tag("update-policy")).parse(input)?;
+    let (input, policy) = alt((
+        map(ws(tag("local")), |_| UpdatePolicy::Local),
+        map(
+            delimited(
+                ws(char('{')),
+                many0(terminated(update_policy_rule, semicolon)),
+                ws(char('}')),
+            ),
+            UpdatePolicy::Rules,
+        ),
+    ))
+    .parse(input)?;
+    let (input, _) = semicolon(input)?;
+    Ok((input, ZoneStatement::UpdatePolicy(policy)))
+}
 
-        // Check if we've reached the closing brace
-        if let Ok((input, _)) = char::<_, nom::error::Error<&str>>('}')(input) {
-            remaining = input;
-            break;
+/// Parse an address match list option: allow-transfer { 10.0.0.0/8; key "name"; };
+fn parse_match_list_statement(input: &str) -> IResult<&str, ZoneStatement> {
+    let (rest, name) = ws(identifier).parse(input)?;
+    let statement: fn(Vec<AddressMatchElement>) -> ZoneStatement = match name {
+        "allow-query" => ZoneStatement::AllowQuery,
+        "allow-transfer" => ZoneStatement::AllowTransfer,
+        "allow-update" => ZoneStatement::AllowUpdate,
+        "allow-notify" => ZoneStatement::AllowNotify,
+        "allow-update-forwarding" => ZoneStatement::AllowUpdateForwarding,
+        _ => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            )))
         }
+    };
+    let (rest, elements) = address_match_list(rest)?;
+    let (rest, _) = semicolon(rest)?;
+    Ok((rest, statement(elements)))
+}
 
-        // Check for "key" keyword
-        if let Ok((input, _)) = ws(tag("key")).parse(input) {
-            has_key_ref = true;
-            // Skip to the next semicolon
-            let (input, _) = take_until(";")(input)?;
-            let (input, _) = char(';')(input)?;
-            remaining = input;
-        } else if let Ok((input, addr)) = ip_addr(input) {
-            // Try to parse an IP address
-            addrs.push(addr);
-            // Consume the semicolon
-            let (input, _) = semicolon(input)?;
-            remaining = input;
-        } else {
-            // Skip to the next semicolon (handles other unknown statements)
-            let (input, _) = take_until(";")(input)?;
-            let (input, _) = char(';')(input)?;
-            remaining = input;
-        }
+/// Parse a BIND boolean
+fn yes_no(value: &str) -> Option<bool> {
+    match value {
+        "yes" | "true" => Some(true),
+        "no" | "false" => Some(false),
+        _ => None,
     }
+}
 
-    let (input, _) = semicolon(remaining)?;
-
-    // If we found key references, capture the raw directive
-    if has_key_ref {
-        // Extract raw content from start to end
-        let raw_len = start_input.len() - input.len();
-        let raw_content = &start_input[..raw_len];
-        Ok((
-            input,
-            ZoneStatement::AllowUpdateRaw(raw_content.to_string()),
-        ))
-    } else {
-        Ok((input, ZoneStatement::AllowUpdate(addrs)))
-    }
+/// Parse a single-value option: notify explicit; max-zone-ttl 86400;
+///
+/// Values this parser does not type (`transfer-source 192.0.2.1 port 53`,
+/// `max-zone-ttl unlimited`) fail here and are kept verbatim by
+/// [`parse_unknown_statement`].
+fn parse_option_statement(input: &str) -> IResult<&str, ZoneStatement> {
+    let (rest, name) = ws(identifier).parse(input)?;
+    let (rest, value) = ws(take_while1(|c: char| {
+        !c.is_whitespace() && c != ';' && c != '{'
+    }))
+    .parse(rest)?;
+    let (rest, _) = semicolon(rest)?;
+
+    let statement = match name {
+        "notify" => NotifyMode::parse(value).map(ZoneStatement::Notify),
+        "max-transfer-time-in" => value.parse().ok().map(ZoneStatement::MaxTransferTimeIn),
+        "max-transfer-time-out" => value.parse().ok().map(ZoneStatement::MaxTransferTimeOut),
+        "max-transfer-idle-in" => value.parse().ok().map(ZoneStatement::MaxTransferIdleIn),
+        "max-transfer-idle-out" => value.parse().ok().map(ZoneStatement::MaxTransferIdleOut),
+        "transfer-source" => value.parse().ok().map(ZoneStatement::TransferSource),
+        "transfer-source-v6" => value.parse().ok().map(ZoneStatement::TransferSourceV6),
+        "notify-source" => value.parse().ok().map(ZoneStatement::NotifySource),
+        "notify-source-v6" => value.parse().ok().map(ZoneStatement::NotifySourceV6),
+        "ixfr-from-differences" => yes_no(value).map(ZoneStatement::IxfrFromDifferences),
+        "check-names" => CheckNamesMode::parse(value).map(ZoneStatement::CheckNames),
+        "check-mx" => CheckNamesMode::parse(value).map(ZoneStatement::CheckMx),
+        "check-integrity" => yes_no(value).map(ZoneStatement::CheckIntegrity),
+        "max-zone-ttl" => value.parse().ok().map(ZoneStatement::MaxZoneTtl),
+        "max-refresh-time" => value.parse().ok().map(ZoneStatement::MaxRefreshTime),
+        "min-refresh-time" => value.parse().ok().map(ZoneStatement::MinRefreshTime),
+        "max-retry-time" => value.parse().ok().map(ZoneStatement::MaxRetryTime),
+        "min-retry-time" => value.parse().ok().map(ZoneStatement::MinRetryTime),
+        "multi-master" => yes_no(value).map(ZoneStatement::MultiMaster),
+        "request-ixfr" => yes_no(value).map(ZoneStatement::RequestIxfr),
+        "request-expire" => yes_no(value).map(ZoneStatement::RequestExpire),
+        _ => None,
+    };
+
+    statement.map(|statement| (rest, statement)).ok_or_else(|| {
+        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+    })
 }
 
 /// Parse an unknown/generic zone statement (catch-all)
@@ -365,8 +502,9 @@ fn parse_zone_statement(input: &str) -> IResult<&str, ZoneStatement> {
         parse_file_statement,
         parse_primaries_statement,
         parse_also_notify_statement,
-        parse_allow_transfer_statement,
-        parse_allow_update_statement,
+        parse_match_list_statement,
+        parse_update_policy_statement,
+        parse_option_statement,
         // Catch-all for unknown options (must be last)
         parse_unknown_statement,
     ))
@@ -421,7 +559,6 @@ fn parse_zone_config_internal(input: &str) -> IResult<&str, ZoneConfig> {
             ZoneStatement::AllowQuery(a) => config.allow_query = Some(a),
             ZoneStatement::AllowTransfer(a) => config.allow_transfer = Some(a),
             ZoneStatement::AllowUpdate(a) => config.allow_update = Some(a),
-            ZoneStatement::AllowUpdateRaw(raw) => config.allow_update_raw = Some(raw),
             ZoneStatement::AllowUpdateForwarding(a) => config.allow_update_forwarding = Some(a),
             ZoneStatement::AllowNotify(a) => config.allow_notify = Some(a),
 
@@ -479,6 +616,29 @@ fn parse_zone_config_internal(input: &str) -> IResult<&str, ZoneConfig> {
     Ok((input, config))
 }
 
+/// Parse a single address match list element in BIND syntax
+///
+/// # Examples
+///
+/// ```rust
+/// use bindcar::rndc_parser::parse_address_match_element;
+/// use bindcar::rndc_types::AddressMatchElement;
+///
+/// let element = parse_address_match_element("!10.244.0.0/16").unwrap();
+/// assert_eq!(element.to_string(), "!10.244.0.0/16");
+///
+/// let key = parse_address_match_element(r#"key "update-key""#).unwrap();
+/// assert_eq!(key, AddressMatchElement::Key("update-key".to_string()));
+/// ```
+pub fn parse_address_match_element(input: &str) -> ParseResult<AddressMatchElement> {
+    all_consuming(address_match_element)
+        .parse(input)
+        .map(|(_, element)| element)
+        .map_err(|_| {
+            RndcParseError::ParseError(format!("Invalid address match element: {:?}", input))
+        })
+}
+
 /// Parse `rndc showzone` output
 ///
 /// # Examples
@@ -499,3 +659,333 @@ pub fn parse_showzone(input: &str) -> ParseResult<ZoneConfig> {
         Err(nom::Err::Incomplete(_)) => Err(RndcParseError::Incomplete),
     }
 }
+
+/// Convert a date as BIND prints it (`Tue, 14 Jan 2025 10:00:00 GMT`) to
+/// RFC 3339, keeping the text as-is if it cannot be read.
+fn bind_time(value: &str) -> String {
+    chrono::DateTime::parse_from_rfc2822(value)
+        .map(|time| {
+            time.with_timezone(&chrono::Utc)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        })
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// Parse `rndc zonestatus` output
+///
+/// The output is a list of `key: value` lines; unknown keys are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use bindcar::rndc_parser::parse_zonestatus;
+///
+/// let output = "name: example.com\ntype: primary\nserial: 2025010101\nlast loaded: Tue, 14 Jan 2025 10:00:00 GMT\ndynamic: yes\nfrozen: no\n";
+/// let status = parse_zonestatus(output).unwrap();
+/// assert_eq!(status.serial, Some(2025010101));
+/// assert_eq!(status.last_loaded.as_deref(), Some("2025-01-14T10:00:00Z"));
+/// assert!(status.dynamic && !status.frozen);
+/// ```
+pub fn parse_zonestatus(input: &str) -> ParseResult<ZoneStatus> {
+    let mut status = ZoneStatus::default();
+    let mut has_name = false;
+
+    for line in input.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let yes = value == "yes";
+        match key.trim() {
+            "name" => {
+                status.name = value.to_string();
+                has_name = true;
+            }
+            "type" => status.zone_type = ZoneType::parse(value),
+            "files" => {
+                status.files = value
+                    .split(',')
+                    .flat_map(str::split_whitespace)
+                    .map(str::to_string)
+                    .collect()
+            }
+            "serial" => {
+                status.serial = value.parse().ok();
+                status.loaded |= status.serial.is_some();
+            }
+            "loaded serial" => {
+                status.loaded_serial = value.parse().ok();
+                status.loaded = true;
+            }
+            "nodes" => status.nodes = value.parse().ok(),
+            "last loaded" => {
+                status.last_loaded = Some(bind_time(value));
+                status.loaded = true;
+            }
+            "secure" => status.secure = yes,
+            "inline signing" => status.inline_signing = yes,
+            "key maintenance" => status.key_maintenance = Some(value.to_string()),
+            "next key event" => status.next_key_event = Some(bind_time(value)),
+            "next resign node" => status.next_resign_node = Some(value.to_string()),
+            "next resign time" => status.next_resign_time = Some(bind_time(value)),
+            "next refresh" => status.next_refresh = Some(bind_time(value)),
+            "expires" => status.expires = Some(bind_time(value)),
+            "dynamic" => status.dynamic = yes,
+            "frozen" => status.frozen = yes,
+            "reconfigurable via modzone" => status.reconfigurable = yes,
+            _ => {}
+        }
+    }
+
+    if !has_name {
+        return Err(RndcParseError::MissingField("name".to_string()));
+    }
+    Ok(status)
+}
+
+/// Parse a `current/limit` or `current/soft-limit/limit` client count
+fn client_count(value: &str) -> Option<ClientCount> {
+    let counts: Vec<u32> = value
+        .split('/')
+        .map(|n| n.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match counts[..] {
+        [current, limit] => Some(ClientCount {
+            current,
+            soft_limit: None,
+            limit,
+        }),
+        [current, soft_limit, limit] => Some(ClientCount {
+            current,
+            soft_limit: Some(soft_limit),
+            limit,
+        }),
+        _ => None,
+    }
+}
+
+/// Parse `rndc status` output
+///
+/// Most lines are `key: value`; `number of zones` carries the automatic zone
+/// count in parentheses, and query logging and the running state are plain
+/// sentences. Unknown lines are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use bindcar::rndc_parser::parse_server_status;
+///
+/// let output = "version: BIND 9.18.24 <id:>\nnumber of zones: 102 (97 automatic)\nrecursive clients: 0/900/1000\nquery logging is OFF\nserver is up and running\n";
+/// let status = parse_server_status(output).unwrap();
+/// assert_eq!(status.zones, Some(102));
+/// assert_eq!(status.automatic_zones, Some(97));
+/// assert_eq!(status.recursive_clients.unwrap().limit, 1000);
+/// assert!(status.running);
+/// ```
+///
+/// # Errors
+/// Returns [`RndcParseError::MissingField`] if the output has no version line.
+pub fn parse_server_status(input: &str) -> ParseResult<ServerStatus> {
+    let mut status = ServerStatus::default();
+    let mut has_version = false;
+
+    for line in input.lines().map(str::trim) {
+        match line {
+            "server is up and running" => {
+                status.running = true;
+                continue;
+            }
+            "query logging is ON" => {
+                status.query_logging = Some(true);
+                continue;
+            }
+            "query logging is OFF" => {
+                status.query_logging = Some(false);
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let number = value.parse().ok();
+        match key.trim() {
+            "version" => {
+                status.version = value.to_string();
+                has_version = true;
+            }
+            "boot time" => status.boot_time = Some(bind_time(value)),
+            "last configured" => status.last_configured = Some(bind_time(value)),
+            "configuration file" => status.configuration_file = Some(value.to_string()),
+            "CPUs found" => status.cpus = number,
+            "worker threads" => status.worker_threads = number,
+            "UDP listeners per interface" => status.udp_listeners_per_interface = number,
+            "number of zones" => {
+                // "102 (97 automatic)"
+                let (zones, automatic) = value.split_once('(').unwrap_or((value, ""));
+                status.zones = zones.trim().parse().ok();
+                status.automatic_zones = automatic
+                    .trim_end_matches(')')
+                    .trim()
+                    .strip_suffix("automatic")
+                    .and_then(|n| n.trim().parse().ok());
+            }
+            "debug level" => status.debug_level = number,
+            "xfers running" => status.xfers_running = number,
+            "xfers deferred" => status.xfers_deferred = number,
+            "soa queries in progress" => status.soa_queries_in_progress = number,
+            "recursive clients" => status.recursive_clients = client_count(value),
+            "tcp clients" => status.tcp_clients = client_count(value),
+            "TCP high-water" => status.tcp_high_water = number,
+            key => {
+                if let Some(host) = key.strip_prefix("running on ") {
+                    status.hostname = Some(host.to_string());
+                    status.running_on = Some(value.to_string());
+                }
+            }
+        }
+    }
+
+    if !has_version {
+        return Err(RndcParseError::MissingField("version".to_string()));
+    }
+    Ok(status)
+}
+
+/// List the zones in an `rndc dumpdb -zones` dump
+///
+/// Each zone section starts with a `; Zone dump of 'name/class[/view]'`
+/// comment followed by the zone's records, SOA first.
+///
+/// # Examples
+///
+/// ```rust
+/// use bindcar::rndc_parser::parse_dump_zones;
+///
+/// let dump = ";\n; Zone dump of 'example.com/IN'\n;\nexample.com. 3600 IN SOA ns1.example.com. admin.example.com. 7 3600 600 604800 86400\n; Dump complete\n";
+/// let zones = parse_dump_zones(dump);
+/// assert_eq!(zones[0].name, "example.com");
+/// assert_eq!(zones[0].serial, Some(7));
+/// ```
+pub fn parse_dump_zones(input: &str) -> Vec<DumpedZone> {
+    let mut zones: Vec<DumpedZone> = Vec::new();
+    let mut awaiting_soa = false;
+
+    for line in input.lines() {
+        if let Some(header) = line
+            .strip_prefix("; Zone dump of '")
+            .and_then(|rest| rest.strip_suffix('\''))
+        {
+            let mut parts = header.split('/');
+            zones.push(DumpedZone {
+                name: parts.next().unwrap_or_default().to_string(),
+                class: parts.next().unwrap_or("IN").to_string(),
+                view: parts.next().map(str::to_string),
+                serial: None,
+                automatic_empty: false,
+            });
+            awaiting_soa = true;
+            continue;
+        }
+
+        if !awaiting_soa || line.starts_with(';') {
+            continue;
+        }
+
+        // owner ttl class SOA mname rname serial ...
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let Some(pos) = fields.iter().position(|f| f.eq_ignore_ascii_case("SOA")) {
+            if let Some(zone) = zones.last_mut() {
+                zone.serial = fields.get(pos + 3).and_then(|s| s.parse().ok());
+                // Empty zones use the contact "." and serial 0
+                zone.automatic_empty = fields.get(pos + 2) == Some(&".") && zone.serial == Some(0);
+            }
+            awaiting_soa = false;
+        }
+    }
+
+    zones
+}
+
+/// Marker that opens each dump in BIND's statistics file
+const STATS_DUMP_START: &str = "+++ Statistics Dump +++ (";
+
+/// Marker that closes each dump in BIND's statistics file
+const STATS_DUMP_END: &str = "--- Statistics Dump --- (";
+
+/// Parse the latest complete dump in BIND's statistics file
+///
+/// `rndc stats` appends a dump to the statistics file each time it runs. A
+/// dump consists of `++ Section ++` headers, optional `[scope]` lines (a view,
+/// cache or zone) and counter lines of the form `<value> <name>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bindcar::rndc_parser::parse_stats_dump;
+///
+/// let stats = "+++ Statistics Dump +++ (1700000000)\n++ Incoming Requests ++\n                  12 QUERY\n--- Statistics Dump --- (1700000000)\n";
+/// let dump = parse_stats_dump(stats).unwrap();
+/// assert_eq!(dump.timestamp, 1700000000);
+/// assert_eq!(dump.sections[0].counters["QUERY"], 12);
+/// ```
+///
+/// # Errors
+/// Returns [`RndcParseError::MissingField`] if the input has no dump and
+/// [`RndcParseError::Incomplete`] if the latest dump is not finished.
+pub fn parse_stats_dump(input: &str) -> ParseResult<StatsDump> {
+    let start = input
+        .rfind(STATS_DUMP_START)
+        .ok_or_else(|| RndcParseError::MissingField("statistics dump".to_string()))?;
+    let dump = &input[start + STATS_DUMP_START.len()..];
+
+    let (timestamp, body) = dump
+        .split_once(')')
+        .ok_or_else(|| RndcParseError::ParseError("malformed statistics dump header".into()))?;
+    let timestamp: i64 = timestamp.trim().parse().map_err(|_| {
+        RndcParseError::ParseError(format!("invalid statistics timestamp: {}", timestamp))
+    })?;
+    let body = &body[..body
+        .find(STATS_DUMP_END)
+        .ok_or(RndcParseError::Incomplete)?];
+
+    let mut sections: Vec<StatsSection> = Vec::new();
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(name) = line
+            .strip_prefix("++ ")
+            .and_then(|rest| rest.strip_suffix(" ++"))
+        {
+            sections.push(StatsSection {
+                name: name.to_string(),
+                scope: None,
+                counters: Default::default(),
+            });
+        } else if let Some(scope) = line.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            let Some(current) = sections.last_mut() else {
+                continue;
+            };
+            if current.scope.is_none() && current.counters.is_empty() {
+                current.scope = Some(scope.to_string());
+            } else {
+                let name = current.name.clone();
+                sections.push(StatsSection {
+                    name,
+                    scope: Some(scope.to_string()),
+                    counters: Default::default(),
+                });
+            }
+        } else if let Some((value, name)) = line.split_once(char::is_whitespace) {
+            if let (Ok(value), Some(section)) = (value.parse::<u64>(), sections.last_mut()) {
+                section.counters.insert(name.trim().to_string(), value);
+            }
+        }
+    }
+    sections.retain(|s| !s.counters.is_empty());
+
+    Ok(StatsDump {
+        timestamp,
+        sections,
+    })
+}
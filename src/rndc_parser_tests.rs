@@ -13,9 +13,16 @@
 #[cfg(test)]
 mod tests {
     use crate::rndc_parser::parse_showzone;
-    use crate::rndc_types::{ZoneConfig, ZoneType};
+    use crate::rndc_types::{
+        AddressMatchElement, CheckNamesMode, PrimarySpec, UpdatePolicy, UpdatePolicyAction,
+        UpdatePolicyRuleType, ZoneConfig, ZoneType,
+    };
     use std::net::IpAddr;
 
+    fn key(name: &str) -> AddressMatchElement {
+        AddressMatchElement::Key(name.to_string())
+    }
+
     // ========== Parsing Primitives ==========
 
     #[test]
@@ -96,11 +103,24 @@ mod tests {
         let config = parse_showzone(input).unwrap();
         assert_eq!(config.also_notify.as_ref().unwrap().len(), 2);
         assert_eq!(
-            config.also_notify.as_ref().unwrap()[0],
+            config.also_notify.as_ref().unwrap()[0].address,
             "10.244.2.101".parse::<IpAddr>().unwrap()
         );
     }
 
+    #[test]
+    fn test_parse_zone_with_also_notify_port() {
+        let input = r#"zone "example.com" { type primary; file "/var/cache/bind/example.com.zone"; also-notify { 10.244.2.101 port 5353; }; };"#;
+        let config = parse_showzone(input).unwrap();
+        assert_eq!(
+            config.also_notify,
+            Some(vec![PrimarySpec::with_port(
+                "10.244.2.101".parse().unwrap(),
+                5353
+            )])
+        );
+    }
+
     #[test]
     fn test_parse_zone_with_allow_transfer() {
         let input = r#"zone "example.com" { type primary; file "/var/cache/bind/example.com.zone"; allow-transfer { 10.1.1.1; 10.2.2.2; }; };"#;
@@ -108,14 +128,55 @@ mod tests {
         assert_eq!(config.allow_transfer.as_ref().unwrap().len(), 2);
         assert_eq!(
             config.allow_transfer.as_ref().unwrap()[0],
-            "10.1.1.1".parse::<IpAddr>().unwrap()
+            AddressMatchElement::Address("10.1.1.1".parse().unwrap())
         );
         assert_eq!(
             config.allow_transfer.as_ref().unwrap()[1],
-            "10.2.2.2".parse::<IpAddr>().unwrap()
+            AddressMatchElement::Address("10.2.2.2".parse().unwrap())
         );
     }
 
+    #[test]
+    fn test_parse_address_match_lists() {
+        let input = r#"zone "example.com" { type primary; allow-transfer { !10.244.1.1; 10.244.0.0/16; key "xfer"; }; allow-query { localhost; { trusted; !any; }; }; allow-notify { none; }; };"#;
+        let config = parse_showzone(input).unwrap();
+
+        assert_eq!(
+            config.allow_transfer,
+            Some(vec![
+                AddressMatchElement::negated(AddressMatchElement::Address(
+                    "10.244.1.1".parse().unwrap()
+                )),
+                AddressMatchElement::Prefix {
+                    address: "10.244.0.0".parse().unwrap(),
+                    len: 16
+                },
+                key("xfer"),
+            ])
+        );
+        assert_eq!(
+            config.allow_query,
+            Some(vec![
+                AddressMatchElement::Acl("localhost".to_string()),
+                AddressMatchElement::List(vec![
+                    AddressMatchElement::Acl("trusted".to_string()),
+                    AddressMatchElement::negated(AddressMatchElement::Acl("any".to_string())),
+                ]),
+            ])
+        );
+        assert_eq!(
+            config.allow_notify,
+            Some(vec![AddressMatchElement::Acl("none".to_string())])
+        );
+        assert!(config.raw_options.is_empty());
+
+        // Rendering parses back to the same lists
+        let reparsed =
+            parse_showzone(&format!("zone \"example.com\" {}", config.to_rndc_block())).unwrap();
+        assert_eq!(reparsed.allow_transfer, config.allow_transfer);
+        assert_eq!(reparsed.allow_query, config.allow_query);
+    }
+
     // ========== Allow-Update Parsing Tests ==========
 
     #[test]
@@ -125,20 +186,8 @@ mod tests {
 
         let config = parse_showzone(input).unwrap();
 
-        // Should have raw directive
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should capture raw directive for key-based allow-update"
-        );
-        let raw = config.allow_update_raw.as_ref().unwrap();
-        assert!(raw.contains("key"), "Raw should contain 'key' keyword");
-        assert!(raw.contains("mykey"), "Raw should contain key name");
-
-        // Should not have IP list
-        assert!(
-            config.allow_update.is_none() || config.allow_update.as_ref().unwrap().is_empty(),
-            "Should not have IPs when only key is present"
-        );
+        // The key reference is parsed as a typed element
+        assert_eq!(config.allow_update, Some(vec![key("mykey")]));
     }
 
     #[test]
@@ -155,10 +204,10 @@ mod tests {
         assert_eq!(ips[0].to_string(), "10.1.1.1");
         assert_eq!(ips[1].to_string(), "10.2.2.2");
 
-        // Should not have raw directive
+        // Should not have key references
         assert!(
-            config.allow_update_raw.is_none(),
-            "Should not have raw directive when only IPs present"
+            !ips.iter().any(AddressMatchElement::has_key),
+            "Should not have keys when only IPs present"
         );
     }
 
@@ -169,28 +218,21 @@ mod tests {
 
         let config = parse_showzone(input).unwrap();
 
-        // Should capture raw directive
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should capture raw directive"
-        );
-        let raw = config.allow_update_raw.as_ref().unwrap();
-        assert!(raw.contains("key1"), "Raw should contain first key");
-        assert!(raw.contains("key2"), "Raw should contain second key");
+        assert_eq!(config.allow_update, Some(vec![key("key1"), key("key2")]));
     }
 
     // ========== Serialization Tests ==========
 
     #[test]
     fn test_serialize_key_based_allow_update() {
-        // Test that serialization prefers raw directive over empty IP list
+        // Test that a key element is serialized with the key keyword
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
         config.file = Some("/var/cache/bind/test.com.zone".to_string());
-        config.allow_update_raw = Some(r#"{ key "bindy-operator"; };"#.to_string());
+        config.allow_update = Some(vec![key("bindy-operator")]);
 
         let serialized = config.to_rndc_block();
 
-        // Should contain the raw directive
+        // Should contain the key directive
         assert!(
             serialized.contains("allow-update"),
             "Should include allow-update"
@@ -203,33 +245,29 @@ mod tests {
     }
 
     #[test]
-    fn test_serialize_prefer_raw_over_empty_ips() {
-        // Test that raw directive is used even if IP list is empty
+    fn test_serialize_omits_empty_allow_update() {
+        // An empty list is not rendered as an empty (match nothing) block
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
         config.file = Some("/var/cache/bind/test.com.zone".to_string());
-        config.allow_update = Some(Vec::new()); // Empty IP list
-        config.allow_update_raw = Some(r#"{ key "mykey"; };"#.to_string());
+        config.allow_update = Some(Vec::new());
 
         let serialized = config.to_rndc_block();
 
-        // Should use raw directive, not empty IP list
         assert!(
-            serialized.contains("allow-update"),
-            "Should include allow-update from raw"
+            !serialized.contains("allow-update"),
+            "Should not include an empty allow-update"
         );
-        assert!(serialized.contains("key"), "Should use raw directive");
     }
 
     #[test]
-    fn test_serialize_ips_when_no_raw() {
-        // Test that IP list is serialized when raw directive is not present
+    fn test_serialize_ips_without_key() {
+        // Test that an IP-only list is serialized without key references
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
         config.file = Some("/var/cache/bind/test.com.zone".to_string());
         config.allow_update = Some(vec![
             "10.1.1.1".parse().unwrap(),
             "10.2.2.2".parse().unwrap(),
         ]);
-        config.allow_update_raw = None;
 
         let serialized = config.to_rndc_block();
 
@@ -248,11 +286,11 @@ mod tests {
 
     #[test]
     fn test_serialize_no_double_semicolon() {
-        // Test that raw directive doesn't create double semicolons
+        // Test that a key directive doesn't create double semicolons
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
         config.file = Some("/var/cache/bind/test.com.zone".to_string());
-        config.allow_update_raw = Some(r#"{ key "bindy-operator"; };"#.to_string());
-        config.also_notify = Some(vec!["10.1.1.1".parse().unwrap()]);
+        config.allow_update = Some(vec![key("bindy-operator")]);
+        config.also_notify = Some(vec![PrimarySpec::new("10.1.1.1".parse().unwrap())]);
 
         let serialized = config.to_rndc_block();
 
@@ -280,11 +318,8 @@ mod tests {
 
         let mut config = parse_showzone(showzone_output).unwrap();
 
-        // Verify raw directive was captured
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should have raw allow-update directive"
-        );
+        // Verify the key reference was captured
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Simulate PATCH: update allow-transfer only
         config.allow_transfer = Some(vec!["10.244.1.28".parse().unwrap()]);
@@ -317,14 +352,11 @@ mod tests {
 
         let mut config = parse_showzone(showzone_output).unwrap();
 
-        // Verify raw directive was captured
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should have raw allow-update directive"
-        );
+        // Verify the key reference was captured
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Simulate PATCH: update also-notify only
-        config.also_notify = Some(vec!["10.244.1.99".parse().unwrap()]);
+        config.also_notify = Some(vec![PrimarySpec::new("10.244.1.99".parse().unwrap())]);
 
         // Serialize back
         let serialized = config.to_rndc_block();
@@ -354,15 +386,12 @@ mod tests {
 
         let mut config = parse_showzone(showzone_output).unwrap();
 
-        // Verify raw directive was captured
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should have raw allow-update directive"
-        );
+        // Verify the key reference was captured
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Simulate PATCH: update both allow-transfer and also-notify
         config.allow_transfer = Some(vec!["10.244.1.100".parse().unwrap()]);
-        config.also_notify = Some(vec!["10.244.1.101".parse().unwrap()]);
+        config.also_notify = Some(vec![PrimarySpec::new("10.244.1.101".parse().unwrap())]);
 
         // Serialize back
         let serialized = config.to_rndc_block();
@@ -396,17 +425,13 @@ mod tests {
 
         let mut config = parse_showzone(showzone_output).unwrap();
 
-        // Verify raw directive was captured
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should have raw allow-update directive"
-        );
+        // Verify the key reference was captured
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Simulate PATCH: update all three fields (replace key-based with IPs)
         config.allow_transfer = Some(vec!["10.244.1.200".parse().unwrap()]);
-        config.also_notify = Some(vec!["10.244.1.201".parse().unwrap()]);
+        config.also_notify = Some(vec![PrimarySpec::new("10.244.1.201".parse().unwrap())]);
         config.allow_update = Some(vec!["10.244.1.202".parse().unwrap()]);
-        config.allow_update_raw = None; // Clear raw when setting IPs
 
         // Serialize back
         let serialized = config.to_rndc_block();
@@ -434,21 +459,17 @@ mod tests {
     }
 
     #[test]
-    fn test_patch_clears_raw_when_setting_ips() {
+    fn test_patch_replaces_key_with_ips() {
         // Simulate PATCH operation that replaces key-based allow-update with IPs
         let showzone_output = r#"zone "example.ca" { type primary; file "/var/cache/bind/example.ca.zone"; allow-update { key "bindy-operator"; }; };"#;
 
         let mut config = parse_showzone(showzone_output).unwrap();
 
-        // Verify raw directive was captured
-        assert!(
-            config.allow_update_raw.is_some(),
-            "Should have raw allow-update directive"
-        );
+        // Verify the key reference was captured
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Simulate PATCH: replace with IP list
         config.allow_update = Some(vec!["10.1.1.1".parse().unwrap()]);
-        config.allow_update_raw = None; // Clear raw when setting IPs
 
         // Serialize back
         let serialized = config.to_rndc_block();
@@ -482,8 +503,8 @@ mod tests {
 
         let config = parse_showzone(input).unwrap();
 
-        // Verify raw directive was captured
-        assert!(config.allow_update_raw.is_some());
+        // Verify the key reference was captured
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Serialize back to RNDC format
         let serialized = config.to_rndc_block();
@@ -518,34 +539,31 @@ mod tests {
             Some("/var/cache/bind/internal.local.zone".to_string())
         );
 
-        // Verify allow-transfer parsed correctly (CIDR notation stripped)
+        // Verify allow-transfer parsed correctly (CIDR notation kept)
         assert_eq!(config.allow_transfer.as_ref().unwrap().len(), 2);
         assert_eq!(
             config.allow_transfer.as_ref().unwrap()[0],
-            "10.244.1.18".parse::<IpAddr>().unwrap()
+            AddressMatchElement::Prefix {
+                address: "10.244.1.18".parse().unwrap(),
+                len: 32
+            }
         );
         assert_eq!(
-            config.allow_transfer.as_ref().unwrap()[1],
-            "10.244.1.21".parse::<IpAddr>().unwrap()
+            config.allow_transfer.as_ref().unwrap()[1].to_string(),
+            "10.244.1.21/32"
         );
 
-        // Verify allow-update parsed (key references are captured in raw)
-        if let Some(allow_update) = &config.allow_update {
-            assert_eq!(
-                allow_update.len(),
-                0,
-                "Should not extract IPs from key-based allow-update"
-            );
-        }
+        // Verify allow-update parsed as a key reference
+        assert_eq!(config.allow_update, Some(vec![key("bindy-operator")]));
 
         // Verify also-notify parsed correctly
         assert_eq!(config.also_notify.as_ref().unwrap().len(), 2);
         assert_eq!(
-            config.also_notify.as_ref().unwrap()[0],
+            config.also_notify.as_ref().unwrap()[0].address,
             "10.244.1.18".parse::<IpAddr>().unwrap()
         );
         assert_eq!(
-            config.also_notify.as_ref().unwrap()[1],
+            config.also_notify.as_ref().unwrap()[1].address,
             "10.244.1.21".parse::<IpAddr>().unwrap()
         );
     }
@@ -573,14 +591,14 @@ mod tests {
             Some("/var/cache/bind/internal.local.zone".to_string())
         );
 
-        // Verify allow-transfer (CIDR /32 should be stripped)
+        // Verify allow-transfer (CIDR /32 is kept)
         let allow_transfer = config
             .allow_transfer
             .as_ref()
             .expect("allow-transfer should be present");
         assert_eq!(allow_transfer.len(), 2);
-        assert_eq!(allow_transfer[0].to_string(), "10.244.1.18");
-        assert_eq!(allow_transfer[1].to_string(), "10.244.1.21");
+        assert_eq!(allow_transfer[0].to_string(), "10.244.1.18/32");
+        assert_eq!(allow_transfer[1].to_string(), "10.244.1.21/32");
 
         // Verify also-notify
         let also_notify = config
@@ -591,22 +609,11 @@ mod tests {
         assert_eq!(also_notify[0].to_string(), "10.244.1.18");
         assert_eq!(also_notify[1].to_string(), "10.244.1.21");
 
-        // Verify allow-update raw directive is captured (key-based updates)
-        assert!(
-            config.allow_update_raw.is_some(),
-            "allow-update-raw should be present for key-based updates"
-        );
-        assert!(
-            config.allow_update_raw.as_ref().unwrap().contains("key"),
-            "Raw directive should contain 'key' keyword"
-        );
-        assert!(
-            config
-                .allow_update_raw
-                .as_ref()
-                .unwrap()
-                .contains("bindy-operator"),
-            "Raw directive should contain key name"
+        // Verify allow-update key reference is captured (key-based updates)
+        assert_eq!(
+            config.allow_update,
+            Some(vec![key("bindy-operator")]),
+            "allow-update should hold the key reference"
         );
     }
 
@@ -622,8 +629,8 @@ mod tests {
         assert_eq!(config.zone_name, "example.ca");
         assert_eq!(config.zone_type, ZoneType::Primary);
         assert!(
-            config.allow_update_raw.is_some(),
-            "Must capture raw allow-update"
+            config.allow_update.as_ref().is_some_and(|l| l[0].has_key()),
+            "Must capture key-based allow-update"
         );
         assert!(
             config.allow_transfer.is_some(),
@@ -633,7 +640,7 @@ mod tests {
 
         // Simulate PATCH: only update allow-transfer
         config.allow_transfer = Some(vec!["10.244.1.27".parse().unwrap()]);
-        // NOTE: We do NOT touch allow_update or allow_update_raw
+        // NOTE: We do NOT touch allow_update
 
         // Serialize for modzone
         let modzone_config = config.to_rndc_block();
@@ -668,7 +675,7 @@ mod tests {
         let mut config = parse_showzone(showzone_output).unwrap();
 
         // Simulate PATCH: update also-notify
-        config.also_notify = Some(vec!["10.244.1.31".parse().unwrap()]);
+        config.also_notify = Some(vec![PrimarySpec::new("10.244.1.31".parse().unwrap())]);
 
         let modzone_config = config.to_rndc_block();
 
@@ -718,7 +725,8 @@ mod tests {
         assert_eq!(config.zone_name, "example.com");
         assert_eq!(config.zone_type, ZoneType::Primary);
         assert!(config.raw_options.contains_key("zone-statistics"));
-        assert!(config.raw_options.contains_key("max-zone-ttl"));
+        assert_eq!(config.max_zone_ttl, Some(86400));
+        assert!(!config.raw_options.contains_key("max-zone-ttl"));
     }
 
     #[test]
@@ -726,16 +734,58 @@ mod tests {
         let input = r#"zone "example.com" {
             type primary;
             file "/var/cache/bind/example.com.zone";
-            update-policy { grant example.com. zonesub any; };
+            allow-query-on { 192.0.2.53; 2001:db8::53; };
         };"#;
 
         let config = parse_showzone(input).unwrap();
 
         assert_eq!(config.zone_name, "example.com");
-        assert!(config.raw_options.contains_key("update-policy"));
-        let update_policy = config.raw_options.get("update-policy").unwrap();
-        assert!(update_policy.contains("grant"));
-        assert!(update_policy.contains("zonesub"));
+        let query_on = config.raw_options.get("allow-query-on").unwrap();
+        assert!(query_on.contains("2001:db8::53"));
+    }
+
+    #[test]
+    fn test_parse_update_policy() {
+        let input = r#"zone "example.com" {
+            type primary;
+            update-policy {
+                grant example.com. zonesub any;
+                grant "tenant1-key" name _acme-challenge.example.com. TXT;
+                deny * wildcard *.example.com. A AAAA;
+                grant * tcp-self . PTR(1);
+            };
+        };"#;
+
+        let config = parse_showzone(input).unwrap();
+
+        let Some(UpdatePolicy::Rules(rules)) = &config.update_policy else {
+            panic!("expected rules, got {:?}", config.update_policy);
+        };
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules[0].rule_type, UpdatePolicyRuleType::Zonesub);
+        assert_eq!(rules[0].name, None);
+        assert_eq!(rules[0].types, vec!["any"]);
+        assert_eq!(rules[1].identity, "tenant1-key");
+        assert_eq!(
+            rules[1].name.as_deref(),
+            Some("_acme-challenge.example.com.")
+        );
+        assert_eq!(rules[2].action, UpdatePolicyAction::Deny);
+        assert_eq!(rules[2].types, vec!["A", "AAAA"]);
+        assert_eq!(rules[3].rule_type, UpdatePolicyRuleType::TcpSelf);
+        assert!(!config.raw_options.contains_key("update-policy"));
+
+        let reparsed =
+            parse_showzone(&format!(r#"zone "example.com" {}"#, config.to_rndc_block())).unwrap();
+        assert_eq!(reparsed.update_policy, config.update_policy);
+    }
+
+    #[test]
+    fn test_parse_update_policy_local() {
+        let config =
+            parse_showzone(r#"zone "example.com" { type primary; update-policy local; };"#)
+                .unwrap();
+        assert_eq!(config.update_policy, Some(UpdatePolicy::Local));
     }
 
     #[test]
@@ -780,11 +830,13 @@ mod tests {
         assert_eq!(config.allow_transfer.as_ref().unwrap().len(), 2);
         assert_eq!(config.also_notify.as_ref().unwrap().len(), 1);
 
+        assert_eq!(config.check_integrity, Some(true));
+        assert_eq!(config.check_mx, Some(CheckNamesMode::Fail));
+        assert_eq!(config.max_transfer_time_in, Some(60));
+
         // Unknown options should be in raw_options
-        assert!(config.raw_options.contains_key("check-integrity"));
-        assert!(config.raw_options.contains_key("check-mx"));
+        assert_eq!(config.raw_options.len(), 1);
         assert!(config.raw_options.contains_key("dialup"));
-        assert!(config.raw_options.contains_key("max-transfer-time-in"));
     }
 
     #[test]
@@ -844,10 +896,39 @@ mod tests {
         // Verify port parsing
         assert_eq!(config.primaries.as_ref().unwrap()[1].port, Some(5353));
 
-        // Unknown options
-        assert!(config.raw_options.contains_key("max-refresh-time"));
-        assert!(config.raw_options.contains_key("min-retry-time"));
-        assert!(config.raw_options.contains_key("request-ixfr"));
+        assert_eq!(config.max_refresh_time, Some(3600));
+        assert_eq!(config.min_retry_time, Some(600));
+        assert_eq!(config.request_ixfr, Some(true));
+        assert!(config.raw_options.is_empty());
+    }
+
+    #[test]
+    fn test_untyped_option_values_kept_verbatim() {
+        let input = r#"zone "example.com" {
+            type primary;
+            file "/var/cache/bind/example.com.zone";
+            allow-notify { 10.1.1.1; };
+            transfer-source 10.0.0.1 port 5353;
+            notify-source-v6 2001:db8::53;
+            max-zone-ttl unlimited;
+        };"#;
+
+        let config = parse_showzone(input).unwrap();
+
+        assert_eq!(
+            config.allow_notify,
+            Some(vec![AddressMatchElement::Address(
+                "10.1.1.1".parse().unwrap()
+            )])
+        );
+        assert_eq!(
+            config.notify_source_v6,
+            Some("2001:db8::53".parse::<IpAddr>().unwrap())
+        );
+        assert_eq!(config.raw_options["transfer-source"], "10.0.0.1 port 5353");
+        assert_eq!(config.raw_options["max-zone-ttl"], "unlimited");
+        assert!(config.transfer_source.is_none());
+        assert!(config.max_zone_ttl.is_none());
     }
 
     #[test]
@@ -940,4 +1021,272 @@ mod tests {
         assert!(serialized.contains("option1"));
         assert!(serialized.contains("option5"));
     }
+
+    // ========== zonestatus and dumpdb ==========
+
+    #[test]
+    fn test_parse_zonestatus_primary() {
+        use crate::rndc_parser::parse_zonestatus;
+
+        let output = "name: example.com\n\
+            type: primary\n\
+            files: example.com.zone\n\
+            serial: 2025010105\n\
+            signed serial: 2025010107\n\
+            nodes: 12\n\
+            last loaded: Tue, 14 Jan 2025 10:00:00 GMT\n\
+            secure: no\n\
+            dynamic: yes\n\
+            frozen: no\n\
+            reconfigurable via modzone: yes\n";
+        let status = parse_zonestatus(output).unwrap();
+
+        assert_eq!(status.name, "example.com");
+        assert_eq!(status.zone_type, Some(ZoneType::Primary));
+        assert_eq!(status.files, ["example.com.zone"]);
+        assert_eq!(status.serial, Some(2025010105));
+        assert_eq!(status.nodes, Some(12));
+        assert_eq!(status.last_loaded.as_deref(), Some("2025-01-14T10:00:00Z"));
+        assert!(status.loaded);
+        assert!(!status.secure);
+        assert!(status.dynamic);
+        assert!(!status.frozen);
+        assert!(status.reconfigurable);
+    }
+
+    #[test]
+    fn test_parse_zonestatus_signed_secondary() {
+        use crate::rndc_parser::parse_zonestatus;
+
+        let output = "name: example.net\n\
+            type: secondary\n\
+            files: example.net.db, example.net.inc\n\
+            serial: 2025010210\n\
+            signed serial: 2025010210\n\
+            loaded serial: 2025010207\n\
+            nodes: 40\n\
+            last loaded: Wed, 15 Jan 2025 08:30:00 GMT\n\
+            next refresh: Wed, 15 Jan 2025 09:30:00 GMT\n\
+            expires: Wed, 22 Jan 2025 08:30:00 GMT\n\
+            secure: yes\n\
+            inline signing: yes\n\
+            key maintenance: automatic\n\
+            next key event: Wed, 15 Jan 2025 10:00:00 GMT\n\
+            next resign node: www.example.net/A\n\
+            next resign time: not a date\n\
+            dynamic: no\n\
+            reconfigurable via modzone: no\n";
+        let status = parse_zonestatus(output).unwrap();
+
+        assert_eq!(status.files, ["example.net.db", "example.net.inc"]);
+        assert_eq!(status.loaded_serial, Some(2025010207));
+        assert_eq!(status.next_refresh.as_deref(), Some("2025-01-15T09:30:00Z"));
+        assert_eq!(status.expires.as_deref(), Some("2025-01-22T08:30:00Z"));
+        assert!(status.secure && status.inline_signing);
+        assert_eq!(status.key_maintenance.as_deref(), Some("automatic"));
+        assert_eq!(
+            status.next_resign_node.as_deref(),
+            Some("www.example.net/A")
+        );
+        // Dates BIND prints in another form are kept as-is
+        assert_eq!(status.next_resign_time.as_deref(), Some("not a date"));
+        assert!(!status.reconfigurable);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["zoneType"], "secondary");
+        assert_eq!(json["inlineSigning"], true);
+    }
+
+    #[test]
+    fn test_parse_server_status() {
+        use crate::rndc_parser::parse_server_status;
+        use crate::rndc_types::ClientCount;
+
+        let output = "\
+version: BIND 9.18.24-1ubuntu1.1-Ubuntu (Extended Support Version) <id:>
+running on ns1: Linux x86_64 6.5.0-35-generic #35-Ubuntu SMP
+boot time: Tue, 14 Jan 2025 09:00:00 GMT
+last configured: Tue, 14 Jan 2025 09:05:00 GMT
+configuration file: /etc/bind/named.conf
+CPUs found: 4
+worker threads: 4
+UDP listeners per interface: 4
+number of zones: 102 (97 automatic)
+debug level: 0
+xfers running: 2
+xfers deferred: 1
+soa queries in progress: 0
+query logging is ON
+recursive clients: 3/900/1000
+tcp clients: 5/150
+TCP high-water: 12
+server is up and running
+";
+        let status = parse_server_status(output).unwrap();
+
+        assert!(status.version.starts_with("BIND 9.18.24"));
+        assert_eq!(status.hostname.as_deref(), Some("ns1"));
+        assert!(status.running_on.as_deref().unwrap().starts_with("Linux"));
+        assert_eq!(status.boot_time.as_deref(), Some("2025-01-14T09:00:00Z"));
+        assert_eq!(
+            status.configuration_file.as_deref(),
+            Some("/etc/bind/named.conf")
+        );
+        assert_eq!(status.cpus, Some(4));
+        assert_eq!(status.zones, Some(102));
+        assert_eq!(status.automatic_zones, Some(97));
+        assert_eq!(status.debug_level, Some(0));
+        assert_eq!(status.xfers_running, Some(2));
+        assert_eq!(status.xfers_deferred, Some(1));
+        assert_eq!(status.query_logging, Some(true));
+        assert_eq!(
+            status.recursive_clients,
+            Some(ClientCount {
+                current: 3,
+                soft_limit: Some(900),
+                limit: 1000
+            })
+        );
+        assert_eq!(
+            status.tcp_clients,
+            Some(ClientCount {
+                current: 5,
+                soft_limit: None,
+                limit: 150
+            })
+        );
+        assert_eq!(status.tcp_high_water, Some(12));
+        assert!(status.running);
+
+        assert!(parse_server_status("server is up and running\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_zonestatus_unloaded_secondary() {
+        use crate::rndc_parser::parse_zonestatus;
+
+        let status = parse_zonestatus("name: example.org\ntype: slave\nfrozen: yes\n").unwrap();
+        assert_eq!(status.zone_type, Some(ZoneType::Secondary));
+        assert_eq!(status.serial, None);
+        assert!(!status.loaded);
+        assert!(status.frozen);
+
+        assert!(parse_zonestatus("type: primary\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_dump_zones() {
+        use crate::rndc_parser::parse_dump_zones;
+
+        let dump = ";\n\
+            ; Start view _default\n\
+            ;\n\
+            ;\n\
+            ; Zone dump of 'example.com/IN'\n\
+            ;\n\
+            example.com.\t3600\tIN SOA\tns1.example.com. admin.example.com. 42 3600 600 604800 86400\n\
+            example.com.\t3600\tIN NS\tns1.example.com.\n\
+            ;\n\
+            ; Zone dump of '10.in-addr.arpa/IN'\n\
+            ;\n\
+            10.in-addr.arpa.\t86400\tIN SOA\t10.in-addr.arpa. . 0 28800 7200 604800 86400\n\
+            ;\n\
+            ; Zone dump of 'internal.example/IN/internal'\n\
+            ;\n\
+            ; Zone dump of 'version.bind/CH/_bind'\n\
+            ;\n\
+            ; Dump complete\n";
+        let zones = parse_dump_zones(dump);
+
+        let summary: Vec<_> = zones
+            .iter()
+            .map(|z| {
+                (
+                    z.name.as_str(),
+                    z.class.as_str(),
+                    z.view.as_deref(),
+                    z.serial,
+                    z.automatic_empty,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("example.com", "IN", None, Some(42), false),
+                ("10.in-addr.arpa", "IN", None, Some(0), true),
+                ("internal.example", "IN", Some("internal"), None, false),
+                ("version.bind", "CH", Some("_bind"), None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_stats_dump_uses_latest_dump() {
+        use crate::rndc_parser::parse_stats_dump;
+
+        let stats = "\
++++ Statistics Dump +++ (1700000000)
+++ Incoming Requests ++
+                   1 QUERY
+--- Statistics Dump --- (1700000000)
++++ Statistics Dump +++ (1700000600)
+++ Incoming Requests ++
+                  12 QUERY
+                   2 UPDATE
+++ Incoming Queries ++
+                   9 A
+                   3 AAAA
+++ Zone Maintenance Statistics ++
+++ Resolver Statistics ++
+[Common]
+                   4 mismatch
+[View: default]
+                  20 IPv4 queries sent
+++ Cache DB RRsets ++
+[View: default (Cache: default)]
+                   5 A
+                   1 !AAAA
+++ Per Zone Query Statistics ++
+[example.com]
+                   7 QrySuccess
+--- Statistics Dump --- (1700000600)
+";
+        let dump = parse_stats_dump(stats).unwrap();
+        assert_eq!(dump.timestamp, 1700000600);
+
+        let sections: Vec<(&str, Option<&str>)> = dump
+            .sections
+            .iter()
+            .map(|s| (s.name.as_str(), s.scope.as_deref()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Incoming Requests", None),
+                ("Incoming Queries", None),
+                ("Resolver Statistics", Some("Common")),
+                ("Resolver Statistics", Some("View: default")),
+                ("Cache DB RRsets", Some("View: default (Cache: default)")),
+                ("Per Zone Query Statistics", Some("example.com")),
+            ]
+        );
+        assert_eq!(dump.sections[0].counters["QUERY"], 12);
+        assert_eq!(dump.sections[3].counters["IPv4 queries sent"], 20);
+        assert_eq!(dump.sections[4].counters["!AAAA"], 1);
+    }
+
+    #[test]
+    fn test_parse_stats_dump_rejects_missing_or_unfinished_dump() {
+        use crate::rndc_parser::{parse_stats_dump, RndcParseError};
+
+        assert!(matches!(
+            parse_stats_dump(""),
+            Err(RndcParseError::MissingField(_))
+        ));
+        assert!(matches!(
+            parse_stats_dump("+++ Statistics Dump +++ (1700000000)\n++ Incoming Requests ++\n"),
+            Err(RndcParseError::Incomplete)
+        ));
+    }
 }
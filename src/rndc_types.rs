@@ -4,10 +4,12 @@
 //! RNDC data types for parsing BIND9 output
 //!
 //! This module defines the core data structures used for parsing
-//! RNDC command outputs (showzone, zonestatus, status).
+//! RNDC command outputs (showzone, zonestatus, status, dumpdb, stats).
 
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::net::IpAddr;
+use utoipa::ToSchema;
 
 /// DNS class
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -71,7 +73,21 @@ impl ZoneType {
     }
 }
 
-/// Primary server specification for secondary zones
+impl Serialize for ZoneType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ZoneType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        ZoneType::parse(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown zone type: {}", s)))
+    }
+}
+
+/// Server address with optional port, as used by `primaries` and `also-notify`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PrimarySpec {
     pub address: IpAddr,
@@ -94,6 +110,268 @@ impl PrimarySpec {
     }
 }
 
+impl std::fmt::Display for PrimarySpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "{} port {}", self.address, port),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+/// Element of a BIND9 address match list (`allow-transfer`, `allow-query`, ...)
+///
+/// Elements are matched in order and the first match wins, so a negated
+/// element must come before a broader one it carves out of:
+/// `{ !10.0.0.1; 10.0.0.0/8; }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressMatchElement {
+    /// A single address: `192.0.2.1`
+    Address(IpAddr),
+    /// A CIDR prefix: `10.244.0.0/16`
+    Prefix { address: IpAddr, len: u8 },
+    /// A TSIG key: `key "name"`
+    Key(String),
+    /// A named ACL, including the built-ins `any`, `none`, `localhost` and
+    /// `localnets`
+    Acl(String),
+    /// A negated element: `!192.0.2.1`
+    Negated(Box<AddressMatchElement>),
+    /// A nested list: `{ 192.0.2.1; key "name"; }`
+    List(Vec<AddressMatchElement>),
+}
+
+impl AddressMatchElement {
+    /// Negate an element.
+    pub fn negated(element: AddressMatchElement) -> Self {
+        AddressMatchElement::Negated(Box::new(element))
+    }
+
+    /// Whether the element is, or contains, a TSIG key
+    pub fn has_key(&self) -> bool {
+        match self {
+            AddressMatchElement::Key(_) => true,
+            AddressMatchElement::Negated(inner) => inner.has_key(),
+            AddressMatchElement::List(elements) => elements.iter().any(Self::has_key),
+            _ => false,
+        }
+    }
+}
+
+impl From<IpAddr> for AddressMatchElement {
+    fn from(address: IpAddr) -> Self {
+        AddressMatchElement::Address(address)
+    }
+}
+
+impl std::fmt::Display for AddressMatchElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressMatchElement::Address(address) => write!(f, "{}", address),
+            AddressMatchElement::Prefix { address, len } => write!(f, "{}/{}", address, len),
+            AddressMatchElement::Key(name) => write!(f, "key \"{}\"", name),
+            AddressMatchElement::Acl(name) => write!(f, "{}", name),
+            AddressMatchElement::Negated(inner) => write!(f, "!{}", inner),
+            AddressMatchElement::List(elements) => write!(f, "{}", render_match_list(elements)),
+        }
+    }
+}
+
+/// Parses one element in BIND syntax, e.g. `10.244.0.0/16`, `!192.0.2.1`,
+/// `key "name"` or `any`.
+impl std::str::FromStr for AddressMatchElement {
+    type Err = crate::rndc_parser::RndcParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::rndc_parser::parse_address_match_element(s)
+    }
+}
+
+/// Render an address match list: `{ 10.0.0.0/8; key "name"; }`
+pub fn render_match_list(elements: &[AddressMatchElement]) -> String {
+    let mut list = String::from("{ ");
+    for element in elements {
+        list.push_str(&format!("{}; ", element));
+    }
+    list.push('}');
+    list
+}
+
+/// Whether an `update-policy` rule grants or denies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdatePolicyAction {
+    Grant,
+    Deny,
+}
+
+impl UpdatePolicyAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdatePolicyAction::Grant => "grant",
+            UpdatePolicyAction::Deny => "deny",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "grant" => Some(UpdatePolicyAction::Grant),
+            "deny" => Some(UpdatePolicyAction::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// How an `update-policy` rule matches the names an update may change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdatePolicyRuleType {
+    /// Exactly the rule's name
+    Name,
+    /// The rule's name and everything below it
+    Subdomain,
+    /// Names matching the rule's wildcard name
+    Wildcard,
+    /// The zone apex and everything below it (no name field)
+    Zonesub,
+    /// The name equal to the signer's identity
+    #[serde(rename = "self")]
+    SelfName,
+    /// The signer's identity and everything below it
+    Selfsub,
+    /// Names one label below the signer's identity
+    Selfwild,
+    /// Reverse name of the TCP client address
+    TcpSelf,
+    /// 6to4 reverse name of the TCP client address
+    #[serde(rename = "6to4-self")]
+    SixToFourSelf,
+    /// Decided by an external daemon
+    External,
+    MsSelf,
+    MsSelfsub,
+    MsSubdomain,
+    Krb5Self,
+    Krb5Selfsub,
+    Krb5Subdomain,
+}
+
+impl UpdatePolicyRuleType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdatePolicyRuleType::Name => "name",
+            UpdatePolicyRuleType::Subdomain => "subdomain",
+            UpdatePolicyRuleType::Wildcard => "wildcard",
+            UpdatePolicyRuleType::Zonesub => "zonesub",
+            UpdatePolicyRuleType::SelfName => "self",
+            UpdatePolicyRuleType::Selfsub => "selfsub",
+            UpdatePolicyRuleType::Selfwild => "selfwild",
+            UpdatePolicyRuleType::TcpSelf => "tcp-self",
+            UpdatePolicyRuleType::SixToFourSelf => "6to4-self",
+            UpdatePolicyRuleType::External => "external",
+            UpdatePolicyRuleType::MsSelf => "ms-self",
+            UpdatePolicyRuleType::MsSelfsub => "ms-selfsub",
+            UpdatePolicyRuleType::MsSubdomain => "ms-subdomain",
+            UpdatePolicyRuleType::Krb5Self => "krb5-self",
+            UpdatePolicyRuleType::Krb5Selfsub => "krb5-selfsub",
+            UpdatePolicyRuleType::Krb5Subdomain => "krb5-subdomain",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "name" => Some(UpdatePolicyRuleType::Name),
+            "subdomain" => Some(UpdatePolicyRuleType::Subdomain),
+            "wildcard" => Some(UpdatePolicyRuleType::Wildcard),
+            "zonesub" => Some(UpdatePolicyRuleType::Zonesub),
+            "self" => Some(UpdatePolicyRuleType::SelfName),
+            "selfsub" => Some(UpdatePolicyRuleType::Selfsub),
+            "selfwild" => Some(UpdatePolicyRuleType::Selfwild),
+            "tcp-self" => Some(UpdatePolicyRuleType::TcpSelf),
+            "6to4-self" => Some(UpdatePolicyRuleType::SixToFourSelf),
+            "external" => Some(UpdatePolicyRuleType::External),
+            "ms-self" => Some(UpdatePolicyRuleType::MsSelf),
+            "ms-selfsub" => Some(UpdatePolicyRuleType::MsSelfsub),
+            "ms-subdomain" => Some(UpdatePolicyRuleType::MsSubdomain),
+            "krb5-self" => Some(UpdatePolicyRuleType::Krb5Self),
+            "krb5-selfsub" => Some(UpdatePolicyRuleType::Krb5Selfsub),
+            "krb5-subdomain" => Some(UpdatePolicyRuleType::Krb5Subdomain),
+            _ => None,
+        }
+    }
+
+    /// Whether the rule has a name field; only `zonesub` omits it
+    pub fn takes_name(&self) -> bool {
+        *self != UpdatePolicyRuleType::Zonesub
+    }
+}
+
+/// One `update-policy` rule: `grant tenant1-key name _acme-challenge.example.com. TXT;`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePolicyRule {
+    /// "grant" or "deny"
+    pub action: UpdatePolicyAction,
+
+    /// Signer the rule applies to, usually a TSIG key name; "*" matches any
+    pub identity: String,
+
+    /// How `name` is matched, e.g. "name", "subdomain" or "zonesub"
+    pub rule_type: UpdatePolicyRuleType,
+
+    /// Name the rule type matches against; omitted for "zonesub"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Record types the rule covers; empty means all but SOA, NS, RRSIG and NSEC(3)
+    /// Example: ["TXT"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+}
+
+impl std::fmt::Display for UpdatePolicyRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.action.as_str(),
+            self.identity,
+            self.rule_type.as_str()
+        )?;
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+        for record_type in &self.types {
+            write!(f, " {}", record_type)?;
+        }
+        Ok(())
+    }
+}
+
+/// A zone's `update-policy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdatePolicy {
+    /// `update-policy local;`: the `local-ddns` session key may update anything
+    Local,
+    /// `update-policy { rule; ... };`
+    Rules(Vec<UpdatePolicyRule>),
+}
+
+impl std::fmt::Display for UpdatePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdatePolicy::Local => write!(f, "local"),
+            UpdatePolicy::Rules(rules) => {
+                write!(f, "{{ ")?;
+                for rule in rules {
+                    write!(f, "{}; ", rule)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
 /// Forwarder specification for forward zones
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ForwarderSpec {
@@ -277,18 +555,15 @@ pub struct ZoneConfig {
 
     // Primary/Secondary options
     pub primaries: Option<Vec<PrimarySpec>>,
-    pub also_notify: Option<Vec<IpAddr>>,
+    pub also_notify: Option<Vec<PrimarySpec>>,
     pub notify: Option<NotifyMode>,
 
     // Access Control options
-    pub allow_query: Option<Vec<IpAddr>>,
-    pub allow_transfer: Option<Vec<IpAddr>>,
-    pub allow_update: Option<Vec<IpAddr>>,
-    /// Raw allow-update directive (e.g., "{ key \"name\"; }")
-    /// Used to preserve key-based allow-update when no IPs are specified
-    pub allow_update_raw: Option<String>,
-    pub allow_update_forwarding: Option<Vec<IpAddr>>,
-    pub allow_notify: Option<Vec<IpAddr>>,
+    pub allow_query: Option<Vec<AddressMatchElement>>,
+    pub allow_transfer: Option<Vec<AddressMatchElement>>,
+    pub allow_update: Option<Vec<AddressMatchElement>>,
+    pub allow_update_forwarding: Option<Vec<AddressMatchElement>>,
+    pub allow_notify: Option<Vec<AddressMatchElement>>,
 
     // Transfer Control options
     pub max_transfer_time_in: Option<u32>,
@@ -301,8 +576,7 @@ pub struct ZoneConfig {
     pub notify_source_v6: Option<IpAddr>,
 
     // Dynamic Update options
-    /// Raw update-policy directive (complex grammar, kept as raw)
-    pub update_policy: Option<String>,
+    pub update_policy: Option<UpdatePolicy>,
     pub journal: Option<String>,
     pub ixfr_from_differences: Option<bool>,
 
@@ -356,7 +630,6 @@ impl ZoneConfig {
             allow_query: None,
             allow_transfer: None,
             allow_update: None,
-            allow_update_raw: None,
             allow_update_forwarding: None,
             allow_notify: None,
             max_transfer_time_in: None,
@@ -413,13 +686,7 @@ impl ZoneConfig {
             if !primaries.is_empty() {
                 let primary_list = primaries
                     .iter()
-                    .map(|p| {
-                        if let Some(port) = p.port {
-                            format!("{} port {}", p.address, port)
-                        } else {
-                            p.address.to_string()
-                        }
-                    })
+                    .map(|p| p.to_string())
                     .collect::<Vec<_>>()
                     .join("; ");
                 parts.push(format!("primaries {{ {}; }}", primary_list));
@@ -431,7 +698,7 @@ impl ZoneConfig {
             if !also_notify.is_empty() {
                 let notify_list = also_notify
                     .iter()
-                    .map(|ip| ip.to_string())
+                    .map(|spec| spec.to_string())
                     .collect::<Vec<_>>()
                     .join("; ");
                 parts.push(format!("also-notify {{ {}; }}", notify_list));
@@ -443,66 +710,17 @@ impl ZoneConfig {
             parts.push(format!("notify {}", notify.as_str()));
         }
 
-        // Allow-query
-        if let Some(ref allow_query) = self.allow_query {
-            if !allow_query.is_empty() {
-                let query_list = allow_query
-                    .iter()
-                    .map(|ip| ip.to_string())
-                    .collect::<Vec<_>>()
-                    .join("; ");
-                parts.push(format!("allow-query {{ {}; }}", query_list));
-            }
-        }
-
-        // Allow-transfer
-        if let Some(ref allow_transfer) = self.allow_transfer {
-            if !allow_transfer.is_empty() {
-                let transfer_list = allow_transfer
-                    .iter()
-                    .map(|ip| ip.to_string())
-                    .collect::<Vec<_>>()
-                    .join("; ");
-                parts.push(format!("allow-transfer {{ {}; }}", transfer_list));
-            }
-        }
-
-        // Allow-update (prefer raw directive if present, otherwise use IP list)
-        if let Some(ref raw) = self.allow_update_raw {
-            let raw_trimmed = raw.trim_end().trim_end_matches(';').trim();
-            parts.push(format!("allow-update {}", raw_trimmed));
-        } else if let Some(ref allow_update) = self.allow_update {
-            if !allow_update.is_empty() {
-                let update_list = allow_update
-                    .iter()
-                    .map(|ip| ip.to_string())
-                    .collect::<Vec<_>>()
-                    .join("; ");
-                parts.push(format!("allow-update {{ {}; }}", update_list));
-            }
-        }
-
-        // Allow-update-forwarding
-        if let Some(ref allow_update_forwarding) = self.allow_update_forwarding {
-            if !allow_update_forwarding.is_empty() {
-                let list = allow_update_forwarding
-                    .iter()
-                    .map(|ip| ip.to_string())
-                    .collect::<Vec<_>>()
-                    .join("; ");
-                parts.push(format!("allow-update-forwarding {{ {}; }}", list));
-            }
-        }
-
-        // Allow-notify
-        if let Some(ref allow_notify) = self.allow_notify {
-            if !allow_notify.is_empty() {
-                let list = allow_notify
-                    .iter()
-                    .map(|ip| ip.to_string())
-                    .collect::<Vec<_>>()
-                    .join("; ");
-                parts.push(format!("allow-notify {{ {}; }}", list));
+        // Address match lists
+        let match_lists = [
+            ("allow-query", &self.allow_query),
+            ("allow-transfer", &self.allow_transfer),
+            ("allow-update", &self.allow_update),
+            ("allow-update-forwarding", &self.allow_update_forwarding),
+            ("allow-notify", &self.allow_notify),
+        ];
+        for (name, list) in match_lists {
+            if let Some(elements) = list.as_deref().filter(|l| !l.is_empty()) {
+                parts.push(format!("{} {}", name, render_match_list(elements)));
             }
         }
 
@@ -536,8 +754,7 @@ impl ZoneConfig {
 
         // Dynamic update options
         if let Some(ref policy) = self.update_policy {
-            let policy_trimmed = policy.trim_end().trim_end_matches(';').trim();
-            parts.push(format!("update-policy {}", policy_trimmed));
+            parts.push(format!("update-policy {}", policy));
         }
         if let Some(ref journal) = self.journal {
             parts.push(format!(r#"journal "{}""#, journal));
@@ -643,3 +860,198 @@ impl ZoneConfig {
         format!("{{ {}; }};", parts.join("; "))
     }
 }
+
+/// Zone state from `rndc zonestatus`
+///
+/// Times are RFC 3339 (UTC) when BIND's date could be read, and BIND's text
+/// otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneStatus {
+    pub name: String,
+    /// Zone type; `None` if BIND reported a type bindcar does not know
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub zone_type: Option<ZoneType>,
+    /// Zone file followed by the files it includes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Serial currently served; absent when the zone failed to load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<u32>,
+    /// Serial of the unsigned zone, for inline-signed zones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loaded_serial: Option<u32>,
+    /// Number of names in the zone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_loaded: Option<String>,
+    pub loaded: bool,
+    /// Whether the zone is DNSSEC-signed
+    pub secure: bool,
+    pub inline_signing: bool,
+    /// DNSSEC key maintenance mode, e.g. "automatic" or "none"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_maintenance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_key_event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_resign_node: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_resign_time: Option<String>,
+    /// Next refresh check, for secondary zones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_refresh: Option<String>,
+    /// When a secondary zone expires without a successful refresh
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    pub dynamic: bool,
+    pub frozen: bool,
+    /// Whether the zone was added with `rndc addzone` and can be changed with
+    /// `rndc modzone`
+    pub reconfigurable: bool,
+}
+
+/// A client count from `rndc status`: `current/limit` or
+/// `current/soft-limit/limit`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCount {
+    pub current: u32,
+    /// Soft quota, above which BIND starts dropping the oldest clients
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_limit: Option<u32>,
+    pub limit: u32,
+}
+
+/// Server state from `rndc status`
+///
+/// Times are RFC 3339 (UTC) when BIND's date could be read, and BIND's text
+/// otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    /// BIND version string, e.g. "BIND 9.18.24 (Extended Support Version) <id:>"
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Operating system BIND runs on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_configured: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker_threads: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub udp_listeners_per_interface: Option<u32>,
+    /// Zones served, including automatic empty zones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zones: Option<u32>,
+    /// Automatic empty zones (RFC 6303) among `zones`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automatic_zones: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_level: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xfers_running: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xfers_deferred: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soa_queries_in_progress: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_logging: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recursive_clients: Option<ClientCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_clients: Option<ClientCount>,
+    /// Most TCP clients seen at once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_high_water: Option<u32>,
+    /// Whether BIND reported "server is up and running"
+    pub running: bool,
+}
+
+/// A zone listed in an `rndc dumpdb -zones` dump
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpedZone {
+    pub name: String,
+    pub class: String,
+    /// View name; `None` for the default view
+    pub view: Option<String>,
+    /// SOA serial of the dumped zone contents
+    pub serial: Option<u32>,
+    /// Built-in empty zone (RFC 6303) that BIND creates on its own
+    pub automatic_empty: bool,
+}
+
+/// What `rndc dumpdb` writes to the dump file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DumpScope {
+    /// Cache, zones and resolver state
+    All,
+    /// The cache only (rndc's default)
+    #[default]
+    Cache,
+    /// Zone contents only
+    Zones,
+    /// The address database
+    Adb,
+    /// The bad-server cache
+    Bad,
+    /// Expired cache entries too
+    Expired,
+    /// The SERVFAIL cache
+    Fail,
+}
+
+impl DumpScope {
+    /// The `rndc dumpdb` flag for this scope
+    pub fn flag(&self) -> &'static str {
+        match self {
+            DumpScope::All => "-all",
+            DumpScope::Cache => "-cache",
+            DumpScope::Zones => "-zones",
+            DumpScope::Adb => "-adb",
+            DumpScope::Bad => "-bad",
+            DumpScope::Expired => "-expired",
+            DumpScope::Fail => "-fail",
+        }
+    }
+
+    /// Whether the dump lists zone contents
+    pub fn includes_zones(&self) -> bool {
+        matches!(self, DumpScope::All | DumpScope::Zones)
+    }
+}
+
+/// One block of counters in an `rndc stats` dump
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSection {
+    /// Section title, e.g. "Name Server Statistics"
+    pub name: String,
+    /// Bracketed scope within the section, e.g. "View: default" or a zone name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Counter values by counter name
+    pub counters: BTreeMap<String, u64>,
+}
+
+/// The latest dump in BIND's statistics file (`rndc stats`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsDump {
+    /// When the dump was written, in seconds since the Unix epoch
+    pub timestamp: i64,
+    /// Sections that have at least one counter, in file order
+    pub sections: Vec<StatsSection>,
+}
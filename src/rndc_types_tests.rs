@@ -260,13 +260,13 @@ mod tests {
     fn test_to_rndc_block_with_also_notify() {
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
         config.also_notify = Some(vec![
-            "10.0.0.1".parse().unwrap(),
-            "10.0.0.2".parse().unwrap(),
+            PrimarySpec::new("10.0.0.1".parse().unwrap()),
+            PrimarySpec::with_port("10.0.0.2".parse().unwrap(), 5353),
         ]);
 
         let block = config.to_rndc_block();
 
-        assert!(block.contains("also-notify { 10.0.0.1; 10.0.0.2; }"));
+        assert!(block.contains("also-notify { 10.0.0.1; 10.0.0.2 port 5353; }"));
     }
 
     #[test]
@@ -290,9 +290,9 @@ mod tests {
     }
 
     #[test]
-    fn test_to_rndc_block_with_allow_update_raw() {
+    fn test_to_rndc_block_with_allow_update_key() {
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
-        config.allow_update_raw = Some(r#"{ key "update-key"; };"#.to_string());
+        config.allow_update = Some(vec![AddressMatchElement::Key("update-key".to_string())]);
 
         let block = config.to_rndc_block();
 
@@ -301,17 +301,66 @@ mod tests {
     }
 
     #[test]
-    fn test_to_rndc_block_prefers_raw_over_ips() {
+    fn test_to_rndc_block_renders_match_list_elements_in_order() {
         let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
-        config.allow_update = Some(vec!["10.1.1.1".parse().unwrap()]);
-        config.allow_update_raw = Some(r#"{ key "mykey"; };"#.to_string());
+        config.allow_transfer = Some(vec![
+            AddressMatchElement::negated("10.244.1.1".parse().unwrap()),
+            AddressMatchElement::Prefix {
+                address: "10.244.0.0".parse().unwrap(),
+                len: 16,
+            },
+            AddressMatchElement::Key("transfer-key".to_string()),
+            AddressMatchElement::Acl("localnets".to_string()),
+            AddressMatchElement::List(vec![AddressMatchElement::Address(
+                "2001:db8::1".parse().unwrap(),
+            )]),
+        ]);
 
         let block = config.to_rndc_block();
 
-        // Raw should be used
-        assert!(block.contains("allow-update { key \"mykey\"; }"));
-        // IP should not appear
-        assert!(!block.contains("10.1.1.1"));
+        assert!(block.contains(
+            r#"allow-transfer { !10.244.1.1; 10.244.0.0/16; key "transfer-key"; localnets; { 2001:db8::1; }; }"#
+        ));
+    }
+
+    #[test]
+    fn test_to_rndc_block_with_update_policy() {
+        let mut config = ZoneConfig::new("test.com".to_string(), ZoneType::Primary);
+        config.update_policy = Some(UpdatePolicy::Rules(vec![
+            UpdatePolicyRule {
+                action: UpdatePolicyAction::Grant,
+                identity: "tenant1-key".to_string(),
+                rule_type: UpdatePolicyRuleType::Name,
+                name: Some("_acme-challenge.test.com.".to_string()),
+                types: vec!["TXT".to_string()],
+            },
+            UpdatePolicyRule {
+                action: UpdatePolicyAction::Deny,
+                identity: "*".to_string(),
+                rule_type: UpdatePolicyRuleType::Zonesub,
+                name: None,
+                types: vec![],
+            },
+        ]));
+
+        let block = config.to_rndc_block();
+        assert!(block.contains(
+            "update-policy { grant tenant1-key name _acme-challenge.test.com. TXT; deny * zonesub; };"
+        ));
+
+        config.update_policy = Some(UpdatePolicy::Local);
+        assert!(config.to_rndc_block().contains("update-policy local;"));
+    }
+
+    #[test]
+    fn test_update_policy_rule_json() {
+        let rule: UpdatePolicyRule = serde_json::from_str(
+            r#"{"action": "grant", "identity": "*", "ruleType": "tcp-self", "name": ".", "types": ["PTR"]}"#,
+        )
+        .unwrap();
+        assert_eq!(rule.rule_type, UpdatePolicyRuleType::TcpSelf);
+        assert_eq!(rule.to_string(), "grant * tcp-self . PTR");
+        assert_eq!(serde_json::to_value(&rule).unwrap()["ruleType"], "tcp-self");
     }
 
     #[test]
@@ -376,9 +425,9 @@ mod tests {
     fn test_to_rndc_block_comprehensive() {
         let mut config = ZoneConfig::new("example.com".to_string(), ZoneType::Primary);
         config.file = Some("/var/cache/bind/example.com.zone".to_string());
-        config.also_notify = Some(vec!["10.1.1.1".parse().unwrap()]);
+        config.also_notify = Some(vec![PrimarySpec::new("10.1.1.1".parse().unwrap())]);
         config.allow_transfer = Some(vec!["10.2.2.2".parse().unwrap()]);
-        config.allow_update_raw = Some(r#"{ key "mykey"; };"#.to_string());
+        config.allow_update = Some(vec![AddressMatchElement::Key("mykey".to_string())]);
         config.notify = Some(NotifyMode::Yes);
         config.max_transfer_time_in = Some(3600);
         config.inline_signing = Some(true);
@@ -498,4 +547,30 @@ mod tests {
         // Should be minimal
         assert_eq!(block, "{ type primary; };");
     }
+
+    #[test]
+    fn test_dump_scope_flags() {
+        assert_eq!(DumpScope::default(), DumpScope::Cache);
+        assert_eq!(DumpScope::All.flag(), "-all");
+        assert_eq!(DumpScope::Zones.flag(), "-zones");
+        assert!(DumpScope::All.includes_zones());
+        assert!(!DumpScope::Cache.includes_zones());
+        assert_eq!(
+            serde_json::from_str::<DumpScope>(r#""expired""#).unwrap(),
+            DumpScope::Expired
+        );
+    }
+
+    #[test]
+    fn test_zone_type_serde_uses_bind_names() {
+        assert_eq!(
+            serde_json::to_string(&ZoneType::Delegation).unwrap(),
+            r#""delegation-only""#
+        );
+        assert_eq!(
+            serde_json::from_str::<ZoneType>(r#""slave""#).unwrap(),
+            ZoneType::Secondary
+        );
+        assert!(serde_json::from_str::<ZoneType>(r#""bogus""#).is_err());
+    }
 }
@@ -0,0 +1,377 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Server-wide rndc operations
+//!
+//! This module implements HTTP handlers for maintenance that applies to the
+//! whole server rather than one zone:
+//! - Reloading the configuration (`reconfig`) or everything (`reload`)
+//! - Flushing the cache, a name or a subtree (`flush`, `flushname`, `flushtree`)
+//! - Writing dynamic zone journals to their files (`sync -clean`)
+//! - Dumping the cache or zones (`dumpdb`)
+//! - Reading the server statistics (`stats`)
+//!
+//! `dumpdb` and `stats` only make BIND write a file into its working
+//! directory, which bindcar shares with it (see [`crate::bind_files`]). The
+//! handlers wait for the file there, one dump at a time, and return its
+//! parsed contents.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    rndc_parser,
+    rndc_types::{DumpScope, DumpedZone, StatsDump},
+    types::{ApiError, AppState},
+    zones::{validate_rndc_identifier, validate_zone_name},
+};
+
+/// Dump file written by `rndc dumpdb`, relative to BIND's working directory
+pub(crate) const BIND_DUMP_FILE_NAME: &str = "named_dump.db";
+
+/// Statistics file written by `rndc stats`, relative to BIND's working directory
+pub(crate) const BIND_STATS_FILE_NAME: &str = "named.stats";
+
+/// How long to wait for BIND to finish writing a dump or statistics file
+const BIND_FILE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Response from a server-wide rndc command
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ServerCommandResponse {
+    pub success: bool,
+    pub message: String,
+    /// rndc output, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+/// Request to flush the cache
+///
+/// An empty request flushes every cache. With `name`, only that name is
+/// flushed, or the name and everything below it with `tree`.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FlushRequest {
+    /// Name to flush instead of the whole cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Also flush every name below `name`
+    #[serde(default)]
+    pub tree: bool,
+
+    /// Only flush this view's cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view: Option<String>,
+}
+
+/// Query parameters of a database dump
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DumpQuery {
+    /// "all", "cache" (default), "zones", "adb", "bad", "expired" or "fail"
+    pub scope: Option<DumpScope>,
+}
+
+/// Result of a database dump
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpResponse {
+    pub scope: DumpScope,
+    /// Path of the dump file in the zone directory
+    pub file: String,
+    /// Size of the dump file in bytes
+    pub size_bytes: u64,
+    /// Zones in the dump, for the "all" and "zones" scopes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zones: Option<Vec<DumpedZone>>,
+}
+
+/// Validate a flush request.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] for an invalid name or view, or for
+/// `tree` without a name.
+pub(crate) fn validate_flush_request(request: &FlushRequest) -> Result<(), ApiError> {
+    if let Some(name) = &request.name {
+        validate_zone_name(name)?;
+    } else if request.tree {
+        return Err(ApiError::InvalidRequest(
+            "tree requires a name to flush".to_string(),
+        ));
+    }
+    if let Some(view) = &request.view {
+        validate_rndc_identifier("view", view)?;
+    }
+    Ok(())
+}
+
+/// Wait for BIND to write a file in its working directory, then parse it.
+///
+/// The file counts once `parse` accepts its contents, i.e. BIND has finished
+/// writing it. Modification times are too coarse to tell a new file from an
+/// old one, so callers remove the previous file first or have `parse` check
+/// the timestamp BIND writes into it.
+pub(crate) async fn read_bind_file<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Option<T>,
+) -> anyhow::Result<T> {
+    let deadline = tokio::time::Instant::now() + BIND_FILE_TIMEOUT;
+    loop {
+        match tokio::fs::read_to_string(path).await {
+            Ok(content) => {
+                if let Some(parsed) = parse(&content) {
+                    return Ok(parsed);
+                }
+            }
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            Err(_) => {}
+        }
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!("timed out waiting for {}", path.display());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Whether an `rndc dumpdb` file is complete
+pub(crate) fn is_complete_dump(dump: &str) -> bool {
+    dump.trim_end().ends_with("; Dump complete")
+}
+
+fn command_response(command: &str, output: String) -> Json<ServerCommandResponse> {
+    info!("rndc {} completed", command);
+    Json(ServerCommandResponse {
+        success: true,
+        message: format!("rndc {} completed", command),
+        details: (!output.trim().is_empty()).then_some(output),
+    })
+}
+
+fn command_failed(command: &str, e: anyhow::Error) -> ApiError {
+    error!("RNDC {} failed: {}", command, e);
+    ApiError::RndcError(e.to_string())
+}
+
+/// Reload the configuration file and load new zones
+///
+/// Runs `rndc reconfig`. Existing zones are not reloaded.
+#[utoipa::path(
+    post,
+    path = "/api/v1/server/reconfig",
+    responses(
+        (status = 200, description = "Configuration reloaded", body = ServerCommandResponse),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "server"
+)]
+pub async fn reconfig(
+    State(state): State<AppState>,
+) -> Result<Json<ServerCommandResponse>, ApiError> {
+    info!("Reloading server configuration");
+
+    let output = state
+        .rndc
+        .reconfig()
+        .await
+        .map_err(|e| command_failed("reconfig", e))?;
+    Ok(command_response("reconfig", output))
+}
+
+/// Reload the configuration file and all zones
+///
+/// Runs `rndc reload` without a zone name.
+#[utoipa::path(
+    post,
+    path = "/api/v1/server/reload",
+    responses(
+        (status = 200, description = "Server reloaded", body = ServerCommandResponse),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "server"
+)]
+pub async fn reload_server(
+    State(state): State<AppState>,
+) -> Result<Json<ServerCommandResponse>, ApiError> {
+    info!("Reloading server");
+
+    let output = state
+        .rndc
+        .reload_all()
+        .await
+        .map_err(|e| command_failed("reload", e))?;
+    Ok(command_response("reload", output))
+}
+
+/// Flush the cache
+///
+/// Runs `rndc flush`, `flushname` or `flushtree` depending on the request.
+/// The body may be omitted to flush every cache.
+#[utoipa::path(
+    post,
+    path = "/api/v1/server/flush",
+    request_body(content = Option<FlushRequest>, description = "What to flush; omit to flush every cache"),
+    responses(
+        (status = 200, description = "Cache flushed", body = ServerCommandResponse),
+        (status = 400, description = "Invalid name or view"),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "server"
+)]
+pub async fn flush_cache(
+    State(state): State<AppState>,
+    request: Option<Json<FlushRequest>>,
+) -> Result<Json<ServerCommandResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    validate_flush_request(&request)?;
+    let view = request.view.as_deref();
+
+    let (command, result) = match (&request.name, request.tree) {
+        (None, _) => {
+            info!("Flushing cache");
+            ("flush", state.rndc.flush(view).await)
+        }
+        (Some(name), false) => {
+            info!("Flushing {} from the cache", name);
+            ("flushname", state.rndc.flushname(name, view).await)
+        }
+        (Some(name), true) => {
+            info!("Flushing {} and below from the cache", name);
+            ("flushtree", state.rndc.flushtree(name, view).await)
+        }
+    };
+    let output = result.map_err(|e| command_failed(command, e))?;
+    Ok(command_response(command, output))
+}
+
+/// Write dynamic zone journals to their zone files
+///
+/// Runs `rndc sync -clean`, which also removes the journal files.
+#[utoipa::path(
+    post,
+    path = "/api/v1/server/sync",
+    responses(
+        (status = 200, description = "Zones synced", body = ServerCommandResponse),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "server"
+)]
+pub async fn sync_zones(
+    State(state): State<AppState>,
+) -> Result<Json<ServerCommandResponse>, ApiError> {
+    info!("Syncing dynamic zones to their files");
+
+    let output = state
+        .rndc
+        .sync_clean()
+        .await
+        .map_err(|e| command_failed("sync", e))?;
+    Ok(command_response("sync -clean", output))
+}
+
+/// Dump the cache or zones to BIND's dump file
+///
+/// Runs `rndc dumpdb` and waits for BIND to finish writing the file. For the
+/// "all" and "zones" scopes the zones in the dump are listed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/server/dumpdb",
+    params(DumpQuery),
+    responses(
+        (status = 200, description = "Dump written", body = DumpResponse),
+        (status = 400, description = "Unknown scope"),
+        (status = 500, description = "RNDC command failed or the dump file could not be read")
+    ),
+    tag = "server"
+)]
+pub async fn dump_database(
+    State(state): State<AppState>,
+    Query(query): Query<DumpQuery>,
+) -> Result<Json<DumpResponse>, ApiError> {
+    let scope = query.scope.unwrap_or_default();
+    info!("Dumping server database ({})", scope.flag());
+
+    let _dumping = state.bind_files.exclusive().await;
+    let path = state.bind_files.dump_file();
+    state.bind_files.remove_dump_file().await.map_err(|e| {
+        error!("Failed to remove previous dump file: {}", e);
+        ApiError::InternalError(format!("Failed to remove previous dump file: {}", e))
+    })?;
+    state
+        .rndc
+        .dumpdb(scope)
+        .await
+        .map_err(|e| command_failed("dumpdb", e))?;
+
+    let (size_bytes, zones) = read_bind_file(&path, |dump| {
+        is_complete_dump(dump).then(|| {
+            let zones = scope
+                .includes_zones()
+                .then(|| rndc_parser::parse_dump_zones(dump));
+            (dump.len() as u64, zones)
+        })
+    })
+    .await
+    .map_err(|e| {
+        error!("Failed to read dump file: {:#}", e);
+        ApiError::InternalError(format!("Failed to read dump file: {:#}", e))
+    })?;
+
+    Ok(Json(DumpResponse {
+        scope,
+        file: path.display().to_string(),
+        size_bytes,
+        zones,
+    }))
+}
+
+/// Get server statistics
+///
+/// Runs `rndc stats` and returns the dump it appends to BIND's statistics
+/// file, as counters grouped by section and scope.
+#[utoipa::path(
+    get,
+    path = "/api/v1/server/stats",
+    responses(
+        (status = 200, description = "Server statistics", body = StatsDump),
+        (status = 500, description = "RNDC command failed or the statistics file could not be read")
+    ),
+    tag = "server"
+)]
+pub async fn server_stats(State(state): State<AppState>) -> Result<Json<StatsDump>, ApiError> {
+    info!("Getting server statistics");
+
+    let _dumping = state.bind_files.exclusive().await;
+    let path = state.bind_files.stats_file();
+    // Dumps are stamped in whole seconds
+    let started_secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    state
+        .rndc
+        .stats()
+        .await
+        .map_err(|e| command_failed("stats", e))?;
+
+    let stats = read_bind_file(&path, |content| {
+        rndc_parser::parse_stats_dump(content)
+            .ok()
+            .filter(|dump| dump.timestamp >= started_secs)
+    })
+    .await
+    .map_err(|e| {
+        error!("Failed to read statistics file: {:#}", e);
+        ApiError::InternalError(format!("Failed to read statistics file: {:#}", e))
+    })?;
+
+    Ok(Json(stats))
+}
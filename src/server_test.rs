@@ -0,0 +1,66 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for server-wide rndc operations
+
+#[cfg(test)]
+mod tests {
+    use crate::server::*;
+    use crate::types::ApiError;
+
+    fn flush(json: &str) -> Result<(), ApiError> {
+        validate_flush_request(&serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_validate_flush_request() {
+        flush("{}").unwrap();
+        flush(r#"{"name": "example.com"}"#).unwrap();
+        flush(r#"{"name": "example.com", "tree": true, "view": "internal"}"#).unwrap();
+
+        for json in [
+            r#"{"tree": true}"#,
+            r#"{"name": "example.com; flush"}"#,
+            r#"{"view": "internal external"}"#,
+        ] {
+            assert!(
+                matches!(flush(json), Err(ApiError::InvalidRequest(_))),
+                "accepted {}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_complete_dump() {
+        assert!(is_complete_dump(
+            ";\n; Cache dump of view '_default'\n;\n; Dump complete\n"
+        ));
+        assert!(!is_complete_dump(";\n; Cache dump of view '_default'\n;\n"));
+    }
+
+    #[tokio::test]
+    async fn test_read_bind_file_waits_for_complete_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("named_dump.db");
+
+        let writer = {
+            let path = path.clone();
+            tokio::spawn(async move {
+                tokio::fs::write(&path, "; partial\n").await.unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+                tokio::fs::write(&path, "; partial\n; Dump complete\n")
+                    .await
+                    .unwrap();
+            })
+        };
+
+        let content = read_bind_file(&path, |dump| {
+            is_complete_dump(dump).then(|| dump.to_string())
+        })
+        .await
+        .unwrap();
+        writer.await.unwrap();
+        assert!(content.ends_with("; Dump complete\n"));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! SOA serial number management
+//!
+//! Secondaries only pick up a zone change when the SOA serial increases
+//! (RFC 1982 serial arithmetic). Rather than trusting the serial a client
+//! sends, bindcar can generate it with a [`SerialStrategy`] and always moves
+//! it forward from the serial BIND currently serves, as reported by
+//! `rndc zonestatus`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bindcar::soa_serial::{resolve_serial, SerialStrategy};
+//!
+//! // Date-based serial for 2025-01-15, zone currently at the same day's 03
+//! let now = chrono::DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z").unwrap();
+//! let serial = resolve_serial(Some(SerialStrategy::Date), 1, Some(2025011503), now.into());
+//! assert_eq!(serial, 2025011504);
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How bindcar chooses the SOA serial of a generated zone file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SerialStrategy {
+    /// Current serial plus one; the requested serial when the zone is new
+    Increment,
+    /// Seconds since the Unix epoch
+    Unixtime,
+    /// `YYYYMMDDnn`, starting at `nn = 01` each day
+    Date,
+}
+
+/// RFC 1982 serial number comparison: whether `a` is greater than `b`.
+pub fn serial_gt(a: u32, b: u32) -> bool {
+    a != b && a.wrapping_sub(b) < 0x8000_0000
+}
+
+/// Serial produced by a strategy before the monotonicity check.
+fn candidate_serial(
+    strategy: SerialStrategy,
+    requested: u32,
+    current: Option<u32>,
+    now: DateTime<Utc>,
+) -> u32 {
+    match strategy {
+        SerialStrategy::Increment => current.map_or(requested, |c| c.wrapping_add(1)),
+        SerialStrategy::Unixtime => now.timestamp() as u32,
+        SerialStrategy::Date => now
+            .format("%Y%m%d01")
+            .to_string()
+            .parse()
+            .unwrap_or(requested),
+    }
+}
+
+/// Serial to publish for a zone.
+///
+/// Without a strategy the `requested` serial is used as-is. Whatever the
+/// candidate, when the `current` serial is known the result is guaranteed to
+/// be greater than it in RFC 1982 terms, falling back to `current + 1`
+/// (wrapping past 2^32).
+pub fn resolve_serial(
+    strategy: Option<SerialStrategy>,
+    requested: u32,
+    current: Option<u32>,
+    now: DateTime<Utc>,
+) -> u32 {
+    let candidate = strategy.map_or(requested, |s| candidate_serial(s, requested, current, now));
+
+    match current {
+        Some(current) if !serial_gt(candidate, current) => current.wrapping_add(1),
+        _ => candidate,
+    }
+}
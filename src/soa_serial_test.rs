@@ -0,0 +1,77 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for SOA serial management
+
+#[cfg(test)]
+mod tests {
+    use crate::soa_serial::*;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_serial_gt_uses_serial_arithmetic() {
+        assert!(serial_gt(10, 5));
+        assert!(!serial_gt(5, 10));
+        assert!(!serial_gt(7, 7));
+        assert!(serial_gt(3, u32::MAX));
+        assert!(!serial_gt(0x8000_0000, 0));
+    }
+
+    #[test]
+    fn test_without_strategy_requested_serial_must_advance() {
+        assert_eq!(resolve_serial(None, 10, Some(5), now()), 10);
+        assert_eq!(resolve_serial(None, 5, Some(10), now()), 11);
+        assert_eq!(resolve_serial(None, 10, Some(10), now()), 11);
+        assert_eq!(resolve_serial(None, 3, Some(u32::MAX), now()), 3);
+        assert_eq!(resolve_serial(None, u32::MAX, Some(u32::MAX), now()), 0);
+        assert_eq!(resolve_serial(None, 42, None, now()), 42);
+    }
+
+    #[test]
+    fn test_increment_strategy() {
+        let strategy = Some(SerialStrategy::Increment);
+        assert_eq!(resolve_serial(strategy, 1, Some(41), now()), 42);
+        assert_eq!(resolve_serial(strategy, 1, Some(u32::MAX), now()), 0);
+        assert_eq!(resolve_serial(strategy, 7, None, now()), 7);
+    }
+
+    #[test]
+    fn test_unixtime_strategy() {
+        let strategy = Some(SerialStrategy::Unixtime);
+        let ts = now().timestamp() as u32;
+        assert_eq!(resolve_serial(strategy, 1, None, now()), ts);
+        assert_eq!(resolve_serial(strategy, 1, Some(ts - 100), now()), ts);
+        // Clock behind the served serial: still moves forward
+        assert_eq!(resolve_serial(strategy, 1, Some(ts + 100), now()), ts + 101);
+    }
+
+    #[test]
+    fn test_date_strategy() {
+        let strategy = Some(SerialStrategy::Date);
+        assert_eq!(resolve_serial(strategy, 1, None, now()), 2025011501);
+        assert_eq!(
+            resolve_serial(strategy, 1, Some(2025011409), now()),
+            2025011501
+        );
+        assert_eq!(
+            resolve_serial(strategy, 1, Some(2025011507), now()),
+            2025011508
+        );
+        // Served serial from another scheme that is already far ahead
+        assert_eq!(
+            resolve_serial(strategy, 1, Some(3_000_000_000), now()),
+            3_000_000_001
+        );
+    }
+
+    #[test]
+    fn test_strategy_deserializes_lowercase() {
+        let strategy: SerialStrategy = serde_json::from_str("\"unixtime\"").unwrap();
+        assert_eq!(strategy, SerialStrategy::Unixtime);
+        assert!(serde_json::from_str::<SerialStrategy>("\"Date\"").is_err());
+    }
+}
@@ -8,11 +8,15 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::error;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::{nsupdate::NsupdateExecutor, rndc::RndcExecutor};
+use crate::{
+    bind_files::BindFiles, nsupdate::NsupdateExecutor, rndc::RndcExecutor,
+    zone_check::ZoneCheckIssue,
+};
 
 /// Application state shared across handlers
 #[derive(Clone)]
@@ -23,6 +27,42 @@ pub struct AppState {
     pub nsupdate: Arc<NsupdateExecutor>,
     /// Zone file directory
     pub zone_dir: String,
+    /// RFC 9432 catalog zone that created primary zones are added to
+    pub catalog_zone: Option<String>,
+    /// BIND's dump and statistics files and the served zone cache
+    pub bind_files: Arc<BindFiles>,
+}
+
+/// Query parameters accepted by every mutating zone and record endpoint
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct DryRunQuery {
+    /// Validate against the live server and return the plan without applying it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// What a mutating request would do, returned instead of executing it when
+/// `dryRun=true`
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationPlan {
+    /// Zone file content that would be written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone_file: Option<String>,
+
+    /// rndc commands that would run (e.g., `addzone example.com { ... };`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rndc_commands: Vec<String>,
+
+    /// Dynamic update that would be sent, in nsupdate syntax
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsupdate_script: Option<String>,
+
+    /// Files that would be removed from the zone directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_files: Vec<String>,
 }
 
 /// Error response
@@ -30,6 +70,9 @@ pub struct AppState {
 pub struct ErrorResponse {
     pub error: String,
     pub details: Option<String>,
+    /// Per-record problems, for zone consistency check failures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues: Option<Vec<ZoneCheckIssue>>,
 }
 
 /// API error type
@@ -61,6 +104,18 @@ pub enum ApiError {
 
     #[error("Invalid record: {0}")]
     InvalidRecord(String),
+
+    #[error("Record conflict: {0}")]
+    RecordConflict(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Update outcome unknown: {0}")]
+    UpdateOutcomeUnknown(String),
+
+    #[error("Zone consistency check failed: {} issue(s)", .0.len())]
+    ZoneCheckFailed(Vec<ZoneCheckIssue>),
 }
 
 /// Generic, non-revealing message returned to clients for any 5xx error.
@@ -84,6 +139,11 @@ impl IntoResponse for ApiError {
             ApiError::ZoneAlreadyExists(_) => (StatusCode::CONFLICT, self.to_string()),
             ApiError::DynamicUpdatesNotEnabled(_) => (StatusCode::BAD_REQUEST, self.to_string()),
             ApiError::InvalidRecord(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            ApiError::RecordConflict(_) => (StatusCode::CONFLICT, self.to_string()),
+            ApiError::PreconditionFailed(_) => (StatusCode::PRECONDITION_FAILED, self.to_string()),
+            ApiError::ZoneCheckFailed(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            // Built from the rcode only; the caller must re-read before retrying
+            ApiError::UpdateOutcomeUnknown(_) => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
             ApiError::ZoneFileError(_)
             | ApiError::RndcError(_)
             | ApiError::InternalError(_)
@@ -96,9 +156,15 @@ impl IntoResponse for ApiError {
             }
         };
 
+        let issues = match self {
+            ApiError::ZoneCheckFailed(issues) => Some(issues),
+            _ => None,
+        };
+
         let body = Json(ErrorResponse {
             error: error_message,
             details: None,
+            issues,
         });
 
         (status, body).into_response()
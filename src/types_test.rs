@@ -33,6 +33,11 @@ fn test_app_state_clone() {
         rndc: rndc.clone(),
         nsupdate: nsupdate.clone(),
         zone_dir: "/test/dir".to_string(),
+        catalog_zone: None,
+        bind_files: Arc::new(crate::bind_files::BindFiles::new(
+            "/test/dir",
+            crate::bind_files::DEFAULT_ZONE_LIST_CACHE_TTL,
+        )),
     };
 
     let cloned = state.clone();
@@ -44,6 +49,7 @@ fn test_error_response_serialization() {
     let response = ErrorResponse {
         error: "Test error".to_string(),
         details: Some("Details here".to_string()),
+        issues: None,
     };
 
     let json = serde_json::to_string(&response).unwrap();
@@ -56,6 +62,7 @@ fn test_error_response_without_details() {
     let response = ErrorResponse {
         error: "Test error".to_string(),
         details: None,
+        issues: None,
     };
 
     let json = serde_json::to_string(&response).unwrap();
@@ -156,6 +163,29 @@ fn test_api_error_invalid_record() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+#[test]
+fn test_api_error_prerequisite_failures() {
+    let error = ApiError::RecordConflict("the RRset already exists".to_string());
+    assert_eq!(
+        error.to_string(),
+        "Record conflict: the RRset already exists"
+    );
+    assert_eq!(error.into_response().status(), StatusCode::CONFLICT);
+
+    let error = ApiError::PreconditionFailed("the RRset does not exist".to_string());
+    assert_eq!(
+        error.to_string(),
+        "Precondition failed: the RRset does not exist"
+    );
+    assert_eq!(
+        error.into_response().status(),
+        StatusCode::PRECONDITION_FAILED
+    );
+
+    let error = ApiError::UpdateOutcomeUnknown("the RRset does not exist".to_string());
+    assert_eq!(error.into_response().status(), StatusCode::GATEWAY_TIMEOUT);
+}
+
 /// A-3: 5xx response bodies must NOT echo internal detail (raw rndc/nsupdate
 /// stderr, paths). They are replaced with a generic message; the detail is
 /// logged server-side only.
@@ -212,6 +242,9 @@ fn test_api_error_display() {
         ApiError::DynamicUpdatesNotEnabled("test.com".to_string()),
         ApiError::NsupdateError("nsupdate error".to_string()),
         ApiError::InvalidRecord("invalid".to_string()),
+        ApiError::RecordConflict("conflict".to_string()),
+        ApiError::PreconditionFailed("precondition".to_string()),
+        ApiError::UpdateOutcomeUnknown("unknown".to_string()),
     ];
 
     for error in errors {
@@ -220,3 +253,52 @@ fn test_api_error_display() {
         let _ = format!("{:?}", error);
     }
 }
+
+#[test]
+fn test_operation_plan_serialization_skips_empty_fields() {
+    let plan = OperationPlan {
+        rndc_commands: vec!["delzone example.com".to_string()],
+        ..Default::default()
+    };
+
+    let json = serde_json::to_value(&plan).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "rndcCommands": ["delzone example.com"] })
+    );
+}
+
+#[test]
+fn test_dry_run_query_defaults_to_false() {
+    let query: DryRunQuery = serde_json::from_str("{}").unwrap();
+    assert!(!query.dry_run);
+    let query: DryRunQuery = serde_json::from_str(r#"{"dryRun": true}"#).unwrap();
+    assert!(query.dry_run);
+}
+
+#[tokio::test]
+async fn test_zone_check_error_body_lists_issues() {
+    let error = ApiError::ZoneCheckFailed(vec![crate::zone_check::ZoneCheckIssue {
+        check: "multiple-cname".to_string(),
+        severity: crate::zone_check::ZoneCheckSeverity::Error,
+        name: "www.example.com.".to_string(),
+        record_type: "CNAME".to_string(),
+        data: "other.example.net.".to_string(),
+        message: "www.example.com. has more than one CNAME record".to_string(),
+    }]);
+    assert_eq!(
+        error.to_string(),
+        "Zone consistency check failed: 1 issue(s)"
+    );
+
+    let response = error.into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["issues"][0]["check"], "multiple-cname");
+    assert_eq!(body["issues"][0]["severity"], "error");
+    assert_eq!(body["issues"][0]["type"], "CNAME");
+    assert_eq!(body["issues"][0]["name"], "www.example.com.");
+}
@@ -0,0 +1,352 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Bulk zone operations
+//!
+//! `POST /api/v1/zones:batch` runs `rndc reload`, `notify` or `retransfer` on
+//! many zones in one request, e.g. retransferring every secondary zone after a
+//! primary failover. Zones are named explicitly or picked with a selector
+//! (name prefix and/or zone type) from the zones BIND serves.
+//!
+//! Operations run with bounded concurrency, and each zone gets its own result:
+//! a zone that fails does not stop the others.
+
+use std::future::Future;
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tracing::{error, info};
+use utoipa::ToSchema;
+
+use crate::{
+    metrics,
+    rndc::RndcExecutor,
+    rndc_parser, rndc_types,
+    types::{ApiError, AppState},
+    zones::{self, validate_zone_name},
+};
+
+/// Largest number of zones one batch may operate on
+pub const MAX_BATCH_ZONES: usize = 5000;
+
+/// Default and maximum number of rndc commands in flight
+const DEFAULT_BATCH_CONCURRENCY: usize = 16;
+const MAX_BATCH_CONCURRENCY: usize = 64;
+
+/// Operation run on each zone of a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchOperation {
+    /// `rndc reload <zone>`
+    Reload,
+    /// `rndc notify <zone>`
+    Notify,
+    /// `rndc retransfer <zone>` (secondary zones)
+    Retransfer,
+}
+
+impl BatchOperation {
+    /// Operation name, as used in metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BatchOperation::Reload => "reload",
+            BatchOperation::Notify => "notify",
+            BatchOperation::Retransfer => "retransfer",
+        }
+    }
+
+    async fn run(&self, rndc: &RndcExecutor, zone_name: &str) -> anyhow::Result<String> {
+        match self {
+            BatchOperation::Reload => rndc.reload(zone_name).await,
+            BatchOperation::Notify => rndc.notify(zone_name).await,
+            BatchOperation::Retransfer => rndc.retransfer(zone_name).await,
+        }
+    }
+}
+
+/// Zones picked from those BIND serves in the default view
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ZoneSelector {
+    /// Only zones whose name starts with this prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// Only zones of this type (e.g. "primary", "secondary")
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub zone_type: Option<String>,
+}
+
+/// Request to run one operation on many zones
+///
+/// Exactly one of `zones` and `selector` must be given.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchZoneRequest {
+    pub operation: BatchOperation,
+
+    /// Zones to operate on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zones: Option<Vec<String>>,
+
+    /// Select the zones instead of naming them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<ZoneSelector>,
+
+    /// Number of rndc commands in flight (default 16, max 64)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+}
+
+/// Outcome of the operation on one zone
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchZoneResult {
+    pub zone: String,
+    pub success: bool,
+    /// rndc output on success
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    /// rndc error on failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response of a batch operation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchZoneResponse {
+    pub operation: BatchOperation,
+    /// Number of zones the operation ran on
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// One result per zone, in request order (selected zones sorted by name)
+    pub results: Vec<BatchZoneResult>,
+}
+
+/// Validate the shape of a batch request and return its concurrency.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] unless exactly one of `zones` and
+/// `selector` is given, or if the concurrency or zone list is out of bounds.
+pub(crate) fn validate_batch_request(request: &BatchZoneRequest) -> Result<usize, ApiError> {
+    match (&request.zones, &request.selector) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::InvalidRequest(
+                "zones and selector cannot both be given".to_string(),
+            ))
+        }
+        (None, None) => {
+            return Err(ApiError::InvalidRequest(
+                "Either zones or selector is required".to_string(),
+            ))
+        }
+        (Some(zones), None) => {
+            if zones.is_empty() {
+                return Err(ApiError::InvalidRequest(
+                    "zones cannot be empty".to_string(),
+                ));
+            }
+            if zones.len() > MAX_BATCH_ZONES {
+                return Err(ApiError::InvalidRequest(format!(
+                    "A batch can name at most {} zones",
+                    MAX_BATCH_ZONES
+                )));
+            }
+            for zone in zones {
+                validate_zone_name(zone)?;
+            }
+        }
+        (None, Some(selector)) => {
+            if let Some(zone_type) = &selector.zone_type {
+                rndc_types::ZoneType::parse(zone_type).ok_or_else(|| {
+                    ApiError::InvalidRequest(format!("Unknown zone type: {}", zone_type))
+                })?;
+            }
+        }
+    }
+
+    let concurrency = request.concurrency.unwrap_or(DEFAULT_BATCH_CONCURRENCY);
+    if concurrency == 0 || concurrency > MAX_BATCH_CONCURRENCY {
+        return Err(ApiError::InvalidRequest(format!(
+            "concurrency must be between 1 and {}",
+            MAX_BATCH_CONCURRENCY
+        )));
+    }
+    Ok(concurrency)
+}
+
+/// Drop repeated zone names, keeping the first occurrence.
+///
+/// Names differing only in case or a trailing dot are the same zone.
+pub(crate) fn dedup_zone_names(zones: &[String]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    zones
+        .iter()
+        .filter(|zone| seen.insert(zone.trim_end_matches('.').to_ascii_lowercase()))
+        .cloned()
+        .collect()
+}
+
+/// Run `task` on every item with at most `concurrency` tasks in flight.
+///
+/// Results are returned in the order of `items`.
+pub(crate) async fn run_bounded<T, R, F, Fut>(items: Vec<T>, concurrency: usize, task: F) -> Vec<R>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+    R: Send + 'static,
+{
+    let mut tasks = JoinSet::new();
+    let mut results = Vec::with_capacity(items.len());
+    for (i, item) in items.into_iter().enumerate() {
+        if tasks.len() >= concurrency {
+            if let Some(done) = tasks.join_next().await {
+                results.push(done.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())));
+            }
+        }
+        let future = task(item);
+        tasks.spawn(async move { (i, future.await) });
+    }
+    while let Some(done) = tasks.join_next().await {
+        results.push(done.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())));
+    }
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Resolve a selector to the matching zones BIND serves, sorted by name.
+async fn select_zones(
+    state: &AppState,
+    selector: &ZoneSelector,
+    concurrency: usize,
+) -> Result<Vec<String>, ApiError> {
+    let served = zones::served_zone_names(state).await.map_err(|e| {
+        error!("Failed to list served zones for batch: {:#}", e);
+        ApiError::RndcError(format!("Failed to list served zones: {:#}", e))
+    })?;
+    let candidates: Vec<String> = served
+        .into_iter()
+        .filter(|name| {
+            selector
+                .prefix
+                .as_ref()
+                .is_none_or(|p| name.starts_with(p.as_str()))
+        })
+        .collect();
+
+    let Some(zone_type) = selector
+        .zone_type
+        .as_deref()
+        .and_then(rndc_types::ZoneType::parse)
+    else {
+        return Ok(candidates);
+    };
+
+    // A zone whose status cannot be read is not selected
+    let types = run_bounded(candidates.clone(), concurrency, |name| {
+        let rndc = state.rndc.clone();
+        async move {
+            let output = rndc.zonestatus(&name).await.ok()?;
+            rndc_parser::parse_zonestatus(&output).ok()?.zone_type
+        }
+    })
+    .await;
+
+    Ok(candidates
+        .into_iter()
+        .zip(types)
+        .filter(|(_, t)| t.as_ref() == Some(&zone_type))
+        .map(|(name, _)| name)
+        .collect())
+}
+
+/// Run an operation on many zones
+///
+/// Reloads, notifies or retransfers the named or selected zones with bounded
+/// concurrency. The response is 200 with a result per zone even if some of
+/// them failed; check `failed`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/zones:batch",
+    request_body = BatchZoneRequest,
+    responses(
+        (status = 200, description = "Operation run on every zone; see per-zone results", body = BatchZoneResponse),
+        (status = 400, description = "Invalid request, zone name or selector"),
+        (status = 500, description = "Served zones could not be listed for the selector")
+    ),
+    tag = "zones"
+)]
+pub async fn batch_zones(
+    State(state): State<AppState>,
+    Json(request): Json<BatchZoneRequest>,
+) -> Result<Json<BatchZoneResponse>, ApiError> {
+    let operation = request.operation;
+    info!("Batch {} requested", operation.as_str());
+
+    let concurrency = validate_batch_request(&request)?;
+    let targets = match (&request.zones, &request.selector) {
+        (Some(zones), _) => dedup_zone_names(zones),
+        (None, Some(selector)) => select_zones(&state, selector, concurrency).await?,
+        (None, None) => unreachable!("rejected by validate_batch_request"),
+    };
+    if targets.len() > MAX_BATCH_ZONES {
+        return Err(ApiError::InvalidRequest(format!(
+            "Selector matches {} zones; a batch can operate on at most {}",
+            targets.len(),
+            MAX_BATCH_ZONES
+        )));
+    }
+
+    info!(
+        "Running {} on {} zones ({} at a time)",
+        operation.as_str(),
+        targets.len(),
+        concurrency
+    );
+
+    let results = run_bounded(targets, concurrency, |zone| {
+        let rndc = state.rndc.clone();
+        async move {
+            let outcome = operation.run(&rndc, &zone).await;
+            metrics::record_zone_operation(operation.as_str(), outcome.is_ok());
+            match outcome {
+                Ok(output) => BatchZoneResult {
+                    zone,
+                    success: true,
+                    details: Some(output),
+                    error: None,
+                },
+                Err(e) => {
+                    error!("RNDC {} failed for {}: {}", operation.as_str(), zone, e);
+                    BatchZoneResult {
+                        zone,
+                        success: false,
+                        details: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            }
+        }
+    })
+    .await;
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let failed = results.len() - succeeded;
+    info!(
+        "Batch {} finished: {} succeeded, {} failed",
+        operation.as_str(),
+        succeeded,
+        failed
+    );
+
+    Ok(Json(BatchZoneResponse {
+        operation,
+        total: results.len(),
+        succeeded,
+        failed,
+        results,
+    }))
+}
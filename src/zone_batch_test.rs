@@ -0,0 +1,167 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for bulk zone operations
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    use crate::types::{ApiError, AppState};
+    use crate::zone_batch::*;
+    use crate::{NsupdateExecutor, RndcExecutor};
+
+    fn request(json: &str) -> BatchZoneRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn assert_invalid(result: Result<usize, ApiError>, expected: &str) {
+        match result {
+            Err(ApiError::InvalidRequest(msg)) => {
+                assert!(msg.contains(expected), "unexpected message: {}", msg)
+            }
+            other => panic!("expected InvalidRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_batch_request() {
+        assert_eq!(
+            validate_batch_request(&request(
+                r#"{"operation": "retransfer", "zones": ["example.com", "example.org"]}"#
+            ))
+            .unwrap(),
+            16
+        );
+        assert_eq!(
+            validate_batch_request(&request(
+                r#"{"operation": "reload", "selector": {"prefix": "tenant-", "type": "secondary"}, "concurrency": 4}"#
+            ))
+            .unwrap(),
+            4
+        );
+
+        let cases = [
+            (r#"{"operation": "reload"}"#, "Either zones or selector"),
+            (
+                r#"{"operation": "reload", "zones": ["a.com"], "selector": {}}"#,
+                "cannot both",
+            ),
+            (r#"{"operation": "notify", "zones": []}"#, "cannot be empty"),
+            (
+                r#"{"operation": "notify", "zones": ["a.com", "../etc"]}"#,
+                "",
+            ),
+            (
+                r#"{"operation": "notify", "selector": {"type": "bogus"}}"#,
+                "Unknown zone type",
+            ),
+            (
+                r#"{"operation": "notify", "zones": ["a.com"], "concurrency": 0}"#,
+                "concurrency",
+            ),
+            (
+                r#"{"operation": "notify", "zones": ["a.com"], "concurrency": 65}"#,
+                "concurrency",
+            ),
+        ];
+        for (json, expected) in cases {
+            assert_invalid(validate_batch_request(&request(json)), expected);
+        }
+
+        let too_many = BatchZoneRequest {
+            operation: BatchOperation::Reload,
+            zones: Some(vec!["example.com".to_string(); MAX_BATCH_ZONES + 1]),
+            selector: None,
+            concurrency: None,
+        };
+        assert_invalid(validate_batch_request(&too_many), "at most");
+    }
+
+    #[test]
+    fn test_unknown_operation_rejected_by_serde() {
+        let result: Result<BatchZoneRequest, _> =
+            serde_json::from_str(r#"{"operation": "delete", "zones": ["example.com"]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_dedup_zone_names_keeps_first() {
+        let zones: Vec<String> = ["b.com", "a.com", "B.com.", "a.com"]
+            .iter()
+            .map(|z| z.to_string())
+            .collect();
+        assert_eq!(dedup_zone_names(&zones), ["b.com", "a.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_limits_concurrency_and_keeps_order() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let results = run_bounded((0..50).collect(), 4, |i: u64| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                // Later items finish first
+                tokio::time::sleep(std::time::Duration::from_millis(50 - i)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i * 2
+            }
+        })
+        .await;
+
+        assert_eq!(results, (0..50).map(|i| i * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_route_is_not_a_zone_name() {
+        let state = AppState {
+            rndc: Arc::new(
+                RndcExecutor::new(
+                    "127.0.0.1:953".to_string(),
+                    "sha256".to_string(),
+                    "dGVzdC1zZWNyZXQtaGVyZQ==".to_string(),
+                )
+                .unwrap(),
+            ),
+            nsupdate: Arc::new(
+                NsupdateExecutor::new("127.0.0.1".to_string(), 53, None, None, None).unwrap(),
+            ),
+            zone_dir: "/tmp".to_string(),
+            catalog_zone: None,
+            bind_files: Arc::new(crate::bind_files::BindFiles::new(
+                "/tmp",
+                crate::bind_files::DEFAULT_ZONE_LIST_CACHE_TTL,
+            )),
+        };
+        async fn zone_handler() -> &'static str {
+            "zone"
+        }
+        let app = Router::new()
+            .route("/zones:batch", post(batch_zones))
+            .route("/zones/{name}", get(zone_handler).post(zone_handler))
+            .with_state(state);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/zones:batch")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"operation": "reload"}"#))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
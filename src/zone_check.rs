@@ -0,0 +1,284 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Zone consistency checks in the spirit of `named-checkzone`
+//!
+//! BIND only rejects an inconsistent zone after its file has been written and
+//! `rndc addzone` has run. These checks run on the rendered zone before any
+//! of that happens, and report every problem with the record that caused it:
+//!
+//! - exactly one SOA, at the apex, and at least one apex NS
+//! - a CNAME owner has no other data, and only one CNAME
+//! - NS targets are not CNAMEs
+//! - in-zone NS targets have address records; a target below a delegation
+//!   needs glue when it is below the NS record's own delegation
+//!
+//! Problems BIND only warns about and loads the zone anyway are reported as
+//! warnings: data outside the zone (which BIND ignores), missing sibling
+//! glue (a target below another delegation of the same zone), and MX and SRV
+//! targets that are CNAMEs (`check-mx-cname` and `check-srv-cname` default to
+//! `warn`).
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::zone_file_parser::{zone_apex, ResourceRecord};
+
+/// Types that may coexist with a CNAME (RFC 2181, RFC 4035).
+const CNAME_COMPATIBLE_TYPES: &[&str] = &["CNAME", "RRSIG", "NSEC", "NSEC3"];
+
+/// Whether an issue stops the zone from loading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ZoneCheckSeverity {
+    /// BIND would refuse to load the zone
+    Error,
+    /// BIND loads the zone, ignoring or tolerating the record
+    Warning,
+}
+
+/// One consistency problem, tied to the record that caused it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneCheckIssue {
+    /// Identifier of the failed check (e.g. "cname-and-other-data")
+    pub check: String,
+
+    /// Whether the issue is an error or a warning
+    pub severity: ZoneCheckSeverity,
+
+    /// Owner name of the offending record (absolute)
+    pub name: String,
+
+    /// Type of the offending record
+    #[serde(rename = "type")]
+    pub record_type: String,
+
+    /// RDATA of the offending record
+    pub data: String,
+
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl ZoneCheckIssue {
+    fn new(check: &str, record: &ResourceRecord, message: String) -> Self {
+        Self {
+            check: check.to_string(),
+            severity: ZoneCheckSeverity::Error,
+            name: record.name.clone(),
+            record_type: record.record_type.clone(),
+            data: record.data.clone(),
+            message,
+        }
+    }
+
+    fn warning(check: &str, record: &ResourceRecord, message: String) -> Self {
+        Self {
+            severity: ZoneCheckSeverity::Warning,
+            ..Self::new(check, record, message)
+        }
+    }
+
+    /// Whether BIND would refuse to load the zone because of this issue
+    pub fn is_error(&self) -> bool {
+        self.severity == ZoneCheckSeverity::Error
+    }
+}
+
+/// Whether `name` is at or below `apex` (both absolute, lower case).
+fn in_zone(name: &str, apex: &str) -> bool {
+    apex == "." || name == apex || name.ends_with(&format!(".{}", apex))
+}
+
+/// Whether a zone-file owner name, as written, lies outside the zone.
+///
+/// Relative names and `@` are always inside it.
+pub fn is_out_of_zone(name: &str, zone_name: &str) -> bool {
+    name.ends_with('.')
+        && !in_zone(
+            &name.to_ascii_lowercase(),
+            &zone_apex(zone_name).to_ascii_lowercase(),
+        )
+}
+
+/// Target name of an NS, MX or SRV record, lower case.
+fn target_of(record: &ResourceRecord) -> Option<String> {
+    let field = match record.record_type.as_str() {
+        "NS" => 0,
+        "MX" => 1,
+        "SRV" => 3,
+        _ => return None,
+    };
+    record
+        .data
+        .split_whitespace()
+        .nth(field)
+        .map(str::to_ascii_lowercase)
+}
+
+/// Check a zone's records for consistency.
+///
+/// `records` must have absolute owner names and RDATA, as produced by
+/// [`parse_records`](crate::zone_file_parser::parse_records). Returns every
+/// issue found, in record order; an empty list means the zone is consistent.
+pub fn check_zone(records: &[ResourceRecord], zone_name: &str) -> Vec<ZoneCheckIssue> {
+    let apex = zone_apex(zone_name).to_ascii_lowercase();
+    let mut issues = Vec::new();
+
+    let mut types_by_owner: HashMap<String, HashSet<&str>> = HashMap::new();
+    for record in records {
+        types_by_owner
+            .entry(record.name.to_ascii_lowercase())
+            .or_default()
+            .insert(record.record_type.as_str());
+    }
+    let has_type = |name: &str, record_type: &str| {
+        types_by_owner
+            .get(name)
+            .is_some_and(|types| types.contains(record_type))
+    };
+
+    // Delegation points: in-zone owners of NS records below the apex
+    let cuts: HashSet<&str> = types_by_owner
+        .iter()
+        .filter(|(owner, types)| {
+            types.contains("NS") && owner.as_str() != apex && in_zone(owner, &apex)
+        })
+        .map(|(owner, _)| owner.as_str())
+        .collect();
+    // The delegation point at or above `name`, if any
+    let cut_above = |name: &str| {
+        let mut labels = name;
+        while in_zone(labels, &apex) && labels != apex {
+            if cuts.contains(labels) {
+                return Some(labels.to_string());
+            }
+            labels = labels.split_once('.').map_or(".", |(_, parent)| parent);
+        }
+        None
+    };
+
+    let mut soa_seen = false;
+    let mut cnames_seen = HashSet::new();
+
+    for record in records {
+        let owner = record.name.to_ascii_lowercase();
+
+        if !in_zone(&owner, &apex) {
+            issues.push(ZoneCheckIssue::warning(
+                "out-of-zone",
+                record,
+                format!("{} is not within zone {} and is ignored", record.name, apex),
+            ));
+            continue;
+        }
+
+        match record.record_type.as_str() {
+            "SOA" if owner != apex => issues.push(ZoneCheckIssue::new(
+                "soa-not-at-apex",
+                record,
+                "SOA record must be at the zone apex".to_string(),
+            )),
+            "SOA" if soa_seen => issues.push(ZoneCheckIssue::new(
+                "multiple-soa",
+                record,
+                "zone has more than one SOA record".to_string(),
+            )),
+            "SOA" => soa_seen = true,
+            "CNAME" => {
+                if !cnames_seen.insert(owner.clone()) {
+                    issues.push(ZoneCheckIssue::new(
+                        "multiple-cname",
+                        record,
+                        format!("{} has more than one CNAME record", record.name),
+                    ));
+                }
+                let mut others: Vec<_> = types_by_owner[&owner]
+                    .iter()
+                    .filter(|t| !CNAME_COMPATIBLE_TYPES.contains(t))
+                    .copied()
+                    .collect();
+                if !others.is_empty() {
+                    others.sort_unstable();
+                    issues.push(ZoneCheckIssue::new(
+                        "cname-and-other-data",
+                        record,
+                        format!(
+                            "{} has a CNAME and other data ({})",
+                            record.name,
+                            others.join(", ")
+                        ),
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(target) = target_of(record) {
+            if has_type(&target, "CNAME") {
+                let message = format!("{} target {} is a CNAME", record.record_type, target);
+                issues.push(if record.record_type == "NS" {
+                    ZoneCheckIssue::new("target-is-cname", record, message)
+                } else {
+                    ZoneCheckIssue::warning("target-is-cname", record, message)
+                });
+            } else if record.record_type == "NS"
+                && in_zone(&target, &apex)
+                && !has_type(&target, "A")
+                && !has_type(&target, "AAAA")
+            {
+                match cut_above(&target) {
+                    None => issues.push(ZoneCheckIssue::new(
+                        "missing-glue",
+                        record,
+                        format!(
+                            "NS target {} is in the zone but has no A or AAAA record",
+                            target
+                        ),
+                    )),
+                    // In-bailiwick of this delegation: only glue can reach it
+                    Some(cut) if cut == owner => issues.push(ZoneCheckIssue::new(
+                        "missing-glue",
+                        record,
+                        format!(
+                            "NS target {} is below the delegation {} but has no glue A or AAAA record",
+                            target, cut
+                        ),
+                    )),
+                    Some(cut) => issues.push(ZoneCheckIssue::warning(
+                        "missing-sibling-glue",
+                        record,
+                        format!(
+                            "NS target {} is below the delegation {} and has no glue A or AAAA record",
+                            target, cut
+                        ),
+                    )),
+                }
+            }
+        }
+    }
+
+    let apex_issue = |check: &str, record_type: &str, message: &str| ZoneCheckIssue {
+        check: check.to_string(),
+        severity: ZoneCheckSeverity::Error,
+        name: apex.clone(),
+        record_type: record_type.to_string(),
+        data: String::new(),
+        message: message.to_string(),
+    };
+    if !soa_seen {
+        issues.push(apex_issue("missing-soa", "SOA", "zone has no SOA record"));
+    }
+    if !has_type(&apex, "NS") {
+        issues.push(apex_issue(
+            "missing-ns",
+            "NS",
+            "zone has no NS records at the apex",
+        ));
+    }
+
+    issues
+}
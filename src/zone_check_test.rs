@@ -0,0 +1,159 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for zone consistency checks
+
+#[cfg(test)]
+mod tests {
+    use crate::zone_check::*;
+    use crate::zone_file_parser::parse_records;
+
+    const HEADER: &str = "$TTL 3600\n\
+        @ IN SOA ns1.example.com. admin.example.com. 1 3600 600 604800 86400\n\
+        @ IN NS ns1.example.com.\n\
+        ns1 IN A 192.0.2.1\n";
+
+    fn check(extra: &str) -> Vec<ZoneCheckIssue> {
+        let content = format!("{}{}", HEADER, extra);
+        let (records, _) = parse_records(&content, "example.com").unwrap();
+        check_zone(&records, "example.com")
+    }
+
+    fn checks(issues: &[ZoneCheckIssue]) -> Vec<&str> {
+        issues.iter().map(|i| i.check.as_str()).collect()
+    }
+
+    #[test]
+    fn test_consistent_zone_has_no_issues() {
+        let issues = check(
+            "www IN A 192.0.2.2\n\
+             alias IN CNAME www\n\
+             @ IN MX 10 mail.example.net.\n\
+             _sip._tcp IN SRV 0 5 5060 www\n\
+             sub IN NS ns.other.net.\n",
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_cname_and_other_data() {
+        let issues = check("www IN CNAME other.example.net.\nwww IN TXT \"x\"\n");
+        assert_eq!(checks(&issues), vec!["cname-and-other-data"]);
+        assert_eq!(issues[0].name, "www.example.com.");
+        assert_eq!(issues[0].record_type, "CNAME");
+        assert!(issues[0].message.contains("TXT"));
+    }
+
+    #[test]
+    fn test_cname_at_apex_conflicts_with_soa() {
+        let issues = check("@ IN CNAME other.example.net.\n");
+        assert_eq!(checks(&issues), vec!["cname-and-other-data"]);
+    }
+
+    #[test]
+    fn test_multiple_cnames() {
+        let issues = check("www IN CNAME a.example.net.\nwww IN CNAME b.example.net.\n");
+        assert_eq!(checks(&issues), vec!["multiple-cname"]);
+    }
+
+    #[test]
+    fn test_targets_must_not_be_cnames() {
+        let issues = check(
+            "alias IN CNAME ns1\n\
+             @ IN MX 10 alias\n\
+             _sip._tcp IN SRV 0 5 5060 alias.example.com.\n\
+             sub IN NS alias\n",
+        );
+        assert_eq!(
+            checks(&issues),
+            vec!["target-is-cname", "target-is-cname", "target-is-cname"]
+        );
+        let severities: Vec<_> = issues
+            .iter()
+            .map(|i| (i.record_type.as_str(), i.is_error()))
+            .collect();
+        // Like check-mx-cname and check-srv-cname, only NS is fatal
+        assert_eq!(
+            severities,
+            vec![("MX", false), ("SRV", false), ("NS", true)]
+        );
+        assert_eq!(issues[0].data, "10 alias.example.com.");
+    }
+
+    #[test]
+    fn test_mx_and_srv_cname_targets_are_warnings() {
+        let issues = check("alias IN CNAME mail.example.net.\n@ IN MX 10 alias\n");
+        assert_eq!(checks(&issues), vec!["target-is-cname"]);
+        assert_eq!(issues[0].severity, ZoneCheckSeverity::Warning);
+        assert!(issues[0].message.contains("MX target alias.example.com."));
+
+        let issues = check("alias IN CNAME sip.example.net.\n_sip._tcp IN SRV 0 5 5060 alias\n");
+        assert_eq!(checks(&issues), vec!["target-is-cname"]);
+        assert_eq!(issues[0].severity, ZoneCheckSeverity::Warning);
+        assert!(issues[0].message.contains("SRV target alias.example.com."));
+    }
+
+    #[test]
+    fn test_in_zone_ns_requires_glue() {
+        let issues = check("@ IN NS ns2\nsub IN NS ns.sub\n");
+        assert_eq!(checks(&issues), vec!["missing-glue", "missing-glue"]);
+        assert!(issues.iter().all(ZoneCheckIssue::is_error));
+        assert!(issues[0].message.contains("ns2.example.com."));
+        assert!(issues[1]
+            .message
+            .contains("below the delegation sub.example.com."));
+    }
+
+    #[test]
+    fn test_glue_below_child_delegations() {
+        // In-bailiwick glue present, deeper in the child, and out of zone
+        let issues = check(
+            "sub IN NS ns.sub\n\
+             ns.sub IN A 192.0.2.5\n\
+             deep IN NS a.b.deep\n\
+             a.b.deep IN AAAA 2001:db8::5\n\
+             ext IN NS ns.example.net.\n",
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+
+        // A target below another delegation is sibling glue: only a warning
+        let issues = check("sub IN NS ns.sub\nns.sub IN A 192.0.2.5\nother IN NS ns2.sub\n");
+        assert_eq!(checks(&issues), vec!["missing-sibling-glue"]);
+        assert_eq!(issues[0].severity, ZoneCheckSeverity::Warning);
+        assert_eq!(issues[0].name, "other.example.com.");
+    }
+
+    #[test]
+    fn test_out_of_zone_and_soa_placement() {
+        let issues = check(
+            "www.example.net. IN A 192.0.2.9\n\
+             sub IN SOA ns1 admin 1 2 3 4 5\n\
+             @ IN SOA ns1 admin 1 2 3 4 5\n",
+        );
+        assert_eq!(
+            checks(&issues),
+            vec!["out-of-zone", "soa-not-at-apex", "multiple-soa"]
+        );
+        // BIND ignores out-of-zone data; the SOA problems stop the load
+        assert_eq!(issues[0].severity, ZoneCheckSeverity::Warning);
+        assert!(issues[1].is_error() && issues[2].is_error());
+    }
+
+    #[test]
+    fn test_is_out_of_zone() {
+        assert!(is_out_of_zone("ns1.example.net.", "example.com"));
+        assert!(is_out_of_zone("badexample.com.", "example.com"));
+        assert!(!is_out_of_zone("NS1.Example.COM.", "example.com"));
+        assert!(!is_out_of_zone("example.com.", "example.com."));
+        assert!(!is_out_of_zone("www", "example.com"));
+        assert!(!is_out_of_zone("@", "example.com"));
+    }
+
+    #[test]
+    fn test_missing_soa_and_ns() {
+        let (records, _) = parse_records("www IN A 192.0.2.1\n", "example.com").unwrap();
+        let issues = check_zone(&records, "example.com");
+        assert_eq!(checks(&issues), vec!["missing-soa", "missing-ns"]);
+        assert_eq!(issues[0].name, "example.com.");
+    }
+}
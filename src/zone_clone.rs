@@ -0,0 +1,106 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Copying a zone to a new origin
+//!
+//! `POST /api/v1/zones/{name}/clone` reads the live records of a zone and
+//! moves them to another apex: owner names at or below the source apex, and
+//! the domain names in CNAME, NS, PTR, DNAME, MX, SRV and SOA RDATA that point
+//! into the zone, are rewritten. Names outside the zone are left alone, as is
+//! free-form RDATA such as TXT.
+//!
+//! DNSSEC records are dropped: their signatures cover the old names, and BIND
+//! signs the new zone itself if it has a DNSSEC policy.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bindcar::zone_clone::rename;
+//!
+//! let apex = rename("www.example.com.", "example.com", "staging.example.com");
+//! assert_eq!(apex, "www.staging.example.com.");
+//! assert_eq!(rename("example.net.", "example.com", "staging.example.com"), "example.net.");
+//! ```
+
+use crate::{
+    rndc_types::ZoneConfig,
+    zone_diff::DNSSEC_MAINTAINED_TYPES,
+    zone_file_parser::{name_rdata_fields, zone_apex, ResourceRecord},
+};
+
+/// Options that name a file of the source zone and must not be shared
+const PER_ZONE_FILE_OPTIONS: &[&str] = &["journal"];
+
+/// Move an absolute name from the `source` zone to the `target` zone.
+///
+/// Names outside `source` are returned unchanged. The labels below the apex
+/// keep their case.
+pub fn rename(name: &str, source: &str, target: &str) -> String {
+    let source = zone_apex(source);
+    let target = zone_apex(target);
+
+    if name.eq_ignore_ascii_case(&source) {
+        return target;
+    }
+    match name.len().checked_sub(source.len() + 1) {
+        Some(split)
+            if name.is_char_boundary(split)
+                && name[split..].starts_with('.')
+                && name[split + 1..].eq_ignore_ascii_case(&source) =>
+        {
+            format!("{}.{}", &name[..split], target)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Rewrite a zone's records for the `target` origin.
+///
+/// DNSSEC records maintained by BIND are dropped.
+pub fn rename_records(
+    records: &[ResourceRecord],
+    source: &str,
+    target: &str,
+) -> Vec<ResourceRecord> {
+    records
+        .iter()
+        .filter(|record| {
+            !DNSSEC_MAINTAINED_TYPES.contains(&record.record_type.to_ascii_uppercase().as_str())
+        })
+        .map(|record| {
+            let name_fields = name_rdata_fields(&record.record_type);
+            let data = record
+                .data
+                .split(' ')
+                .enumerate()
+                .map(|(i, field)| {
+                    if name_fields.contains(&i) {
+                        rename(field, source, target)
+                    } else {
+                        field.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            ResourceRecord {
+                name: rename(&record.name, source, target),
+                ttl: record.ttl,
+                record_type: record.record_type.clone(),
+                data,
+            }
+        })
+        .collect()
+}
+
+/// The `rndc addzone` configuration of the clone: the source zone's options,
+/// with its own zone file in `zone_dir`.
+pub fn clone_zone_config(source: &ZoneConfig, target: &str, zone_dir: &str) -> ZoneConfig {
+    let mut config = source.clone();
+    config.zone_name = target.to_string();
+    config.file = Some(format!("{}/{}.zone", zone_dir, target));
+    for option in PER_ZONE_FILE_OPTIONS {
+        config.raw_options.remove(*option);
+    }
+    config
+}
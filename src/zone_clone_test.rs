@@ -0,0 +1,127 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for copying a zone to a new origin
+
+#[cfg(test)]
+mod tests {
+    use crate::rndc_parser::parse_showzone;
+    use crate::zone_clone::*;
+    use crate::zone_file_parser::{build_zone_config, parse_records, ResourceRecord};
+
+    const SOURCE_ZONE: &str = r#"$TTL 3600
+@ IN SOA ns1.example.com. hostmaster.example.com. ( 2025010101 3600 600 604800 86400 )
+@ IN NS ns1.example.com.
+@ IN NS ns.example.net.
+ns1 IN A 192.0.2.1
+www IN CNAME @
+api IN CNAME lb.example.net.
+@ IN MX 10 mail
+_sip._tcp IN SRV 0 5 5060 sip.Example.COM.
+txt IN TXT "example.com stays as text"
+@ 300 IN RRSIG SOA 13 2 3600 20250201000000 20250101000000 12345 example.com. c2lnbmF0dXJl
+"#;
+
+    fn renamed(name: &str, record_type: &str) -> Vec<ResourceRecord> {
+        let (records, _) = parse_records(SOURCE_ZONE, "example.com").unwrap();
+        rename_records(&records, "example.com", "staging.example.com")
+            .into_iter()
+            .filter(|r| r.name == name && r.record_type == record_type)
+            .collect()
+    }
+
+    #[test]
+    fn test_rename() {
+        let cases = [
+            ("example.com.", "staging.example.com."),
+            ("www.example.com.", "www.staging.example.com."),
+            ("WWW.Example.COM.", "WWW.staging.example.com."),
+            ("a.b.example.com.", "a.b.staging.example.com."),
+            ("example.net.", "example.net."),
+            ("notexample.com.", "notexample.com."),
+            ("com.", "com."),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                rename(name, "example.com", "staging.example.com."),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_rename_records_rewrites_owners_and_in_zone_targets() {
+        let soa = renamed("staging.example.com.", "SOA");
+        assert_eq!(
+            soa[0].data,
+            "ns1.staging.example.com. hostmaster.staging.example.com. 2025010101 3600 600 604800 86400"
+        );
+
+        let ns: Vec<_> = renamed("staging.example.com.", "NS")
+            .into_iter()
+            .map(|r| r.data)
+            .collect();
+        assert_eq!(ns, ["ns1.staging.example.com.", "ns.example.net."]);
+
+        assert_eq!(
+            renamed("www.staging.example.com.", "CNAME")[0].data,
+            "staging.example.com."
+        );
+        assert_eq!(
+            renamed("api.staging.example.com.", "CNAME")[0].data,
+            "lb.example.net."
+        );
+        assert_eq!(
+            renamed("staging.example.com.", "MX")[0].data,
+            "10 mail.staging.example.com."
+        );
+        assert_eq!(
+            renamed("_sip._tcp.staging.example.com.", "SRV")[0].data,
+            "0 5 5060 sip.staging.example.com."
+        );
+        assert_eq!(
+            renamed("txt.staging.example.com.", "TXT")[0].data,
+            "\"example.com stays as text\""
+        );
+        assert!(renamed("staging.example.com.", "RRSIG").is_empty());
+    }
+
+    #[test]
+    fn test_renamed_records_build_a_zone_for_the_target() {
+        let (records, _) = parse_records(SOURCE_ZONE, "example.com").unwrap();
+        let records = rename_records(&records, "example.com", "staging.example.com");
+
+        let config = build_zone_config("staging.example.com", None, &records).unwrap();
+
+        assert_eq!(config.soa.serial, 2025010101);
+        assert_eq!(
+            config.name_servers,
+            ["ns1.staging.example.com.", "ns.example.net."]
+        );
+        assert_eq!(
+            config.name_server_ips.get("ns1.staging.example.com."),
+            Some(&"192.0.2.1".to_string())
+        );
+        assert!(config.records.iter().any(|r| r.name == "www"));
+    }
+
+    #[test]
+    fn test_clone_zone_config_keeps_options_with_own_file() {
+        let source = parse_showzone(
+            r#"zone "example.com" { type primary; file "/var/cache/bind/example.com.zone"; journal "/var/cache/bind/example.com.jnl"; allow-transfer { 10.0.0.0/8; }; allow-update { key "update-key"; }; notify explicit; };"#,
+        )
+        .unwrap();
+
+        let config = clone_zone_config(&source, "staging.example.com", "/var/cache/bind");
+
+        assert_eq!(config.zone_name, "staging.example.com");
+        assert_eq!(
+            config.file.as_deref(),
+            Some("/var/cache/bind/staging.example.com.zone")
+        );
+        assert_eq!(config.allow_transfer, source.allow_transfer);
+        assert_eq!(config.allow_update, source.allow_update);
+        assert_eq!(config.notify, source.notify);
+        assert!(!config.to_rndc_block().contains("journal"));
+    }
+}
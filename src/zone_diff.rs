@@ -0,0 +1,221 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Record-level diff between a zone's live contents and a desired state
+//!
+//! Used by full zone replacement (`PUT /api/v1/zones/{name}`) to turn a desired
+//! [`ZoneConfig`](crate::zones::ZoneConfig) into the minimal set of record
+//! deletions and additions. Both sides are [`ResourceRecord`]s with absolute
+//! owner names; RDATA is compared in a normalized form so that the rendering
+//! differences between a zone file and an AXFR (case of names, quoting of TXT
+//! strings, IPv6 compression, hex and base64 spelling) do not show up as
+//! changes.
+//!
+//! The SOA is not part of the record diff: its serial is managed separately
+//! (see [`soa_changed`] and [`crate::soa_serial`]). DNSSEC records maintained by BIND
+//! itself are ignored on both sides so that a replacement never strips the
+//! signatures of a signed zone.
+
+use std::collections::HashSet;
+
+use crate::{dns_wire, zone_file_parser::ResourceRecord};
+
+/// Record types generated and maintained by BIND for signed zones.
+pub(crate) const DNSSEC_MAINTAINED_TYPES: &[&str] = &[
+    "RRSIG",
+    "NSEC",
+    "NSEC3",
+    "NSEC3PARAM",
+    "DNSKEY",
+    "CDS",
+    "CDNSKEY",
+    "TYPE65534",
+];
+
+/// Record types whose RDATA holds domain names, which compare case-insensitively.
+const NAME_RDATA_TYPES: &[&str] = &["NS", "CNAME", "PTR", "DNAME", "MX", "SRV", "SOA"];
+
+/// Changes needed to turn the live record set into the desired one
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecordDiff {
+    /// Live records that are not in the desired state
+    pub removed: Vec<ResourceRecord>,
+    /// Desired records that are not live
+    pub added: Vec<ResourceRecord>,
+}
+
+impl RecordDiff {
+    /// Whether the live and desired record sets already match
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+/// Split TXT-style RDATA into character-strings, quoted or bare.
+fn char_strings(data: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = data.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut s = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        s.push('\\');
+                        if let Some(escaped) = chars.next() {
+                            s.push(escaped);
+                        }
+                    }
+                    '"' => break,
+                    _ => s.push(c),
+                }
+            }
+            strings.push(s);
+        } else {
+            let mut s = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                s.push(c);
+                chars.next();
+            }
+            strings.push(s);
+        }
+    }
+
+    strings
+}
+
+/// Normalize presentation-format RDATA for comparison.
+pub(crate) fn normalize_rdata(record_type: &str, data: &str) -> String {
+    let record_type = record_type.to_ascii_uppercase();
+    let collapsed = data.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match record_type.as_str() {
+        "A" => collapsed
+            .parse::<std::net::Ipv4Addr>()
+            .map(|ip| ip.to_string())
+            .unwrap_or(collapsed),
+        "AAAA" => collapsed
+            .parse::<std::net::Ipv6Addr>()
+            .map(|ip| ip.to_string())
+            .unwrap_or(collapsed),
+        "TXT" => char_strings(data)
+            .iter()
+            .map(|s| format!("\"{}\"", s))
+            .collect::<Vec<_>>()
+            .join(" "),
+        "CAA" => {
+            let mut fields = collapsed.splitn(3, ' ');
+            let flags = fields.next().unwrap_or_default();
+            let tag = fields.next().unwrap_or_default().to_ascii_lowercase();
+            let value = char_strings(fields.next().unwrap_or_default()).join(" ");
+            format!("{} {} \"{}\"", flags, tag, value)
+        }
+        t if NAME_RDATA_TYPES.contains(&t) => collapsed.to_ascii_lowercase(),
+        // Round trip through wire format: hex case, base64 spacing, mnemonics
+        // and parameter order differ between what was written and the AXFR
+        t => dns_wire::record_type_code(t)
+            .and_then(|code| dns_wire::canonical_rdata(code, data))
+            .unwrap_or(collapsed),
+    }
+}
+
+/// Identity of a record for diffing: owner, type, normalized RDATA and TTL.
+fn record_key(record: &ResourceRecord) -> (String, String, String, Option<u32>) {
+    (
+        record.name.to_ascii_lowercase(),
+        record.record_type.to_ascii_uppercase(),
+        normalize_rdata(&record.record_type, &record.data),
+        record.ttl,
+    )
+}
+
+/// Whether a record takes part in the diff (not the SOA, not DNSSEC-maintained).
+fn is_diffable(record: &ResourceRecord) -> bool {
+    let record_type = record.record_type.to_ascii_uppercase();
+    record_type != "SOA" && !DNSSEC_MAINTAINED_TYPES.contains(&record_type.as_str())
+}
+
+/// Compute the record deletions and additions that turn `live` into `desired`.
+///
+/// A record whose TTL differs is reported as removed and re-added. A desired
+/// record without a TTL matches a live record with any TTL.
+pub fn diff_records(live: &[ResourceRecord], desired: &[ResourceRecord]) -> RecordDiff {
+    let live: Vec<_> = live.iter().filter(|r| is_diffable(r)).collect();
+    let desired: Vec<_> = desired.iter().filter(|r| is_diffable(r)).collect();
+
+    let matches = |l: &ResourceRecord, d: &ResourceRecord| {
+        let (lk, dk) = (record_key(l), record_key(d));
+        lk.0 == dk.0 && lk.1 == dk.1 && lk.2 == dk.2 && (dk.3.is_none() || lk.3 == dk.3)
+    };
+
+    let removed = live
+        .iter()
+        .filter(|l| !desired.iter().any(|d| matches(l, d)))
+        .map(|r| (*r).clone())
+        .collect();
+
+    let mut seen = HashSet::new();
+    let added = desired
+        .iter()
+        .filter(|d| !live.iter().any(|l| matches(l, d)))
+        .filter(|d| seen.insert(record_key(d)))
+        .map(|r| (*r).clone())
+        .collect();
+
+    RecordDiff { removed, added }
+}
+
+/// Find the SOA record in a record set.
+pub fn find_soa(records: &[ResourceRecord]) -> Option<&ResourceRecord> {
+    records
+        .iter()
+        .find(|r| r.record_type.eq_ignore_ascii_case("SOA"))
+}
+
+/// Serial field (third RDATA field) of an SOA record.
+pub fn soa_serial(soa: &ResourceRecord) -> Option<u32> {
+    soa.data.split_whitespace().nth(2)?.parse().ok()
+}
+
+/// Whether two SOA records differ in anything other than the serial.
+pub fn soa_changed(live: &ResourceRecord, desired: &ResourceRecord) -> bool {
+    let fields = |soa: &ResourceRecord| {
+        normalize_rdata("SOA", &soa.data)
+            .split(' ')
+            .enumerate()
+            .filter(|(i, _)| *i != 2)
+            .map(|(_, f)| f.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    fields(live) != fields(desired) || desired.ttl.is_some_and(|ttl| Some(ttl) != live.ttl)
+}
+
+/// Replace the serial of an SOA record's RDATA.
+pub fn with_serial(soa: &ResourceRecord, serial: u32) -> ResourceRecord {
+    let data = soa
+        .data
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, f)| {
+            if i == 2 {
+                serial.to_string()
+            } else {
+                f.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    ResourceRecord {
+        data,
+        ..soa.clone()
+    }
+}
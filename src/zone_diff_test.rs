@@ -0,0 +1,140 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for the zone record diff
+
+#[cfg(test)]
+mod tests {
+    use crate::zone_diff::*;
+    use crate::zone_file_parser::ResourceRecord;
+
+    fn rr(name: &str, ttl: u32, record_type: &str, data: &str) -> ResourceRecord {
+        ResourceRecord {
+            name: name.to_string(),
+            ttl: Some(ttl),
+            record_type: record_type.to_string(),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_rdata() {
+        assert_eq!(normalize_rdata("TXT", "hello world"), "\"hello\" \"world\"");
+        assert_eq!(normalize_rdata("TXT", "\"a \\\"b\\\"\""), "\"a \\\"b\\\"\"");
+        assert_eq!(normalize_rdata("AAAA", "2001:DB8:0:0::1"), "2001:db8::1");
+        assert_eq!(
+            normalize_rdata("MX", "10  Mail.Example.COM."),
+            "10 mail.example.com."
+        );
+        assert_eq!(
+            normalize_rdata("CAA", "0 ISSUE letsencrypt.org"),
+            "0 issue \"letsencrypt.org\""
+        );
+    }
+
+    #[test]
+    fn test_normalize_rdata_matches_transferred_form() {
+        // As written in a request, and as rendered from an AXFR
+        for (record_type, written, transferred) in [
+            ("DS", "60485 RSASHA256 2 ab CD", "60485 8 2 ABCD"),
+            ("TLSA", "3 1 1 abcdef", "3 1 1 ABCDEF"),
+            ("DNSKEY", "257 3 15 AQ ID", "257 3 15 AQID"),
+            (
+                "HTTPS",
+                "1 . port=443 alpn=h2,h3",
+                "1 . alpn=\"h2,h3\" port=443",
+            ),
+            ("HINFO", "INTEL Linux", "\"INTEL\" \"Linux\""),
+            ("SSHFP", "\\# 4 04020102", "4 2 0102"),
+        ] {
+            assert_eq!(
+                normalize_rdata(record_type, written),
+                normalize_rdata(record_type, transferred),
+                "{} {}",
+                record_type,
+                written
+            );
+        }
+        assert_ne!(
+            normalize_rdata("TLSA", "3 1 1 abcdef"),
+            normalize_rdata("TLSA", "3 1 1 abcdee")
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_sets_is_empty() {
+        let live = vec![
+            rr("example.com.", 3600, "SOA", "ns1. admin. 5 1 2 3 4"),
+            rr("www.example.com.", 300, "A", "192.0.2.1"),
+            rr("example.com.", 3600, "TXT", "\"v=spf1 -all\""),
+        ];
+        let desired = vec![
+            rr("example.com.", 3600, "SOA", "ns1. admin. 1 1 2 3 4"),
+            rr("WWW.example.com.", 300, "A", "192.0.2.1"),
+            rr("example.com.", 3600, "TXT", "\"v=spf1 -all\""),
+        ];
+        assert!(diff_records(&live, &desired).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_additions_removals_and_ttl_changes() {
+        let live = vec![
+            rr("www.example.com.", 300, "A", "192.0.2.1"),
+            rr("old.example.com.", 300, "A", "192.0.2.9"),
+            rr("api.example.com.", 300, "A", "192.0.2.5"),
+        ];
+        let desired = vec![
+            rr("www.example.com.", 300, "A", "192.0.2.1"),
+            rr("new.example.com.", 300, "A", "192.0.2.10"),
+            rr("new.example.com.", 300, "A", "192.0.2.10"),
+            rr("api.example.com.", 60, "A", "192.0.2.5"),
+        ];
+
+        let diff = diff_records(&live, &desired);
+        let removed: Vec<_> = diff.removed.iter().map(|r| r.name.as_str()).collect();
+        let added: Vec<_> = diff.added.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(removed, vec!["old.example.com.", "api.example.com."]);
+        assert_eq!(added, vec!["new.example.com.", "api.example.com."]);
+    }
+
+    #[test]
+    fn test_diff_ignores_dnssec_records() {
+        let live = vec![
+            rr("example.com.", 3600, "DNSKEY", "257 3 13 AAAA"),
+            rr(
+                "example.com.",
+                3600,
+                "RRSIG",
+                "SOA 13 2 3600 1 2 3 example.com. sig",
+            ),
+        ];
+        assert!(diff_records(&live, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_soa_helpers() {
+        let live = rr(
+            "example.com.",
+            3600,
+            "SOA",
+            "ns1. admin. 2025010105 1 2 3 4",
+        );
+        let same = rr(
+            "example.com.",
+            3600,
+            "SOA",
+            "NS1. admin. 2025010101 1 2 3 4",
+        );
+        let changed = rr(
+            "example.com.",
+            3600,
+            "SOA",
+            "ns1. admin. 2025010101 7 2 3 4",
+        );
+
+        assert!(!soa_changed(&live, &same));
+        assert!(soa_changed(&live, &changed));
+        assert_eq!(soa_serial(&live), Some(2025010105));
+        assert_eq!(with_serial(&changed, 42).data, "ns1. admin. 42 7 2 3 4");
+    }
+}
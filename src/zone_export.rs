@@ -0,0 +1,217 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Zone snapshots for backup, review and migration
+//!
+//! `GET /api/v1/zones/{name}/export` combines the records a zone currently
+//! serves with its options from `rndc showzone` into a [`ZoneExport`]:
+//!
+//! - The create-request part is a [`CreateZoneRequest`], so the JSON or YAML
+//!   form can be posted back to `POST /api/v1/zones` as-is.
+//! - Options a create request cannot carry (`allowQuery`, `notify`, transfer
+//!   limits, ...) are listed under `options` in `PATCH` form, to be applied with
+//!   `PATCH /api/v1/zones/{name}` after the zone is created.
+//!
+//! Output is canonical: records are sorted, glue is listed with the other
+//! records, and DNSSEC records maintained by BIND are left out, so two exports
+//! of an unchanged zone are identical and diff cleanly in git.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    rndc_types::{self, AddressMatchElement, NotifyMode, PrimarySpec, UpdatePolicy},
+    types::ApiError,
+    zone_diff::{self, DNSSEC_MAINTAINED_TYPES},
+    zone_file_parser::{self, relative_name, zone_apex, ResourceRecord},
+    zone_options,
+    zones::{CreateZoneRequest, DnsRecord, ModifyZoneRequest, ZONE_TYPE_PRIMARY},
+};
+
+/// Output format of a zone export
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// BIND9 master file with the zone's records
+    Zone,
+    /// [`ZoneExport`] as JSON
+    #[default]
+    Json,
+    /// [`ZoneExport`] as YAML
+    Yaml,
+}
+
+impl ExportFormat {
+    /// Media type of the rendered export
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Zone => "text/dns",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Yaml => "application/yaml",
+        }
+    }
+}
+
+/// Snapshot of a zone's records and options
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ZoneExport {
+    /// The zone as a create request
+    #[serde(flatten)]
+    pub zone: CreateZoneRequest,
+
+    /// Options a create request cannot carry, to apply with a modify request
+    #[serde(default, skip_serializing_if = "has_no_options")]
+    pub options: ModifyZoneRequest,
+}
+
+fn has_no_options(options: &ModifyZoneRequest) -> bool {
+    zone_options::options_set(options).is_empty()
+}
+
+/// Render a server spec in the `ip` / `ip:port` form requests use.
+fn server_entry(spec: &PrimarySpec) -> String {
+    match (spec.port, spec.address) {
+        (None, address) => address.to_string(),
+        (Some(port), std::net::IpAddr::V6(address)) => format!("[{}]:{}", address, port),
+        (Some(port), address) => format!("{}:{}", address, port),
+    }
+}
+
+fn match_list(elements: &Option<Vec<AddressMatchElement>>) -> Option<Vec<String>> {
+    elements
+        .as_ref()
+        .map(|list| list.iter().map(|e| e.to_string()).collect())
+}
+
+/// The modify-request form of the options that [`CreateZoneRequest`] has no
+/// field for.
+fn modify_options(live: &rndc_types::ZoneConfig, allow_update: bool) -> ModifyZoneRequest {
+    ModifyZoneRequest {
+        allow_update: match_list(&live.allow_update).filter(|_| allow_update),
+        allow_query: match_list(&live.allow_query),
+        allow_notify: match_list(&live.allow_notify),
+        allow_update_forwarding: match_list(&live.allow_update_forwarding),
+        // "master-only" is the pre-9.18 spelling, which modify requests reject
+        notify: live.notify.map(|mode| match mode {
+            NotifyMode::MasterOnly => NotifyMode::PrimaryOnly.as_str().to_string(),
+            mode => mode.as_str().to_string(),
+        }),
+        max_transfer_time_in: live.max_transfer_time_in,
+        max_transfer_time_out: live.max_transfer_time_out,
+        max_transfer_idle_in: live.max_transfer_idle_in,
+        max_transfer_idle_out: live.max_transfer_idle_out,
+        transfer_source: live.transfer_source.map(|a| a.to_string()),
+        transfer_source_v6: live.transfer_source_v6.map(|a| a.to_string()),
+        notify_source: live.notify_source.map(|a| a.to_string()),
+        notify_source_v6: live.notify_source_v6.map(|a| a.to_string()),
+        ixfr_from_differences: live.ixfr_from_differences,
+        check_names: live.check_names.map(|mode| mode.as_str().to_string()),
+        check_mx: live.check_mx.map(|mode| mode.as_str().to_string()),
+        check_integrity: live.check_integrity,
+        max_zone_ttl: live.max_zone_ttl,
+        max_refresh_time: live.max_refresh_time,
+        min_refresh_time: live.min_refresh_time,
+        max_retry_time: live.max_retry_time,
+        min_retry_time: live.min_retry_time,
+        multi_master: live.multi_master,
+        request_ixfr: live.request_ixfr,
+        request_expire: live.request_expire,
+        ..Default::default()
+    }
+}
+
+/// Build the snapshot of a primary zone from its `showzone` configuration and
+/// the records it serves.
+///
+/// # Errors
+/// Returns [`ApiError::ZoneFileError`] if the records have no SOA at the apex.
+pub fn snapshot(
+    live: &rndc_types::ZoneConfig,
+    records: &[ResourceRecord],
+) -> Result<ZoneExport, ApiError> {
+    let records: Vec<ResourceRecord> = records
+        .iter()
+        .filter(|r| !DNSSEC_MAINTAINED_TYPES.contains(&r.record_type.to_ascii_uppercase().as_str()))
+        .cloned()
+        .collect();
+    let default_ttl = zone_diff::find_soa(&records).and_then(|soa| soa.ttl);
+
+    let mut content = zone_file_parser::build_zone_config(&live.zone_name, default_ttl, &records)
+        .map_err(|e| {
+        ApiError::ZoneFileError(format!("Failed to read zone {}: {}", live.zone_name, e))
+    })?;
+
+    // Glue lives in a map with no stable order; list it with the records
+    let apex = zone_apex(&live.zone_name);
+    for (name, address) in content.name_server_ips.drain() {
+        content.records.push(DnsRecord {
+            name: relative_name(&name, &apex),
+            record_type: "A".to_string(),
+            value: address,
+            ttl: None,
+            priority: None,
+            data: None,
+        });
+    }
+    content.records.sort_by_cached_key(|r| {
+        (
+            r.name.to_ascii_lowercase(),
+            r.record_type.clone(),
+            r.priority,
+            r.value.clone(),
+            r.ttl,
+        )
+    });
+    content.name_servers.sort();
+
+    content.also_notify = live
+        .also_notify
+        .as_ref()
+        .map(|specs| specs.iter().map(server_entry).collect());
+    content.allow_transfer = match_list(&live.allow_transfer);
+    content.inline_signing = live.inline_signing;
+    content.dnssec_policy = live
+        .raw_options
+        .get("dnssec-policy")
+        .map(|policy| policy.trim_matches('"').to_string());
+
+    // A single key is what `updateKeyName` creates; anything else is kept as
+    // an address match list
+    let update_key_name = match live.allow_update.as_deref() {
+        Some([AddressMatchElement::Key(key)]) => Some(key.clone()),
+        _ => None,
+    };
+    let update_policy = match &live.update_policy {
+        Some(UpdatePolicy::Rules(rules)) => Some(rules.clone()),
+        _ => None,
+    };
+
+    Ok(ZoneExport {
+        options: modify_options(live, update_key_name.is_none()),
+        zone: CreateZoneRequest {
+            zone_name: live.zone_name.clone(),
+            zone_type: ZONE_TYPE_PRIMARY.to_string(),
+            zone_config: content,
+            update_key_name,
+            update_policy,
+            serial_strategy: None,
+            catalog: None,
+        },
+    })
+}
+
+/// Render a snapshot in the requested format.
+///
+/// # Errors
+/// Returns [`ApiError::InternalError`] if serialization fails.
+pub fn render(export: &ZoneExport, format: ExportFormat) -> Result<String, ApiError> {
+    match format {
+        ExportFormat::Zone => Ok(export.zone.zone_config.to_zone_file()),
+        ExportFormat::Json => serde_json::to_string_pretty(export)
+            .map(|json| json + "\n")
+            .map_err(|e| ApiError::InternalError(e.to_string())),
+        ExportFormat::Yaml => {
+            serde_yaml_ng::to_string(export).map_err(|e| ApiError::InternalError(e.to_string()))
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for zone snapshots
+
+#[cfg(test)]
+mod tests {
+    use crate::rndc_parser::parse_showzone;
+    use crate::rndc_types::{self, UpdatePolicyRuleType};
+    use crate::zone_export::*;
+    use crate::zone_file_parser::{parse_records, parse_zone_file, ResourceRecord};
+    use crate::zones::{validate_zone_request, CreateZoneRequest};
+
+    const RECORDS: &str = r#"$TTL 3600
+@ IN SOA ns1.example.com. hostmaster.example.com. ( 2025010107 3600 600 604800 86400 )
+@ IN NS ns2.example.com.
+@ IN NS ns1.example.com.
+www 300 IN A 192.0.2.80
+ns2 IN A 192.0.2.2
+@ IN MX 10 mail.example.com.
+api IN CNAME www.example.com.
+ns1 IN A 192.0.2.1
+mail IN A 192.0.2.25
+@ 3600 IN RRSIG SOA 13 2 3600 20250201000000 20250101000000 12345 example.com. c2lnbmF0dXJl
+"#;
+
+    fn live(options: &str) -> rndc_types::ZoneConfig {
+        parse_showzone(&format!(
+            r#"zone "example.com" {{ type primary; file "/var/cache/bind/example.com.zone"; {} }};"#,
+            options
+        ))
+        .unwrap()
+    }
+
+    fn records() -> Vec<ResourceRecord> {
+        parse_records(RECORDS, "example.com").unwrap().0
+    }
+
+    #[test]
+    fn test_snapshot_is_canonical() {
+        let export = snapshot(&live(""), &records()).unwrap();
+        let config = &export.zone.zone_config;
+
+        assert_eq!(config.ttl, 3600);
+        assert_eq!(config.soa.serial, 2025010107);
+        assert_eq!(
+            config.name_servers,
+            ["ns1.example.com.", "ns2.example.com."]
+        );
+        assert!(config.name_server_ips.is_empty());
+
+        let names: Vec<(&str, &str)> = config
+            .records
+            .iter()
+            .map(|r| (r.name.as_str(), r.record_type.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("@", "MX"),
+                ("api", "CNAME"),
+                ("mail", "A"),
+                ("ns1", "A"),
+                ("ns2", "A"),
+                ("www", "A"),
+            ]
+        );
+        // Only TTLs that differ from $TTL are kept
+        assert_eq!(config.records[5].ttl, Some(300));
+        assert_eq!(config.records[4].ttl, None);
+
+        // Record order in the source does not matter
+        let mut shuffled = records();
+        shuffled.reverse();
+        let again = snapshot(&live(""), &shuffled).unwrap();
+        assert_eq!(
+            render(&again, ExportFormat::Json).unwrap(),
+            render(&export, ExportFormat::Json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_export_is_a_create_request() {
+        let live = live(
+            r#"allow-transfer { 10.0.0.0/8; key "xfr"; }; also-notify { 192.0.2.9 port 5353; 2001:db8::9 port 5353; }; allow-update { key "update-key"; }; allow-query { any; }; notify master-only; max-zone-ttl 86400;"#,
+        );
+        let export = snapshot(&live, &records()).unwrap();
+        let json = render(&export, ExportFormat::Json).unwrap();
+
+        let request: CreateZoneRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request.zone_name, "example.com");
+        assert_eq!(request.update_key_name.as_deref(), Some("update-key"));
+        assert_eq!(
+            request.zone_config.allow_transfer,
+            Some(vec!["10.0.0.0/8".to_string(), r#"key "xfr""#.to_string()])
+        );
+        assert_eq!(
+            request.zone_config.also_notify,
+            Some(vec![
+                "192.0.2.9:5353".to_string(),
+                "[2001:db8::9]:5353".to_string()
+            ])
+        );
+        validate_zone_request(&request).unwrap();
+
+        // Options a create request cannot carry, in PATCH form
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["options"],
+            serde_json::json!({
+                "allowQuery": ["any"],
+                "notify": "primary-only",
+                "maxZoneTtl": 86400
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_policy_and_address_allow_update() {
+        let export = snapshot(
+            &live("update-policy { grant tenant1-key name _acme-challenge.example.com. TXT; };"),
+            &records(),
+        )
+        .unwrap();
+        let rules = export.zone.update_policy.unwrap();
+        assert_eq!(rules[0].rule_type, UpdatePolicyRuleType::Name);
+        assert!(export.zone.update_key_name.is_none());
+
+        let export = snapshot(&live("allow-update { 10.0.0.1; };"), &records()).unwrap();
+        assert!(export.zone.update_key_name.is_none());
+        assert_eq!(
+            export.options.allow_update,
+            Some(vec!["10.0.0.1".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_zone_and_yaml_formats() {
+        let export = snapshot(&live("notify explicit;"), &records()).unwrap();
+
+        let zone_file = render(&export, ExportFormat::Zone).unwrap();
+        let parsed = parse_zone_file(&zone_file, "example.com").unwrap();
+        assert_eq!(parsed.soa.serial, 2025010107);
+        assert!(!zone_file.contains("RRSIG"));
+
+        let yaml = render(&export, ExportFormat::Yaml).unwrap();
+        let restored: ZoneExport = serde_yaml_ng::from_str(&yaml).unwrap();
+        assert_eq!(restored.zone.zone_config.records.len(), 6);
+        assert_eq!(restored.options.notify.as_deref(), Some("explicit"));
+        assert_eq!(ExportFormat::Yaml.content_type(), "application/yaml");
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Zone file (RFC 1035 master file) parser
+//!
+//! This module parses BIND9 zone files back into the structured [`ZoneConfig`]
+//! used by the zone API, so `GET /api/v1/zones/{name}` can return the records
+//! actually held in `<zone>.zone` rather than only the `rndc zonestatus` summary.
+//!
+//! Supported master-file syntax:
+//! - `$ORIGIN` and `$TTL` directives
+//! - Parenthesized multi-line records (e.g. the SOA block)
+//! - `;` comments, blank-owner continuation lines, `@` and relative names
+//! - Optional TTL (with BIND `w`/`d`/`h`/`m`/`s` units) and class in either order
+//! - Quoted character strings (TXT, CAA, ...) including escaped quotes
+//!
+//! `$INCLUDE` and `$GENERATE` are rejected: they would let file content pull in
+//! arbitrary files or expand into unbounded record sets (C-2).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bindcar::zone_file_parser::parse_zone_file;
+//!
+//! let content = r#"
+//! $TTL 3600
+//! @ IN SOA ns1.example.com. admin.example.com. ( 2025010101 3600 600 604800 86400 )
+//! @ IN NS ns1.example.com.
+//! www IN A 192.0.2.1
+//! "#;
+//!
+//! let config = parse_zone_file(content, "example.com").unwrap();
+//! assert_eq!(config.soa.serial, 2025010101);
+//! assert_eq!(config.records[0].name, "www");
+//! ```
+
+use crate::zones::{DnsRecord, SoaRecord, ZoneConfig};
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{anychar, char, none_of},
+    combinator::{map, recognize},
+    multi::many0,
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Zone file parse errors
+#[derive(Debug, Error)]
+pub enum ZoneFileParseError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+
+    #[error("Line {line}: unsupported directive {directive}")]
+    UnsupportedDirective { line: usize, directive: String },
+
+    #[error("Unbalanced parentheses in record starting on line {0}")]
+    UnbalancedParens(usize),
+
+    #[error("Missing SOA record at zone apex")]
+    MissingSoa,
+}
+
+pub type ParseResult<T> = Result<T, ZoneFileParseError>;
+
+/// A single resource record read from a zone file.
+///
+/// Owner names and domain-name RDATA fields (CNAME/NS/PTR/DNAME targets, MX and
+/// SRV targets, SOA MNAME/RNAME) are made absolute against the effective
+/// `$ORIGIN`, so records from different parts of a file compare consistently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRecord {
+    /// Absolute owner name with trailing dot (e.g. "www.example.com.")
+    pub name: String,
+    /// TTL, if explicitly given on the record or inherited from `$TTL`
+    pub ttl: Option<u32>,
+    /// Record type in upper case (e.g. "A", "MX")
+    pub record_type: String,
+    /// Presentation-format RDATA, tokens separated by single spaces
+    pub data: String,
+}
+
+// ========== Tokenizer ==========
+
+/// A presentation-format token. Quoted tokens keep their quotes so that
+/// character-string RDATA (TXT, CAA) is reproduced verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+/// One logical entry: a line, or several physical lines joined by parentheses.
+#[derive(Debug)]
+struct Entry {
+    line: usize,
+    /// The entry began with whitespace, so the owner is the previous owner.
+    blank_owner: bool,
+    tokens: Vec<Token>,
+}
+
+/// Parse a quoted character string, honouring backslash escapes: "a \"b\" c"
+fn quoted_token(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(
+        char('"'),
+        many0(alt((
+            recognize(preceded(char('\\'), anychar)),
+            recognize(none_of("\\\"")),
+        ))),
+        char('"'),
+    ))
+    .parse(input)
+}
+
+/// Parse an unquoted token (anything up to whitespace, `;`, parens or a quote)
+fn bare_token(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| !c.is_whitespace() && !matches!(c, ';' | '(' | ')' | '"'))(input)
+}
+
+/// Parse a token of either form
+fn token(input: &str) -> IResult<&str, Token> {
+    alt((
+        map(quoted_token, |s: &str| Token {
+            text: s.to_string(),
+            quoted: true,
+        }),
+        map(bare_token, |s: &str| Token {
+            text: s.to_string(),
+            quoted: false,
+        }),
+    ))
+    .parse(input)
+}
+
+/// Split master-file content into logical entries.
+fn tokenize(content: &str) -> ParseResult<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut line = 1;
+    let mut depth = 0usize;
+    let mut current: Option<Entry> = None;
+    let mut at_line_start = true;
+    let mut rest = content;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '\n' => {
+                line += 1;
+                at_line_start = true;
+                rest = &rest[1..];
+                if depth == 0 {
+                    if let Some(entry) = current.take() {
+                        if !entry.tokens.is_empty() {
+                            entries.push(entry);
+                        }
+                    }
+                }
+                continue;
+            }
+            ';' => {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                rest = &rest[end..];
+                continue;
+            }
+            _ => {}
+        }
+
+        let entry = current.get_or_insert_with(|| Entry {
+            line,
+            blank_owner: at_line_start && c.is_whitespace(),
+            tokens: Vec::new(),
+        });
+        at_line_start = false;
+
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '(' {
+            depth += 1;
+            rest = &rest[1..];
+        } else if c == ')' {
+            depth = depth.checked_sub(1).ok_or(ZoneFileParseError::Syntax {
+                line,
+                message: "unexpected ')'".to_string(),
+            })?;
+            rest = &rest[1..];
+        } else {
+            let (remaining, tok) = token(rest).map_err(|_| ZoneFileParseError::Syntax {
+                line,
+                message: "unterminated quoted string".to_string(),
+            })?;
+            line += rest[..rest.len() - remaining.len()].matches('\n').count();
+            entry.tokens.push(tok);
+            rest = remaining;
+        }
+    }
+
+    if depth != 0 {
+        return Err(ZoneFileParseError::UnbalancedParens(
+            current.map(|e| e.line).unwrap_or(line),
+        ));
+    }
+    if let Some(entry) = current {
+        if !entry.tokens.is_empty() {
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+// ========== Field Helpers ==========
+
+/// Parse a BIND TTL value: plain seconds ("3600") or unit form ("1h30m", "1w").
+pub(crate) fn parse_ttl(s: &str) -> Option<u32> {
+    if s.is_empty() {
+        return None;
+    }
+    if let Ok(v) = s.parse::<u32>() {
+        return Some(v);
+    }
+    if !s.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut total: u64 = 0;
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let multiplier = match c.to_ascii_lowercase() {
+            'w' => 604_800,
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let value: u64 = number.parse().ok()?;
+        total = total.checked_add(value.checked_mul(multiplier)?)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return None;
+    }
+
+    u32::try_from(total).ok()
+}
+
+/// Make a master-file name absolute against `origin` (which has a trailing dot).
+pub(crate) fn absolute_name(name: &str, origin: &str) -> String {
+    if name == "@" {
+        origin.to_string()
+    } else if name.ends_with('.') {
+        name.to_string()
+    } else if origin == "." {
+        format!("{}.", name)
+    } else {
+        format!("{}.{}", name, origin)
+    }
+}
+
+/// Absolute form of a zone name: "example.com" and "example.com." both become
+/// "example.com.".
+pub(crate) fn zone_apex(zone_name: &str) -> String {
+    let trimmed = zone_name.trim_end_matches('.');
+    if trimmed.is_empty() {
+        ".".to_string()
+    } else {
+        format!("{}.", trimmed)
+    }
+}
+
+/// Render an absolute name relative to the zone apex, as `to_zone_file` expects:
+/// `@` for the apex, the leading labels for in-zone names, FQDN otherwise.
+pub(crate) fn relative_name(name: &str, origin: &str) -> String {
+    let lower = name.to_ascii_lowercase();
+    let origin_lower = origin.to_ascii_lowercase();
+    if lower == origin_lower {
+        "@".to_string()
+    } else if let Some(prefix) = lower
+        .strip_suffix(&origin_lower)
+        .and_then(|p| p.strip_suffix('.'))
+    {
+        name[..prefix.len()].to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Index of every RDATA field that holds a domain name, for a record type.
+pub(crate) fn name_rdata_fields(record_type: &str) -> &'static [usize] {
+    match record_type {
+        "CNAME" | "NS" | "PTR" | "DNAME" => &[0],
+        "MX" => &[1],
+        "SRV" => &[3],
+        "SOA" => &[0, 1],
+        _ => &[],
+    }
+}
+
+/// Absolutize the domain-name fields of a record's RDATA.
+fn absolutize_rdata(record_type: &str, fields: &[Token], origin: &str) -> Vec<String> {
+    let name_fields = name_rdata_fields(record_type);
+
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, tok)| {
+            if !tok.quoted && name_fields.contains(&i) {
+                absolute_name(&tok.text, origin)
+            } else {
+                tok.text.clone()
+            }
+        })
+        .collect()
+}
+
+fn is_class(token: &str) -> bool {
+    matches!(token.to_ascii_uppercase().as_str(), "IN" | "CH" | "HS")
+}
+
+// ========== Record Parser ==========
+
+/// Parse zone file content into absolute resource records.
+///
+/// `origin` is the zone name (with or without trailing dot) and is the initial
+/// `$ORIGIN`. Returns the records together with the `$TTL` default in effect at
+/// the end of the file, if any.
+///
+/// # Errors
+/// Returns [`ZoneFileParseError`] on malformed syntax, an unsupported directive
+/// (`$INCLUDE`, `$GENERATE`), or a non-IN class.
+pub fn parse_records(
+    content: &str,
+    origin: &str,
+) -> ParseResult<(Vec<ResourceRecord>, Option<u32>)> {
+    let mut origin = zone_apex(origin);
+    let mut default_ttl: Option<u32> = None;
+    let mut last_owner: Option<String> = None;
+    let mut records = Vec::new();
+
+    for entry in tokenize(content)? {
+        let line = entry.line;
+        let syntax = |message: &str| ZoneFileParseError::Syntax {
+            line,
+            message: message.to_string(),
+        };
+        let first = &entry.tokens[0];
+
+        // Directives
+        if !entry.blank_owner && !first.quoted && first.text.starts_with('$') {
+            let directive = first.text.to_ascii_uppercase();
+            let arg = entry.tokens.get(1).map(|t| t.text.as_str());
+            match directive.as_str() {
+                "$ORIGIN" => {
+                    let arg = arg.ok_or_else(|| syntax("$ORIGIN requires a name"))?;
+                    origin = absolute_name(arg, &origin);
+                }
+                "$TTL" => {
+                    let arg = arg.ok_or_else(|| syntax("$TTL requires a value"))?;
+                    default_ttl = Some(parse_ttl(arg).ok_or_else(|| syntax("invalid $TTL"))?);
+                }
+                _ => {
+                    return Err(ZoneFileParseError::UnsupportedDirective {
+                        line,
+                        directive: first.text.clone(),
+                    })
+                }
+            }
+            continue;
+        }
+
+        // Owner
+        let mut fields = entry.tokens.iter().peekable();
+        let owner = if entry.blank_owner {
+            last_owner
+                .clone()
+                .ok_or_else(|| syntax("record has no owner name"))?
+        } else {
+            let tok = fields.next().expect("entry has at least one token");
+            absolute_name(&tok.text, &origin)
+        };
+
+        // Optional TTL and class, in either order, then the type.
+        let mut ttl = None;
+        let record_type = loop {
+            let tok = fields
+                .next()
+                .ok_or_else(|| syntax("record is missing a type"))?;
+            if ttl.is_none() && !tok.quoted {
+                if let Some(v) = parse_ttl(&tok.text) {
+                    ttl = Some(v);
+                    continue;
+                }
+            }
+            if is_class(&tok.text) {
+                if !tok.text.eq_ignore_ascii_case("IN") {
+                    return Err(syntax(&format!("unsupported class {}", tok.text)));
+                }
+                continue;
+            }
+            break tok.text.to_ascii_uppercase();
+        };
+
+        let rdata: Vec<Token> = fields.cloned().collect();
+        if rdata.is_empty() {
+            return Err(syntax(&format!("{} record has no data", record_type)));
+        }
+
+        records.push(ResourceRecord {
+            name: owner.clone(),
+            ttl: ttl.or(default_ttl),
+            data: absolutize_rdata(&record_type, &rdata, &origin).join(" "),
+            record_type,
+        });
+        last_owner = Some(owner);
+    }
+
+    Ok((records, default_ttl))
+}
+
+// ========== Zone Config Builder ==========
+
+/// Parse the SOA RDATA fields into a [`SoaRecord`].
+fn parse_soa(data: &str) -> Option<SoaRecord> {
+    let fields: Vec<&str> = data.split_whitespace().collect();
+    if fields.len() != 7 {
+        return None;
+    }
+    Some(SoaRecord {
+        primary_ns: fields[0].to_string(),
+        admin_email: fields[1].to_string(),
+        serial: fields[2].parse().ok()?,
+        refresh: parse_ttl(fields[3])?,
+        retry: parse_ttl(fields[4])?,
+        expire: parse_ttl(fields[5])?,
+        negative_ttl: parse_ttl(fields[6])?,
+    })
+}
+
+/// Build a [`ZoneConfig`] from absolute resource records.
+///
+/// Apex NS records become `name_servers`, the first A record owned by one of
+/// those name servers becomes its `name_server_ips` glue entry, and everything
+/// else becomes a [`DnsRecord`] named relative to the apex — the same shape
+/// [`ZoneConfig::to_zone_file`] renders, so a generated zone round-trips.
+///
+/// # Errors
+/// Returns [`ZoneFileParseError::MissingSoa`] if there is no well-formed SOA
+/// record at the apex.
+pub fn build_zone_config(
+    zone_name: &str,
+    default_ttl: Option<u32>,
+    records: &[ResourceRecord],
+) -> ParseResult<ZoneConfig> {
+    let apex = zone_apex(zone_name);
+    let is_apex = |name: &str| name.eq_ignore_ascii_case(&apex);
+
+    let soa_record = records
+        .iter()
+        .find(|r| r.record_type == "SOA" && is_apex(&r.name))
+        .ok_or(ZoneFileParseError::MissingSoa)?;
+    let soa = parse_soa(&soa_record.data).ok_or(ZoneFileParseError::MissingSoa)?;
+
+    let name_servers: Vec<String> = records
+        .iter()
+        .filter(|r| r.record_type == "NS" && is_apex(&r.name))
+        .map(|r| r.data.clone())
+        .collect();
+
+    let mut name_server_ips = HashMap::new();
+    let mut dns_records = Vec::new();
+
+    for record in records {
+        if is_apex(&record.name) && matches!(record.record_type.as_str(), "SOA" | "NS") {
+            continue;
+        }
+
+        let is_glue = record.record_type == "A"
+            && name_servers
+                .iter()
+                .any(|ns| ns.eq_ignore_ascii_case(&record.name));
+        if is_glue && !name_server_ips.contains_key(&record.name) {
+            name_server_ips.insert(record.name.clone(), record.data.clone());
+            continue;
+        }
+
+        let mut value = record.data.as_str();
+        let mut priority = None;
+        if matches!(record.record_type.as_str(), "MX" | "SRV") {
+            if let Some((first, rest)) = record.data.split_once(' ') {
+                if let Ok(p) = first.parse::<u16>() {
+                    priority = Some(p);
+                    value = rest;
+                }
+            }
+        }
+
+        dns_records.push(DnsRecord {
+            name: relative_name(&record.name, &apex),
+            record_type: record.record_type.clone(),
+            value: value.to_string(),
+            ttl: record.ttl.filter(|t| Some(*t) != default_ttl),
+            priority,
+            data: None,
+        });
+    }
+
+    Ok(ZoneConfig {
+        ttl: default_ttl.or(soa_record.ttl).unwrap_or(soa.negative_ttl),
+        soa,
+        name_servers,
+        name_server_ips,
+        records: dns_records,
+        also_notify: None,
+        allow_transfer: None,
+        primaries: None,
+        dnssec_policy: None,
+        inline_signing: None,
+        forwarders: None,
+        forward: None,
+    })
+}
+
+/// Parse a zone file into a structured [`ZoneConfig`].
+///
+/// # Arguments
+/// * `content` - Zone file text
+/// * `zone_name` - Zone name, used as the initial `$ORIGIN`
+///
+/// # Errors
+/// Returns [`ZoneFileParseError`] on malformed syntax, an unsupported directive,
+/// or a missing SOA record.
+pub fn parse_zone_file(content: &str, zone_name: &str) -> ParseResult<ZoneConfig> {
+    let (records, default_ttl) = parse_records(content, zone_name)?;
+    build_zone_config(zone_name, default_ttl, &records)
+}
@@ -0,0 +1,356 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Unit tests for the zone file parser
+//!
+//! Covers master-file syntax ($ORIGIN, $TTL, parentheses, comments, relative
+//! names, quoted strings), rejection of unsafe directives, and round-tripping
+//! the output of `ZoneConfig::to_zone_file`.
+
+#[cfg(test)]
+mod tests {
+    use crate::zone_file_parser::{
+        absolute_name, parse_records, parse_ttl, parse_zone_file, relative_name, ZoneFileParseError,
+    };
+    use crate::zones::{DnsRecord, SoaRecord, ZoneConfig};
+    use std::collections::HashMap;
+
+    const BASIC_ZONE: &str = r#"
+$TTL 3600
+$ORIGIN example.com.
+@   IN  SOA ns1.example.com. admin.example.com. (
+        2025010101  ; Serial
+        3600        ; Refresh
+        600         ; Retry
+        604800      ; Expire
+        86400 )     ; Negative TTL
+
+@       IN  NS  ns1
+@       IN  NS  ns2.example.com.
+ns1     IN  A   192.0.2.10
+www 300 IN  A   192.0.2.1
+        IN  AAAA 2001:db8::1
+mail    IN  A   192.0.2.25
+@       IN  MX  10 mail
+alias   IN  CNAME www
+@       IN  TXT "v=spf1 mx -all" "second; string"
+_sip._tcp IN SRV 10 5 5060 sip.example.com.
+"#;
+
+    // ========== Field Helpers ==========
+
+    #[test]
+    fn test_parse_ttl_plain_and_units() {
+        assert_eq!(parse_ttl("3600"), Some(3600));
+        assert_eq!(parse_ttl("1h"), Some(3600));
+        assert_eq!(parse_ttl("1h30m"), Some(5400));
+        assert_eq!(parse_ttl("1W"), Some(604_800));
+        assert_eq!(parse_ttl("2d"), Some(172_800));
+        assert_eq!(parse_ttl("A"), None);
+        assert_eq!(parse_ttl("10x"), None);
+        assert_eq!(parse_ttl("1h5"), None);
+        assert_eq!(parse_ttl(""), None);
+    }
+
+    #[test]
+    fn test_parse_ttl_rejects_overflow() {
+        // Each unit fits in u64 but the product or sum does not
+        assert_eq!(parse_ttl("99999999999999999w"), None);
+        assert_eq!(parse_ttl("18446744073709551615s1s"), None);
+        // Fits in u64 but not in a 32-bit TTL
+        assert_eq!(parse_ttl("7102w"), None);
+        assert_eq!(parse_ttl("7101w"), Some(7101 * 604_800));
+
+        let error = parse_zone_file(
+            "$TTL 99999999999999999w
+@ IN NS ns1.example.com.
+",
+            "example.com",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("invalid $TTL"), "{}", error);
+    }
+
+    #[test]
+    fn test_absolute_and_relative_names() {
+        assert_eq!(absolute_name("@", "example.com."), "example.com.");
+        assert_eq!(absolute_name("www", "example.com."), "www.example.com.");
+        assert_eq!(absolute_name("other.org.", "example.com."), "other.org.");
+
+        assert_eq!(relative_name("example.com.", "example.com."), "@");
+        assert_eq!(relative_name("www.example.com.", "example.com."), "www");
+        assert_eq!(relative_name("WWW.Example.COM.", "example.com."), "WWW");
+        assert_eq!(relative_name("other.org.", "example.com."), "other.org.");
+        assert_eq!(
+            relative_name("notexample.com.", "example.com."),
+            "notexample.com."
+        );
+    }
+
+    // ========== Record Parsing ==========
+
+    #[test]
+    fn test_parse_records_basic_zone() {
+        let (records, default_ttl) = parse_records(BASIC_ZONE, "example.com").unwrap();
+        assert_eq!(default_ttl, Some(3600));
+
+        let soa = &records[0];
+        assert_eq!(soa.record_type, "SOA");
+        assert_eq!(soa.name, "example.com.");
+        assert_eq!(
+            soa.data,
+            "ns1.example.com. admin.example.com. 2025010101 3600 600 604800 86400"
+        );
+
+        // Relative NS target is made absolute
+        assert_eq!(records[1].data, "ns1.example.com.");
+    }
+
+    #[test]
+    fn test_parse_records_blank_owner_inherits_previous() {
+        let (records, _) = parse_records(BASIC_ZONE, "example.com").unwrap();
+        let aaaa = records.iter().find(|r| r.record_type == "AAAA").unwrap();
+        assert_eq!(aaaa.name, "www.example.com.");
+        // Blank-owner record without a TTL falls back to $TTL
+        assert_eq!(aaaa.ttl, Some(3600));
+    }
+
+    #[test]
+    fn test_parse_records_quoted_strings_preserved() {
+        let (records, _) = parse_records(BASIC_ZONE, "example.com").unwrap();
+        let txt = records.iter().find(|r| r.record_type == "TXT").unwrap();
+        // The ';' inside quotes is not a comment
+        assert_eq!(txt.data, r#""v=spf1 mx -all" "second; string""#);
+    }
+
+    #[test]
+    fn test_parse_records_escaped_quote() {
+        let content = "$TTL 60\n@ IN TXT \"say \\\"hi\\\"\"\n";
+        let (records, _) = parse_records(content, "example.com").unwrap();
+        assert_eq!(records[0].data, r#""say \"hi\"""#);
+    }
+
+    #[test]
+    fn test_parse_records_ttl_and_class_in_either_order() {
+        let content = "a 300 IN A 192.0.2.1\nb IN 300 A 192.0.2.2\nc A 192.0.2.3\n";
+        let (records, _) = parse_records(content, "example.com").unwrap();
+        assert_eq!(records[0].ttl, Some(300));
+        assert_eq!(records[1].ttl, Some(300));
+        assert_eq!(records[2].ttl, None);
+        assert!(records.iter().all(|r| r.record_type == "A"));
+    }
+
+    #[test]
+    fn test_parse_records_origin_change() {
+        let content = "$ORIGIN sub.example.com.\nhost IN A 192.0.2.1\n$ORIGIN example.com.\nwww IN CNAME host.sub\n";
+        let (records, _) = parse_records(content, "example.com").unwrap();
+        assert_eq!(records[0].name, "host.sub.example.com.");
+        assert_eq!(records[1].data, "host.sub.example.com.");
+    }
+
+    #[test]
+    fn test_parse_records_relative_origin() {
+        let content = "$ORIGIN sub\nhost IN A 192.0.2.1\n";
+        let (records, _) = parse_records(content, "example.com").unwrap();
+        assert_eq!(records[0].name, "host.sub.example.com.");
+    }
+
+    #[test]
+    fn test_parse_records_lowercase_type_normalized() {
+        let (records, _) = parse_records("www in a 192.0.2.1\n", "example.com").unwrap();
+        assert_eq!(records[0].record_type, "A");
+    }
+
+    // ========== Errors ==========
+
+    #[test]
+    fn test_parse_rejects_include_directive() {
+        let err = parse_records("$INCLUDE /etc/bind/rndc.key\n", "example.com").unwrap_err();
+        assert!(matches!(
+            err,
+            ZoneFileParseError::UnsupportedDirective { line: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_generate_directive() {
+        let err = parse_records(
+            "$TTL 60\n$GENERATE 1-100 host$ A 192.0.2.$\n",
+            "example.com",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ZoneFileParseError::UnsupportedDirective { line: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_unbalanced_parens() {
+        let err = parse_records("@ IN SOA a. b. ( 1 2 3 4 5\n", "example.com").unwrap_err();
+        assert!(matches!(err, ZoneFileParseError::UnbalancedParens(1)));
+
+        let err = parse_records("@ IN A 192.0.2.1 )\n", "example.com").unwrap_err();
+        assert!(matches!(err, ZoneFileParseError::Syntax { line: 1, .. }));
+    }
+
+    #[test]
+    fn test_parse_rejects_unterminated_quote() {
+        let err = parse_records("@ IN TXT \"never closed\n", "example.com").unwrap_err();
+        assert!(matches!(err, ZoneFileParseError::Syntax { .. }));
+    }
+
+    #[test]
+    fn test_parse_rejects_non_in_class() {
+        let err = parse_records("@ CH TXT \"x\"\n", "example.com").unwrap_err();
+        assert!(matches!(err, ZoneFileParseError::Syntax { .. }));
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_rdata() {
+        let err = parse_records("www IN A\n", "example.com").unwrap_err();
+        assert!(matches!(err, ZoneFileParseError::Syntax { line: 1, .. }));
+    }
+
+    #[test]
+    fn test_parse_zone_file_requires_soa() {
+        let err =
+            parse_zone_file("$TTL 60\n@ IN NS ns1.example.com.\n", "example.com").unwrap_err();
+        assert!(matches!(err, ZoneFileParseError::MissingSoa));
+    }
+
+    // ========== Zone Config Building ==========
+
+    #[test]
+    fn test_parse_zone_file_structure() {
+        let config = parse_zone_file(BASIC_ZONE, "example.com").unwrap();
+
+        assert_eq!(config.ttl, 3600);
+        assert_eq!(config.soa.primary_ns, "ns1.example.com.");
+        assert_eq!(config.soa.admin_email, "admin.example.com.");
+        assert_eq!(config.soa.serial, 2025010101);
+        assert_eq!(config.soa.negative_ttl, 86400);
+        assert_eq!(
+            config.name_servers,
+            vec!["ns1.example.com.", "ns2.example.com."]
+        );
+        assert_eq!(
+            config.name_server_ips.get("ns1.example.com."),
+            Some(&"192.0.2.10".to_string())
+        );
+
+        let www = config
+            .records
+            .iter()
+            .find(|r| r.name == "www" && r.record_type == "A")
+            .unwrap();
+        assert_eq!(www.value, "192.0.2.1");
+        assert_eq!(www.ttl, Some(300));
+
+        let mx = config
+            .records
+            .iter()
+            .find(|r| r.record_type == "MX")
+            .unwrap();
+        assert_eq!(mx.name, "@");
+        assert_eq!(mx.priority, Some(10));
+        assert_eq!(mx.value, "mail.example.com.");
+
+        let srv = config
+            .records
+            .iter()
+            .find(|r| r.record_type == "SRV")
+            .unwrap();
+        assert_eq!(srv.name, "_sip._tcp");
+        assert_eq!(srv.priority, Some(10));
+        assert_eq!(srv.value, "5 5060 sip.example.com.");
+    }
+
+    #[test]
+    fn test_parse_zone_file_without_ttl_uses_soa_minimum() {
+        let content = "@ IN SOA ns1.example.com. admin.example.com. 1 3600 600 604800 300\n";
+        let config = parse_zone_file(content, "example.com").unwrap();
+        assert_eq!(config.ttl, 300);
+    }
+
+    #[test]
+    fn test_parse_zone_file_soa_timer_units() {
+        let content = "$TTL 1h\n@ IN SOA ns1.example.com. admin.example.com. ( 7 1h 10m 1w 1d )\n";
+        let config = parse_zone_file(content, "example.com.").unwrap();
+        assert_eq!(config.ttl, 3600);
+        assert_eq!(config.soa.refresh, 3600);
+        assert_eq!(config.soa.retry, 600);
+        assert_eq!(config.soa.expire, 604_800);
+        assert_eq!(config.soa.negative_ttl, 86400);
+    }
+
+    #[test]
+    fn test_round_trip_generated_zone_file() {
+        let mut name_server_ips = HashMap::new();
+        name_server_ips.insert("ns1.example.com.".to_string(), "192.0.2.10".to_string());
+
+        let original = ZoneConfig {
+            ttl: 3600,
+            soa: SoaRecord {
+                primary_ns: "ns1.example.com.".to_string(),
+                admin_email: "admin.example.com.".to_string(),
+                serial: 2025010101,
+                refresh: 3600,
+                retry: 600,
+                expire: 604_800,
+                negative_ttl: 86400,
+            },
+            name_servers: vec!["ns1.example.com.".to_string()],
+            name_server_ips,
+            records: vec![
+                DnsRecord {
+                    name: "www".to_string(),
+                    record_type: "A".to_string(),
+                    value: "192.0.2.1".to_string(),
+                    ttl: Some(300),
+                    priority: None,
+                    data: None,
+                },
+                DnsRecord {
+                    name: "@".to_string(),
+                    record_type: "MX".to_string(),
+                    value: "mail.example.com.".to_string(),
+                    ttl: None,
+                    priority: Some(10),
+                    data: None,
+                },
+                DnsRecord {
+                    name: "@".to_string(),
+                    record_type: "TXT".to_string(),
+                    value: r#""v=spf1 -all""#.to_string(),
+                    ttl: None,
+                    priority: None,
+                    data: None,
+                },
+            ],
+            also_notify: None,
+            allow_transfer: None,
+            primaries: None,
+            dnssec_policy: None,
+            inline_signing: None,
+            forwarders: None,
+            forward: None,
+        };
+
+        let parsed = parse_zone_file(&original.to_zone_file(), "example.com").unwrap();
+
+        assert_eq!(parsed.ttl, original.ttl);
+        assert_eq!(parsed.soa.serial, original.soa.serial);
+        assert_eq!(parsed.soa.primary_ns, original.soa.primary_ns);
+        assert_eq!(parsed.name_servers, original.name_servers);
+        assert_eq!(parsed.name_server_ips, original.name_server_ips);
+        assert_eq!(parsed.records.len(), original.records.len());
+        for (got, want) in parsed.records.iter().zip(&original.records) {
+            assert_eq!(got.name, want.name);
+            assert_eq!(got.record_type, want.record_type);
+            assert_eq!(got.value, want.value);
+            assert_eq!(got.ttl, want.ttl);
+            assert_eq!(got.priority, want.priority);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Crash-safe zone file handling
+//!
+//! Zone files are written to a temporary file in the zone directory, fsynced
+//! and renamed over the target, so BIND (or a restart) never sees a partially
+//! written file.
+//!
+//! A [`ZoneFileTransaction`] pairs the file changes of a zone operation with
+//! its `rndc` step. Files that would be overwritten or removed are first moved
+//! aside; if the `rndc` step fails, [`rollback`](ZoneFileTransaction::rollback)
+//! removes what was written and puts them back, so the disk never disagrees
+//! with BIND.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+/// Suffix of files moved aside by a transaction
+const ROLLBACK_SUFFIX: &str = "rollback";
+
+/// Write `content` to `path` atomically: temp file, fsync, rename, then fsync
+/// of the directory so the rename itself is durable.
+pub async fn write_atomic(path: &Path, content: String) -> io::Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(content.as_bytes())?;
+        file.as_file().sync_all()?;
+        file.persist(&path).map_err(|e| e.error)?;
+        std::fs::File::open(dir)?.sync_all()
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+/// File changes of one zone operation that can be undone
+#[derive(Debug)]
+pub struct ZoneFileTransaction {
+    zone_file: PathBuf,
+    journal: PathBuf,
+    /// (original path, path it was moved aside to)
+    stashed: Vec<(PathBuf, PathBuf)>,
+    wrote_zone_file: bool,
+}
+
+impl ZoneFileTransaction {
+    /// Start a transaction for `<zone_dir>/<zone_name>.zone` and its journal.
+    pub fn new(zone_dir: &str, zone_name: &str) -> Self {
+        let dir = PathBuf::from(zone_dir);
+        Self {
+            zone_file: dir.join(format!("{}.zone", zone_name)),
+            journal: dir.join(format!("{}.zone.jnl", zone_name)),
+            stashed: Vec::new(),
+            wrote_zone_file: false,
+        }
+    }
+
+    /// Path of the zone file
+    pub fn zone_file(&self) -> &Path {
+        &self.zone_file
+    }
+
+    /// Move an existing file aside so that rollback can restore it.
+    async fn stash(&mut self, path: PathBuf) -> io::Result<()> {
+        if !tokio::fs::try_exists(&path).await? {
+            return Ok(());
+        }
+
+        let mut stash_path = path.clone().into_os_string();
+        stash_path.push(format!(".{}", ROLLBACK_SUFFIX));
+        let stash_path = PathBuf::from(stash_path);
+
+        tokio::fs::rename(&path, &stash_path).await?;
+        self.stashed.push((path, stash_path));
+        Ok(())
+    }
+
+    /// Write a new zone file, moving any existing zone file and journal aside.
+    ///
+    /// A stale journal would otherwise be replayed over the new content.
+    pub async fn write_zone_file(&mut self, content: String) -> io::Result<()> {
+        self.stash(self.zone_file.clone()).await?;
+        self.stash(self.journal.clone()).await?;
+        self.wrote_zone_file = true;
+        write_atomic(&self.zone_file, content).await
+    }
+
+    /// Remove the zone file and journal (moved aside until commit).
+    pub async fn remove_zone_files(&mut self) -> io::Result<()> {
+        self.stash(self.zone_file.clone()).await?;
+        self.stash(self.journal.clone()).await
+    }
+
+    /// Paths this transaction removes or replaces
+    pub fn stashed_files(&self) -> impl Iterator<Item = &Path> {
+        self.stashed.iter().map(|(original, _)| original.as_path())
+    }
+
+    /// Keep the changes: delete the files that were moved aside.
+    ///
+    /// Failures are logged only; a leftover stash file does not affect BIND.
+    pub async fn commit(self) {
+        for (original, stash) in &self.stashed {
+            match tokio::fs::remove_file(stash).await {
+                Ok(()) => info!("Removed {}", original.display()),
+                Err(e) => warn!("Failed to remove {}: {}", stash.display(), e),
+            }
+        }
+    }
+
+    /// Undo the changes: remove a written zone file and restore the files that
+    /// were moved aside.
+    ///
+    /// Every step is attempted; the first error is returned.
+    pub async fn rollback(self) -> io::Result<()> {
+        let mut result = Ok(());
+
+        if self.wrote_zone_file {
+            if let Err(e) = tokio::fs::remove_file(&self.zone_file).await {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!(
+                        "Rollback: failed to remove {}: {}",
+                        self.zone_file.display(),
+                        e
+                    );
+                    result = result.and(Err(e));
+                }
+            }
+        }
+
+        for (original, stash) in self.stashed.iter().rev() {
+            match tokio::fs::rename(stash, original).await {
+                Ok(()) => info!("Rollback: restored {}", original.display()),
+                Err(e) => {
+                    warn!("Rollback: failed to restore {}: {}", original.display(), e);
+                    result = result.and(Err(e));
+                }
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for crash-safe zone file handling
+
+#[cfg(test)]
+mod tests {
+    use crate::zone_files::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_replaces_content_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("example.com.zone");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, "new".to_string()).await.unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(file_names(dir.path()), vec!["example.com.zone"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_commit_drops_old_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let zone_dir = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("example.com.zone.jnl"), "journal").unwrap();
+
+        let mut files = ZoneFileTransaction::new(zone_dir, "example.com");
+        files.write_zone_file("zone".to_string()).await.unwrap();
+        files.commit().await;
+
+        assert_eq!(file_names(dir.path()), vec!["example.com.zone"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_rollback_restores_prior_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let zone_dir = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("example.com.zone.jnl"), "journal").unwrap();
+        fs::write(dir.path().join("other.com.zone"), "other").unwrap();
+
+        let mut files = ZoneFileTransaction::new(zone_dir, "example.com");
+        files.write_zone_file("zone".to_string()).await.unwrap();
+        files.rollback().await.unwrap();
+
+        assert_eq!(
+            file_names(dir.path()),
+            vec!["example.com.zone.jnl", "other.com.zone"]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("example.com.zone.jnl")).unwrap(),
+            "journal"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_rollback_restores_previous_zone_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let zone_dir = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("example.com.zone"), "previous").unwrap();
+
+        let mut files = ZoneFileTransaction::new(zone_dir, "example.com");
+        files.write_zone_file("zone".to_string()).await.unwrap();
+        files.rollback().await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("example.com.zone")).unwrap(),
+            "previous"
+        );
+        assert_eq!(file_names(dir.path()), vec!["example.com.zone"]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_then_rollback_or_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let zone_dir = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("example.com.zone"), "zone").unwrap();
+        fs::write(dir.path().join("example.com.zone.jnl"), "journal").unwrap();
+
+        let mut files = ZoneFileTransaction::new(zone_dir, "example.com");
+        files.remove_zone_files().await.unwrap();
+        assert_eq!(files.stashed_files().count(), 2);
+        // While pending, nothing that looks like a zone file remains
+        assert!(!dir.path().join("example.com.zone").exists());
+        files.rollback().await.unwrap();
+        assert_eq!(
+            file_names(dir.path()),
+            vec!["example.com.zone", "example.com.zone.jnl"]
+        );
+
+        let mut files = ZoneFileTransaction::new(zone_dir, "example.com");
+        files.remove_zone_files().await.unwrap();
+        files.commit().await;
+        assert!(file_names(dir.path()).is_empty());
+    }
+}
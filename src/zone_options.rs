@@ -0,0 +1,439 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Zone options that `PATCH /api/v1/zones/{name}` can set or clear
+//!
+//! A [`ModifyZoneRequest`] is applied to the zone's live configuration, as
+//! parsed from `rndc showzone`, and the result is sent back with
+//! `rndc modzone`. Options the request does not mention keep their live values.
+//!
+//! - Lists (`alsoNotify`, `allowQuery`, ...) are replaced as a whole; an empty
+//!   list removes the option.
+//! - Any option named in `clear` is removed from the configuration, so BIND9
+//!   falls back to its default (or the `options`/`view` setting).
+//!
+//! Naming an option in `clear` and also setting it is rejected as ambiguous.
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    rndc_types::{self, AddressMatchElement, CheckNamesMode, NotifyMode, ZoneConfig},
+    types::ApiError,
+    zones::{
+        parse_address_match_list, server_specs, validate_ip_port_list, validate_update_policy,
+        ModifyZoneRequest,
+    },
+};
+
+/// Longest transfer time or idle limit BIND9 accepts, in minutes (28 days).
+const MAX_TRANSFER_MINUTES: u32 = 40_320;
+
+/// Largest TTL allowed by RFC 2181.
+const MAX_TTL: u32 = i32::MAX as u32;
+
+/// A zone option that a modify request can set or clear
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ZoneOption {
+    AlsoNotify,
+    AllowTransfer,
+    AllowUpdate,
+    AllowQuery,
+    AllowNotify,
+    AllowUpdateForwarding,
+    UpdatePolicy,
+    Notify,
+    MaxTransferTimeIn,
+    MaxTransferTimeOut,
+    MaxTransferIdleIn,
+    MaxTransferIdleOut,
+    TransferSource,
+    TransferSourceV6,
+    NotifySource,
+    NotifySourceV6,
+    IxfrFromDifferences,
+    CheckNames,
+    CheckMx,
+    CheckIntegrity,
+    MaxZoneTtl,
+    MaxRefreshTime,
+    MinRefreshTime,
+    MaxRetryTime,
+    MinRetryTime,
+    MultiMaster,
+    RequestIxfr,
+    RequestExpire,
+}
+
+impl ZoneOption {
+    /// Request field name, as used in JSON and error messages
+    pub fn field_name(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// BIND9 statement name (e.g. "max-zone-ttl")
+    pub fn bind_name(&self) -> String {
+        let mut name = String::new();
+        for c in self.field_name().chars() {
+            if c.is_ascii_uppercase() {
+                name.push('-');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+}
+
+/// Options that `request` sets, in declaration order.
+pub fn options_set(request: &ModifyZoneRequest) -> Vec<ZoneOption> {
+    use ZoneOption::*;
+
+    [
+        (AlsoNotify, request.also_notify.is_some()),
+        (AllowTransfer, request.allow_transfer.is_some()),
+        (AllowUpdate, request.allow_update.is_some()),
+        (AllowQuery, request.allow_query.is_some()),
+        (AllowNotify, request.allow_notify.is_some()),
+        (
+            AllowUpdateForwarding,
+            request.allow_update_forwarding.is_some(),
+        ),
+        (UpdatePolicy, request.update_policy.is_some()),
+        (Notify, request.notify.is_some()),
+        (MaxTransferTimeIn, request.max_transfer_time_in.is_some()),
+        (MaxTransferTimeOut, request.max_transfer_time_out.is_some()),
+        (MaxTransferIdleIn, request.max_transfer_idle_in.is_some()),
+        (MaxTransferIdleOut, request.max_transfer_idle_out.is_some()),
+        (TransferSource, request.transfer_source.is_some()),
+        (TransferSourceV6, request.transfer_source_v6.is_some()),
+        (NotifySource, request.notify_source.is_some()),
+        (NotifySourceV6, request.notify_source_v6.is_some()),
+        (IxfrFromDifferences, request.ixfr_from_differences.is_some()),
+        (CheckNames, request.check_names.is_some()),
+        (CheckMx, request.check_mx.is_some()),
+        (CheckIntegrity, request.check_integrity.is_some()),
+        (MaxZoneTtl, request.max_zone_ttl.is_some()),
+        (MaxRefreshTime, request.max_refresh_time.is_some()),
+        (MinRefreshTime, request.min_refresh_time.is_some()),
+        (MaxRetryTime, request.max_retry_time.is_some()),
+        (MinRetryTime, request.min_retry_time.is_some()),
+        (MultiMaster, request.multi_master.is_some()),
+        (RequestIxfr, request.request_ixfr.is_some()),
+        (RequestExpire, request.request_expire.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(option, set)| set.then_some(option))
+    .collect()
+}
+
+/// Remove an option from the configuration.
+fn clear_option(config: &mut ZoneConfig, option: ZoneOption) {
+    use ZoneOption::*;
+
+    match option {
+        AlsoNotify => config.also_notify = None,
+        AllowTransfer => config.allow_transfer = None,
+        AllowUpdate => config.allow_update = None,
+        AllowQuery => config.allow_query = None,
+        AllowNotify => config.allow_notify = None,
+        AllowUpdateForwarding => config.allow_update_forwarding = None,
+        UpdatePolicy => config.update_policy = None,
+        Notify => config.notify = None,
+        MaxTransferTimeIn => config.max_transfer_time_in = None,
+        MaxTransferTimeOut => config.max_transfer_time_out = None,
+        MaxTransferIdleIn => config.max_transfer_idle_in = None,
+        MaxTransferIdleOut => config.max_transfer_idle_out = None,
+        TransferSource => config.transfer_source = None,
+        TransferSourceV6 => config.transfer_source_v6 = None,
+        NotifySource => config.notify_source = None,
+        NotifySourceV6 => config.notify_source_v6 = None,
+        IxfrFromDifferences => config.ixfr_from_differences = None,
+        CheckNames => config.check_names = None,
+        CheckMx => config.check_mx = None,
+        CheckIntegrity => config.check_integrity = None,
+        MaxZoneTtl => config.max_zone_ttl = None,
+        MaxRefreshTime => config.max_refresh_time = None,
+        MinRefreshTime => config.min_refresh_time = None,
+        MaxRetryTime => config.max_retry_time = None,
+        MinRetryTime => config.min_retry_time = None,
+        MultiMaster => config.multi_master = None,
+        RequestIxfr => config.request_ixfr = None,
+        RequestExpire => config.request_expire = None,
+    }
+}
+
+/// Parse an address match list; an empty list removes the option.
+fn match_list(
+    field: &str,
+    entries: &[String],
+) -> Result<Option<Vec<AddressMatchElement>>, ApiError> {
+    let elements = parse_address_match_list(field, entries)?;
+    Ok((!elements.is_empty()).then_some(elements))
+}
+
+/// Parse a source address of the given family.
+fn source_address(field: &str, value: &str, v6: bool) -> Result<IpAddr, ApiError> {
+    match value.parse::<IpAddr>() {
+        Ok(addr) if addr.is_ipv6() == v6 => Ok(addr),
+        _ => Err(ApiError::InvalidRequest(format!(
+            "{} must be an {} address: {:?}",
+            field,
+            if v6 { "IPv6" } else { "IPv4" },
+            value
+        ))),
+    }
+}
+
+/// Check a transfer time or idle limit in minutes.
+fn transfer_minutes(field: &str, value: u32) -> Result<u32, ApiError> {
+    if (1..=MAX_TRANSFER_MINUTES).contains(&value) {
+        Ok(value)
+    } else {
+        Err(ApiError::InvalidRequest(format!(
+            "{} must be between 1 and {} minutes",
+            field, MAX_TRANSFER_MINUTES
+        )))
+    }
+}
+
+/// Parse a check-names / check-mx mode.
+fn check_mode(field: &str, value: &str) -> Result<CheckNamesMode, ApiError> {
+    CheckNamesMode::parse(value).ok_or_else(|| {
+        ApiError::InvalidRequest(format!(
+            "Invalid {}: {}. Must be 'fail', 'warn' or 'ignore'",
+            field, value
+        ))
+    })
+}
+
+/// Reject a lower bound above its upper bound after the change is applied.
+fn check_bounds(min_field: &str, min: Option<u32>, max: Option<u32>) -> Result<(), ApiError> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(ApiError::InvalidRequest(format!(
+            "{} ({}) must not exceed its maximum ({})",
+            min_field, min, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a modify request sets or clears something, and never both for
+/// the same option.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] (HTTP 400) otherwise.
+pub fn validate_modify_request(request: &ModifyZoneRequest) -> Result<(), ApiError> {
+    let set = options_set(request);
+    if set.is_empty() && request.clear.is_empty() {
+        return Err(ApiError::InvalidRequest(
+            "At least one option to set or clear must be provided".to_string(),
+        ));
+    }
+    if let Some(option) = request.clear.iter().find(|o| set.contains(o)) {
+        return Err(ApiError::InvalidRequest(format!(
+            "'{}' cannot be both set and cleared",
+            option.field_name()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Apply a modify request to a zone's live configuration.
+///
+/// The configuration is only changed if the whole request is valid.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] when [`validate_modify_request`] fails
+/// or a value is one BIND9 would reject.
+pub fn apply_zone_options(
+    config: &mut ZoneConfig,
+    request: &ModifyZoneRequest,
+) -> Result<(), ApiError> {
+    validate_modify_request(request)?;
+
+    let mut updated = config.clone();
+    for option in &request.clear {
+        clear_option(&mut updated, *option);
+    }
+    // Options the parser could not type are kept verbatim; drop them so a
+    // changed option is not rendered twice
+    for option in options_set(request).iter().chain(&request.clear) {
+        updated.raw_options.remove(&option.bind_name());
+    }
+
+    if let Some(also_notify) = &request.also_notify {
+        // Same "ip" / "ip:port" entries as zone creation
+        validate_ip_port_list("also-notify", also_notify)?;
+        let specs = server_specs(also_notify);
+        updated.also_notify = (!specs.is_empty()).then_some(specs);
+    }
+    if let Some(allow_transfer) = &request.allow_transfer {
+        updated.allow_transfer = match_list("allow-transfer", allow_transfer)?;
+    }
+    if let Some(allow_update) = &request.allow_update {
+        updated.allow_update = match_list("allow-update", allow_update)?;
+    }
+    if let Some(allow_query) = &request.allow_query {
+        updated.allow_query = match_list("allow-query", allow_query)?;
+    }
+    if let Some(allow_notify) = &request.allow_notify {
+        updated.allow_notify = match_list("allow-notify", allow_notify)?;
+    }
+    if let Some(forwarding) = &request.allow_update_forwarding {
+        updated.allow_update_forwarding = match_list("allow-update-forwarding", forwarding)?;
+    }
+    if let Some(rules) = &request.update_policy {
+        updated.update_policy = if rules.is_empty() {
+            None
+        } else {
+            validate_update_policy("updatePolicy", rules)?;
+            Some(rndc_types::UpdatePolicy::Rules(rules.clone()))
+        };
+    }
+    // BIND9 rejects a zone with both; switching between them needs the other
+    // one cleared in the same request
+    if updated.allow_update.is_some() && updated.update_policy.is_some() {
+        return Err(ApiError::InvalidRequest(
+            "A zone cannot have both allowUpdate and updatePolicy; clear one of them".to_string(),
+        ));
+    }
+
+    if let Some(notify) = &request.notify {
+        // "master-only" is the pre-9.18 spelling of "primary-only"
+        updated.notify = Some(
+            NotifyMode::parse(notify)
+                .filter(|mode| *mode != NotifyMode::MasterOnly)
+                .ok_or_else(|| {
+                    ApiError::InvalidRequest(format!(
+                        "Invalid notify: {}. Must be 'yes', 'no', 'explicit' or 'primary-only'",
+                        notify
+                    ))
+                })?,
+        );
+    }
+
+    let transfer_limits = [
+        (
+            "maxTransferTimeIn",
+            request.max_transfer_time_in,
+            &mut updated.max_transfer_time_in,
+        ),
+        (
+            "maxTransferTimeOut",
+            request.max_transfer_time_out,
+            &mut updated.max_transfer_time_out,
+        ),
+        (
+            "maxTransferIdleIn",
+            request.max_transfer_idle_in,
+            &mut updated.max_transfer_idle_in,
+        ),
+        (
+            "maxTransferIdleOut",
+            request.max_transfer_idle_out,
+            &mut updated.max_transfer_idle_out,
+        ),
+    ];
+    for (field, value, target) in transfer_limits {
+        if let Some(value) = value {
+            *target = Some(transfer_minutes(field, value)?);
+        }
+    }
+
+    let sources = [
+        (
+            "transferSource",
+            &request.transfer_source,
+            false,
+            &mut updated.transfer_source,
+        ),
+        (
+            "transferSourceV6",
+            &request.transfer_source_v6,
+            true,
+            &mut updated.transfer_source_v6,
+        ),
+        (
+            "notifySource",
+            &request.notify_source,
+            false,
+            &mut updated.notify_source,
+        ),
+        (
+            "notifySourceV6",
+            &request.notify_source_v6,
+            true,
+            &mut updated.notify_source_v6,
+        ),
+    ];
+    for (field, value, v6, target) in sources {
+        if let Some(value) = value {
+            *target = Some(source_address(field, value, v6)?);
+        }
+    }
+
+    if let Some(mode) = &request.check_names {
+        updated.check_names = Some(check_mode("checkNames", mode)?);
+    }
+    if let Some(mode) = &request.check_mx {
+        updated.check_mx = Some(check_mode("checkMx", mode)?);
+    }
+
+    if let Some(ttl) = request.max_zone_ttl {
+        if ttl > MAX_TTL {
+            return Err(ApiError::InvalidRequest(format!(
+                "maxZoneTtl must not exceed {}",
+                MAX_TTL
+            )));
+        }
+        updated.max_zone_ttl = Some(ttl);
+    }
+
+    let intervals = [
+        (request.max_refresh_time, &mut updated.max_refresh_time),
+        (request.min_refresh_time, &mut updated.min_refresh_time),
+        (request.max_retry_time, &mut updated.max_retry_time),
+        (request.min_retry_time, &mut updated.min_retry_time),
+    ];
+    for (value, target) in intervals {
+        if value.is_some() {
+            *target = value;
+        }
+    }
+    check_bounds(
+        "minRefreshTime",
+        updated.min_refresh_time,
+        updated.max_refresh_time,
+    )?;
+    check_bounds(
+        "minRetryTime",
+        updated.min_retry_time,
+        updated.max_retry_time,
+    )?;
+
+    let flags = [
+        (
+            request.ixfr_from_differences,
+            &mut updated.ixfr_from_differences,
+        ),
+        (request.check_integrity, &mut updated.check_integrity),
+        (request.multi_master, &mut updated.multi_master),
+        (request.request_ixfr, &mut updated.request_ixfr),
+        (request.request_expire, &mut updated.request_expire),
+    ];
+    for (value, target) in flags {
+        if value.is_some() {
+            *target = value;
+        }
+    }
+
+    *config = updated;
+    Ok(())
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2025 Erick Bourgeois, firestoned
+// SPDX-License-Identifier: MIT
+
+//! Tests for applying zone option changes
+
+#[cfg(test)]
+mod tests {
+    use crate::rndc_parser::parse_showzone;
+    use crate::rndc_types::{AddressMatchElement, CheckNamesMode, NotifyMode, ZoneConfig};
+    use crate::types::ApiError;
+    use crate::zone_options::*;
+    use crate::zones::ModifyZoneRequest;
+
+    fn live_config() -> ZoneConfig {
+        parse_showzone(
+            r#"zone "example.com" { type primary; file "/var/cache/bind/example.com.zone"; allow-query { 10.0.0.1; }; allow-update { key "update-key"; }; max-zone-ttl 86400; min-retry-time 300; notify explicit; };"#,
+        )
+        .unwrap()
+    }
+
+    fn request(json: &str) -> ModifyZoneRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn assert_invalid(result: Result<(), ApiError>, expected: &str) {
+        match result {
+            Err(ApiError::InvalidRequest(msg)) => {
+                assert!(msg.contains(expected), "unexpected message: {}", msg)
+            }
+            other => panic!("expected InvalidRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_apply_sets_typed_options_and_keeps_the_rest() {
+        let mut config = live_config();
+        let request = request(
+            r#"{
+                "allowNotify": ["192.0.2.1"],
+                "notify": "primary-only",
+                "maxTransferTimeIn": 60,
+                "transferSource": "192.0.2.53",
+                "notifySourceV6": "2001:db8::53",
+                "checkNames": "warn",
+                "ixfrFromDifferences": true,
+                "maxRetryTime": 600
+            }"#,
+        );
+
+        apply_zone_options(&mut config, &request).unwrap();
+
+        assert_eq!(config.notify, Some(NotifyMode::PrimaryOnly));
+        assert_eq!(config.max_transfer_time_in, Some(60));
+        assert_eq!(config.transfer_source, Some("192.0.2.53".parse().unwrap()));
+        assert_eq!(
+            config.notify_source_v6,
+            Some("2001:db8::53".parse().unwrap())
+        );
+        assert_eq!(config.check_names, Some(CheckNamesMode::Warn));
+        assert_eq!(config.ixfr_from_differences, Some(true));
+        assert_eq!(config.max_retry_time, Some(600));
+        // Untouched options keep their live values
+        assert_eq!(config.allow_query, Some(vec!["10.0.0.1".parse().unwrap()]));
+        assert_eq!(config.max_zone_ttl, Some(86400));
+        assert_eq!(
+            config.allow_update,
+            Some(vec![AddressMatchElement::Key("update-key".to_string())])
+        );
+
+        let block = config.to_rndc_block();
+        assert!(block.contains("notify primary-only;"));
+        assert!(block.contains("allow-notify { 192.0.2.1; };"));
+        assert!(block.contains("transfer-source 192.0.2.53;"));
+    }
+
+    #[test]
+    fn test_clear_removes_options() {
+        let mut config = live_config();
+        let request = request(r#"{"clear": ["allowQuery", "maxZoneTtl", "allowUpdate"]}"#);
+
+        apply_zone_options(&mut config, &request).unwrap();
+
+        assert!(config.allow_query.is_none());
+        assert!(config.max_zone_ttl.is_none());
+        assert!(config.allow_update.is_none());
+        assert_eq!(config.notify, Some(NotifyMode::Explicit));
+        assert!(!config.to_rndc_block().contains("max-zone-ttl"));
+    }
+
+    #[test]
+    fn test_untyped_live_value_replaced_not_duplicated() {
+        let mut config = parse_showzone(
+            r#"zone "example.com" { type primary; transfer-source 10.0.0.1 port 5353; max-zone-ttl unlimited; };"#,
+        )
+        .unwrap();
+
+        let request = request(r#"{"transferSource": "192.0.2.1", "clear": ["maxZoneTtl"]}"#);
+        apply_zone_options(&mut config, &request).unwrap();
+
+        let block = config.to_rndc_block();
+        assert_eq!(block.matches("transfer-source").count(), 1);
+        assert!(block.contains("transfer-source 192.0.2.1;"));
+        assert!(!block.contains("max-zone-ttl"));
+    }
+
+    #[test]
+    fn test_empty_list_removes_option() {
+        let mut config = live_config();
+        apply_zone_options(&mut config, &request(r#"{"allowQuery": []}"#)).unwrap();
+        assert!(config.allow_query.is_none());
+    }
+
+    #[test]
+    fn test_update_policy_replaces_allow_update() {
+        let mut config = live_config();
+        let replace = request(
+            r#"{
+                "updatePolicy": [{"action": "grant", "identity": "tenant1-key", "ruleType": "name",
+                                  "name": "_acme-challenge.example.com.", "types": ["TXT"]}],
+                "clear": ["allowUpdate"]
+            }"#,
+        );
+
+        apply_zone_options(&mut config, &replace).unwrap();
+
+        assert!(config.allow_update.is_none());
+        let block = config.to_rndc_block();
+        assert!(block.contains(
+            "update-policy { grant tenant1-key name _acme-challenge.example.com. TXT; };"
+        ));
+        assert!(!block.contains("allow-update"));
+
+        // The policy survives the round trip through showzone
+        let reparsed = parse_showzone(&format!(r#"zone "example.com" {}"#, block)).unwrap();
+        assert_eq!(reparsed.update_policy, config.update_policy);
+
+        apply_zone_options(&mut config, &request(r#"{"updatePolicy": []}"#)).unwrap();
+        assert!(config.update_policy.is_none());
+    }
+
+    #[test]
+    fn test_update_policy_conflicts_with_allow_update() {
+        let mut config = live_config();
+        let before = config.to_rndc_block();
+        let request = request(
+            r#"{"updatePolicy": [{"action": "grant", "identity": "k", "ruleType": "zonesub"}]}"#,
+        );
+        assert_invalid(
+            apply_zone_options(&mut config, &request),
+            "both allowUpdate and updatePolicy",
+        );
+        assert_eq!(config.to_rndc_block(), before);
+    }
+
+    #[test]
+    fn test_request_must_change_something() {
+        assert_invalid(
+            validate_modify_request(&ModifyZoneRequest::default()),
+            "At least one option",
+        );
+    }
+
+    #[test]
+    fn test_set_and_clear_same_option_rejected() {
+        let request = request(r#"{"maxZoneTtl": 3600, "clear": ["maxZoneTtl"]}"#);
+        assert_invalid(
+            validate_modify_request(&request),
+            "'maxZoneTtl' cannot be both set and cleared",
+        );
+    }
+
+    #[test]
+    fn test_invalid_values_leave_config_unchanged() {
+        let cases = [
+            (r#"{"notify": "master-only"}"#, "Invalid notify"),
+            (r#"{"checkMx": "strict"}"#, "Invalid checkMx"),
+            (r#"{"maxTransferIdleOut": 0}"#, "maxTransferIdleOut"),
+            (r#"{"maxTransferTimeOut": 40321}"#, "maxTransferTimeOut"),
+            (r#"{"transferSource": "2001:db8::1"}"#, "IPv4"),
+            (r#"{"notifySourceV6": "192.0.2.1"}"#, "IPv6"),
+            (r#"{"maxZoneTtl": 2147483648}"#, "maxZoneTtl"),
+            (r#"{"allowNotify": ["10.0.0.1; any"]}"#, "allow-notify"),
+            (r#"{"allowQuery": ["10.1.2.3/8"]}"#, "allow-query"),
+            (r#"{"alsoNotify": ["192.0.2.1:port"]}"#, "also-notify"),
+            // Merged with the live min-retry-time of 300
+            (r#"{"maxRetryTime": 60}"#, "minRetryTime"),
+            (
+                r#"{"minRefreshTime": 900, "maxRefreshTime": 600}"#,
+                "minRefreshTime",
+            ),
+        ];
+
+        for (json, expected) in cases {
+            let mut config = live_config();
+            let before = config.to_rndc_block();
+            assert_invalid(apply_zone_options(&mut config, &request(json)), expected);
+            assert_eq!(config.to_rndc_block(), before, "changed by {}", json);
+        }
+    }
+
+    #[test]
+    fn test_unknown_clear_option_rejected_by_serde() {
+        let result: Result<ModifyZoneRequest, _> =
+            serde_json::from_str(r#"{"clear": ["notAnOption"]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zone_option_names() {
+        assert_eq!(
+            ZoneOption::TransferSourceV6.field_name(),
+            "transferSourceV6"
+        );
+        assert_eq!(
+            ZoneOption::AllowUpdateForwarding.field_name(),
+            "allowUpdateForwarding"
+        );
+        assert_eq!(
+            ZoneOption::TransferSourceV6.bind_name(),
+            "transfer-source-v6"
+        );
+        assert_eq!(ZoneOption::MaxZoneTtl.bind_name(), "max-zone-ttl");
+    }
+}
@@ -10,20 +10,44 @@
 //! - Getting zone status
 //! - Freezing/thawing zones
 //! - Notifying secondaries
+//! - Replacing a zone with a desired state (diff and apply)
+//! - Cloning a zone to a new origin
+//! - Importing a zone from master-file text
+//! - Exporting a zone snapshot
+//! - Syncing and inspecting a zone's journal
+//!
+//! Bulk reload, notify and retransfer live in [`crate::zone_batch`].
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::path::PathBuf;
-use tracing::{debug, error, info};
-use utoipa::ToSchema;
+use tracing::{debug, error, info, warn};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
+    catalog::{self, CatalogError, CatalogOperation, CatalogProperties},
+    journal::{self, JournalHeader, JournalTransaction},
     metrics,
-    types::{ApiError, AppState},
+    record_data::RecordData,
+    rndc_parser,
+    rndc_types::{
+        self, AddressMatchElement, ForwardMode, ForwarderSpec, PrimarySpec, UpdatePolicy,
+        UpdatePolicyRule, ZoneType,
+    },
+    soa_serial::{self, SerialStrategy},
+    types::{ApiError, AppState, DryRunQuery, OperationPlan},
+    zone_check::{self, ZoneCheckIssue},
+    zone_clone, zone_diff,
+    zone_export::{self, ExportFormat, ZoneExport},
+    zone_file_parser::{self, ResourceRecord},
+    zone_files::{self, ZoneFileTransaction},
+    zone_options::{apply_zone_options, validate_modify_request, ZoneOption},
 };
 
 /// Zone type constants
@@ -139,28 +163,110 @@ pub(crate) fn validate_rndc_identifier(field: &str, value: &str) -> Result<(), A
     Ok(())
 }
 
-/// Validate that every entry in an IP-address list parses as an [`IpAddr`].
+/// Parse the entries of a BIND9 address match list (`allow-transfer`,
+/// `allow-query`, ...).
 ///
-/// `create_zone` interpolates these values directly into the `rndc addzone`
-/// configuration literal — `primaries {{ .. }}`, `also-notify {{ .. }}`,
-/// `allow-transfer {{ .. }}`. Without this check an entry such as
-/// `"1.2.3.4; }; zone \"x\" { type primary; ..."` would close the brace block
-/// and inject arbitrary configuration into the running `named` (B-8 / C-1).
-/// Requiring a strict `IpAddr` parse rejects every metacharacter (`}`, `{`,
-/// `;`, `"`, whitespace) since none can appear in a valid IPv4/IPv6 literal.
+/// Each entry is one element in BIND syntax: an address (`192.0.2.1`), a CIDR
+/// prefix (`10.244.0.0/16`), a TSIG key (`key "name"`), a built-in or named
+/// ACL (`any`, `localnets`, `trusted`), or any of those negated with `!`.
 ///
-/// [`IpAddr`]: std::net::IpAddr
+/// `create_zone` and `modify_zone` send these lists to `named` inside the
+/// `rndc addzone`/`modzone` configuration literal. An entry such as
+/// `"1.2.3.4; }; zone \"x\" { type primary; ..."` must never reach it verbatim,
+/// or it would close the brace block and inject arbitrary configuration
+/// (B-8 / C-1). Entries are therefore parsed into
+/// [`AddressMatchElement`]s, which only accept address literals and names made
+/// of letters, digits, `.`, `-` and `_`, and the list is rendered from the
+/// parsed form.
 ///
 /// # Errors
 /// Returns [`ApiError::InvalidRequest`] (HTTP 400) if any entry is not a valid
-/// IP address.
-pub(crate) fn validate_ip_list(field: &str, ips: &[String]) -> Result<(), ApiError> {
-    for ip in ips {
-        if ip.parse::<std::net::IpAddr>().is_err() {
-            return Err(ApiError::InvalidRequest(format!(
-                "{} contains an invalid IP address: {:?}",
-                field, ip
-            )));
+/// element.
+pub(crate) fn parse_address_match_list(
+    field: &str,
+    entries: &[String],
+) -> Result<Vec<AddressMatchElement>, ApiError> {
+    entries
+        .iter()
+        .map(|entry| {
+            entry.parse().map_err(|_| {
+                ApiError::InvalidRequest(format!(
+                    "{} contains an invalid address match element: {:?}",
+                    field, entry
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Validate the rules of a structured `update-policy`.
+///
+/// Rules are rendered unquoted into the `rndc addzone`/`modzone` configuration
+/// literal, so every free-text field is restricted to the characters its BIND9
+/// grammar needs:
+/// - `identity`: letters, digits and `.-_*/@` (TSIG key names, `*`, Kerberos
+///   principals such as `host/ns1.example.com@EXAMPLE.COM`)
+/// - `name`: letters, digits and `.-_*`; required for every rule type except
+///   `zonesub`, which must not have one
+/// - `types`: letters and digits, with an optional `(n)` record limit
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] (HTTP 400) for an empty rule list or an
+/// invalid rule.
+pub(crate) fn validate_update_policy(
+    field: &str,
+    rules: &[UpdatePolicyRule],
+) -> Result<(), ApiError> {
+    if rules.is_empty() {
+        return Err(ApiError::InvalidRequest(format!(
+            "{} must contain at least one rule",
+            field
+        )));
+    }
+
+    let valid = |value: &str, extra: &str| {
+        !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || extra.contains(c))
+    };
+    let valid_type = |value: &str| match value.split_once('(') {
+        Some((name, limit)) => {
+            valid(name, "")
+                && limit
+                    .strip_suffix(')')
+                    .is_some_and(|n| valid(n, "") && n.chars().all(|c| c.is_ascii_digit()))
+        }
+        None => valid(value, ""),
+    };
+
+    for (index, rule) in rules.iter().enumerate() {
+        let invalid =
+            |reason: String| ApiError::InvalidRequest(format!("{}[{}]: {}", field, index, reason));
+
+        if !valid(&rule.identity, ".-_*/@") {
+            return Err(invalid(format!("invalid identity {:?}", rule.identity)));
+        }
+        match (&rule.name, rule.rule_type.takes_name()) {
+            (Some(name), true) if !valid(name, ".-_*") => {
+                return Err(invalid(format!("invalid name {:?}", name)));
+            }
+            (None, true) => {
+                return Err(invalid(format!(
+                    "rule type '{}' requires a name",
+                    rule.rule_type.as_str()
+                )));
+            }
+            (Some(_), false) => {
+                return Err(invalid(format!(
+                    "rule type '{}' does not take a name",
+                    rule.rule_type.as_str()
+                )));
+            }
+            _ => {}
+        }
+        if let Some(record_type) = rule.types.iter().find(|t| !valid_type(t)) {
+            return Err(invalid(format!("invalid record type {:?}", record_type)));
         }
     }
 
@@ -208,13 +314,19 @@ pub(crate) fn parse_ip_port_entry(s: &str) -> Option<(&str, Option<u16>)> {
     Some((s, None))
 }
 
-/// Render an IP:port entry into BIND config syntax (`ip port N`).
-pub(crate) fn render_ip_port_entry(s: &str) -> String {
-    if let Some((addr, Some(port))) = parse_ip_port_entry(s) {
-        format!("{} port {}; ", addr, port)
-    } else {
-        format!("{}; ", s)
-    }
+/// Convert validated `ip` / `ip:port` entries to BIND9 server specs, which
+/// render in BIND's `ip port N` syntax.
+pub(crate) fn server_specs(entries: &[String]) -> Vec<PrimarySpec> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (address, port) = parse_ip_port_entry(entry)?;
+            Some(PrimarySpec {
+                address: address.parse().ok()?,
+                port,
+            })
+        })
+        .collect()
 }
 
 /// Validate transfer endpoints rendered into BIND `primaries` / `also-notify` blocks.
@@ -225,7 +337,8 @@ pub(crate) fn render_ip_port_entry(s: &str) -> String {
 /// `named` on an unprivileged transfer port.
 ///
 /// Entries are rendered to BIND's `port` keyword syntax internally.
-/// `allow-transfer` does not support per-endpoint ports and must use bare IPs.
+/// `allow-transfer` takes an address match list instead; see
+/// [`parse_address_match_list`].
 pub(crate) fn validate_ip_port_list(field: &str, entries: &[String]) -> Result<(), ApiError> {
     for entry in entries {
         if parse_ip_port_entry(entry).is_none() {
@@ -292,9 +405,8 @@ fn validate_zone_file_hostname(field: &str, value: &str) -> Result<(), ApiError>
 ///   `$GENERATE` master-file directive.
 /// - Glue record IPs must parse as an [`IpAddr`](std::net::IpAddr).
 /// - Each embedded record is held to the same rules as the add-record endpoint
-///   ([`validate_record_type`](crate::records::validate_record_type),
-///   [`validate_record_name`](crate::records::validate_record_name),
-///   [`validate_record_value`](crate::records::validate_record_value)).
+///   ([`validate_record_name`](crate::records::validate_record_name),
+///   [`resolve_record_value`](crate::records::resolve_record_value)).
 ///
 /// # Errors
 /// Returns [`ApiError::InvalidRequest`] or [`ApiError::InvalidRecord`] (both
@@ -318,9 +430,13 @@ pub(crate) fn validate_zone_config_content(config: &ZoneConfig) -> Result<(), Ap
     }
 
     for record in &config.records {
-        crate::records::validate_record_type(&record.record_type)?;
         crate::records::validate_record_name(&record.name)?;
-        crate::records::validate_record_value(&record.record_type, &record.value)?;
+        crate::records::resolve_record_value(
+            &record.record_type,
+            &record.value,
+            record.priority,
+            record.data.as_ref(),
+        )?;
     }
 
     Ok(())
@@ -449,6 +565,25 @@ pub struct SoaRecord {
     pub negative_ttl: u32,
 }
 
+impl Default for SoaRecord {
+    /// An SOA without name server or admin email; primary zones must set both.
+    fn default() -> Self {
+        Self {
+            primary_ns: String::new(),
+            admin_email: String::new(),
+            serial: default_serial(),
+            refresh: default_refresh(),
+            retry: default_retry(),
+            expire: default_expire(),
+            negative_ttl: default_negative_ttl(),
+        }
+    }
+}
+
+fn default_zone_ttl() -> u32 {
+    3600
+}
+
 fn default_serial() -> u32 {
     // Generate serial as YYYYMMDD01
     let now = chrono::Utc::now();
@@ -479,11 +614,12 @@ pub struct DnsRecord {
     /// Record name (e.g., "www", "@")
     pub name: String,
 
-    /// Record type (e.g., "A", "AAAA", "CNAME", "MX", "TXT")
-    #[serde(rename = "type")]
+    /// Record type (e.g., "A", "AAAA", "CNAME", "MX", "TXT"); optional with `data`
+    #[serde(rename = "type", default)]
     pub record_type: String,
 
-    /// Record value (e.g., "192.0.2.1", "example.com.")
+    /// Record value (e.g., "192.0.2.1", "example.com."); omitted with `data`
+    #[serde(default)]
     pub value: String,
 
     /// Optional TTL (uses zone default if not specified)
@@ -493,23 +629,35 @@ pub struct DnsRecord {
     /// Optional priority (for MX, SRV records)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u16>,
+
+    /// Structured record data, instead of `value` and `priority`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
 }
 
 /// Structured zone configuration
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ZoneConfig {
-    /// Default TTL for the zone (e.g., 3600)
+    /// Default TTL for the zone (default: 3600)
+    ///
+    /// The zone content fields (`ttl`, `soa`, `nameServers`, `nameServerIps`
+    /// and `records`) are only used by primary zones and may be omitted for
+    /// other zone types.
+    #[serde(default = "default_zone_ttl")]
     pub ttl: u32,
 
-    /// SOA record
+    /// SOA record (required for primary zones)
+    #[serde(default)]
     pub soa: SoaRecord,
 
     /// Name servers for the zone
+    #[serde(default)]
     pub name_servers: Vec<String>,
 
     /// A records for nameservers (glue records)
     /// Maps nameserver hostname to IP address (e.g., "ns1.example.com." -> "192.0.2.1")
+    #[serde(default)]
     pub name_server_ips: std::collections::HashMap<String, String>,
 
     /// DNS records in the zone
@@ -521,17 +669,27 @@ pub struct ZoneConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub also_notify: Option<Vec<String>>,
 
-    /// IP addresses allowed to transfer the zone (BIND9 allow-transfer)
-    /// Example: ["10.244.2.101", "10.244.2.102"]
+    /// Address match list of clients allowed to transfer the zone (BIND9 allow-transfer)
+    /// Example: ["10.244.0.0/16", "!10.244.2.1", "key \"transfer-key\""]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_transfer: Option<Vec<String>>,
 
-    /// IP addresses of primary servers for secondary zones (BIND9 primaries/masters)
+    /// IP addresses of primary servers (BIND9 primaries/masters)
     /// Example: ["192.0.2.1", "192.0.2.2"]
-    /// Required for secondary zone types
+    /// Required for secondary, stub and mirror zones
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primaries: Option<Vec<String>>,
 
+    /// Servers to forward queries to (BIND9 forwarders), as "ip" or "ip:port"
+    /// Example: ["10.0.0.53", "10.0.1.53:5353"]
+    /// Required for forward zones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forwarders: Option<Vec<String>>,
+
+    /// Forwarding mode: "only" or "first" (BIND9 forward)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward: Option<String>,
+
     /// DNSSEC policy name to apply to this zone (BIND9 9.16+)
     ///
     /// Specifies the name of a `dnssec-policy` block defined in `named.conf.options`.
@@ -603,15 +761,20 @@ impl ZoneConfig {
                 String::new()
             };
 
-            let priority_str = if let Some(priority) = record.priority {
-                format!("{} ", priority)
-            } else {
-                String::new()
+            let (record_type, value) = match &record.data {
+                Some(data) => (data.record_type(), data.rdata()),
+                None => match record.priority {
+                    Some(priority) => (
+                        record.record_type.as_str(),
+                        format!("{} {}", priority, record.value),
+                    ),
+                    None => (record.record_type.as_str(), record.value.clone()),
+                },
             };
 
             zone_file.push_str(&format!(
-                "{} {}IN {} {}{}\n",
-                record.name, ttl_str, record.record_type, priority_str, record.value
+                "{} {}IN {} {}\n",
+                record.name, ttl_str, record_type, value
             ));
         }
 
@@ -626,7 +789,8 @@ pub struct CreateZoneRequest {
     /// Zone name (e.g., "example.com")
     pub zone_name: String,
 
-    /// Zone type ("primary" or "secondary")
+    /// Zone type: "primary", "secondary", "stub", "forward" or "mirror".
+    /// Hint and redirect zones are not supported (see [`parse_zone_type`]).
     pub zone_type: String,
 
     /// Structured zone configuration
@@ -634,26 +798,163 @@ pub struct CreateZoneRequest {
 
     /// Optional: TSIG key name for allow-update
     pub update_key_name: Option<String>,
+
+    /// Optional: `update-policy` rules for a primary zone, for finer grants
+    /// than `updateKeyName` (which allows the key to update anything).
+    /// Cannot be combined with `updateKeyName`.
+    /// Example: [{"action": "grant", "identity": "tenant1-key", "ruleType": "name",
+    /// "name": "_acme-challenge.example.com.", "types": ["TXT"]}]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_policy: Option<Vec<UpdatePolicyRule>>,
+
+    /// Optional: how bindcar generates the SOA serial instead of using
+    /// `zoneConfig.soa.serial` as-is ("increment", "unixtime" or "date")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial_strategy: Option<SerialStrategy>,
+
+    /// Optional: catalog zone properties of a primary zone; requires a
+    /// configured catalog zone (`BIND_CATALOG_ZONE`). Only used on create.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog: Option<CatalogProperties>,
 }
 
-/// Request to modify a zone configuration
+/// Request to clone a zone
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
+pub struct CloneZoneRequest {
+    /// Name of the new zone (e.g., "staging.example.com")
+    pub target_zone_name: String,
+}
+
+/// Request to modify a zone configuration
+///
+/// Only the options present are changed; see [`crate::zone_options`] for how
+/// a request is applied to the live configuration.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ModifyZoneRequest {
     /// IP addresses of secondary servers to notify when zone changes (BIND9 also-notify)
     /// Example: ["10.244.2.101", "10.244.2.102"]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub also_notify: Option<Vec<String>>,
 
-    /// IP addresses allowed to transfer the zone (BIND9 allow-transfer)
-    /// Example: ["10.244.2.101", "10.244.2.102"]
+    /// Address match list of clients allowed to transfer the zone (BIND9 allow-transfer)
+    /// Example: ["10.244.0.0/16", "!10.244.2.1", "key \"transfer-key\""]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_transfer: Option<Vec<String>>,
 
-    /// IP addresses allowed to update the zone dynamically (BIND9 allow-update)
-    /// Example: ["10.244.2.101", "10.244.2.102"]
+    /// Address match list of clients allowed to update the zone dynamically (BIND9 allow-update)
+    /// Example: ["10.244.2.101", "key \"update-key\""]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_update: Option<Vec<String>>,
+
+    /// Address match list of clients allowed to query the zone (BIND9 allow-query)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_query: Option<Vec<String>>,
+
+    /// Address match list of servers allowed to send NOTIFY for a secondary zone
+    /// (BIND9 allow-notify)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_notify: Option<Vec<String>>,
+
+    /// Address match list of clients whose updates a secondary forwards to the
+    /// primary (BIND9 allow-update-forwarding)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_update_forwarding: Option<Vec<String>>,
+
+    /// `update-policy` rules, replacing the zone's current policy; an empty
+    /// list removes it. The zone must not also have `allowUpdate`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_policy: Option<Vec<UpdatePolicyRule>>,
+
+    /// NOTIFY behaviour: "yes", "no", "explicit" or "primary-only"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify: Option<String>,
+
+    /// Inbound transfer time limit in minutes (1-40320)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_transfer_time_in: Option<u32>,
+
+    /// Outbound transfer time limit in minutes (1-40320)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_transfer_time_out: Option<u32>,
+
+    /// Inbound transfer idle limit in minutes (1-40320)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_transfer_idle_in: Option<u32>,
+
+    /// Outbound transfer idle limit in minutes (1-40320)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_transfer_idle_out: Option<u32>,
+
+    /// IPv4 source address for zone transfers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_source: Option<String>,
+
+    /// IPv6 source address for zone transfers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_source_v6: Option<String>,
+
+    /// IPv4 source address for NOTIFY messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_source: Option<String>,
+
+    /// IPv6 source address for NOTIFY messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_source_v6: Option<String>,
+
+    /// Keep IXFR journals for changes made by reloading the zone file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ixfr_from_differences: Option<bool>,
+
+    /// Owner name checks: "fail", "warn" or "ignore"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_names: Option<String>,
+
+    /// MX target address checks: "fail", "warn" or "ignore"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_mx: Option<String>,
+
+    /// Run post-load zone integrity checks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_integrity: Option<bool>,
+
+    /// Largest TTL accepted when loading the zone, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_zone_ttl: Option<u32>,
+
+    /// Upper bound on the SOA refresh interval a secondary uses, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_refresh_time: Option<u32>,
+
+    /// Lower bound on the SOA refresh interval a secondary uses, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_refresh_time: Option<u32>,
+
+    /// Upper bound on the SOA retry interval a secondary uses, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retry_time: Option<u32>,
+
+    /// Lower bound on the SOA retry interval a secondary uses, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_retry_time: Option<u32>,
+
+    /// Treat all primaries as one source of serial numbers (secondary zones)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_master: Option<bool>,
+
+    /// Request IXFR rather than AXFR from primaries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_ixfr: Option<bool>,
+
+    /// Honour the EDNS EXPIRE option from primaries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_expire: Option<bool>,
+
+    /// Options to remove, returning them to BIND9's default
+    /// Example: ["allowQuery", "maxZoneTtl"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clear: Vec<ZoneOption>,
 }
 
 /// Response from zone operations
@@ -663,12 +964,43 @@ pub struct ZoneResponse {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Zone check warnings: content BIND loads anyway, such as out-of-zone
+    /// records, which are left out of the zone file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ZoneCheckIssue>,
+    /// Catalog update that failed after the zone was created or deleted
+    #[serde(
+        rename = "catalogError",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub catalog_error: Option<CatalogError>,
+    /// Planned actions, present only for `dryRun=true` requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<OperationPlan>,
 }
 
 /// Server status response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ServerStatusResponse {
+    /// `rndc status` output
     pub status: String,
+    /// The same output parsed; absent if it could not be parsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<rndc_types::ServerStatus>,
+}
+
+/// Zone status response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ZoneStatusResponse {
+    pub success: bool,
+    pub message: String,
+    /// `rndc zonestatus` output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    /// The same output parsed; absent if it could not be parsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<rndc_types::ZoneStatus>,
 }
 
 /// Zone information
@@ -681,250 +1013,628 @@ pub struct ZoneInfo {
     pub serial: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_path: Option<String>,
+    /// Zone content parsed from the zone file (SOA, name servers, records)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone_config: Option<ZoneConfig>,
+    /// Zone state from `rndc zonestatus`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<rndc_types::ZoneStatus>,
 }
 
 /// List of zones response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ZoneListResponse {
+    /// Names of the zones on this page
     pub zones: Vec<String>,
+    /// Number of zones on this page
     pub count: usize,
+    /// Type, serial and state of each zone on this page
+    #[serde(default)]
+    pub entries: Vec<ZoneSummary>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
-/// Create a new zone
+/// Disagreement between BIND and the zone directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZoneDrift {
+    /// A zone file exists but BIND does not serve the zone
+    NotLoaded,
+    /// BIND serves a primary zone that has no file in the zone directory
+    MissingFile,
+    /// A static zone's file has a different serial than BIND serves
+    /// (edited but not reloaded)
+    SerialMismatch,
+}
+
+/// One zone in a zone listing
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneSummary {
+    pub name: String,
+    /// Zone type reported by BIND
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone_type: Option<String>,
+    /// Serial currently served
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<u32>,
+    pub loaded: bool,
+    pub dynamic: bool,
+    pub frozen: bool,
+    /// Whether `<zone_dir>/<name>.zone` exists
+    pub has_file: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift: Option<ZoneDrift>,
+}
+
+/// Default and maximum page size of the zone listing
+const DEFAULT_ZONE_LIST_LIMIT: usize = 100;
+const MAX_ZONE_LIST_LIMIT: usize = 1000;
+
+/// Number of `rndc zonestatus` calls in flight while listing zones
+const ZONE_STATUS_CONCURRENCY: usize = 16;
+
+/// Largest zone file accepted by the import endpoint (16 MiB)
+pub const MAX_ZONE_IMPORT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Media types accepted by the import endpoint: `text/dns` (RFC 4027) and
+/// plain text
+const ZONE_IMPORT_CONTENT_TYPES: &[&str] = &["text/dns", "text/plain"];
+
+/// Query parameters of a zone import
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct ImportZoneQuery {
+    /// TSIG key name for allow-update, as `updateKeyName` on create
+    pub update_key_name: Option<String>,
+    /// How the SOA serial is generated instead of taking the file's serial
+    pub serial_strategy: Option<SerialStrategy>,
+}
+
+/// Query parameters of a zone sync
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncZoneQuery {
+    /// Remove the journal after writing it to the zone file
+    #[serde(default)]
+    pub clean: bool,
+}
+
+/// Default and maximum number of journal transactions returned
+const DEFAULT_JOURNAL_LIMIT: usize = 20;
+const MAX_JOURNAL_LIMIT: usize = 1000;
+
+/// Query parameters of a journal listing
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ZoneJournalQuery {
+    /// Number of most recent transactions to return (default 20, max 1000)
+    pub limit: Option<usize>,
+}
+
+/// A zone's journal: its size, serial range and most recent changes
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneJournalResponse {
+    pub zone_name: String,
+    /// Path of the journal file
+    pub file: String,
+    /// Journal size in bytes; 0 if the zone has no journal
+    pub size_bytes: u64,
+    /// Format and serial range; absent if the zone has no journal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<JournalHeader>,
+    /// Number of transactions in the journal
+    pub transaction_count: usize,
+    /// The most recent transactions, oldest first
+    pub transactions: Vec<JournalTransaction>,
+}
+
+/// Query parameters of a zone export
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportZoneQuery {
+    /// "zone", "json" (default) or "yaml"
+    pub format: Option<ExportFormat>,
+}
+
+/// Query parameters of the zone listing
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListZonesQuery {
+    /// Only zones of this type (e.g. "primary", "secondary")
+    #[serde(rename = "type")]
+    #[param(rename = "type")]
+    pub zone_type: Option<String>,
+    /// Only zones whose name starts with this prefix
+    pub prefix: Option<String>,
+    /// `nextCursor` of the previous page
+    pub cursor: Option<String>,
+    /// Page size (default 100, max 1000)
+    pub limit: Option<usize>,
+}
+
+/// Parse the `zoneType` of a create or replace request.
 ///
-/// This endpoint:
-/// 1. Generates zone file from structured configuration
-/// 2. Writes the zone file to disk
-/// 3. Executes `rndc addzone` to add the zone to BIND9
-#[utoipa::path(
-    post,
-    path = "/api/v1/zones",
-    request_body = CreateZoneRequest,
-    responses(
-        (status = 201, description = "Zone created successfully", body = ZoneResponse),
-        (status = 400, description = "Invalid request"),
-        (status = 409, description = "Zone already exists"),
-        (status = 500, description = "RNDC command failed"),
-        (status = 500, description = "Internal server error")
-    ),
-    tag = "zones"
-)]
-pub async fn create_zone(
-    State(state): State<AppState>,
-    Json(request): Json<CreateZoneRequest>,
-) -> Result<(StatusCode, Json<ZoneResponse>), ApiError> {
-    info!("Creating zone: {}", request.zone_name);
+/// Only current BIND9 names are accepted ("primary", not "master").
+/// Delegation-only zones no longer exist in BIND9. Hint and redirect zones
+/// must be named ".", which is not a valid zone name here: it cannot be a URL
+/// path segment, since clients remove `.` segments, and it has no
+/// `<name>.zone` file. They stay in `named.conf`.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] for any other zone type.
+pub(crate) fn parse_zone_type(zone_type: &str) -> Result<ZoneType, ApiError> {
+    match ZoneType::parse(zone_type) {
+        Some(
+            parsed @ (ZoneType::Primary
+            | ZoneType::Secondary
+            | ZoneType::Stub
+            | ZoneType::Forward
+            | ZoneType::Mirror),
+        ) if parsed.as_str() == zone_type => Ok(parsed),
+        Some(parsed @ (ZoneType::Hint | ZoneType::Redirect)) => {
+            Err(ApiError::InvalidRequest(format!(
+                "{} zones are not supported: they must be named '.', which cannot be \
+                 managed through the API; configure them in named.conf",
+                parsed.as_str()
+            )))
+        }
+        _ => Err(ApiError::InvalidRequest(format!(
+            "Invalid zone type: {}. Must be one of: primary, secondary, stub, forward, mirror",
+            zone_type
+        ))),
+    }
+}
 
-    // Debug log the full request payload
-    if let Ok(json_payload) = serde_json::to_string_pretty(&request) {
-        debug!("POST /api/v1/zones payload: {}", json_payload);
+/// Reject request options that BIND9 does not accept for the zone type, so
+/// `rndc addzone` never fails on them.
+fn validate_zone_type_options(
+    request: &CreateZoneRequest,
+    zone_type: ZoneType,
+) -> Result<(), ApiError> {
+    use ZoneType::{Forward, Mirror, Primary, Secondary, Stub};
+
+    let config = &request.zone_config;
+    let options: [(&str, bool, &[ZoneType]); 8] = [
+        (
+            "updateKeyName",
+            request.update_key_name.is_some(),
+            &[Primary, Secondary],
+        ),
+        ("updatePolicy", request.update_policy.is_some(), &[Primary]),
+        (
+            "dnssecPolicy",
+            config.dnssec_policy.is_some(),
+            &[Primary, Secondary],
+        ),
+        (
+            "inlineSigning",
+            config.inline_signing.is_some(),
+            &[Primary, Secondary],
+        ),
+        (
+            "alsoNotify",
+            config.also_notify.is_some(),
+            &[Primary, Secondary, Mirror],
+        ),
+        (
+            "allowTransfer",
+            config.allow_transfer.is_some(),
+            &[Primary, Secondary, Mirror],
+        ),
+        (
+            "forwarders",
+            config.forwarders.is_some(),
+            &[Primary, Secondary, Stub, Forward],
+        ),
+        (
+            "forward",
+            config.forward.is_some(),
+            &[Primary, Secondary, Stub, Forward],
+        ),
+    ];
+    for (field, present, allowed) in options {
+        if present && !allowed.contains(&zone_type) {
+            return Err(ApiError::InvalidRequest(format!(
+                "'{}' is not supported for {} zones",
+                field,
+                zone_type.as_str()
+            )));
+        }
     }
 
-    // Validate zone name (strict DNS grammar; prevents path traversal into the
-    // zone directory and command injection into rndc addzone).
-    if let Err(e) = validate_zone_name(&request.zone_name) {
-        metrics::record_zone_operation("create", false);
-        return Err(e);
+    // BIND9 rejects a zone with both allow-update and update-policy
+    if request.update_key_name.is_some() && request.update_policy.is_some() {
+        return Err(ApiError::InvalidRequest(
+            "'updateKeyName' and 'updatePolicy' cannot be used together".to_string(),
+        ));
     }
 
-    // Validate zone type
-    if request.zone_type != ZONE_TYPE_PRIMARY && request.zone_type != ZONE_TYPE_SECONDARY {
-        metrics::record_zone_operation("create", false);
+    let needs_primaries = matches!(zone_type, Secondary | Stub | Mirror);
+    if needs_primaries && config.primaries.as_ref().is_none_or(|p| p.is_empty()) {
         return Err(ApiError::InvalidRequest(format!(
-            "Invalid zone type: {}. Must be '{}' or '{}'",
-            request.zone_type, ZONE_TYPE_PRIMARY, ZONE_TYPE_SECONDARY
+            "{} zones require at least one primary server in 'primaries' field",
+            capitalize(zone_type.as_str())
         )));
     }
 
-    // Validate secondary zone requirements
-    if request.zone_type == ZONE_TYPE_SECONDARY
-        && request
-            .zone_config
-            .primaries
-            .as_ref()
-            .is_none_or(|p| p.is_empty())
-    {
-        metrics::record_zone_operation("create", false);
+    if zone_type == Forward && config.forwarders.as_ref().is_none_or(|f| f.is_empty()) {
         return Err(ApiError::InvalidRequest(
-            "Secondary zones require at least one primary server in 'primaries' field".to_string(),
+            "Forward zones require at least one server in 'forwarders' field".to_string(),
         ));
     }
 
+    if let Some(forward) = &config.forward {
+        if ForwardMode::parse(forward).is_none() {
+            return Err(ApiError::InvalidRequest(format!(
+                "Invalid forward mode: {}. Must be 'only' or 'first'",
+                forward
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Upper-case the first character, for zone types at the start of a message.
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// Validate a create or replace request before anything is written to disk or
+/// sent to rndc.
+///
+/// Returns the parsed zone type.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] (HTTP 400) for an invalid zone name or
+/// type, an option the zone type does not support, a missing `primaries` or
+/// `forwarders` list, an unsafe RNDC identifier, a malformed address list, or
+/// zone-file content that fails the C-2 checks.
+pub(crate) fn validate_zone_request(request: &CreateZoneRequest) -> Result<ZoneType, ApiError> {
+    // Validate zone name (strict DNS grammar; prevents path traversal into the
+    // zone directory and command injection into rndc addzone).
+    validate_zone_name(&request.zone_name)?;
+
+    let zone_type = parse_zone_type(&request.zone_type)?;
+    validate_zone_type_options(request, zone_type)?;
+
     // Validate optional RNDC identifiers up front, before any filesystem writes,
     // so an injection attempt never reaches the rndc addzone config literal.
     if let Some(key_name) = &request.update_key_name {
-        if let Err(e) = validate_rndc_identifier("updateKeyName", key_name) {
-            metrics::record_zone_operation("create", false);
-            return Err(e);
-        }
+        validate_rndc_identifier("updateKeyName", key_name)?;
+    }
+    if let Some(rules) = &request.update_policy {
+        validate_update_policy("updatePolicy", rules)?;
     }
     if let Some(dnssec_policy) = &request.zone_config.dnssec_policy {
-        if let Err(e) = validate_rndc_identifier("dnssecPolicy", dnssec_policy) {
-            metrics::record_zone_operation("create", false);
-            return Err(e);
-        }
+        validate_rndc_identifier("dnssecPolicy", dnssec_policy)?;
     }
 
     if let Some(primaries) = &request.zone_config.primaries {
-        if let Err(e) = validate_ip_port_list("primaries", primaries) {
-            metrics::record_zone_operation("create", false);
-            return Err(e);
-        }
+        validate_ip_port_list("primaries", primaries)?;
     }
     if let Some(also_notify) = &request.zone_config.also_notify {
-        if let Err(e) = validate_ip_port_list("also-notify", also_notify) {
-            metrics::record_zone_operation("create", false);
-            return Err(e);
-        }
+        validate_ip_port_list("also-notify", also_notify)?;
     }
     if let Some(allow_transfer) = &request.zone_config.allow_transfer {
-        if let Err(e) = validate_ip_list("allow-transfer", allow_transfer) {
-            metrics::record_zone_operation("create", false);
-            return Err(e);
-        }
+        parse_address_match_list("allow-transfer", allow_transfer)?;
+    }
+    if let Some(forwarders) = &request.zone_config.forwarders {
+        validate_ip_port_list("forwarders", forwarders)?;
     }
 
     // Validate the zone-file content fields before rendering (C-2). Records
     // embedded in the create request never passed the per-request validation
     // applied by the add-record endpoint, so a control character in any field
     // could inject extra zone-file lines / directives ($INCLUDE, $GENERATE).
-    if request.zone_type == ZONE_TYPE_PRIMARY {
-        if let Err(e) = validate_zone_config_content(&request.zone_config) {
-            metrics::record_zone_operation("create", false);
-            return Err(e);
+    if zone_type == ZoneType::Primary {
+        validate_zone_config_content(&request.zone_config)?;
+
+        // Catch what BIND would only reject after the file is written and
+        // addzone has run: CNAME conflicts, CNAME targets, missing glue.
+        let (records, _) = zone_file_parser::parse_records(
+            &request.zone_config.to_zone_file(),
+            &request.zone_name,
+        )
+        .map_err(|e| ApiError::InvalidRequest(format!("Invalid zone content: {}", e)))?;
+        let issues = zone_check::check_zone(&records, &request.zone_name);
+        if issues.iter().any(ZoneCheckIssue::is_error) {
+            return Err(ApiError::ZoneCheckFailed(issues));
         }
     }
 
-    // Generate zone file content from structured configuration (only for primary zones)
-    let zone_content = if request.zone_type == ZONE_TYPE_PRIMARY {
-        request.zone_config.to_zone_file()
-    } else {
-        String::new() // Secondary zones don't need zone files
+    Ok(zone_type)
+}
+
+/// Drop what BIND would ignore from a validated primary zone's content, and
+/// return the zone check warnings to report.
+///
+/// Records owned outside the zone (in `records` or `nameServerIps`) are
+/// removed, as BIND ignores them on load; other warnings change nothing.
+pub(crate) fn apply_zone_check_warnings(request: &mut CreateZoneRequest) -> Vec<ZoneCheckIssue> {
+    if request.zone_type != ZONE_TYPE_PRIMARY {
+        return Vec::new();
+    }
+    let Ok((records, _)) =
+        zone_file_parser::parse_records(&request.zone_config.to_zone_file(), &request.zone_name)
+    else {
+        return Vec::new();
     };
+    let warnings: Vec<_> = zone_check::check_zone(&records, &request.zone_name)
+        .into_iter()
+        .filter(|issue| !issue.is_error())
+        .collect();
+    for warning in &warnings {
+        warn!(
+            "zone {}: {} ({})",
+            request.zone_name, warning.message, warning.check
+        );
+    }
 
-    // Only write zone file for primary zones
-    let zone_file_name = format!("{}.zone", request.zone_name);
-    let zone_file_path = PathBuf::from(&state.zone_dir).join(&zone_file_name);
+    let zone_name = request.zone_name.clone();
+    let config = &mut request.zone_config;
+    config
+        .records
+        .retain(|record| !zone_check::is_out_of_zone(&record.name, &zone_name));
+    config
+        .name_server_ips
+        .retain(|name, _| !zone_check::is_out_of_zone(name, &zone_name));
+    warnings
+}
 
-    if request.zone_type == ZONE_TYPE_PRIMARY {
-        info!(
-            "Generated zone file content for {}: {} bytes",
-            request.zone_name,
-            zone_content.len()
+/// Build the BIND9 zone configuration for a request.
+///
+/// Every field must already have passed [`validate_zone_request`], which
+/// returned `zone_type`.
+pub(crate) fn build_zone_config(
+    request: &CreateZoneRequest,
+    zone_type: ZoneType,
+    zone_dir: &str,
+) -> rndc_types::ZoneConfig {
+    let config = &request.zone_config;
+    let mut zone = rndc_types::ZoneConfig::new(request.zone_name.clone(), zone_type);
+
+    // Only primary zones are loaded from a file bindcar writes
+    if zone_type == ZoneType::Primary {
+        zone.file = Some(format!("{}/{}.zone", zone_dir, request.zone_name));
+    }
+
+    if matches!(
+        zone_type,
+        ZoneType::Secondary | ZoneType::Stub | ZoneType::Mirror
+    ) {
+        zone.primaries = config.primaries.as_deref().map(server_specs);
+    }
+
+    zone.allow_update = request
+        .update_key_name
+        .as_ref()
+        .map(|key_name| vec![AddressMatchElement::Key(key_name.clone())]);
+    zone.update_policy = request.update_policy.clone().map(UpdatePolicy::Rules);
+    zone.also_notify = config.also_notify.as_deref().map(server_specs);
+    zone.allow_transfer = config
+        .allow_transfer
+        .as_deref()
+        .and_then(|entries| parse_address_match_list("allow-transfer", entries).ok());
+
+    zone.forward = config.forward.as_deref().and_then(ForwardMode::parse);
+    zone.forwarders = config.forwarders.as_ref().map(|forwarders| {
+        server_specs(forwarders)
+            .into_iter()
+            .map(|spec| ForwarderSpec {
+                address: spec.address,
+                port: spec.port,
+                tls_config: None,
+            })
+            .collect()
+    });
+
+    // BIND9 9.16+; not modelled by the showzone parser, so kept as a raw option
+    if let Some(dnssec_policy) = &config.dnssec_policy {
+        zone.raw_options.insert(
+            "dnssec-policy".to_string(),
+            format!(r#""{}""#, dnssec_policy),
         );
+    }
+    zone.inline_signing = config.inline_signing;
 
-        // Clean up any existing journal file to prevent sync issues
-        let journal_file_name = format!("{}.zone.jnl", request.zone_name);
-        let journal_file_path = PathBuf::from(&state.zone_dir).join(&journal_file_name);
-        if journal_file_path.exists() {
-            if let Err(e) = tokio::fs::remove_file(&journal_file_path).await {
-                error!(
-                    "Failed to remove old journal file {}: {}",
-                    journal_file_path.display(),
-                    e
-                );
-            } else {
-                info!("Removed old journal file: {}", journal_file_path.display());
-            }
-        }
-
-        tokio::fs::write(&zone_file_path, &zone_content)
-            .await
-            .map_err(|e| {
-                error!(
-                    "Failed to write zone file {}: {}",
-                    zone_file_path.display(),
-                    e
-                );
-                metrics::record_zone_operation("create", false);
-                ApiError::ZoneFileError(format!("Failed to write zone file: {}", e))
-            })?;
-
-        info!("Wrote zone file: {}", zone_file_path.display());
-    }
+    zone
+}
 
-    // Build zone configuration for rndc addzone
-    let mut config_parts = vec![format!(r#"type {}"#, request.zone_type)];
+/// Render the `rndc addzone` / `rndc modzone` configuration block for a request.
+///
+/// Every interpolated field must already have passed [`validate_zone_request`].
+pub(crate) fn build_zone_config_block(
+    request: &CreateZoneRequest,
+    zone_type: ZoneType,
+    zone_dir: &str,
+) -> String {
+    build_zone_config(request, zone_type, zone_dir).to_rndc_block()
+}
 
-    // Add file path for primary zones
-    if request.zone_type == ZONE_TYPE_PRIMARY {
-        let zone_file_full_path = format!("{}/{}", state.zone_dir, zone_file_name);
-        config_parts.push(format!(r#"file "{}""#, zone_file_full_path));
-    }
-
-    // Add primaries for secondary zones
-    if request.zone_type == ZONE_TYPE_SECONDARY {
-        if let Some(primaries) = &request.zone_config.primaries {
-            if !primaries.is_empty() {
-                let primaries_list = primaries
-                    .iter()
-                    .map(|s| render_ip_port_entry(s))
-                    .collect::<String>();
-                config_parts.push(format!(r#"primaries {{ {} }}"#, primaries_list));
-            }
-        }
-    }
+/// Create a new zone
+///
+/// This endpoint:
+/// 1. Generates zone file from structured configuration
+/// 2. Checks with `rndc showzone` that the zone does not exist yet
+/// 3. Writes the zone file to disk
+/// 4. Executes `rndc addzone` to add the zone to BIND9
+///
+/// An existing zone is rejected with 409 before any file is touched.
+///
+/// With `dryRun=true` nothing is written or executed; the response carries the
+/// rendered zone file and `addzone` command instead.
+#[utoipa::path(
+    post,
+    path = "/api/v1/zones",
+    request_body = CreateZoneRequest,
+    params(DryRunQuery),
+    responses(
+        (status = 200, description = "Dry run: planned actions", body = ZoneResponse),
+        (status = 201, description = "Zone created successfully", body = ZoneResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 409, description = "Zone already exists"),
+        (status = 500, description = "RNDC command failed"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "zones"
+)]
+pub async fn create_zone(
+    State(state): State<AppState>,
+    Query(query): Query<DryRunQuery>,
+    Json(mut request): Json<CreateZoneRequest>,
+) -> Result<(StatusCode, Json<ZoneResponse>), ApiError> {
+    info!("Creating zone: {}", request.zone_name);
 
-    // Add allow-update if TSIG key is provided
-    if let Some(key_name) = &request.update_key_name {
-        config_parts.push(format!(r#"allow-update {{ key "{}"; }}"#, key_name));
+    // Debug log the full request payload
+    if let Ok(json_payload) = serde_json::to_string_pretty(&request) {
+        debug!("POST /api/v1/zones payload: {}", json_payload);
     }
 
-    // Add also-notify if secondary IPs are provided
-    if let Some(also_notify) = &request.zone_config.also_notify {
-        if !also_notify.is_empty() {
-            let notify_list = also_notify
-                .iter()
-                .map(|s| render_ip_port_entry(s))
-                .collect::<String>();
-            config_parts.push(format!(r#"also-notify {{ {} }}"#, notify_list));
+    let zone_type = match validate_zone_request(&request).and_then(|zone_type| {
+        validate_catalog_request(&request, state.catalog_zone.as_deref()).map(|()| zone_type)
+    }) {
+        Ok(zone_type) => zone_type,
+        Err(e) => {
+            metrics::record_zone_operation("create", false);
+            return Err(e);
         }
-    }
+    };
+    let catalog = catalog_for(&request, state.catalog_zone.as_deref());
+    let warnings = apply_zone_check_warnings(&mut request);
+
+    // A new zone has no served serial to advance from
+    request.zone_config.soa.serial = soa_serial::resolve_serial(
+        request.serial_strategy,
+        request.zone_config.soa.serial,
+        None,
+        chrono::Utc::now(),
+    );
 
-    // Add allow-transfer if secondary IPs are provided
-    if let Some(allow_transfer) = &request.zone_config.allow_transfer {
-        if !allow_transfer.is_empty() {
-            let transfer_list = allow_transfer
-                .iter()
-                .map(|ip| format!("{}; ", ip))
-                .collect::<String>();
-            config_parts.push(format!(r#"allow-transfer {{ {} }}"#, transfer_list));
-        }
-    }
+    // Generate zone file content from structured configuration (only for primary zones)
+    let zone_content = if request.zone_type == ZONE_TYPE_PRIMARY {
+        request.zone_config.to_zone_file()
+    } else {
+        String::new() // Secondary zones don't need zone files
+    };
 
-    // Add DNSSEC policy if specified (BIND9 9.16+)
-    if let Some(dnssec_policy) = &request.zone_config.dnssec_policy {
-        config_parts.push(format!(r#"dnssec-policy "{}""#, dnssec_policy));
+    // Build zone configuration for rndc addzone
+    let zone_config = build_zone_config_block(&request, zone_type, &state.zone_dir);
+
+    // Check that the zone does not exist before any file is written, so that
+    // the files of a live zone are never stashed or overwritten.
+    let existing = match state.rndc.showzone(&request.zone_name).await {
+        Ok(_) => Err(ApiError::ZoneAlreadyExists(request.zone_name.clone())),
+        Err(e) if e.to_string().contains("not found") => Ok(()),
+        Err(e) => Err(ApiError::RndcError(e.to_string())),
+    };
+    if let Err(e) = existing {
+        metrics::record_zone_operation("create", false);
+        return Err(e);
     }
 
-    // Add inline-signing if specified (required for DNSSEC with dynamic zones)
-    if let Some(inline_signing) = request.zone_config.inline_signing {
-        config_parts.push(format!(
-            r#"inline-signing {}"#,
-            if inline_signing { "yes" } else { "no" }
+    if query.dry_run {
+        info!("Dry run: zone {} would be created", request.zone_name);
+        return Ok((
+            StatusCode::OK,
+            Json(ZoneResponse {
+                success: true,
+                message: format!("Dry run: zone {} would be created", request.zone_name),
+                details: None,
+                warnings: warnings.clone(),
+                catalog_error: None,
+                plan: Some(OperationPlan {
+                    zone_file: (request.zone_type == ZONE_TYPE_PRIMARY).then_some(zone_content),
+                    rndc_commands: vec![format!("addzone {} {}", request.zone_name, zone_config)],
+                    nsupdate_script: catalog
+                        .map(|catalog_zone| {
+                            catalog::add_member_script(
+                                &state.nsupdate,
+                                catalog_zone,
+                                &request.zone_name,
+                                &request.catalog.clone().unwrap_or_default(),
+                            )
+                        })
+                        .transpose()
+                        .map_err(|e| ApiError::InvalidRequest(e.to_string()))?,
+                    ..Default::default()
+                }),
+            }),
         ));
     }
 
-    // Join all parts into final configuration
-    let zone_config = format!("{{ {}; }};", config_parts.join("; "));
+    // Only write zone file for primary zones. Any previous file and journal
+    // are kept aside until addzone succeeds.
+    let mut files = ZoneFileTransaction::new(&state.zone_dir, &request.zone_name);
+
+    if request.zone_type == ZONE_TYPE_PRIMARY {
+        info!(
+            "Generated zone file content for {}: {} bytes",
+            request.zone_name,
+            zone_content.len()
+        );
+
+        if let Err(e) = files.write_zone_file(zone_content).await {
+            error!(
+                "Failed to write zone file {}: {}",
+                files.zone_file().display(),
+                e
+            );
+            metrics::record_zone_operation("create", false);
+            rollback_zone_files(files, &request.zone_name).await;
+            return Err(ApiError::ZoneFileError(format!(
+                "Failed to write zone file: {}",
+                e
+            )));
+        }
+
+        info!("Wrote zone file: {}", files.zone_file().display());
+    }
 
     // Execute rndc addzone
-    let output = state
-        .rndc
-        .addzone(&request.zone_name, &zone_config)
-        .await
-        .map_err(|e| {
+    let output = match state.rndc.addzone(&request.zone_name, &zone_config).await {
+        Ok(output) => output,
+        Err(e) => {
             error!("RNDC addzone failed for {}: {}", request.zone_name, e);
             metrics::record_zone_operation("create", false);
+            rollback_zone_files(files, &request.zone_name).await;
 
             // Check if zone already exists
             let error_msg = e.to_string();
-            if error_msg.contains("already exists") {
+            return Err(if error_msg.contains("already exists") {
                 ApiError::ZoneAlreadyExists(request.zone_name.clone())
             } else {
                 ApiError::RndcError(error_msg)
-            }
-        })?;
+            });
+        }
+    };
+    files.commit().await;
+    state.bind_files.zone_added(&request.zone_name).await;
+
+    // The zone is served either way; a catalog failure is reported, not fatal.
+    let mut details = output;
+    let mut catalog_error = None;
+    if let Some(catalog_zone) = catalog {
+        let properties = request.catalog.clone().unwrap_or_default();
+        catalog_error = join_catalog(
+            &state,
+            catalog_zone,
+            &request.zone_name,
+            &properties,
+            &mut details,
+        )
+        .await;
+    }
 
     info!("Zone {} created successfully", request.zone_name);
     metrics::record_zone_operation("create", true);
@@ -934,20 +1644,95 @@ pub async fn create_zone(
         Json(ZoneResponse {
             success: true,
             message: format!("Zone {} created successfully", request.zone_name),
-            details: Some(output),
+            details: Some(details),
+            warnings,
+            catalog_error,
+            plan: None,
         }),
     ))
 }
 
+/// Reject catalog properties that cannot be applied: no catalog zone is
+/// configured, or the zone is not a primary zone.
+fn validate_catalog_request(
+    request: &CreateZoneRequest,
+    catalog_zone: Option<&str>,
+) -> Result<(), ApiError> {
+    let Some(properties) = &request.catalog else {
+        return Ok(());
+    };
+    if catalog_zone.is_none() {
+        return Err(ApiError::InvalidRequest(format!(
+            "'catalog' requires a catalog zone to be configured ({})",
+            catalog::CATALOG_ZONE_ENV
+        )));
+    }
+    if request.zone_type != ZONE_TYPE_PRIMARY {
+        return Err(ApiError::InvalidRequest(
+            "'catalog' is only supported for primary zones".to_string(),
+        ));
+    }
+    catalog::validate_properties(properties)
+}
+
+/// The catalog zone a new zone joins: primary zones only, and never the
+/// catalog itself.
+fn catalog_for<'a>(request: &CreateZoneRequest, catalog_zone: Option<&'a str>) -> Option<&'a str> {
+    catalog_zone.filter(|catalog_zone| {
+        request.zone_type == ZONE_TYPE_PRIMARY && !is_same_zone(catalog_zone, &request.zone_name)
+    })
+}
+
+/// Add a new zone to the catalog zone. A failure is logged, appended to
+/// `details` as a warning and returned for the response's `catalogError`;
+/// the zone is served either way.
+async fn join_catalog(
+    state: &AppState,
+    catalog_zone: &str,
+    zone_name: &str,
+    properties: &CatalogProperties,
+    details: &mut String,
+) -> Option<CatalogError> {
+    let e = catalog::add_member(&state.nsupdate, catalog_zone, zone_name, properties)
+        .await
+        .err()?;
+    error!(
+        "Failed to add zone {} to catalog {}: {:#}",
+        zone_name, catalog_zone, e
+    );
+    let failure = CatalogError::new(catalog_zone, zone_name, CatalogOperation::Add);
+    details.push_str(&format!("\nWarning: {}", failure.message));
+    Some(failure)
+}
+
+/// Whether two zone names refer to the same zone.
+fn is_same_zone(a: &str, b: &str) -> bool {
+    a.trim_end_matches('.')
+        .eq_ignore_ascii_case(b.trim_end_matches('.'))
+}
+
+/// Undo a zone's file changes after a failed step, logging rather than
+/// masking the original error.
+async fn rollback_zone_files(files: ZoneFileTransaction, zone_name: &str) {
+    if let Err(e) = files.rollback().await {
+        error!("Failed to roll back zone files for {}: {}", zone_name, e);
+    }
+}
+
 /// Delete a zone
+///
+/// With `dryRun=true` the zone's existence is checked and the `delzone` command
+/// and files that would be removed are returned without executing anything.
 #[utoipa::path(
     delete,
     path = "/api/v1/zones/{name}",
     params(
-        ("name" = String, Path, description = "Zone name to delete")
+        ("name" = String, Path, description = "Zone name to delete"),
+        DryRunQuery
     ),
     responses(
         (status = 200, description = "Zone deleted successfully", body = ZoneResponse),
+        (status = 404, description = "Zone not found (dry run)"),
         (status = 500, description = "RNDC command failed")
     ),
     tag = "zones"
@@ -955,6 +1740,7 @@ pub async fn create_zone(
 pub async fn delete_zone(
     State(state): State<AppState>,
     Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
 ) -> Result<Json<ZoneResponse>, ApiError> {
     info!("Deleting zone: {}", zone_name);
 
@@ -964,45 +1750,89 @@ pub async fn delete_zone(
         metrics::record_zone_operation("delete", false);
         return Err(e);
     }
+    let catalog = state
+        .catalog_zone
+        .as_deref()
+        .filter(|catalog_zone| !is_same_zone(catalog_zone, &zone_name));
+
+    if query.dry_run {
+        state.rndc.showzone(&zone_name).await.map_err(|e| {
+            if e.to_string().contains("not found") {
+                ApiError::ZoneNotFound(zone_name.clone())
+            } else {
+                ApiError::RndcError(e.to_string())
+            }
+        })?;
 
-    // Execute rndc delzone
-    let output = state.rndc.delzone(&zone_name).await.map_err(|e| {
-        error!("RNDC delzone failed for {}: {}", zone_name, e);
-        metrics::record_zone_operation("delete", false);
-        ApiError::RndcError(e.to_string())
-    })?;
-
-    // Delete zone file
-    let zone_file_name = format!("{}.zone", zone_name);
-    let zone_file_path = PathBuf::from(&state.zone_dir).join(&zone_file_name);
-
-    if zone_file_path.exists() {
-        if let Err(e) = tokio::fs::remove_file(&zone_file_path).await {
-            error!(
-                "Failed to delete zone file {}: {}",
-                zone_file_path.display(),
-                e
-            );
-            // Don't fail the request if file deletion fails - zone is already removed from BIND9
-        } else {
-            info!("Deleted zone file: {}", zone_file_path.display());
-        }
+        let removed_files = [
+            format!("{}.zone", zone_name),
+            format!("{}.zone.jnl", zone_name),
+        ]
+        .iter()
+        .map(|name| PathBuf::from(&state.zone_dir).join(name))
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string())
+        .collect();
+
+        info!("Dry run: zone {} would be deleted", zone_name);
+        return Ok(Json(ZoneResponse {
+            success: true,
+            message: format!("Dry run: zone {} would be deleted", zone_name),
+            details: None,
+            warnings: Vec::new(),
+            catalog_error: None,
+            plan: Some(OperationPlan {
+                rndc_commands: vec![format!("delzone {}", zone_name)],
+                nsupdate_script: catalog
+                    .map(|catalog_zone| {
+                        catalog::remove_member_script(&state.nsupdate, catalog_zone, &zone_name)
+                    })
+                    .transpose()
+                    .map_err(|e| ApiError::InvalidRequest(e.to_string()))?,
+                removed_files,
+                ..Default::default()
+            }),
+        }));
     }
 
-    // Delete journal file if it exists
-    let journal_file_name = format!("{}.zone.jnl", zone_name);
-    let journal_file_path = PathBuf::from(&state.zone_dir).join(&journal_file_name);
+    // Move the zone file and journal aside first: if that fails the zone is
+    // untouched, and if delzone fails they are restored.
+    let mut files = ZoneFileTransaction::new(&state.zone_dir, &zone_name);
+    if let Err(e) = files.remove_zone_files().await {
+        error!("Failed to remove zone files for {}: {}", zone_name, e);
+        metrics::record_zone_operation("delete", false);
+        rollback_zone_files(files, &zone_name).await;
+        return Err(ApiError::ZoneFileError(format!(
+            "Failed to remove zone files: {}",
+            e
+        )));
+    }
 
-    if journal_file_path.exists() {
-        if let Err(e) = tokio::fs::remove_file(&journal_file_path).await {
+    // Execute rndc delzone
+    let output = match state.rndc.delzone(&zone_name).await {
+        Ok(output) => output,
+        Err(e) => {
+            error!("RNDC delzone failed for {}: {}", zone_name, e);
+            metrics::record_zone_operation("delete", false);
+            rollback_zone_files(files, &zone_name).await;
+            return Err(ApiError::RndcError(e.to_string()));
+        }
+    };
+    files.commit().await;
+    state.bind_files.zone_removed(&zone_name).await;
+
+    // Removing a zone that is not a member is a no-op in the catalog.
+    let mut details = output;
+    let mut catalog_error = None;
+    if let Some(catalog_zone) = catalog {
+        if let Err(e) = catalog::remove_member(&state.nsupdate, catalog_zone, &zone_name).await {
             error!(
-                "Failed to delete journal file {}: {}",
-                journal_file_path.display(),
-                e
+                "Failed to remove zone {} from catalog {}: {:#}",
+                zone_name, catalog_zone, e
             );
-            // Don't fail the request if file deletion fails
-        } else {
-            info!("Deleted journal file: {}", journal_file_path.display());
+            let failure = CatalogError::new(catalog_zone, &zone_name, CatalogOperation::Remove);
+            details.push_str(&format!("\nWarning: {}", failure.message));
+            catalog_error = Some(failure);
         }
     }
 
@@ -1012,7 +1842,10 @@ pub async fn delete_zone(
     Ok(Json(ZoneResponse {
         success: true,
         message: format!("Zone {} deleted successfully", zone_name),
-        details: Some(output),
+        details: Some(details),
+        warnings: Vec::new(),
+        catalog_error,
+        plan: None,
     }))
 }
 
@@ -1055,6 +1888,9 @@ pub async fn reload_zone(
         success: true,
         message: format!("Zone {} reloaded successfully", zone_name),
         details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
     }))
 }
 
@@ -1066,7 +1902,7 @@ pub async fn reload_zone(
         ("name" = String, Path, description = "Zone name")
     ),
     responses(
-        (status = 200, description = "Zone status retrieved", body = ZoneResponse),
+        (status = 200, description = "Zone status retrieved", body = ZoneStatusResponse),
         (status = 404, description = "Zone not found"),
         (status = 500, description = "RNDC command failed")
     ),
@@ -1075,7 +1911,7 @@ pub async fn reload_zone(
 pub async fn zone_status(
     State(state): State<AppState>,
     Path(zone_name): Path<String>,
-) -> Result<Json<ZoneResponse>, ApiError> {
+) -> Result<Json<ZoneStatusResponse>, ApiError> {
     info!("Getting status for zone: {}", zone_name);
 
     // Validate the caller-supplied zone name before it reaches rndc (defense
@@ -1091,13 +1927,31 @@ pub async fn zone_status(
         }
     })?;
 
-    Ok(Json(ZoneResponse {
+    let status = parse_status_output(
+        "zonestatus",
+        &zone_name,
+        rndc_parser::parse_zonestatus(&output),
+    );
+
+    Ok(Json(ZoneStatusResponse {
         success: true,
         message: format!("Zone {} status retrieved", zone_name),
         details: Some(output),
+        status,
     }))
 }
 
+/// Keep a parsed rndc status, logging output that could not be parsed.
+fn parse_status_output<T>(
+    command: &str,
+    subject: &str,
+    parsed: rndc_parser::ParseResult<T>,
+) -> Option<T> {
+    parsed
+        .inspect_err(|e| warn!("Unparsable rndc {} output for {}: {}", command, subject, e))
+        .ok()
+}
+
 /// Freeze a zone (disable dynamic updates)
 #[utoipa::path(
     post,
@@ -1137,6 +1991,9 @@ pub async fn freeze_zone(
         success: true,
         message: format!("Zone {} frozen successfully", zone_name),
         details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
     }))
 }
 
@@ -1179,6 +2036,9 @@ pub async fn thaw_zone(
         success: true,
         message: format!("Zone {} thawed successfully", zone_name),
         details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
     }))
 }
 
@@ -1221,6 +2081,9 @@ pub async fn notify_zone(
         success: true,
         message: format!("Notify sent for zone {}", zone_name),
         details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
     }))
 }
 
@@ -1263,6 +2126,153 @@ pub async fn retransfer_zone(
         success: true,
         message: format!("Retransfer initiated for zone {}", zone_name),
         details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
+    }))
+}
+
+/// Write a zone's journal to its zone file
+///
+/// Runs `rndc sync <zone>`, or `rndc sync -clean <zone>` with `clean=true`,
+/// which also removes the journal so it stops growing.
+#[utoipa::path(
+    post,
+    path = "/api/v1/zones/{name}/sync",
+    params(
+        ("name" = String, Path, description = "Zone name to sync"),
+        SyncZoneQuery
+    ),
+    responses(
+        (status = 200, description = "Zone synced", body = ZoneResponse),
+        (status = 400, description = "Invalid zone name"),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "zones"
+)]
+pub async fn sync_zone(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<SyncZoneQuery>,
+) -> Result<Json<ZoneResponse>, ApiError> {
+    info!("Syncing zone: {} (clean: {})", zone_name, query.clean);
+
+    if let Err(e) = validate_zone_name(&zone_name) {
+        metrics::record_zone_operation("sync", false);
+        return Err(e);
+    }
+
+    let output = state
+        .rndc
+        .sync(&zone_name, query.clean)
+        .await
+        .map_err(|e| {
+            error!("RNDC sync failed for {}: {}", zone_name, e);
+            metrics::record_zone_operation("sync", false);
+            ApiError::RndcError(e.to_string())
+        })?;
+
+    info!("Zone {} synced successfully", zone_name);
+    metrics::record_zone_operation("sync", true);
+
+    Ok(Json(ZoneResponse {
+        success: true,
+        message: if query.clean {
+            format!("Zone {} synced and its journal removed", zone_name)
+        } else {
+            format!("Zone {} synced", zone_name)
+        },
+        details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
+    }))
+}
+
+/// Inspect a zone's journal
+///
+/// Decodes `<zone_dir>/<name>.zone.jnl` and returns its serial range and the
+/// most recent IXFR deltas: the serials each change went from and to, and the
+/// records it removed and added.
+#[utoipa::path(
+    get,
+    path = "/api/v1/zones/{name}/journal",
+    params(
+        ("name" = String, Path, description = "Zone name"),
+        ZoneJournalQuery
+    ),
+    responses(
+        (status = 200, description = "Zone journal", body = ZoneJournalResponse),
+        (status = 400, description = "Invalid zone name or limit"),
+        (status = 404, description = "Zone not found"),
+        (status = 500, description = "Journal could not be read or decoded")
+    ),
+    tag = "zones"
+)]
+pub async fn get_zone_journal(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<ZoneJournalQuery>,
+) -> Result<Json<ZoneJournalResponse>, ApiError> {
+    info!("Reading journal of zone: {}", zone_name);
+
+    validate_zone_name(&zone_name)?;
+    let limit = query.limit.unwrap_or(DEFAULT_JOURNAL_LIMIT);
+    if limit == 0 || limit > MAX_JOURNAL_LIMIT {
+        return Err(ApiError::InvalidRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_JOURNAL_LIMIT
+        )));
+    }
+
+    let zone_dir = PathBuf::from(&state.zone_dir);
+    let journal_path = zone_dir.join(format!("{}.zone.jnl", zone_name));
+    let file = journal_path.display().to_string();
+
+    let bytes = match tokio::fs::read(&journal_path).await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            if !zone_dir.join(format!("{}.zone", zone_name)).exists() {
+                return Err(ApiError::ZoneNotFound(zone_name));
+            }
+            return Ok(Json(ZoneJournalResponse {
+                zone_name,
+                file,
+                size_bytes: 0,
+                header: None,
+                transaction_count: 0,
+                transactions: Vec::new(),
+            }));
+        }
+        Err(e) => {
+            error!("Failed to read journal {}: {}", file, e);
+            return Err(ApiError::InternalError(format!(
+                "Failed to read journal: {}",
+                e
+            )));
+        }
+    };
+
+    let size_bytes = bytes.len() as u64;
+    let parsed = tokio::task::spawn_blocking(move || journal::parse_journal(&bytes))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Journal decoding failed: {}", e)))?
+        .map_err(|e| {
+            error!("Failed to decode journal {}: {:#}", file, e);
+            ApiError::InternalError(format!("Failed to decode journal: {:#}", e))
+        })?;
+
+    let transaction_count = parsed.transactions.len();
+    let mut transactions = parsed.transactions;
+    transactions.drain(..transaction_count.saturating_sub(limit));
+
+    Ok(Json(ZoneJournalResponse {
+        zone_name,
+        file,
+        size_bytes,
+        header: Some(parsed.header),
+        transaction_count,
+        transactions,
     }))
 }
 
@@ -1286,67 +2296,225 @@ pub async fn server_status(
         ApiError::RndcError(e.to_string())
     })?;
 
-    Ok(Json(ServerStatusResponse { status: output }))
-}
+    let server = parse_status_output(
+        "status",
+        "server",
+        rndc_parser::parse_server_status(&output),
+    );
 
-/// List all zones
-#[utoipa::path(
-    get,
-    path = "/api/v1/zones",
-    responses(
-        (status = 200, description = "List of zones", body = ZoneListResponse),
-        (status = 500, description = "Failed to read zone directory")
-    ),
-    tag = "zones"
-)]
-pub async fn list_zones(State(state): State<AppState>) -> Result<Json<ZoneListResponse>, ApiError> {
-    info!("Listing all zones");
+    Ok(Json(ServerStatusResponse {
+        status: output,
+        server,
+    }))
+}
 
-    // Get zone files from directory
-    let mut zones = Vec::new();
-    let mut entries = tokio::fs::read_dir(&state.zone_dir).await.map_err(|e| {
+/// Names of the `*.zone` files in the zone directory
+async fn zone_files_on_disk(zone_dir: &str) -> Result<BTreeSet<String>, ApiError> {
+    let mut zones = BTreeSet::new();
+    let mut entries = tokio::fs::read_dir(zone_dir).await.map_err(|e| {
         error!("Failed to read zone directory: {}", e);
         ApiError::InternalError(format!("Failed to read zone directory: {}", e))
     })?;
 
     while let Ok(Some(entry)) = entries.next_entry().await {
         if let Ok(file_name) = entry.file_name().into_string() {
-            if file_name.ends_with(".zone") {
-                // Extract zone name from filename (remove .zone extension)
-                if let Some(zone_name) = file_name.strip_suffix(".zone") {
-                    zones.push(zone_name.to_string());
-                }
+            // Extract zone name from filename (remove .zone extension)
+            if let Some(zone_name) = file_name.strip_suffix(".zone") {
+                zones.insert(zone_name.to_string());
             }
         }
     }
 
-    zones.sort();
-    let count = zones.len();
+    Ok(zones)
+}
 
-    info!("Found {} zones", count);
-    metrics::update_zones_count(count as i64);
+/// Names of the zones BIND serves in the default view, from a cached
+/// `rndc dumpdb -zones`.
+///
+/// Built-in empty zones are skipped.
+pub(crate) async fn served_zone_names(state: &AppState) -> anyhow::Result<BTreeSet<String>> {
+    state
+        .bind_files
+        .served_zone_names(|| state.rndc.dumpdb_zones())
+        .await
+}
 
-    Ok(Json(ZoneListResponse { zones, count }))
+/// Serial of the SOA in a zone file, if it can be read and parsed
+async fn zone_file_serial(zone_dir: &str, zone_name: &str) -> Option<u32> {
+    let path = PathBuf::from(zone_dir).join(format!("{}.zone", zone_name));
+    let content = tokio::fs::read_to_string(path).await.ok()?;
+    let (records, _) = zone_file_parser::parse_records(&content, zone_name).ok()?;
+    zone_diff::find_soa(&records).and_then(zone_diff::soa_serial)
 }
 
-/// Get a specific zone
+/// Build the listing entry for one zone from `rndc zonestatus` and the disk.
+async fn zone_summary(state: AppState, name: String, has_file: bool) -> ZoneSummary {
+    // An rndc failure other than "not found" leaves the state unknown rather
+    // than reporting drift.
+    let (status, on_server) = match state.rndc.zonestatus(&name).await {
+        Ok(output) => (
+            rndc_parser::parse_zonestatus(&output).unwrap_or_default(),
+            true,
+        ),
+        Err(e) if e.to_string().contains("not found") => (Default::default(), false),
+        Err(e) => {
+            warn!("zonestatus for {} failed: {}", name, e);
+            (Default::default(), true)
+        }
+    };
+    let is_primary = status.zone_type == Some(rndc_types::ZoneType::Primary);
+
+    let drift = if !on_server {
+        Some(ZoneDrift::NotLoaded)
+    } else if is_primary && !has_file {
+        Some(ZoneDrift::MissingFile)
+    } else if is_primary && has_file && !status.dynamic && status.serial.is_some() {
+        let file_serial = zone_file_serial(&state.zone_dir, &name).await;
+        (file_serial.is_some() && file_serial != status.serial).then_some(ZoneDrift::SerialMismatch)
+    } else {
+        None
+    };
+
+    ZoneSummary {
+        name,
+        zone_type: status.zone_type.map(|t| t.as_str().to_string()),
+        serial: status.serial,
+        loaded: status.loaded,
+        dynamic: status.dynamic,
+        frozen: status.frozen,
+        has_file,
+        drift,
+    }
+}
+
+/// List zones
+///
+/// Zone names come from BIND (`rndc dumpdb -zones`) merged with the zone files
+/// on disk, so secondary zones and zones added by other means are included and
+/// leftover files are flagged. If the dump cannot be read (e.g. BIND on another
+/// host), only the zone directory is listed. Results are sorted by name and
+/// paginated with an opaque cursor.
 #[utoipa::path(
     get,
-    path = "/api/v1/zones/{name}",
-    params(
-        ("name" = String, Path, description = "Zone name")
-    ),
+    path = "/api/v1/zones",
+    params(ListZonesQuery),
     responses(
-        (status = 200, description = "Zone information", body = ZoneInfo),
-        (status = 404, description = "Zone not found"),
-        (status = 500, description = "RNDC command failed")
+        (status = 200, description = "List of zones", body = ZoneListResponse),
+        (status = 400, description = "Invalid filter or page size"),
+        (status = 500, description = "Failed to read zone directory")
     ),
     tag = "zones"
 )]
-pub async fn get_zone(
+pub async fn list_zones(
     State(state): State<AppState>,
-    Path(zone_name): Path<String>,
-) -> Result<Json<ZoneInfo>, ApiError> {
+    Query(query): Query<ListZonesQuery>,
+) -> Result<Json<ZoneListResponse>, ApiError> {
+    info!("Listing zones");
+
+    let limit = query.limit.unwrap_or(DEFAULT_ZONE_LIST_LIMIT);
+    if limit == 0 || limit > MAX_ZONE_LIST_LIMIT {
+        return Err(ApiError::InvalidRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_ZONE_LIST_LIMIT
+        )));
+    }
+    let type_filter = match &query.zone_type {
+        Some(t) => Some(
+            rndc_types::ZoneType::parse(t)
+                .ok_or_else(|| ApiError::InvalidRequest(format!("Unknown zone type: {}", t)))?,
+        ),
+        None => None,
+    };
+
+    let on_disk = zone_files_on_disk(&state.zone_dir).await?;
+    let mut names = on_disk.clone();
+    match served_zone_names(&state).await {
+        Ok(served) => {
+            metrics::update_zones_count(served.len() as i64);
+            names.extend(served);
+        }
+        Err(e) => {
+            warn!(
+                "Listing zones from disk only; zone dump unavailable: {:#}",
+                e
+            );
+            metrics::update_zones_count(on_disk.len() as i64);
+        }
+    }
+
+    let candidates: Vec<String> = names
+        .into_iter()
+        .filter(|name| query.cursor.as_ref().is_none_or(|c| name > c))
+        .filter(|name| {
+            query
+                .prefix
+                .as_ref()
+                .is_none_or(|p| name.starts_with(p.as_str()))
+        })
+        .collect();
+
+    let mut entries = Vec::new();
+    let mut scanned = 0;
+    'pages: for chunk in candidates.chunks(ZONE_STATUS_CONCURRENCY) {
+        let mut tasks = tokio::task::JoinSet::new();
+        for (i, name) in chunk.iter().enumerate() {
+            let summary = zone_summary(state.clone(), name.clone(), on_disk.contains(name));
+            tasks.spawn(async move { (i, summary.await) });
+        }
+        let mut summaries = tasks.join_all().await;
+        summaries.sort_by_key(|(i, _)| *i);
+
+        for (_, summary) in summaries {
+            scanned += 1;
+            let type_matches = type_filter
+                .as_ref()
+                .is_none_or(|t| summary.zone_type.as_deref() == Some(t.as_str()));
+            if type_matches {
+                entries.push(summary);
+                if entries.len() == limit {
+                    break 'pages;
+                }
+            }
+        }
+    }
+
+    let next_cursor = (scanned < candidates.len())
+        .then(|| entries.last().map(|e| e.name.clone()))
+        .flatten();
+    let zones: Vec<String> = entries.iter().map(|e| e.name.clone()).collect();
+    let count = zones.len();
+
+    info!("Found {} zones", count);
+
+    Ok(Json(ZoneListResponse {
+        zones,
+        count,
+        entries,
+        next_cursor,
+    }))
+}
+
+/// Get a specific zone
+///
+/// Returns the parsed zonestatus together with the SOA, name servers and
+/// records parsed from the zone file on disk.
+#[utoipa::path(
+    get,
+    path = "/api/v1/zones/{name}",
+    params(
+        ("name" = String, Path, description = "Zone name")
+    ),
+    responses(
+        (status = 200, description = "Zone information", body = ZoneInfo),
+        (status = 404, description = "Zone not found"),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "zones"
+)]
+pub async fn get_zone(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+) -> Result<Json<ZoneInfo>, ApiError> {
     info!("Getting zone: {}", zone_name);
 
     // Validate the caller-supplied zone name before it is joined into a
@@ -1373,35 +2541,46 @@ pub async fn get_zone(
         }
     })?;
 
-    // Parse zone type and serial from status output
-    let mut zone_type = "unknown".to_string();
-    let mut serial = None;
-
-    for line in status_output.lines() {
-        if let Some(type_str) = line.strip_prefix("type:").or_else(|| {
-            line.contains("type:")
-                .then(|| line.split("type:").nth(1))
-                .flatten()
-        }) {
-            zone_type = type_str.trim().to_string();
-        }
-
-        if let Some(serial_str) = line.strip_prefix("serial:").or_else(|| {
-            line.contains("serial:")
-                .then(|| line.split("serial:").nth(1))
-                .flatten()
-        }) {
-            if let Ok(s) = serial_str.trim().parse::<u32>() {
-                serial = Some(s);
+    let status = parse_status_output(
+        "zonestatus",
+        &zone_name,
+        rndc_parser::parse_zonestatus(&status_output),
+    );
+    let zone_type = status
+        .as_ref()
+        .and_then(|s| s.zone_type)
+        .map_or("unknown", |t| t.as_str())
+        .to_string();
+    let serial = status.as_ref().and_then(|s| s.serial);
+
+    // Parse the zone file so the response carries the actual records. A file
+    // that cannot be parsed (e.g. hand-edited with an unsupported directive)
+    // still yields the zonestatus summary rather than failing the request.
+    let zone_config = match tokio::fs::read_to_string(&zone_file_path).await {
+        Ok(content) => match crate::zone_file_parser::parse_zone_file(&content, &zone_name) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                warn!("Failed to parse zone file for {}: {}", zone_name, e);
+                None
             }
+        },
+        Err(e) => {
+            warn!(
+                "Failed to read zone file {}: {}",
+                zone_file_path.display(),
+                e
+            );
+            None
         }
-    }
+    };
 
     Ok(Json(ZoneInfo {
         name: zone_name,
         zone_type,
         serial,
         file_path: Some(zone_file_path.display().to_string()),
+        zone_config,
+        status,
     }))
 }
 
@@ -1410,12 +2589,14 @@ pub async fn get_zone(
 /// This endpoint allows updating zone configuration parameters such as
 /// also-notify and allow-transfer IP addresses without recreating the zone.
 /// It uses the `rndc modzone` command to dynamically update the zone configuration.
+/// With `dryRun=true` the `modzone` command is returned instead of executed.
 #[utoipa::path(
     patch,
     path = "/api/v1/zones/{name}",
     request_body = ModifyZoneRequest,
     params(
-        ("name" = String, Path, description = "Zone name to modify")
+        ("name" = String, Path, description = "Zone name to modify"),
+        DryRunQuery
     ),
     responses(
         (status = 200, description = "Zone modified successfully", body = ZoneResponse),
@@ -1429,6 +2610,7 @@ pub async fn get_zone(
 pub async fn modify_zone(
     State(state): State<AppState>,
     Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
     Json(request): Json<ModifyZoneRequest>,
 ) -> Result<Json<ZoneResponse>, ApiError> {
     info!("Modifying zone: {}", zone_name);
@@ -1448,16 +2630,10 @@ pub async fn modify_zone(
         );
     }
 
-    // Validate that at least one field is being updated
-    if request.also_notify.is_none()
-        && request.allow_transfer.is_none()
-        && request.allow_update.is_none()
-    {
+    // Reject an empty or contradictory request before touching BIND
+    if let Err(e) = validate_modify_request(&request) {
         metrics::record_zone_operation("modify", false);
-        return Err(ApiError::InvalidRequest(
-            "At least one field (alsoNotify, allowTransfer, or allowUpdate) must be provided"
-                .to_string(),
-        ));
+        return Err(e);
     }
 
     // Check if zone exists by checking for zone file or querying status
@@ -1513,68 +2689,29 @@ pub async fn modify_zone(
     );
 
     // Update the configuration with new values from the request
-    if let Some(also_notify) = &request.also_notify {
-        // Convert string IPs to IpAddr
-        let ip_addrs: Result<Vec<std::net::IpAddr>, _> =
-            also_notify.iter().map(|s| s.parse()).collect();
-
-        match ip_addrs {
-            Ok(addrs) => {
-                zone_config.also_notify = if addrs.is_empty() { None } else { Some(addrs) };
-            }
-            Err(e) => {
-                metrics::record_zone_operation("modify", false);
-                return Err(ApiError::InvalidRequest(format!(
-                    "Invalid IP address in also-notify: {}",
-                    e
-                )));
-            }
-        }
-    }
-
-    if let Some(allow_transfer) = &request.allow_transfer {
-        // Convert string IPs to IpAddr
-        let ip_addrs: Result<Vec<std::net::IpAddr>, _> =
-            allow_transfer.iter().map(|s| s.parse()).collect();
-
-        match ip_addrs {
-            Ok(addrs) => {
-                zone_config.allow_transfer = if addrs.is_empty() { None } else { Some(addrs) };
-            }
-            Err(e) => {
-                metrics::record_zone_operation("modify", false);
-                return Err(ApiError::InvalidRequest(format!(
-                    "Invalid IP address in allow-transfer: {}",
-                    e
-                )));
-            }
-        }
-    }
-
-    if let Some(allow_update) = &request.allow_update {
-        // Convert string IPs to IpAddr
-        let ip_addrs: Result<Vec<std::net::IpAddr>, _> =
-            allow_update.iter().map(|s| s.parse()).collect();
-
-        match ip_addrs {
-            Ok(addrs) => {
-                zone_config.allow_update = if addrs.is_empty() { None } else { Some(addrs) };
-                // Clear the raw directive since we're setting explicit IPs
-                zone_config.allow_update_raw = None;
-            }
-            Err(e) => {
-                metrics::record_zone_operation("modify", false);
-                return Err(ApiError::InvalidRequest(format!(
-                    "Invalid IP address in allow-update: {}",
-                    e
-                )));
-            }
-        }
+    if let Err(e) = apply_zone_options(&mut zone_config, &request) {
+        metrics::record_zone_operation("modify", false);
+        return Err(e);
     }
 
     // Serialize the updated configuration back to RNDC format
     let rndc_config_block = zone_config.to_rndc_block();
 
+    if query.dry_run {
+        info!("Dry run: zone {} would be modified", zone_name);
+        return Ok(Json(ZoneResponse {
+            success: true,
+            message: format!("Dry run: zone {} would be modified", zone_name),
+            details: None,
+            warnings: Vec::new(),
+            catalog_error: None,
+            plan: Some(OperationPlan {
+                rndc_commands: vec![format!("modzone {} {}", zone_name, rndc_config_block)],
+                ..Default::default()
+            }),
+        }));
+    }
+
     info!(
         "Modifying zone {} with config: {}",
         zone_name, rndc_config_block
@@ -1598,5 +2735,756 @@ pub async fn modify_zone(
         success: true,
         message: format!("Zone {} modified successfully", zone_name),
         details: Some(output),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
     }))
 }
+
+/// Whether the rendered configuration block differs from the live zone
+/// configuration. Both sides go through the `showzone` parser, so options it
+/// does not model are compared in their raw form. A block that cannot be
+/// parsed is treated as changed.
+fn zone_config_differs(
+    zone_name: &str,
+    live: &rndc_types::ZoneConfig,
+    desired_block: &str,
+) -> bool {
+    match crate::rndc_parser::parse_showzone(&format!(r#"zone "{}" {}"#, zone_name, desired_block))
+    {
+        Ok(mut desired) => {
+            desired.zone_name = live.zone_name.clone();
+            desired != *live
+        }
+        Err(_) => true,
+    }
+}
+
+/// Whether a zone accepts dynamic updates
+fn is_dynamic(config: &rndc_types::ZoneConfig) -> bool {
+    config.allow_update.is_some() || config.update_policy.is_some()
+}
+
+/// The records a primary zone currently serves.
+///
+/// A dynamic zone is transferred (AXFR), since updates may still be only in
+/// its journal; a static zone is read from its zone file.
+async fn live_zone_records(
+    state: &AppState,
+    zone_name: &str,
+    live_config: &rndc_types::ZoneConfig,
+) -> Result<Vec<ResourceRecord>, ApiError> {
+    if is_dynamic(live_config) {
+        return state.nsupdate.axfr(zone_name).await.map_err(|e| {
+            error!("AXFR of zone {} failed: {:#}", zone_name, e);
+            ApiError::NsupdateError(format!("Failed to transfer zone: {}", e))
+        });
+    }
+
+    let zone_file_path = PathBuf::from(&state.zone_dir).join(format!("{}.zone", zone_name));
+    let content = tokio::fs::read_to_string(&zone_file_path)
+        .await
+        .map_err(|e| ApiError::ZoneFileError(format!("Failed to read zone file: {}", e)))?;
+    Ok(zone_file_parser::parse_records(&content, zone_name)
+        .map_err(|e| ApiError::ZoneFileError(format!("Failed to parse zone file: {}", e)))?
+        .0)
+}
+
+/// Apply the record changes of a replacement to a primary zone.
+///
+/// Dynamic zones (with `allow-update` or `update-policy`) are diffed against an
+/// AXFR of the zone and changed in a single nsupdate transaction. Static zones
+/// are diffed against their zone file, which is rewritten with a serial ahead of
+/// the live one and reloaded. The zone is frozen around the rewrite when BIND
+/// allows it (inline-signed zones); plain static zones answer "not dynamic" and
+/// are simply reloaded.
+///
+/// When `dry_run` is set nothing is changed; the nsupdate script or the zone
+/// file and rndc commands are recorded in `plan` instead.
+///
+/// Returns a summary of the changes, or `None` when the records already match.
+async fn replace_zone_records(
+    state: &AppState,
+    zone_name: &str,
+    request: &CreateZoneRequest,
+    live_config: &rndc_types::ZoneConfig,
+    dry_run: bool,
+    plan: &mut OperationPlan,
+) -> Result<Option<String>, ApiError> {
+    let (desired, _) =
+        zone_file_parser::parse_records(&request.zone_config.to_zone_file(), zone_name)
+            .map_err(|e| ApiError::InvalidRequest(format!("Invalid zone content: {}", e)))?;
+    let desired_soa = zone_diff::find_soa(&desired)
+        .cloned()
+        .ok_or_else(|| ApiError::InternalError("Rendered zone has no SOA record".to_string()))?;
+
+    let dynamic = is_dynamic(live_config);
+    let live = live_zone_records(state, zone_name, live_config).await?;
+
+    let diff = zone_diff::diff_records(&live, &desired);
+    let live_soa = zone_diff::find_soa(&live);
+    let soa_differs = live_soa.is_none_or(|soa| zone_diff::soa_changed(soa, &desired_soa));
+
+    if diff.is_empty() && !soa_differs {
+        info!("Records of zone {} already match", zone_name);
+        return Ok(None);
+    }
+
+    // BIND may serve a newer serial than the zone file holds (inline signing,
+    // journal not yet synced), so prefer the one reported by zonestatus.
+    let served_serial = match state.rndc.zonestatus(zone_name).await {
+        Ok(output) => rndc_parser::parse_zonestatus(&output)
+            .ok()
+            .and_then(|status| status.serial),
+        Err(e) => {
+            warn!(
+                "zonestatus for {} failed, using the zone's SOA: {}",
+                zone_name, e
+            );
+            None
+        }
+    };
+    let live_serial = served_serial.or_else(|| live_soa.and_then(zone_diff::soa_serial));
+    let serial = soa_serial::resolve_serial(
+        request.serial_strategy,
+        request.zone_config.soa.serial,
+        live_serial,
+        chrono::Utc::now(),
+    );
+
+    if dynamic {
+        let mut added = diff.added.clone();
+        if soa_differs {
+            added.push(zone_diff::with_serial(&desired_soa, serial));
+        }
+
+        if dry_run {
+            plan.nsupdate_script = Some(
+                state
+                    .nsupdate
+                    .apply_changes_script(zone_name, &diff.removed, &added, request.zone_config.ttl)
+                    .map_err(|e| ApiError::InvalidRecord(e.to_string()))?,
+            );
+            return Ok(Some(summarize_record_changes(&diff, soa_differs, serial)));
+        }
+
+        state
+            .nsupdate
+            .apply_changes(zone_name, &diff.removed, &added, request.zone_config.ttl)
+            .await
+            .map_err(|e| {
+                error!("nsupdate replacement failed for {}: {}", zone_name, e);
+                ApiError::NsupdateError(format!("Failed to apply record changes: {}", e))
+            })?;
+    } else {
+        let mut zone_config = request.zone_config.clone();
+        zone_config.soa.serial = serial;
+
+        if dry_run {
+            let inline_signed = live_config.inline_signing == Some(true)
+                || live_config
+                    .raw_options
+                    .get("inline-signing")
+                    .is_some_and(|v| v == "yes");
+            plan.zone_file = Some(zone_config.to_zone_file());
+            plan.rndc_commands.extend(if inline_signed {
+                vec![
+                    format!("freeze {}", zone_name),
+                    format!("thaw {}", zone_name),
+                ]
+            } else {
+                vec![format!("reload {}", zone_name)]
+            });
+            return Ok(Some(summarize_record_changes(&diff, soa_differs, serial)));
+        }
+
+        let frozen = match state.rndc.freeze(zone_name).await {
+            Ok(_) => true,
+            Err(e) if e.to_string().contains("not dynamic") => false,
+            Err(e) => return Err(ApiError::RndcError(e.to_string())),
+        };
+
+        let zone_file_path = PathBuf::from(&state.zone_dir).join(format!("{}.zone", zone_name));
+        let written = zone_files::write_atomic(&zone_file_path, zone_config.to_zone_file()).await;
+
+        let reloaded = if frozen {
+            state.rndc.thaw(zone_name).await
+        } else {
+            state.rndc.reload(zone_name).await
+        };
+
+        written.map_err(|e| {
+            error!(
+                "Failed to write zone file {}: {}",
+                zone_file_path.display(),
+                e
+            );
+            ApiError::ZoneFileError(format!("Failed to write zone file: {}", e))
+        })?;
+        reloaded.map_err(|e| ApiError::RndcError(e.to_string()))?;
+    }
+
+    Ok(Some(summarize_record_changes(&diff, soa_differs, serial)))
+}
+
+/// One-line summary of a record replacement
+fn summarize_record_changes(
+    diff: &zone_diff::RecordDiff,
+    soa_differs: bool,
+    serial: u32,
+) -> String {
+    format!(
+        "{} records removed, {} records added{}",
+        diff.removed.len(),
+        diff.added.len(),
+        if soa_differs {
+            format!(", SOA updated (serial {})", serial)
+        } else {
+            String::new()
+        }
+    )
+}
+
+/// Replace a zone with a desired state
+///
+/// Takes the same body as zone creation, diffs it against the live zone and
+/// applies only the difference, so the zone keeps serving throughout:
+/// 1. Records (primary zones): one nsupdate transaction for dynamic zones, or
+///    a zone file rewrite and reload for static zones
+/// 2. Zone options: `rndc modzone` when the rendered configuration differs
+///    from `rndc showzone`
+///
+/// The zone type cannot be changed in place. With `dryRun=true` the diff is
+/// computed against the live zone but only the plan is returned.
+#[utoipa::path(
+    put,
+    path = "/api/v1/zones/{name}",
+    request_body = CreateZoneRequest,
+    params(
+        ("name" = String, Path, description = "Zone name to replace"),
+        DryRunQuery
+    ),
+    responses(
+        (status = 200, description = "Zone replaced successfully", body = ZoneResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Zone not found"),
+        (status = 500, description = "RNDC or nsupdate command failed"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "zones"
+)]
+pub async fn replace_zone(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
+    Json(mut request): Json<CreateZoneRequest>,
+) -> Result<Json<ZoneResponse>, ApiError> {
+    info!("Replacing zone: {}", zone_name);
+
+    // Debug log the full request payload
+    if let Ok(json_payload) = serde_json::to_string_pretty(&request) {
+        debug!("PUT /api/v1/zones/{} payload: {}", zone_name, json_payload);
+    }
+
+    let zone_type =
+        match validate_zone_name(&zone_name).and_then(|()| validate_zone_request(&request)) {
+            Ok(zone_type) => zone_type,
+            Err(e) => {
+                metrics::record_zone_operation("replace", false);
+                return Err(e);
+            }
+        };
+
+    if !request
+        .zone_name
+        .trim_end_matches('.')
+        .eq_ignore_ascii_case(zone_name.trim_end_matches('.'))
+    {
+        metrics::record_zone_operation("replace", false);
+        return Err(ApiError::InvalidRequest(format!(
+            "Zone name in request body ({}) does not match path ({})",
+            request.zone_name, zone_name
+        )));
+    }
+    let warnings = apply_zone_check_warnings(&mut request);
+
+    // Get current zone configuration from BIND9
+    let showzone_output = state.rndc.showzone(&zone_name).await.map_err(|e| {
+        error!("Failed to get zone configuration for {}: {}", zone_name, e);
+        metrics::record_zone_operation("replace", false);
+        if e.to_string().contains("not found") {
+            ApiError::ZoneNotFound(zone_name.clone())
+        } else {
+            ApiError::RndcError(e.to_string())
+        }
+    })?;
+
+    let live_config = crate::rndc_parser::parse_showzone(&showzone_output).map_err(|e| {
+        error!(
+            "Failed to parse zone configuration for {}: {}",
+            zone_name, e
+        );
+        metrics::record_zone_operation("replace", false);
+        ApiError::RndcError(format!("Failed to parse zone configuration: {}", e))
+    })?;
+
+    if live_config.zone_type.as_str() != request.zone_type {
+        metrics::record_zone_operation("replace", false);
+        return Err(ApiError::InvalidRequest(format!(
+            "Zone {} is {}; changing the zone type requires deleting and re-creating the zone",
+            zone_name,
+            live_config.zone_type.as_str()
+        )));
+    }
+
+    let mut changes = Vec::new();
+    let mut plan = OperationPlan::default();
+
+    // Records first: a replacement that also removes allow-update must still
+    // be able to send its record changes through nsupdate.
+    if request.zone_type == ZONE_TYPE_PRIMARY {
+        match replace_zone_records(
+            &state,
+            &zone_name,
+            &request,
+            &live_config,
+            query.dry_run,
+            &mut plan,
+        )
+        .await
+        {
+            Ok(Some(summary)) => changes.push(summary),
+            Ok(None) => {}
+            Err(e) => {
+                metrics::record_zone_operation("replace", false);
+                return Err(e);
+            }
+        }
+    }
+
+    let desired_block = build_zone_config_block(&request, zone_type, &state.zone_dir);
+    let config_differs = zone_config_differs(&zone_name, &live_config, &desired_block);
+    if config_differs && query.dry_run {
+        plan.rndc_commands
+            .push(format!("modzone {} {}", zone_name, desired_block));
+        changes.push("zone configuration updated".to_string());
+    } else if config_differs {
+        info!(
+            "Modifying zone {} with config: {}",
+            zone_name, desired_block
+        );
+        state
+            .rndc
+            .modzone(&zone_name, &desired_block)
+            .await
+            .map_err(|e| {
+                error!("RNDC modzone failed for {}: {}", zone_name, e);
+                metrics::record_zone_operation("replace", false);
+                ApiError::RndcError(e.to_string())
+            })?;
+        changes.push("zone configuration updated".to_string());
+    }
+
+    if query.dry_run {
+        info!(
+            "Dry run: zone {} has {} change sets",
+            zone_name,
+            changes.len()
+        );
+        return Ok(Json(ZoneResponse {
+            success: true,
+            message: format!("Dry run: zone {} would be replaced", zone_name),
+            details: (!changes.is_empty()).then(|| changes.join("; ")),
+            warnings,
+            catalog_error: None,
+            plan: Some(plan),
+        }));
+    }
+
+    info!(
+        "Zone {} replaced ({} change sets)",
+        zone_name,
+        changes.len()
+    );
+    metrics::record_zone_operation("replace", true);
+
+    Ok(Json(ZoneResponse {
+        success: true,
+        message: if changes.is_empty() {
+            format!("Zone {} already matches the requested state", zone_name)
+        } else {
+            format!("Zone {} replaced successfully", zone_name)
+        },
+        details: (!changes.is_empty()).then(|| changes.join("; ")),
+        warnings,
+        catalog_error: None,
+        plan: None,
+    }))
+}
+
+/// Clone a zone under a new name
+///
+/// Reads the live records of a primary zone (AXFR for dynamic zones, the zone
+/// file otherwise), rewrites owner names and in-zone targets to the new origin
+/// (see [`crate::zone_clone`]) and creates the new zone with the source zone's
+/// `showzone` options through `rndc addzone`. The new zone joins the catalog
+/// zone when one is configured. The source zone is not changed; deleting it
+/// afterwards completes a rename.
+///
+/// With `dryRun=true` the zone file and `addzone` command are returned instead.
+#[utoipa::path(
+    post,
+    path = "/api/v1/zones/{name}/clone",
+    request_body = CloneZoneRequest,
+    params(
+        ("name" = String, Path, description = "Zone name to clone"),
+        DryRunQuery
+    ),
+    responses(
+        (status = 201, description = "Zone cloned successfully", body = ZoneResponse),
+        (status = 200, description = "Dry run plan", body = ZoneResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Source zone not found"),
+        (status = 409, description = "Target zone already exists"),
+        (status = 500, description = "RNDC or AXFR failed")
+    ),
+    tag = "zones"
+)]
+pub async fn clone_zone(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
+    Json(request): Json<CloneZoneRequest>,
+) -> Result<(StatusCode, Json<ZoneResponse>), ApiError> {
+    let target = request.target_zone_name;
+    info!("Cloning zone {} to {}", zone_name, target);
+
+    let validated = validate_zone_name(&zone_name)
+        .and_then(|()| validate_zone_name(&target))
+        .and_then(|()| {
+            if is_same_zone(&zone_name, &target) {
+                Err(ApiError::InvalidRequest(
+                    "targetZoneName must differ from the source zone".to_string(),
+                ))
+            } else {
+                Ok(())
+            }
+        });
+    if let Err(e) = validated {
+        metrics::record_zone_operation("clone", false);
+        return Err(e);
+    }
+
+    let result = clone_zone_inner(&state, &zone_name, &target, query.dry_run).await;
+    if !query.dry_run {
+        metrics::record_zone_operation("clone", result.is_ok());
+    }
+    result
+}
+
+async fn clone_zone_inner(
+    state: &AppState,
+    zone_name: &str,
+    target: &str,
+    dry_run: bool,
+) -> Result<(StatusCode, Json<ZoneResponse>), ApiError> {
+    let showzone_output = state.rndc.showzone(zone_name).await.map_err(|e| {
+        error!("Failed to get zone configuration for {}: {}", zone_name, e);
+        if e.to_string().contains("not found") {
+            ApiError::ZoneNotFound(zone_name.to_string())
+        } else {
+            ApiError::RndcError(e.to_string())
+        }
+    })?;
+    let live_config = rndc_parser::parse_showzone(&showzone_output)
+        .map_err(|e| ApiError::RndcError(format!("Failed to parse zone configuration: {}", e)))?;
+    if live_config.zone_type != ZoneType::Primary {
+        return Err(ApiError::InvalidRequest(format!(
+            "Only primary zones can be cloned; {} is a {} zone",
+            zone_name,
+            live_config.zone_type.as_str()
+        )));
+    }
+
+    let records = live_zone_records(state, zone_name, &live_config).await?;
+    let renamed = zone_clone::rename_records(&records, zone_name, target);
+    let content = zone_file_parser::build_zone_config(target, None, &renamed).map_err(|e| {
+        ApiError::ZoneFileError(format!("Failed to read zone {}: {}", zone_name, e))
+    })?;
+    validate_zone_config_content(&content)?;
+    let zone_content = content.to_zone_file();
+
+    let zone_config =
+        zone_clone::clone_zone_config(&live_config, target, &state.zone_dir).to_rndc_block();
+    let catalog = state
+        .catalog_zone
+        .as_deref()
+        .filter(|catalog_zone| !is_same_zone(catalog_zone, target));
+
+    if dry_run {
+        match state.rndc.showzone(target).await {
+            Ok(_) => return Err(ApiError::ZoneAlreadyExists(target.to_string())),
+            Err(e) if e.to_string().contains("not found") => {}
+            Err(e) => return Err(ApiError::RndcError(e.to_string())),
+        }
+
+        info!("Dry run: zone {} would be cloned to {}", zone_name, target);
+        return Ok((
+            StatusCode::OK,
+            Json(ZoneResponse {
+                success: true,
+                message: format!("Dry run: zone {} would be cloned to {}", zone_name, target),
+                details: Some(format!("{} records", renamed.len())),
+                warnings: Vec::new(),
+                catalog_error: None,
+                plan: Some(OperationPlan {
+                    zone_file: Some(zone_content),
+                    rndc_commands: vec![format!("addzone {} {}", target, zone_config)],
+                    nsupdate_script: catalog
+                        .map(|catalog_zone| {
+                            catalog::add_member_script(
+                                &state.nsupdate,
+                                catalog_zone,
+                                target,
+                                &CatalogProperties::default(),
+                            )
+                        })
+                        .transpose()
+                        .map_err(|e| ApiError::InvalidRequest(e.to_string()))?,
+                    ..Default::default()
+                }),
+            }),
+        ));
+    }
+
+    let mut files = ZoneFileTransaction::new(&state.zone_dir, target);
+    if let Err(e) = files.write_zone_file(zone_content).await {
+        error!(
+            "Failed to write zone file {}: {}",
+            files.zone_file().display(),
+            e
+        );
+        rollback_zone_files(files, target).await;
+        return Err(ApiError::ZoneFileError(format!(
+            "Failed to write zone file: {}",
+            e
+        )));
+    }
+
+    let output = match state.rndc.addzone(target, &zone_config).await {
+        Ok(output) => output,
+        Err(e) => {
+            error!("RNDC addzone failed for {}: {}", target, e);
+            rollback_zone_files(files, target).await;
+
+            let error_msg = e.to_string();
+            return Err(if error_msg.contains("already exists") {
+                ApiError::ZoneAlreadyExists(target.to_string())
+            } else {
+                ApiError::RndcError(error_msg)
+            });
+        }
+    };
+    files.commit().await;
+    state.bind_files.zone_added(target).await;
+
+    let mut details = output;
+    let mut catalog_error = None;
+    if let Some(catalog_zone) = catalog {
+        catalog_error = join_catalog(
+            state,
+            catalog_zone,
+            target,
+            &CatalogProperties::default(),
+            &mut details,
+        )
+        .await;
+    }
+
+    info!(
+        "Zone {} cloned to {} ({} records)",
+        zone_name,
+        target,
+        renamed.len()
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ZoneResponse {
+            success: true,
+            message: format!("Zone {} cloned to {}", zone_name, target),
+            details: Some(details),
+            warnings: Vec::new(),
+            catalog_error,
+            plan: None,
+        }),
+    ))
+}
+
+/// Build the create request for an imported zone file.
+///
+/// The file is parsed with `zone_name` as the initial `$ORIGIN`; `$INCLUDE` and
+/// `$GENERATE` are rejected. The result goes through the same
+/// [`validate_zone_request`] checks as a JSON create request.
+///
+/// # Errors
+/// Returns [`ApiError::InvalidRequest`] (HTTP 400) if the file cannot be parsed.
+pub(crate) fn import_request(
+    zone_name: &str,
+    content: &str,
+    options: ImportZoneQuery,
+) -> Result<CreateZoneRequest, ApiError> {
+    let zone_config = zone_file_parser::parse_zone_file(content, zone_name)
+        .map_err(|e| ApiError::InvalidRequest(format!("Invalid zone file: {}", e)))?;
+
+    Ok(CreateZoneRequest {
+        zone_name: zone_name.to_string(),
+        zone_type: ZONE_TYPE_PRIMARY.to_string(),
+        zone_config,
+        update_key_name: options.update_key_name,
+        update_policy: None,
+        serial_strategy: options.serial_strategy,
+        catalog: None,
+    })
+}
+
+/// Import a primary zone from master-file text
+///
+/// The body is a BIND9 zone file (`Content-Type: text/dns` or `text/plain`).
+/// It is parsed into the structured zone configuration and then created
+/// exactly like `POST /api/v1/zones`: the same content validation, zone
+/// consistency checks, catalog membership and `dryRun` support.
+#[utoipa::path(
+    post,
+    path = "/api/v1/zones/{name}/import",
+    request_body(content = String, content_type = "text/dns", description = "Zone file content"),
+    params(
+        ("name" = String, Path, description = "Zone name, the file's initial $ORIGIN"),
+        DryRunQuery,
+        ImportZoneQuery
+    ),
+    responses(
+        (status = 201, description = "Zone imported successfully", body = ZoneResponse),
+        (status = 400, description = "Invalid zone file or request"),
+        (status = 409, description = "Zone already exists"),
+        (status = 413, description = "Zone file too large"),
+        (status = 500, description = "RNDC command failed")
+    ),
+    tag = "zones"
+)]
+pub async fn import_zone(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<DryRunQuery>,
+    Query(options): Query<ImportZoneQuery>,
+    headers: HeaderMap,
+    content: String,
+) -> Result<(StatusCode, Json<ZoneResponse>), ApiError> {
+    info!(
+        "Importing zone {} ({} bytes of zone file)",
+        zone_name,
+        content.len()
+    );
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase());
+    let request = match content_type {
+        Some(media_type) if ZONE_IMPORT_CONTENT_TYPES.contains(&media_type.as_str()) => {
+            validate_zone_name(&zone_name)
+                .and_then(|()| import_request(&zone_name, &content, options))
+        }
+        _ => Err(ApiError::InvalidRequest(
+            "Content-Type must be text/dns or text/plain".to_string(),
+        )),
+    };
+    let request = match request {
+        Ok(request) => request,
+        Err(e) => {
+            metrics::record_zone_operation("create", false);
+            return Err(e);
+        }
+    };
+
+    create_zone(State(state), Query(query), Json(request)).await
+}
+
+/// Export a zone snapshot
+///
+/// Records come from the server: an AXFR of the zone, falling back to the zone
+/// file for static zones that cannot be transferred. Options come from
+/// `rndc showzone`. See [`crate::zone_export`] for the document; the JSON and
+/// YAML forms can be posted back as a create request.
+#[utoipa::path(
+    get,
+    path = "/api/v1/zones/{name}/export",
+    params(
+        ("name" = String, Path, description = "Zone name to export"),
+        ExportZoneQuery
+    ),
+    responses(
+        (status = 200, description = "Zone snapshot", content(
+            (ZoneExport = "application/json"),
+            (ZoneExport = "application/yaml"),
+            (String = "text/dns")
+        )),
+        (status = 400, description = "Invalid zone name or not a primary zone"),
+        (status = 404, description = "Zone not found"),
+        (status = 500, description = "RNDC command or zone transfer failed")
+    ),
+    tag = "zones"
+)]
+pub async fn export_zone(
+    State(state): State<AppState>,
+    Path(zone_name): Path<String>,
+    Query(query): Query<ExportZoneQuery>,
+) -> Result<Response, ApiError> {
+    let format = query.format.unwrap_or_default();
+    info!("Exporting zone {} as {:?}", zone_name, format);
+
+    validate_zone_name(&zone_name)?;
+
+    let showzone_output = state.rndc.showzone(&zone_name).await.map_err(|e| {
+        error!("Failed to get zone configuration for {}: {}", zone_name, e);
+        if e.to_string().contains("not found") {
+            ApiError::ZoneNotFound(zone_name.clone())
+        } else {
+            ApiError::RndcError(e.to_string())
+        }
+    })?;
+    let live_config = rndc_parser::parse_showzone(&showzone_output)
+        .map_err(|e| ApiError::RndcError(format!("Failed to parse zone configuration: {}", e)))?;
+    if live_config.zone_type != ZoneType::Primary {
+        return Err(ApiError::InvalidRequest(format!(
+            "Only primary zones can be exported; {} is a {} zone",
+            zone_name,
+            live_config.zone_type.as_str()
+        )));
+    }
+
+    // The zone file of a static zone can lag what BIND serves (edited but not
+    // reloaded, ixfr-from-differences), so prefer a transfer
+    let records = match state.nsupdate.axfr(&zone_name).await {
+        Ok(records) => records,
+        Err(e) if !is_dynamic(&live_config) => {
+            warn!(
+                "AXFR of {} failed, exporting its zone file instead: {:#}",
+                zone_name, e
+            );
+            live_zone_records(&state, &zone_name, &live_config).await?
+        }
+        Err(e) => {
+            return Err(ApiError::NsupdateError(format!(
+                "Failed to transfer zone: {}",
+                e
+            )))
+        }
+    };
+
+    let export = zone_export::snapshot(&live_config, &records)?;
+    let body = zone_export::render(&export, format)?;
+
+    Ok(([(header::CONTENT_TYPE, format.content_type())], body).into_response())
+}
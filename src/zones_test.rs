@@ -6,8 +6,9 @@
 use super::zones::*;
 use crate::nsupdate::NsupdateExecutor;
 use crate::rndc::RndcExecutor;
-use crate::types::{ApiError, AppState};
-use axum::extract::{Path, State};
+use crate::rndc_types::{self, UpdatePolicyRule};
+use crate::types::{ApiError, AppState, DryRunQuery};
+use axum::extract::{Path, Query, State};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -31,6 +32,11 @@ fn offline_app_state() -> AppState {
         rndc: Arc::new(rndc),
         nsupdate: Arc::new(nsupdate),
         zone_dir: "/tmp".to_string(),
+        catalog_zone: None,
+        bind_files: Arc::new(crate::bind_files::BindFiles::new(
+            "/tmp",
+            crate::bind_files::DEFAULT_ZONE_LIST_CACHE_TTL,
+        )),
     }
 }
 
@@ -62,6 +68,7 @@ fn test_zone_config_to_zone_file() {
                 value: "192.0.2.1".to_string(),
                 ttl: Some(300),
                 priority: None,
+                data: None,
             },
             DnsRecord {
                 name: "@".to_string(),
@@ -69,6 +76,7 @@ fn test_zone_config_to_zone_file() {
                 value: "mail.example.com.".to_string(),
                 ttl: None,
                 priority: Some(10),
+                data: None,
             },
         ],
         also_notify: None,
@@ -76,6 +84,8 @@ fn test_zone_config_to_zone_file() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -89,6 +99,26 @@ fn test_zone_config_to_zone_file() {
     assert!(zone_file.contains("@ IN MX 10 mail.example.com."));
 }
 
+#[test]
+fn test_zone_file_renders_structured_record_data() {
+    let mut config = clean_zone_config();
+    config.records = serde_json::from_str(
+        r#"[
+            {"name": "_sip._tcp", "data": {"type": "SRV", "priority": 10, "weight": 5, "port": 5060, "target": "sip.example.com."}},
+            {"name": "@", "ttl": 300, "data": {"type": "TXT", "text": "v=spf1 \"quoted\" -all"}}
+        ]"#,
+    )
+    .unwrap();
+    assert!(validate_zone_config_content(&config).is_ok());
+
+    let zone_file = config.to_zone_file();
+    assert!(zone_file.contains("_sip._tcp IN SRV 10 5 5060 sip.example.com.\n"));
+    assert!(zone_file.contains(r#"@ 300 IN TXT "v=spf1 \"quoted\" -all""#));
+
+    config.records[0].value = "10 5 5060 sip.example.com.".to_string();
+    assert!(validate_zone_config_content(&config).is_err());
+}
+
 #[test]
 fn test_soa_record_with_defaults() {
     let json = r#"{
@@ -203,6 +233,9 @@ fn test_zone_response_serialization() {
         success: true,
         message: "Zone created".to_string(),
         details: Some("Output".to_string()),
+        warnings: Vec::new(),
+        catalog_error: None,
+        plan: None,
     };
 
     let json = serde_json::to_string(&response).unwrap();
@@ -233,6 +266,8 @@ fn test_zone_config_empty_name_servers() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -263,12 +298,15 @@ fn test_zone_config_special_characters_in_names() {
             value: "v=DMARC1; p=none".to_string(),
             ttl: None,
             priority: None,
+            data: None,
         }],
         also_notify: None,
         allow_transfer: None,
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -297,6 +335,8 @@ fn test_zone_config_zero_ttl() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -311,6 +351,7 @@ fn test_dns_record_mx_with_priority_zero() {
         value: "mail.example.com.".to_string(),
         ttl: None,
         priority: Some(0),
+        data: None,
     };
 
     let config = ZoneConfig {
@@ -332,6 +373,8 @@ fn test_dns_record_mx_with_priority_zero() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -360,6 +403,7 @@ fn test_multiple_records_same_name() {
                 value: "192.0.2.1".to_string(),
                 ttl: None,
                 priority: None,
+                data: None,
             },
             DnsRecord {
                 name: "@".to_string(),
@@ -367,6 +411,7 @@ fn test_multiple_records_same_name() {
                 value: "192.0.2.2".to_string(),
                 ttl: None,
                 priority: None,
+                data: None,
             },
         ],
         also_notify: None,
@@ -374,6 +419,8 @@ fn test_multiple_records_same_name() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -409,6 +456,8 @@ fn test_zone_config_with_nameserver_glue_records() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -446,6 +495,8 @@ fn test_zone_config_glue_records_serialization() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     // Test that it can be serialized to JSON
@@ -482,6 +533,8 @@ fn test_zone_config_without_nameserver_ips() {
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     };
 
     let zone_file = config.to_zone_file();
@@ -887,6 +940,7 @@ fn test_modify_zone_request_serialization() {
         also_notify: Some(vec!["10.244.2.101".to_string()]),
         allow_transfer: Some(vec!["10.244.2.102".to_string()]),
         allow_update: Some(vec!["10.244.2.103".to_string()]),
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&request).unwrap();
@@ -902,8 +956,7 @@ fn test_modify_zone_request_serialization() {
 fn test_modify_zone_request_serialization_skip_none() {
     let request = ModifyZoneRequest {
         also_notify: Some(vec!["10.244.2.101".to_string()]),
-        allow_transfer: None,
-        allow_update: None,
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&request).unwrap();
@@ -1240,37 +1293,81 @@ fn clean_zone_config() -> ZoneConfig {
             value: "192.0.2.1".to_string(),
             ttl: None,
             priority: None,
+            data: None,
         }],
         also_notify: None,
         allow_transfer: None,
         primaries: None,
         dnssec_policy: None,
         inline_signing: None,
+        forwarders: None,
+        forward: None,
     }
 }
 
 #[test]
-fn test_validate_ip_list_accepts_valid_v4_and_v6() {
-    let ips = vec![
-        "192.0.2.1".to_string(),
-        "10.0.0.5".to_string(),
-        "2001:db8::1".to_string(),
-    ];
-    assert!(validate_ip_list("primaries", &ips).is_ok());
+fn test_parse_address_match_list_accepts_bind_elements() {
+    let entries: Vec<String> = [
+        "192.0.2.1",
+        "2001:db8::1",
+        "10.244.0.0/16",
+        "2001:db8::/32",
+        "!10.244.1.1",
+        r#"key "transfer-key""#,
+        "key update-key",
+        "any",
+        "localnets",
+        "trusted-secondaries",
+    ]
+    .iter()
+    .map(|e| e.to_string())
+    .collect();
+
+    let elements = parse_address_match_list("allow-transfer", &entries).unwrap();
+    let rendered: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        rendered,
+        [
+            "192.0.2.1",
+            "2001:db8::1",
+            "10.244.0.0/16",
+            "2001:db8::/32",
+            "!10.244.1.1",
+            r#"key "transfer-key""#,
+            r#"key "update-key""#,
+            "any",
+            "localnets",
+            "trusted-secondaries",
+        ]
+    );
     // Empty list is a no-op.
-    assert!(validate_ip_list("primaries", &[]).is_ok());
+    assert!(parse_address_match_list("allow-transfer", &[])
+        .unwrap()
+        .is_empty());
 }
 
 #[test]
-fn test_validate_ip_list_rejects_rndc_config_injection() {
+fn test_parse_address_match_list_rejects_rndc_config_injection() {
     // C-1: the classic brace-breakout payload must be rejected.
     let payload = vec![r#"1.2.3.4; }; zone "x" { type primary; file "/etc/passwd"; "#.to_string()];
-    assert!(validate_ip_list("primaries", &payload).is_err());
-
-    // Other non-IP / metacharacter entries.
-    assert!(validate_ip_list("also-notify", &["not-an-ip".to_string()]).is_err());
-    assert!(validate_ip_list("allow-transfer", &["192.0.2.1; any".to_string()]).is_err());
-    assert!(validate_ip_list("primaries", &["".to_string()]).is_err());
+    assert!(parse_address_match_list("allow-transfer", &payload).is_err());
+
+    // Other metacharacter entries and malformed elements.
+    for entry in [
+        "192.0.2.1; any",
+        "",
+        r#"key "a"; any; key "b""#,
+        r#"key "bad key""#,
+        "10.1.2.3/8",
+        "10.0.0.0/33",
+        "!",
+    ] {
+        assert!(
+            parse_address_match_list("allow-transfer", &[entry.to_string()]).is_err(),
+            "{:?} must be rejected",
+            entry
+        );
+    }
 }
 
 #[test]
@@ -1301,20 +1398,15 @@ fn test_validate_ip_port_list_rejects_bad_entries() {
 }
 
 #[test]
-fn test_render_ip_port_entry() {
-    // Bare IPs pass through unchanged (with trailing "; ")
-    assert_eq!(render_ip_port_entry("192.0.2.1"), "192.0.2.1; ");
-    assert_eq!(render_ip_port_entry("2001:db8::1"), "2001:db8::1; ");
+fn test_server_specs_render_bind_port_syntax() {
+    let render = |entry: &str| server_specs(&[entry.to_string()])[0].to_string();
+    // Bare IPs pass through unchanged
+    assert_eq!(render("192.0.2.1"), "192.0.2.1");
+    assert_eq!(render("2001:db8::1"), "2001:db8::1");
     // IPv4:port converts to BIND "port" syntax
-    assert_eq!(
-        render_ip_port_entry("192.0.2.2:5353"),
-        "192.0.2.2 port 5353; "
-    );
+    assert_eq!(render("192.0.2.2:5353"), "192.0.2.2 port 5353");
     // IPv6 bracketed:port converts to BIND "port" syntax (unbracketed)
-    assert_eq!(
-        render_ip_port_entry("[2001:db8::2]:53"),
-        "2001:db8::2 port 53; "
-    );
+    assert_eq!(render("[2001:db8::2]:53"), "2001:db8::2 port 53");
 }
 
 #[test]
@@ -1337,6 +1429,25 @@ fn test_validate_zone_config_content_rejects_include_via_record_value() {
     assert!(validate_zone_config_content(&config).is_err());
 }
 
+#[test]
+fn test_validate_zone_config_content_checks_embedded_ds_and_https_records() {
+    let mut config = clean_zone_config();
+    config.records[0].name = "child".to_string();
+    config.records[0].record_type = "DS".to_string();
+    config.records[0].value =
+        "60485 13 2 2bb183af5f22588179a53b0a98631fad1a292118ef7a4a2b4e5b4c4d5e6f7a8b".to_string();
+    assert!(validate_zone_config_content(&config).is_ok());
+    config.records[0].value = "60485 13 2 abc".to_string();
+    assert!(validate_zone_config_content(&config).is_err());
+
+    let mut config = clean_zone_config();
+    config.records[0].record_type = "HTTPS".to_string();
+    config.records[0].value = "1 . alpn=h2,h3".to_string();
+    assert!(validate_zone_config_content(&config).is_ok());
+    config.records[0].value = "1 . alpn=h2 ; $INCLUDE /etc/shadow".to_string();
+    assert!(validate_zone_config_content(&config).is_err());
+}
+
 #[test]
 fn test_validate_zone_config_content_rejects_injection_via_record_name() {
     let mut config = clean_zone_config();
@@ -1491,12 +1602,12 @@ async fn test_modify_zone_rejects_invalid_zone_name() {
     let state = offline_app_state();
     let request = ModifyZoneRequest {
         also_notify: Some(vec!["192.0.2.1".to_string()]),
-        allow_transfer: None,
-        allow_update: None,
+        ..Default::default()
     };
     let result = modify_zone(
         State(state),
         Path(MALICIOUS_ZONE_NAME.to_string()),
+        Query(DryRunQuery::default()),
         axum::Json(request),
     )
     .await;
@@ -1506,6 +1617,162 @@ async fn test_modify_zone_rejects_invalid_zone_name() {
     );
 }
 
+#[tokio::test]
+async fn test_modify_zone_rejects_set_and_clear_before_rndc() {
+    let state = offline_app_state();
+    let request: ModifyZoneRequest =
+        serde_json::from_str(r#"{"notify": "no", "clear": ["notify"]}"#).unwrap();
+    let result = modify_zone(
+        State(state),
+        Path("example.com".to_string()),
+        Query(DryRunQuery::default()),
+        axum::Json(request),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::InvalidRequest(msg)) if msg.contains("notify")));
+}
+
+#[tokio::test]
+async fn test_clone_zone_rejects_bad_target_before_rndc() {
+    for (source, target) in [
+        ("example.com", MALICIOUS_ZONE_NAME),
+        (MALICIOUS_ZONE_NAME, "staging.example.com"),
+        ("example.com", "Example.COM."),
+    ] {
+        let result = clone_zone(
+            State(offline_app_state()),
+            Path(source.to_string()),
+            Query(DryRunQuery::default()),
+            axum::Json(CloneZoneRequest {
+                target_zone_name: target.to_string(),
+            }),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(ApiError::InvalidRequest(_))),
+            "{} -> {} must be rejected",
+            source,
+            target
+        );
+    }
+}
+
+const IMPORT_ZONE_FILE: &str = r#"$ORIGIN legacy.example.
+$TTL 1h
+@ IN SOA ns1 hostmaster ( 2024060101 3h 15m 1w 1h )
+  IN NS ns1
+  IN MX 10 mail
+ns1 IN A 192.0.2.1
+mail 300 IN A 192.0.2.25
+$ORIGIN lab.legacy.example.
+host IN AAAA 2001:db8::10
+"#;
+
+#[test]
+fn test_import_request_parses_zone_file() {
+    let request = import_request(
+        "legacy.example",
+        IMPORT_ZONE_FILE,
+        ImportZoneQuery {
+            update_key_name: Some("legacy-key".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(request.zone_type, ZONE_TYPE_PRIMARY);
+    assert_eq!(request.update_key_name.as_deref(), Some("legacy-key"));
+    let config = &request.zone_config;
+    assert_eq!(config.ttl, 3600);
+    assert_eq!(config.soa.serial, 2024060101);
+    assert_eq!(config.soa.primary_ns, "ns1.legacy.example.");
+    assert_eq!(config.name_servers, ["ns1.legacy.example."]);
+    assert!(config
+        .records
+        .iter()
+        .any(|r| r.name == "host.lab" && r.record_type == "AAAA"));
+
+    // Passes the same validation as a JSON create request
+    assert_eq!(
+        validate_zone_request(&request).unwrap(),
+        rndc_types::ZoneType::Primary
+    );
+}
+
+#[test]
+fn test_import_request_rejects_directives_and_bad_files() {
+    for content in [
+        "$INCLUDE /etc/passwd\n",
+        "$GENERATE 1-100 host$ A 192.0.2.$\n",
+        "www IN A 192.0.2.1\n",
+        "@ IN SOA ns1 hostmaster ( 1 2 3 4 5\n",
+    ] {
+        match import_request("legacy.example", content, ImportZoneQuery::default()) {
+            Err(ApiError::InvalidRequest(msg)) => assert!(msg.contains("Invalid zone file")),
+            other => panic!("{:?}: expected InvalidRequest, got {:?}", content, other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_import_zone_requires_zone_file_content_type() {
+    for content_type in [None, Some("application/json")] {
+        let mut headers = axum::http::HeaderMap::new();
+        if let Some(value) = content_type {
+            headers.insert(axum::http::header::CONTENT_TYPE, value.parse().unwrap());
+        }
+        let result = import_zone(
+            State(offline_app_state()),
+            Path("legacy.example".to_string()),
+            Query(DryRunQuery::default()),
+            Query(ImportZoneQuery::default()),
+            headers,
+            IMPORT_ZONE_FILE.to_string(),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(ApiError::InvalidRequest(msg)) if msg.contains("Content-Type"))
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_import_zone_rejects_invalid_zone_name_before_rndc() {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        "text/dns; charset=utf-8".parse().unwrap(),
+    );
+    let result = import_zone(
+        State(offline_app_state()),
+        Path(MALICIOUS_ZONE_NAME.to_string()),
+        Query(DryRunQuery::default()),
+        Query(ImportZoneQuery::default()),
+        headers,
+        IMPORT_ZONE_FILE.to_string(),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+}
+
+#[tokio::test]
+async fn test_export_zone_rejects_invalid_zone_name() {
+    let result = export_zone(
+        State(offline_app_state()),
+        Path(MALICIOUS_ZONE_NAME.to_string()),
+        Query(ExportZoneQuery::default()),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+}
+
+#[test]
+fn test_export_format_query() {
+    let query: ExportZoneQuery = serde_json::from_str(r#"{"format": "yaml"}"#).unwrap();
+    assert_eq!(query.format, Some(crate::zone_export::ExportFormat::Yaml));
+    assert!(serde_json::from_str::<ExportZoneQuery>(r#"{"format": "xml"}"#).is_err());
+}
+
 #[test]
 fn test_is_normalized_zone_dir_accepts_absolute_normalized_path() {
     assert!(is_normalized_zone_dir("/etc/bind/zones"));
@@ -1547,3 +1814,504 @@ fn test_resolve_zone_dir_output_is_normalized() {
         resolve_zone_dir(dir.path().to_str().unwrap()).expect("existing directory should resolve");
     assert!(is_normalized_zone_dir(&resolved));
 }
+
+fn replace_request(zone_name: &str) -> CreateZoneRequest {
+    CreateZoneRequest {
+        zone_name: zone_name.to_string(),
+        zone_type: ZONE_TYPE_PRIMARY.to_string(),
+        zone_config: ZoneConfig {
+            ttl: 3600,
+            soa: SoaRecord {
+                primary_ns: "ns1.example.com.".to_string(),
+                admin_email: "admin.example.com.".to_string(),
+                serial: 2025010101,
+                refresh: 3600,
+                retry: 600,
+                expire: 604_800,
+                negative_ttl: 86400,
+            },
+            name_servers: vec!["ns1.example.com.".to_string()],
+            name_server_ips: HashMap::from([(
+                "ns1.example.com.".to_string(),
+                "192.0.2.1".to_string(),
+            )]),
+            records: vec![],
+            also_notify: Some(vec!["10.0.0.1:5353".to_string()]),
+            allow_transfer: Some(vec!["10.0.0.2".to_string()]),
+            primaries: None,
+            dnssec_policy: None,
+            inline_signing: None,
+            forwarders: None,
+            forward: None,
+        },
+        update_key_name: Some("update-key".to_string()),
+        update_policy: None,
+        serial_strategy: None,
+        catalog: None,
+    }
+}
+
+#[tokio::test]
+async fn test_replace_zone_rejects_invalid_zone_name() {
+    let state = offline_app_state();
+    let result = replace_zone(
+        State(state),
+        Path(MALICIOUS_ZONE_NAME.to_string()),
+        Query(DryRunQuery::default()),
+        axum::Json(replace_request("example.com")),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+}
+
+#[tokio::test]
+async fn test_replace_zone_rejects_body_path_mismatch() {
+    // The mismatch must be caught before rndc is contacted (the offline state
+    // has no server, so reaching rndc would yield an RndcError instead).
+    let state = offline_app_state();
+    let result = replace_zone(
+        State(state),
+        Path("example.org".to_string()),
+        Query(DryRunQuery::default()),
+        axum::Json(replace_request("example.com")),
+    )
+    .await;
+    match result {
+        Err(ApiError::InvalidRequest(msg)) => assert!(msg.contains("does not match"), "{}", msg),
+        other => panic!("expected InvalidRequest, got {:?}", other.map(|r| r.0)),
+    }
+}
+
+#[tokio::test]
+async fn test_replace_zone_dry_run_still_validates() {
+    let state = offline_app_state();
+    let result = replace_zone(
+        State(state),
+        Path(MALICIOUS_ZONE_NAME.to_string()),
+        Query(DryRunQuery { dry_run: true }),
+        axum::Json(replace_request("example.com")),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+}
+
+#[test]
+fn test_validate_zone_request_runs_zone_check() {
+    let mut request = replace_request("example.com");
+    request.zone_config.records.push(DnsRecord {
+        name: "ns1".to_string(),
+        record_type: "CNAME".to_string(),
+        value: "host.example.net.".to_string(),
+        ttl: None,
+        priority: None,
+        data: None,
+    });
+
+    match validate_zone_request(&request) {
+        Err(ApiError::ZoneCheckFailed(issues)) => {
+            let checks: Vec<_> = issues.iter().map(|i| i.check.as_str()).collect();
+            assert_eq!(checks, vec!["target-is-cname", "cname-and-other-data"]);
+        }
+        other => panic!("expected ZoneCheckFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_out_of_zone_data_is_dropped_with_a_warning() {
+    let mut request = replace_request("example.com");
+    request
+        .zone_config
+        .name_server_ips
+        .insert("ns2.example.net.".to_string(), "192.0.2.2".to_string());
+    request.zone_config.records.push(DnsRecord {
+        name: "www.example.org.".to_string(),
+        record_type: "A".to_string(),
+        value: "192.0.2.3".to_string(),
+        ttl: None,
+        priority: None,
+        data: None,
+    });
+    request.zone_config.records.push(DnsRecord {
+        name: "www".to_string(),
+        record_type: "A".to_string(),
+        value: "192.0.2.4".to_string(),
+        ttl: None,
+        priority: None,
+        data: None,
+    });
+
+    // BIND ignores out-of-zone data, so it does not fail the request
+    assert!(validate_zone_request(&request).is_ok());
+
+    let warnings = apply_zone_check_warnings(&mut request);
+    let checks: Vec<_> = warnings.iter().map(|w| w.check.as_str()).collect();
+    assert_eq!(checks, vec!["out-of-zone", "out-of-zone"]);
+    assert!(warnings.iter().all(|w| !w.is_error()));
+
+    let zone_file = request.zone_config.to_zone_file();
+    assert!(!zone_file.contains("example.net"), "{}", zone_file);
+    assert!(!zone_file.contains("example.org"), "{}", zone_file);
+    assert!(zone_file.contains("www IN A 192.0.2.4"), "{}", zone_file);
+    assert!(zone_file.contains("ns1.example.com. IN A 192.0.2.1"));
+}
+
+#[test]
+fn test_build_zone_config_block_renders_request() {
+    let block = build_zone_config_block(
+        &replace_request("example.com"),
+        rndc_types::ZoneType::Primary,
+        "/var/cache/bind",
+    );
+    assert_eq!(
+        block,
+        r#"{ type primary; file "/var/cache/bind/example.com.zone"; also-notify { 10.0.0.1 port 5353; }; allow-transfer { 10.0.0.2; }; allow-update { key "update-key"; }; };"#
+    );
+}
+
+fn acme_policy() -> serde_json::Value {
+    serde_json::json!([
+        {"action": "grant", "identity": "tenant1-key", "ruleType": "name",
+         "name": "_acme-challenge.example.com.", "types": ["TXT"]},
+        {"action": "grant", "identity": "tenant2-key", "ruleType": "subdomain",
+         "name": "_acme-challenge.tenant2.example.com.", "types": ["TXT(2)"]},
+        {"action": "deny", "identity": "*", "ruleType": "zonesub"}
+    ])
+}
+
+#[test]
+fn test_build_zone_config_block_renders_update_policy() {
+    let mut request = replace_request("example.com");
+    request.update_key_name = None;
+    request.update_policy = Some(serde_json::from_value(acme_policy()).unwrap());
+
+    validate_zone_request(&request).unwrap();
+    let block = build_zone_config_block(&request, rndc_types::ZoneType::Primary, "/var/cache/bind");
+    assert!(block.contains(
+        "update-policy { grant tenant1-key name _acme-challenge.example.com. TXT; \
+         grant tenant2-key subdomain _acme-challenge.tenant2.example.com. TXT(2); \
+         deny * zonesub; };"
+    ));
+    assert!(!block.contains("allow-update"));
+}
+
+#[test]
+fn test_validate_update_policy_rejects_bad_rules() {
+    let rule =
+        |json: serde_json::Value| -> UpdatePolicyRule { serde_json::from_value(json).unwrap() };
+    let cases = [
+        (
+            rule(
+                serde_json::json!({"action": "grant", "identity": "k; }; zone \"x\" {", "ruleType": "zonesub"}),
+            ),
+            "invalid identity",
+        ),
+        (
+            rule(
+                serde_json::json!({"action": "grant", "identity": "k", "ruleType": "name", "name": "a b"}),
+            ),
+            "invalid name",
+        ),
+        (
+            rule(serde_json::json!({"action": "grant", "identity": "k", "ruleType": "name"})),
+            "requires a name",
+        ),
+        (
+            rule(
+                serde_json::json!({"action": "grant", "identity": "k", "ruleType": "zonesub", "name": "x."}),
+            ),
+            "does not take a name",
+        ),
+        (
+            rule(
+                serde_json::json!({"action": "grant", "identity": "k", "ruleType": "zonesub", "types": ["TXT;"]}),
+            ),
+            "invalid record type",
+        ),
+        (
+            rule(serde_json::json!({"action": "grant", "identity": "", "ruleType": "zonesub"})),
+            "invalid identity",
+        ),
+    ];
+    for (rule, expected) in cases {
+        match validate_update_policy("updatePolicy", std::slice::from_ref(&rule)) {
+            Err(ApiError::InvalidRequest(msg)) => {
+                assert!(msg.contains(expected), "{:?}: {}", rule, msg)
+            }
+            other => panic!("{:?}: expected InvalidRequest, got {:?}", rule, other),
+        }
+    }
+    assert!(validate_update_policy("updatePolicy", &[]).is_err());
+}
+
+#[test]
+fn test_update_policy_request_validation() {
+    let mut request = replace_request("example.com");
+    request.update_policy = Some(serde_json::from_value(acme_policy()).unwrap());
+    match validate_zone_request(&request) {
+        Err(ApiError::InvalidRequest(msg)) => assert!(msg.contains("cannot be used together")),
+        other => panic!("expected InvalidRequest, got {:?}", other),
+    }
+
+    let request: CreateZoneRequest = serde_json::from_value(serde_json::json!({
+        "zoneName": "corp.example",
+        "zoneType": "secondary",
+        "zoneConfig": {"primaries": ["192.0.2.1"]},
+        "updatePolicy": acme_policy(),
+    }))
+    .unwrap();
+    match validate_zone_request(&request) {
+        Err(ApiError::InvalidRequest(msg)) => {
+            assert!(msg.contains("'updatePolicy' is not supported for secondary zones"))
+        }
+        other => panic!("expected InvalidRequest, got {:?}", other),
+    }
+}
+
+fn app_state_with_zone_dir(zone_dir: &str) -> AppState {
+    AppState {
+        zone_dir: zone_dir.to_string(),
+        ..offline_app_state()
+    }
+}
+
+#[tokio::test]
+async fn test_list_zones_rejects_bad_limit_and_type() {
+    for query in [
+        ListZonesQuery {
+            limit: Some(0),
+            ..Default::default()
+        },
+        ListZonesQuery {
+            limit: Some(5000),
+            ..Default::default()
+        },
+        ListZonesQuery {
+            zone_type: Some("bogus".to_string()),
+            ..Default::default()
+        },
+    ] {
+        let result = list_zones(State(offline_app_state()), Query(query)).await;
+        assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+    }
+}
+
+#[tokio::test]
+async fn test_list_zones_paginates_disk_zones_without_server() {
+    // No BIND is reachable: names come from the zone directory and the
+    // per-zone state is unknown rather than reported as drift.
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["a.example", "b.example", "c.example", "other.test"] {
+        std::fs::write(dir.path().join(format!("{}.zone", name)), "").unwrap();
+    }
+    std::fs::write(dir.path().join("a.example.zone.jnl"), "").unwrap();
+    let state = app_state_with_zone_dir(dir.path().to_str().unwrap());
+
+    let page = list_zones(
+        State(state.clone()),
+        Query(ListZonesQuery {
+            limit: Some(2),
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap()
+    .0;
+    assert_eq!(page.zones, vec!["a.example", "b.example"]);
+    assert_eq!(page.next_cursor.as_deref(), Some("b.example"));
+    assert!(page.entries.iter().all(|e| e.has_file && e.drift.is_none()));
+
+    let page = list_zones(
+        State(state.clone()),
+        Query(ListZonesQuery {
+            cursor: page.next_cursor,
+            limit: Some(2),
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap()
+    .0;
+    assert_eq!(page.zones, vec!["c.example", "other.test"]);
+    assert_eq!(page.next_cursor, None);
+
+    let page = list_zones(
+        State(state),
+        Query(ListZonesQuery {
+            prefix: Some("other".to_string()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap()
+    .0;
+    assert_eq!(page.zones, vec!["other.test"]);
+    assert_eq!(page.count, 1);
+}
+
+#[tokio::test]
+async fn test_create_zone_rejects_catalog_without_catalog_zone() {
+    let mut request = replace_request("example.com");
+    request.catalog = Some(crate::catalog::CatalogProperties {
+        groups: vec!["default".to_string()],
+        coo: None,
+    });
+
+    let result = create_zone(
+        State(offline_app_state()),
+        Query(DryRunQuery::default()),
+        axum::Json(request),
+    )
+    .await;
+    match result {
+        Err(ApiError::InvalidRequest(msg)) => {
+            assert!(msg.contains("BIND_CATALOG_ZONE"), "{}", msg)
+        }
+        other => panic!("expected InvalidRequest, got {:?}", other.map(|r| r.0)),
+    }
+}
+
+#[tokio::test]
+async fn test_create_zone_rejects_invalid_catalog_group() {
+    let mut request = replace_request("example.com");
+    request.catalog = Some(crate::catalog::CatalogProperties {
+        groups: vec!["bad\"group".to_string()],
+        coo: None,
+    });
+    let state = AppState {
+        catalog_zone: Some("catalog.invalid".to_string()),
+        ..offline_app_state()
+    };
+
+    let result = create_zone(
+        State(state),
+        Query(DryRunQuery::default()),
+        axum::Json(request),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::InvalidRequest(_))));
+}
+
+/// A request for a zone type that carries no zone content.
+fn typed_request(zone_type: &str, zone_config: serde_json::Value) -> CreateZoneRequest {
+    serde_json::from_value(serde_json::json!({
+        "zoneName": "corp.example",
+        "zoneType": zone_type,
+        "zoneConfig": zone_config,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_forward_zone_renders_forwarders() {
+    let request = typed_request(
+        "forward",
+        serde_json::json!({"forwarders": ["10.0.0.53", "10.0.1.53:5353"], "forward": "only"}),
+    );
+    let zone_type = validate_zone_request(&request).unwrap();
+    assert_eq!(zone_type, rndc_types::ZoneType::Forward);
+    assert_eq!(
+        build_zone_config_block(&request, zone_type, "/var/cache/bind"),
+        "{ type forward; forward only; forwarders { 10.0.0.53; 10.0.1.53 port 5353; }; };"
+    );
+}
+
+#[test]
+fn test_stub_and_mirror_zones_render_primaries_without_file() {
+    for zone_type in ["stub", "mirror"] {
+        let request = typed_request(zone_type, serde_json::json!({"primaries": ["192.0.2.1"]}));
+        let parsed = validate_zone_request(&request).unwrap();
+        assert_eq!(
+            build_zone_config_block(&request, parsed, "/var/cache/bind"),
+            format!("{{ type {}; primaries {{ 192.0.2.1; }}; }};", zone_type)
+        );
+    }
+}
+
+#[test]
+fn test_zone_type_specific_validation() {
+    let rejected = [
+        ("forward", serde_json::json!({}), "forwarders"),
+        (
+            "forward",
+            serde_json::json!({"forwarders": []}),
+            "forwarders",
+        ),
+        ("stub", serde_json::json!({}), "Stub zones require"),
+        ("mirror", serde_json::json!({}), "Mirror zones require"),
+        (
+            "forward",
+            serde_json::json!({"forwarders": ["10.0.0.53"], "forward": "sometimes"}),
+            "forward mode",
+        ),
+        (
+            "forward",
+            serde_json::json!({"forwarders": ["10.0.0.53"], "allowTransfer": ["10.0.0.2"]}),
+            "'allowTransfer' is not supported for forward zones",
+        ),
+        (
+            "stub",
+            serde_json::json!({"primaries": ["192.0.2.1"], "inlineSigning": true}),
+            "'inlineSigning' is not supported for stub zones",
+        ),
+        (
+            "forward",
+            serde_json::json!({"forwarders": ["nope"]}),
+            "forwarders",
+        ),
+        (
+            "redirect",
+            serde_json::json!({}),
+            "redirect zones are not supported",
+        ),
+        (
+            "hint",
+            serde_json::json!({}),
+            "hint zones are not supported",
+        ),
+        ("master", serde_json::json!({}), "Invalid zone type"),
+        (
+            "delegation-only",
+            serde_json::json!({}),
+            "Invalid zone type",
+        ),
+    ];
+    for (zone_type, config, expected) in rejected {
+        match validate_zone_request(&typed_request(zone_type, config.clone())) {
+            Err(ApiError::InvalidRequest(msg)) => {
+                assert!(msg.contains(expected), "{} {}: {}", zone_type, config, msg)
+            }
+            other => panic!(
+                "{} {}: expected InvalidRequest, got {:?}",
+                zone_type, config, other
+            ),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_create_zone_checks_existence_before_touching_files() {
+    // The existence check runs before the zone file transaction; when it
+    // cannot confirm that the zone is new, the live files are left as they are.
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let zone_file = dir.path().join("example.com.zone");
+    let journal = dir.path().join("example.com.zone.jnl");
+    tokio::fs::write(&zone_file, "live zone").await.unwrap();
+    tokio::fs::write(&journal, "live journal").await.unwrap();
+
+    let mut state = offline_app_state();
+    state.zone_dir = dir.path().to_str().unwrap().to_string();
+    let result = create_zone(
+        State(state),
+        Query(DryRunQuery::default()),
+        axum::Json(replace_request("example.com")),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(ApiError::RndcError(_) | ApiError::ZoneAlreadyExists(_))
+    ));
+    assert_eq!(std::fs::read_to_string(&zone_file).unwrap(), "live zone");
+    assert_eq!(std::fs::read_to_string(&journal).unwrap(), "live journal");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+}